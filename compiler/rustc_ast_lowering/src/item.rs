@@ -1,4 +1,5 @@
 use super::errors::{InvalidAbi, InvalidAbiReason, InvalidAbiSuggestion, MisplacedRelaxTraitBound};
+use super::unsafe_ops;
 use super::ResolverAstLoweringExt;
 use super::{AstOwner, ImplTraitContext, ImplTraitPosition};
 use super::{FnDeclKind, LoweringContext, ParamMode};
@@ -236,6 +237,7 @@ fn lower_item_kind(
             },
             ItemKind::ForeignMod(fm) => hir::ItemKind::ForeignMod {
                 abi: fm.abi.map_or(abi::Abi::FALLBACK, |abi| self.lower_abi(abi)),
+                unsafety: self.lower_unsafety(fm.unsafety),
                 items: self
                     .arena
                     .alloc_from_iter(fm.items.iter().map(|x| self.lower_foreign_item_ref(x))),
@@ -940,10 +942,12 @@ fn record_body(
         params: &'hir [hir::Param<'hir>],
         value: hir::Expr<'hir>,
     ) -> hir::BodyId {
+        let value = &*self.arena.alloc(value);
         let body = hir::Body {
             coroutine_kind: self.coroutine_kind,
             params,
-            value: self.arena.alloc(value),
+            value,
+            may_use_unsafe: unsafe_ops::may_use_unsafe(params, value),
         };
         let id = body.id();
         debug_assert_eq!(id.hir_id.owner, self.current_hir_id_owner);
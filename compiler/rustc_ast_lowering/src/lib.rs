@@ -87,6 +87,7 @@ macro_rules! arena_vec {
 mod lifetime_collector;
 mod pat;
 mod path;
+mod unsafe_ops;
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
 
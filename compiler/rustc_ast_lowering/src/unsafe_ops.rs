@@ -0,0 +1,68 @@
+//! Computes [`hir::Body::may_use_unsafe`], which lets the unsafety checker skip bodies which
+//! can't contain any unsafe operation.
+//!
+//! We can't resolve the types of expressions yet, so we consider every construct which is an
+//! unsafe operation for some types: calls, dereferences, field accesses and struct expressions,
+//! which may access unions or types with a restricted range of valid values, the patterns which
+//! may do the same, inline assembly and paths to statics. Closures and inline constants are
+//! checked together with their parent, so we consider them too.
+
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::intravisit::{self, Visitor};
+
+pub(super) fn may_use_unsafe<'hir>(
+    params: &'hir [hir::Param<'hir>],
+    value: &'hir hir::Expr<'hir>,
+) -> bool {
+    let mut visitor = UnsafeOpVisitor { found: false };
+    for param in params {
+        visitor.visit_param(param);
+    }
+    visitor.visit_expr(value);
+    visitor.found
+}
+
+struct UnsafeOpVisitor {
+    found: bool,
+}
+
+impl<'hir> Visitor<'hir> for UnsafeOpVisitor {
+    fn visit_expr(&mut self, expr: &'hir hir::Expr<'hir>) {
+        if self.found {
+            return;
+        }
+        match expr.kind {
+            hir::ExprKind::Call(..)
+            | hir::ExprKind::MethodCall(..)
+            | hir::ExprKind::Unary(hir::UnOp::Deref, _)
+            | hir::ExprKind::Field(..)
+            | hir::ExprKind::Struct(..)
+            | hir::ExprKind::InlineAsm(_)
+            | hir::ExprKind::Closure(_)
+            | hir::ExprKind::ConstBlock(_)
+            | hir::ExprKind::Path(hir::QPath::Resolved(
+                _,
+                hir::Path { res: Res::Def(DefKind::Static(_), _), .. },
+            )) => self.found = true,
+            _ => intravisit::walk_expr(self, expr),
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'hir hir::Pat<'hir>) {
+        if self.found {
+            return;
+        }
+        match pat.kind {
+            // Matching on the fields of a union reads them, and binding a field by reference, which
+            // default binding modes do without a `ref`, may borrow a field with a restricted range
+            // of valid values.
+            hir::PatKind::Struct(..)
+            | hir::PatKind::TupleStruct(..)
+            | hir::PatKind::Binding(hir::BindingAnnotation(hir::ByRef::Yes, _), ..) => {
+                self.found = true
+            }
+            _ => intravisit::walk_pat(self, pat),
+        }
+    }
+}
@@ -936,7 +936,9 @@ fn visit_item(&mut self, item: &'a Item) {
                     &item.vis,
                     errors::VisibilityNotPermittedNote::IndividualForeignItems,
                 );
-                if let &Unsafe::Yes(span) = unsafety {
+                if let &Unsafe::Yes(span) = unsafety
+                    && !self.features.unsafe_extern_blocks
+                {
                     self.err_handler().emit_err(errors::UnsafeItem { span, kind: "extern block" });
                 }
                 if abi.is_none() {
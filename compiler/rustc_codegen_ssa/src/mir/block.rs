@@ -327,17 +327,38 @@ fn codegen_switchint_terminator(
             let (test_value, target) = target_iter.next().unwrap();
             let lltrue = helper.llbb_with_cleanup(self, target);
             let llfalse = helper.llbb_with_cleanup(self, targets.otherwise());
+            // Whether the test is expected to succeed, when only one of the targets is cold. As for
+            // assertions, this is passed through `llvm.expect` for branch hinting.
+            let expected = match (
+                self.cold_blocks.contains(target),
+                self.cold_blocks.contains(targets.otherwise()),
+            ) {
+                (false, true) => Some(true),
+                (true, false) => Some(false),
+                _ => None,
+            };
+            let expect = |bx: &mut Bx, cond, expected: Option<bool>| match expected {
+                Some(expected) => bx.expect(cond, expected),
+                None => cond,
+            };
             if switch_ty == bx.tcx().types.bool {
                 // Don't generate trivial icmps when switching on bool.
                 match test_value {
-                    0 => bx.cond_br(discr.immediate(), llfalse, lltrue),
-                    1 => bx.cond_br(discr.immediate(), lltrue, llfalse),
+                    0 => {
+                        let cond = expect(bx, discr.immediate(), expected.map(|e| !e));
+                        bx.cond_br(cond, llfalse, lltrue)
+                    }
+                    1 => {
+                        let cond = expect(bx, discr.immediate(), expected);
+                        bx.cond_br(cond, lltrue, llfalse)
+                    }
                     _ => bug!(),
                 }
             } else {
                 let switch_llty = bx.immediate_backend_type(bx.layout_of(switch_ty));
                 let llval = bx.const_uint_big(switch_llty, test_value);
                 let cmp = bx.icmp(IntPredicate::IntEQ, discr.immediate(), llval);
+                let cmp = expect(bx, cmp, expected);
                 bx.cond_br(cmp, lltrue, llfalse);
             }
         } else if self.cx.sess().opts.optimize == OptLevel::No
@@ -7,6 +7,7 @@
 use rustc_middle::mir::UnwindTerminateReason;
 use rustc_middle::ty::layout::{FnAbiOf, HasTyCtxt, TyAndLayout};
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt, TypeFoldable, TypeVisitableExt};
+use rustc_session::config::OptLevel;
 use rustc_target::abi::call::{FnAbi, PassMode};
 
 use std::iter;
@@ -71,6 +72,10 @@ pub struct FunctionCx<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> {
     /// The funclet status of each basic block
     cleanup_kinds: Option<IndexVec<mir::BasicBlock, analyze::CleanupKind>>,
 
+    /// The basic blocks which only lead to a panic or to `unreachable`, whose branches are
+    /// hinted as unlikely. Empty when not optimizing.
+    cold_blocks: BitSet<mir::BasicBlock>,
+
     /// When targeting MSVC, this stores the cleanup info for each funclet BB.
     /// This is initialized at the same time as the `landing_pads` entry for the
     /// funclets' head block, i.e. when needed by an unwind / `cleanup_ret` edge.
@@ -190,6 +195,12 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
             })
             .collect();
 
+    let cold_blocks = if cx.sess().opts.optimize == OptLevel::No {
+        BitSet::new_empty(mir.basic_blocks.len())
+    } else {
+        mir.cold_blocks(cx.tcx())
+    };
+
     let mut fx = FunctionCx {
         instance,
         mir,
@@ -201,6 +212,7 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         unreachable_block: None,
         terminate_block: None,
         cleanup_kinds,
+        cold_blocks,
         landing_pads: IndexVec::from_elem(None, &mir.basic_blocks),
         funclets: IndexVec::from_fn_n(|_| None, mir.basic_blocks.len()),
         locals: locals::Locals::empty(),
@@ -636,6 +636,19 @@ fn mir_assign_valid_types(&self, src: Ty<'tcx>, dest: Ty<'tcx>) -> bool {
 
         crate::util::relate_types(self.tcx, self.param_env, variance, src, dest)
     }
+
+    /// Whether `RemoveUnneededDrops` removes all the drops of `Copy` values: it may be disabled
+    /// with `-Zmir-enable-passes`, and only removes drops as long as there is fuel left.
+    fn removes_copy_drops(&self) -> bool {
+        let opts = &self.tcx.sess.opts.unstable_opts;
+        opts.fuel.is_none()
+            && opts
+                .mir_enable_passes
+                .iter()
+                .rev()
+                .find(|(name, _)| name == "RemoveUnneededDrops")
+                .map_or(true, |&(_, enabled)| enabled)
+    }
 }
 
 impl<'a, 'tcx> Visitor<'tcx> for TypeChecker<'a, 'tcx> {
@@ -1310,8 +1323,23 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     );
                 }
             }
+            TerminatorKind::Drop { place, .. } => {
+                // Unless it is disabled or out of fuel, `RemoveUnneededDrops` removes the drops of
+                // `Copy` values, so any left in optimized MIR were added by a later pass. Shims
+                // don't go through that pass, and may drop `Copy` closures. This check is somewhat
+                // expensive, like the one for `Operand::Copy`.
+                if self.tcx.sess.opts.unstable_opts.validate_mir
+                    && self.mir_phase == MirPhase::Runtime(RuntimePhase::Optimized)
+                    && matches!(self.body.source.instance, InstanceDef::Item(_))
+                    && self.removes_copy_drops()
+                {
+                    let ty = place.ty(&self.body.local_decls, self.tcx).ty;
+                    if ty.is_copy_modulo_regions(self.tcx, self.param_env) {
+                        self.fail(location, format!("`Drop` of `Copy` type {ty} in optimized MIR"));
+                    }
+                }
+            }
             TerminatorKind::Goto { .. }
-            | TerminatorKind::Drop { .. }
             | TerminatorKind::Yield { .. }
             | TerminatorKind::FalseEdge { .. }
             | TerminatorKind::FalseUnwind { .. }
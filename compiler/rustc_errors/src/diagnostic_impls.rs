@@ -9,6 +9,7 @@
 use rustc_span::symbol::{Ident, MacroRulesNormalizedIdent, Symbol};
 use rustc_span::Span;
 use rustc_target::abi::TargetDataLayoutErrors;
+use rustc_target::spec::abi::Abi;
 use rustc_target::spec::{PanicStrategy, SplitDebuginfo, StackProtector, TargetTriple};
 use rustc_type_ir as type_ir;
 use std::backtrace::Backtrace;
@@ -78,6 +79,7 @@ fn into_diagnostic_arg(self) -> DiagnosticArgValue<'static> {
     &TargetTriple,
     SplitDebuginfo,
     ExitStatus,
+    Abi,
 );
 
 impl IntoDiagnosticArg for i32 {
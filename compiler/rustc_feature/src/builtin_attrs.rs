@@ -319,7 +319,9 @@ pub struct BuiltinAttribute {
     ),
     ungated!(must_use, Normal, template!(Word, NameValueStr: "reason"), FutureWarnFollowing),
     gated!(
-        must_not_suspend, Normal, template!(Word, NameValueStr: "reason"), WarnFollowing,
+        must_not_suspend, Normal,
+        template!(Word, List: r#"reason = "...", label = "...""#, NameValueStr: "reason"),
+        WarnFollowing,
         experimental!(must_not_suspend)
     ),
     ungated!(
@@ -688,6 +690,11 @@ pub struct BuiltinAttribute {
         rustc_never_returns_null_ptr, Normal, template!(Word), ErrorFollowing,
         "#[rustc_never_returns_null_ptr] is used to mark functions returning non-null pointers."
     ),
+    rustc_attr!(
+        rustc_exact_chunks, Normal, template!(Word), ErrorFollowing,
+        "#[rustc_exact_chunks] is used to mark functions returning iterators over slices \
+        whose length is the last argument of the function."
+    ),
     rustc_attr!(
         rustc_coherence_is_core, AttributeType::CrateLevel, template!(Word), ErrorFollowing, @only_local: true,
         "#![rustc_coherence_is_core] allows inherent methods on builtin types, only intended to be used in `core`."
@@ -810,6 +817,7 @@ pub struct BuiltinAttribute {
         template!(Word, List: "span_delayed_bug_from_inside_query"), WarnFollowingWordOnly
     ),
     rustc_attr!(TEST, rustc_dump_user_args, Normal, template!(Word), WarnFollowing),
+    rustc_attr!(TEST, rustc_dump_required_target_features, Normal, template!(Word), WarnFollowing),
     rustc_attr!(TEST, rustc_evaluate_where_clauses, Normal, template!(Word), WarnFollowing),
     rustc_attr!(
         TEST, rustc_if_this_changed, Normal, template!(Word, List: "DepNode"), DuplicatesOk
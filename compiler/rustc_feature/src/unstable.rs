@@ -602,6 +602,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, unix_sigpipe, "1.65.0", Some(97889), None),
     /// Allows unnamed fields of struct and union type
     (incomplete, unnamed_fields, "1.74.0", Some(49804), None),
+    /// Allows declaring `extern` blocks as `unsafe`.
+    (unstable, unsafe_extern_blocks, "CURRENT_RUSTC_VERSION", Some(123743), None),
     /// Allows unsized fn parameters.
     (unstable, unsized_fn_params, "1.49.0", Some(48055), None),
     /// Allows unsized rvalues at arguments and parameters.
@@ -1324,6 +1324,10 @@ pub struct Body<'hir> {
     pub params: &'hir [Param<'hir>],
     pub value: &'hir Expr<'hir>,
     pub coroutine_kind: Option<CoroutineKind>,
+    /// Whether the body contains an expression or pattern which may be an unsafe operation,
+    /// like a call or an access to a field or a static. This only looks at the syntax, so a
+    /// body with a call to a safe function still sets it.
+    pub may_use_unsafe: bool,
 }
 
 impl<'hir> Body<'hir> {
@@ -2945,7 +2949,7 @@ pub fn item_id(&self) -> ItemId {
         expect_mod, &'hir Mod<'hir>, ItemKind::Mod(m), m;
 
         expect_foreign_mod, (Abi, &'hir [ForeignItemRef]),
-            ItemKind::ForeignMod { abi, items }, (*abi, items);
+            ItemKind::ForeignMod { abi, items, .. }, (*abi, items);
 
         expect_global_asm, &'hir InlineAsm<'hir>, ItemKind::GlobalAsm(asm), asm;
 
@@ -3060,8 +3064,8 @@ pub enum ItemKind<'hir> {
     Macro(&'hir ast::MacroDef, MacroKind),
     /// A module.
     Mod(&'hir Mod<'hir>),
-    /// An external module, e.g. `extern { .. }`.
-    ForeignMod { abi: Abi, items: &'hir [ForeignItemRef] },
+    /// An external module, e.g. `extern { .. }` or `unsafe extern { .. }`.
+    ForeignMod { abi: Abi, unsafety: Unsafety, items: &'hir [ForeignItemRef] },
     /// Module-level inline assembly (from `global_asm!`).
     GlobalAsm(&'hir InlineAsm<'hir>),
     /// A type alias, e.g., `type Foo = Bar<u8>`.
@@ -495,7 +495,7 @@ pub fn walk_item<'v, V: Visitor<'v>>(visitor: &mut V, item: &'v Item<'v>) {
             // `visit_mod()` takes care of visiting the `Item`'s `HirId`.
             visitor.visit_mod(module, item.span, item.hir_id())
         }
-        ItemKind::ForeignMod { abi: _, items } => {
+        ItemKind::ForeignMod { abi: _, unsafety: _, items } => {
             visitor.visit_id(item.hir_id());
             walk_list!(visitor, visit_foreign_item_ref, items);
         }
@@ -523,7 +523,7 @@ fn check_item_type(tcx: TyCtxt<'_>, id: hir::ItemId) {
         }
         DefKind::ForeignMod => {
             let it = tcx.hir().item(id);
-            let hir::ItemKind::ForeignMod { abi, items } = it.kind else {
+            let hir::ItemKind::ForeignMod { abi, items, .. } = it.kind else {
                 return;
             };
             check_abi(tcx, it.hir_id(), it.span, abi);
@@ -530,8 +530,10 @@ fn print_item(&mut self, item: &hir::Item<'_>) {
                 self.print_mod(_mod, attrs);
                 self.bclose(item.span);
             }
-            hir::ItemKind::ForeignMod { abi, items } => {
-                self.head("extern");
+            hir::ItemKind::ForeignMod { abi, unsafety, items } => {
+                self.head("");
+                self.print_unsafety(unsafety);
+                self.word_nbsp("extern");
                 self.word_nbsp(abi.to_string());
                 self.bopen();
                 self.print_inner_attributes(self.attrs(item.hir_id()));
@@ -485,10 +485,35 @@ pub fn instantiate(
             // the alias can be normalized to something which does not
             // mention `?0`.
 
-            // FIXME(-Ztrait-solver=next): replace this with `AliasRelate`
             let &ty::Alias(kind, data) = a_ty.kind() else {
                 bug!("generalization should only result in infer vars for aliases");
             };
+            if self.infcx.next_trait_solver() && kind == ty::AliasKind::Projection {
+                // The new solver relates the alias lazily, which also handles subtyping and
+                // normalizing the aliases nested in it.
+                let (a_ty, b_ty, direction) = match ambient_variance {
+                    ty::Variance::Invariant => (a_ty, b_ty, ty::AliasRelationDirection::Equate),
+                    ty::Variance::Covariant => (a_ty, b_ty, ty::AliasRelationDirection::Subtype),
+                    ty::Variance::Contravariant => {
+                        (b_ty, a_ty, ty::AliasRelationDirection::Subtype)
+                    }
+                    ty::Variance::Bivariant => {
+                        unreachable!("no code should be generalizing bivariantly (currently)")
+                    }
+                };
+                self.obligations.push(Obligation::new(
+                    self.tcx(),
+                    self.trace.cause.clone(),
+                    self.param_env,
+                    ty::Binder::dummy(ty::PredicateKind::AliasRelate(
+                        a_ty.into(),
+                        b_ty.into(),
+                        direction,
+                    )),
+                ));
+                return Ok(());
+            }
+
             if !self.infcx.next_trait_solver() {
                 // The old solver only accepts projection predicates for associated types.
                 match kind {
@@ -500,9 +525,9 @@ pub fn instantiate(
             }
 
             // FIXME: This does not handle subtyping correctly, we should switch to
-            // alias-relate in the new solver and could instead create a new inference
-            // variable for `a_ty`, emitting `Projection(a_ty, a_infer)` and
-            // `a_infer <: b_ty`.
+            // alias-relate in the new solver for the other aliases as well and could instead
+            // create a new inference variable for `a_ty`, emitting `Projection(a_ty, a_infer)`
+            // and `a_infer <: b_ty`.
             self.obligations.push(Obligation::new(
                 self.tcx(),
                 self.trace.cause.clone(),
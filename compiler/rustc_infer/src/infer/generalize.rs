@@ -151,7 +151,15 @@ struct Generalizer<'me, 'tcx, D> {
     /// The root term (const or type) we're generalizing. Used for cycle errors.
     root_term: Term<'tcx>,
 
-    cache: SsoHashMap<Ty<'tcx>, Ty<'tcx>>,
+    /// The generalization of the types we already generalized, along with the
+    /// ambient variance they were generalized in.
+    ///
+    /// The same type may be generalized differently depending on the variance:
+    /// in an invariant position we keep type and region variables we can name
+    /// as is, while in other positions we replace them with fresh ones. Reusing
+    /// the result of one for the other would either lose the equality required
+    /// by invariant type constructors, or needlessly require it.
+    cache: SsoHashMap<(Ty<'tcx>, ty::Variance), Ty<'tcx>>,
 
     /// This is set once we're generalizing the arguments of an alias.
     ///
@@ -239,7 +247,7 @@ fn relate_with_variance<T: Relate<'tcx>>(
     fn tys(&mut self, t: Ty<'tcx>, t2: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
         assert_eq!(t, t2); // we are misusing TypeRelation here; both LHS and RHS ought to be ==
 
-        if let Some(&result) = self.cache.get(&t) {
+        if let Some(&result) = self.cache.get(&(t, self.ambient_variance)) {
             return Ok(result);
         }
 
@@ -371,7 +379,7 @@ fn tys(&mut self, t: Ty<'tcx>, t2: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
             _ => relate::structurally_relate_tys(self, t, t),
         }?;
 
-        self.cache.insert(t, g);
+        self.cache.insert((t, self.ambient_variance), g);
         Ok(g)
     }
 
@@ -13,6 +13,7 @@
 
 use hir::def_id::LocalDefId;
 use rustc_hir as hir;
+use rustc_middle::traits::solve::{AmbiguityCause, OverflowOrigin};
 use rustc_middle::ty::error::{ExpectedFound, TypeError};
 use rustc_middle::ty::{self, Const, ToPredicate, Ty, TyCtxt};
 use rustc_span::Span;
@@ -137,7 +138,10 @@ pub enum FulfillmentErrorCode<'tcx> {
     CodeAmbiguity {
         /// Overflow reported from the new solver `-Ztrait-solver=next`, which will
         /// be reported as an regular error as opposed to a fatal error.
-        overflow: bool,
+        overflow: Option<OverflowOrigin>,
+        /// Why the new solver found the goal to be ambiguous, used to tell the user
+        /// whether more type annotations are needed. `None` for the old solver.
+        ambiguity: Option<AmbiguityCause>,
     },
 }
 
@@ -46,8 +46,13 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             super::CodeConstEquateError(ref a, ref b) => {
                 write!(f, "CodeConstEquateError({a:?}, {b:?})")
             }
-            super::CodeAmbiguity { overflow: false } => write!(f, "Ambiguity"),
-            super::CodeAmbiguity { overflow: true } => write!(f, "Overflow"),
+            super::CodeAmbiguity { overflow: None, ambiguity: None } => write!(f, "Ambiguity"),
+            super::CodeAmbiguity { overflow: None, ambiguity: Some(cause) } => {
+                write!(f, "Ambiguity({cause:?})")
+            }
+            super::CodeAmbiguity { overflow: Some(origin), .. } => {
+                write!(f, "Overflow({origin:?})")
+            }
             super::CodeCycle(ref cycle) => write!(f, "Cycle({cycle:?})"),
         }
     }
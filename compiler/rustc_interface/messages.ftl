@@ -46,6 +46,10 @@ interface_rustc_error_fatal =
 interface_rustc_error_unexpected_annotation =
     unexpected annotation used with `#[rustc_error(...)]`!
 
+interface_solver_depth =
+    the trait solver reached a recursion depth of {$depth}
+    .note = proving the obligations of this item requires a `recursion_limit` of at least {$depth}
+
 interface_temps_dir_error =
     failed to find or create the directory specified by `--temps-dir`
 
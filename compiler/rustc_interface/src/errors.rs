@@ -90,6 +90,15 @@ pub struct FailedWritingFile<'a> {
 #[diag(interface_proc_macro_crate_panic_abort)]
 pub struct ProcMacroCratePanicAbort;
 
+#[derive(Diagnostic)]
+#[diag(interface_solver_depth)]
+#[note]
+pub struct SolverDepth {
+    #[primary_span]
+    pub span: Span,
+    pub depth: usize,
+}
+
 #[derive(Diagnostic)]
 #[diag(interface_unsupported_crate_type_for_target)]
 pub struct UnsupportedCrateTypeForTarget<'a> {
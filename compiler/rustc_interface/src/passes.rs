@@ -13,7 +13,7 @@
 use rustc_expand::base::{ExtCtxt, LintStoreExpand};
 use rustc_feature::Features;
 use rustc_fs_util::try_canonicalize;
-use rustc_hir::def_id::{StableCrateId, LOCAL_CRATE};
+use rustc_hir::def_id::{StableCrateId, CRATE_DEF_ID, LOCAL_CRATE};
 use rustc_lint::{unerased_lint_store, BufferedEarlyLint, EarlyCheckNode, LintStore};
 use rustc_metadata::creader::CStore;
 use rustc_middle::arena::Arena;
@@ -759,6 +759,10 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
                 tcx.ensure().unused_generic_params(ty::InstanceDef::Item(def_id.to_def_id()));
             }
         }
+        if !tcx.sess.opts.unstable_opts.thir_unsafeck {
+            rustc_mir_transform::check_unsafety::check_static_mut_never_written(tcx);
+            rustc_mir_transform::check_unsafety::emit_unsafety_json(tcx);
+        }
     });
 
     tcx.hir().par_body_owners(|def_id| {
@@ -771,6 +775,22 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
     sess.time("layout_testing", || layout_test::test_layout(tcx));
     sess.time("abi_testing", || abi_test::test_abi(tcx));
 
+    if sess.opts.unstable_opts.report_solver_depth {
+        // Obligations without an item are attributed to the crate root, and the items whose
+        // goals don't have any nested goals are not interesting.
+        let mut solver_depths: Vec<_> = tcx
+            .solver_depths
+            .lock()
+            .iter()
+            .filter(|&(&def_id, &depth)| def_id != CRATE_DEF_ID && depth > 0)
+            .map(|(&def_id, &depth)| (def_id, depth))
+            .collect();
+        solver_depths.sort_by_key(|&(def_id, _)| tcx.def_span(def_id));
+        for (def_id, depth) in solver_depths {
+            sess.emit_note(errors::SolverDepth { span: tcx.def_span(def_id), depth });
+        }
+    }
+
     // Avoid overwhelming user with errors if borrow checking failed.
     // I'm not sure how helpful this is, to be honest, but it avoids a
     // lot of annoying errors in the ui tests (basically,
@@ -942,6 +962,8 @@ pub fn start_codegen<'tcx>(
         }
     }
 
+    rustc_mir_transform::print_mir_opt_timings(tcx);
+
     codegen
 }
 
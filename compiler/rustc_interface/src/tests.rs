@@ -661,6 +661,7 @@ macro_rules! untracked {
     untracked!(dont_buffer_diagnostics, true);
     untracked!(dump_dep_graph, true);
     untracked!(dump_mir, Some(String::from("abc")));
+    untracked!(dump_mir_cfg, Some(String::from("abc")));
     untracked!(dump_mir_dataflow, true);
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
@@ -682,6 +683,8 @@ macro_rules! untracked {
     untracked!(macro_backtrace, true);
     untracked!(meta_stats, true);
     untracked!(mir_include_spans, true);
+    untracked!(mir_inline_report, true);
+    untracked!(mir_opt_timings, Some(10));
     untracked!(nll_facts, true);
     untracked!(no_analysis, true);
     untracked!(no_leak_check, true);
@@ -750,6 +753,7 @@ macro_rules! tracked {
     tracked!(dual_proc_macros, true);
     tracked!(dwarf_version, Some(5));
     tracked!(emit_thin_lto, false);
+    tracked!(emit_unsafety_json, SwitchWithOptPath::Enabled(Some("unsafety-dir/".into())));
     tracked!(export_executable_symbols, true);
     tracked!(fewer_names, Some(true));
     tracked!(flatten_format_args, false);
@@ -778,6 +782,7 @@ macro_rules! tracked {
     tracked!(mir_opt_level, Some(4));
     tracked!(move_size_limit, Some(4096));
     tracked!(mutable_noalias, false);
+    tracked!(next_solver_normalization_fuel, Some(8));
     tracked!(no_generate_arange_section, true);
     tracked!(no_jump_tables, true);
     tracked!(no_link, true);
@@ -801,6 +806,7 @@ macro_rules! tracked {
     tracked!(relro_level, Some(RelroLevel::Full));
     tracked!(remap_cwd_prefix, Some(PathBuf::from("abc")));
     tracked!(report_delayed_bugs, true);
+    tracked!(require_unsafe_blocks, true);
     tracked!(sanitizer, SanitizerSet::ADDRESS);
     tracked!(sanitizer_cfi_canonical_jump_tables, None);
     tracked!(sanitizer_cfi_generalize_pointers, Some(true));
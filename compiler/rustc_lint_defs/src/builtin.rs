@@ -47,6 +47,7 @@
         HIDDEN_GLOB_REEXPORTS,
         ILL_FORMED_ATTRIBUTE_INPUT,
         ILLEGAL_FLOATING_POINT_LITERAL_PATTERN,
+        IMPLICIT_FN_POINTER_COERCIONS,
         IMPLIED_BOUNDS_ENTAILMENT,
         INCOMPLETE_INCLUDE,
         INDIRECT_STRUCTURAL_MATCH,
@@ -79,6 +80,7 @@
         PROC_MACRO_BACK_COMPAT,
         PROC_MACRO_DERIVE_RESOLUTION_FALLBACK,
         PUB_USE_OF_PRIVATE_EXTERN_CRATE,
+        REDUNDANT_UNCHECKED_INDEXING,
         REFINING_IMPL_TRAIT,
         RENAMED_AND_REMOVED_LINTS,
         REPR_TRANSPARENT_EXTERNAL_PRIVATE_FIELDS,
@@ -90,6 +92,7 @@
         SINGLE_USE_LIFETIMES,
         SOFT_UNSTABLE,
         STABLE_FEATURES,
+        STATIC_MUT_NEVER_WRITTEN,
         SUSPICIOUS_AUTO_TRAIT_IMPLS,
         TEST_UNSTABLE_LINT,
         TEXT_DIRECTION_CODEPOINT_IN_COMMENT,
@@ -128,6 +131,7 @@
         UNUSED_QUALIFICATIONS,
         UNUSED_TUPLE_STRUCT_FIELDS,
         UNUSED_UNSAFE,
+        UNUSED_UNSAFE_AFTER_INLINING,
         UNUSED_VARIABLES,
         USELESS_DEPRECATED,
         WARNINGS,
@@ -1580,6 +1584,79 @@
     "unnecessary use of an `unsafe` block"
 }
 
+declare_lint! {
+    /// The `unused_unsafe_after_inlining` lint detects `unsafe` blocks which
+    /// are only needed to call `unsafe` functions that turn out not to need
+    /// `unsafe` once they are inlined.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs optimizations)
+    /// #![warn(unused_unsafe_after_inlining)]
+    ///
+    /// #[inline]
+    /// unsafe fn nothing_unsafe() -> u32 {
+    ///     1
+    /// }
+    ///
+    /// pub fn main() {
+    ///     unsafe { nothing_unsafe() };
+    /// }
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// warning: `unsafe` block is unnecessary after inlining
+    ///  --> lint_example.rs:9:5
+    ///   |
+    /// 9 |     unsafe { nothing_unsafe() };
+    ///   |     ^^^^^^ unnecessary `unsafe` block
+    /// ```
+    ///
+    /// along with a note that the `unsafe` functions called in the block were
+    /// inlined, and that none of them need `unsafe` themselves.
+    ///
+    /// ### Explanation
+    ///
+    /// The `unsafe` block is still required to call the `unsafe` function,
+    /// but the function may not need to be `unsafe` at all. This lint only
+    /// fires when the MIR inliner runs, and only looks at the functions it
+    /// inlined, so it is both incomplete and allowed by default.
+    pub UNUSED_UNSAFE_AFTER_INLINING,
+    Allow,
+    "`unsafe` block which is unnecessary once the functions it calls are inlined"
+}
+
+declare_lint! {
+    /// The `static_mut_never_written` lint detects `static mut` items which
+    /// are read, but never written to.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// static mut LIMIT: u32 = 10;
+    ///
+    /// fn main() {
+    ///     println!("{}", unsafe { LIMIT });
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Every access to a `static mut` requires `unsafe`, even when it is only
+    /// ever read. A `static` which is never modified doesn't need to be
+    /// mutable, and can be read safely.
+    ///
+    /// Statics which are exported, or whose symbol is visible to foreign code,
+    /// may be written outside of the crate, so this lint ignores them.
+    pub STATIC_MUT_NEVER_WRITTEN,
+    Warn,
+    "`static mut` which is never written"
+}
+
 declare_lint! {
     /// The `unused_mut` lint detects mut variables which don't need to be
     /// mutable.
@@ -2942,6 +3019,69 @@
     "suggest casting to a function pointer when attempting to take references to function items",
 }
 
+declare_lint! {
+    /// The `implicit_fn_pointer_coercions` lint detects function items which
+    /// are implicitly coerced to function pointers.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(implicit_fn_pointer_coercions)]
+    ///
+    /// fn foo(x: u32) -> u32 { x }
+    ///
+    /// fn apply(f: fn(u32) -> u32) -> u32 { f(1) }
+    ///
+    /// fn main() {
+    ///     apply(foo);
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Each function item has its own zero-sized type, which is silently
+    /// coerced to a function pointer where one is expected. The coercion
+    /// itself is free, but it hides that calls through the resulting pointer
+    /// are indirect. Casting the function item with `as` makes this explicit.
+    pub IMPLICIT_FN_POINTER_COERCIONS,
+    Allow,
+    "detects function items implicitly coerced to function pointers",
+}
+
+declare_lint! {
+    /// The `redundant_unchecked_indexing` lint detects calls to
+    /// `get_unchecked` on a slice with an index which was already checked
+    /// against the length of the slice.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(redundant_unchecked_indexing)]
+    ///
+    /// fn get(v: &[u32], i: usize) -> u32 {
+    ///     if i < v.len() {
+    ///         unsafe { *v.get_unchecked(i) }
+    ///     } else {
+    ///         0
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// `get_unchecked` and `get_unchecked_mut` skip the bounds check, so it is
+    /// up to the caller to make sure that the index is in bounds. When the code
+    /// right before already checks it, indexing the slice is just as fast, as
+    /// the optimizer removes the second check, and doesn't need `unsafe` code.
+    pub REDUNDANT_UNCHECKED_INDEXING,
+    Allow,
+    "detects unchecked indexing of a slice with an index already checked against its length",
+}
+
 declare_lint! {
     /// The `uninhabited_static` lint detects uninhabited statics.
     ///
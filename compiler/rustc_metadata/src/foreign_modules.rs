@@ -18,7 +18,7 @@ pub(crate) fn collect(tcx: TyCtxt<'_>, LocalCrate: LocalCrate) -> FxIndexMap<Def
         let def_id = id.owner_id.to_def_id();
         let item = tcx.hir().item(id);
 
-        if let hir::ItemKind::ForeignMod { abi, items } = item.kind {
+        if let hir::ItemKind::ForeignMod { abi, items, .. } = item.kind {
             let foreign_items = items.iter().map(|it| it.id.owner_id.to_def_id()).collect();
             modules.insert(def_id, ForeignModule { def_id, abi, foreign_items });
         }
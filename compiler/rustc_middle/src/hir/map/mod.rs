@@ -730,6 +730,18 @@ pub fn get_foreign_abi(self, hir_id: HirId) -> Abi {
         )
     }
 
+    /// Whether the foreign item `hir_id` is declared in an `unsafe extern` block.
+    pub fn is_in_unsafe_foreign_mod(self, hir_id: HirId) -> bool {
+        let parent = self.get_parent_item(hir_id);
+        matches!(
+            self.tcx.hir_owner(parent).map(|owner| owner.node),
+            Some(OwnerNode::Item(Item {
+                kind: ItemKind::ForeignMod { unsafety: Unsafety::Unsafe, .. },
+                ..
+            }))
+        )
+    }
+
     pub fn expect_owner(self, def_id: LocalDefId) -> OwnerNode<'hir> {
         self.tcx
             .hir_owner(OwnerId { def_id })
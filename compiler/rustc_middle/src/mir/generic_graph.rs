@@ -3,8 +3,9 @@
 use rustc_middle::mir::*;
 use rustc_middle::ty::TyCtxt;
 
-/// Convert an MIR function into a gsgdt Graph
-pub fn mir_fn_to_generic_graph<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'_>) -> Graph {
+/// Convert an MIR function into a gsgdt Graph. If `only_cfg` is set, the statements of the blocks
+/// are not shown, only their number.
+pub fn mir_fn_to_generic_graph<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'_>, only_cfg: bool) -> Graph {
     let def_id = body.source.def_id();
     let def_name = graphviz_safe_def_name(def_id);
     let graph_name = format!("Mir_{def_name}");
@@ -14,7 +15,7 @@ pub fn mir_fn_to_generic_graph<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'_>) -> Grap
     let nodes: Vec<Node> = body
         .basic_blocks
         .iter_enumerated()
-        .map(|(block, _)| bb_to_graph_node(block, body, dark_mode))
+        .map(|(block, _)| bb_to_graph_node(block, body, dark_mode, only_cfg))
         .collect();
 
     // Edges
@@ -34,12 +35,17 @@ pub fn mir_fn_to_generic_graph<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'_>) -> Grap
     Graph::new(graph_name, nodes, edges)
 }
 
-fn bb_to_graph_node(block: BasicBlock, body: &Body<'_>, dark_mode: bool) -> Node {
+fn bb_to_graph_node(
+    block: BasicBlock,
+    body: &Body<'_>,
+    dark_mode: bool,
+    only_cfg: bool,
+) -> Node {
     let def_id = body.source.def_id();
     let data = &body[block];
     let label = node(def_id, block);
 
-    let (title, bgcolor) = if data.is_cleanup {
+    let (mut title, bgcolor) = if data.is_cleanup {
         let color = if dark_mode { "royalblue" } else { "lightblue" };
         (format!("{} (cleanup)", block.index()), color)
     } else {
@@ -48,7 +54,13 @@ fn bb_to_graph_node(block: BasicBlock, body: &Body<'_>, dark_mode: bool) -> Node
     };
 
     let style = NodeStyle { title_bg: Some(bgcolor.to_owned()), ..Default::default() };
-    let mut stmts: Vec<String> = data.statements.iter().map(|x| format!("{x:?}")).collect();
+    let mut stmts: Vec<String> = if only_cfg {
+        let count = data.statements.len();
+        title.push_str(&format!(" ({count} statement{})", if count == 1 { "" } else { "s" }));
+        Vec::new()
+    } else {
+        data.statements.iter().map(|x| format!("{x:?}")).collect()
+    };
 
     // add the terminator to the stmts, gsgdt can print it out separately
     let mut terminator_head = String::new();
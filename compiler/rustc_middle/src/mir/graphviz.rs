@@ -50,6 +50,43 @@ pub fn write_mir_fn_graphviz<'tcx, W>(
     subgraph: bool,
     w: &mut W,
 ) -> io::Result<()>
+where
+    W: Write,
+{
+    // Graph label
+    let mut label = String::from("");
+    // FIXME: remove this unwrap
+    write_graph_label(tcx, body, &mut label).unwrap();
+    write_generic_graphviz(tcx, body, subgraph, false, label, w)
+}
+
+/// Write a graphviz DOT graph of the control-flow graph of the MIR, labeled with `boundary`, the
+/// point in the pipeline the MIR is at. The blocks only show their number of statements.
+pub fn write_mir_fn_cfg_graphviz<'tcx, W>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'_>,
+    boundary: &str,
+    w: &mut W,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let label = format!(
+        "fn {} {}",
+        dot::escape_html(&tcx.def_path_str(body.source.def_id())),
+        dot::escape_html(boundary),
+    );
+    write_generic_graphviz(tcx, body, false, true, label, w)
+}
+
+fn write_generic_graphviz<'tcx, W>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'_>,
+    subgraph: bool,
+    only_cfg: bool,
+    label: String,
+    w: &mut W,
+) -> io::Result<()>
 where
     W: Write,
 {
@@ -66,11 +103,7 @@ pub fn write_mir_fn_graphviz<'tcx, W>(
         content_attrs.push(r#"fontcolor="white""#);
     }
 
-    // Graph label
-    let mut label = String::from("");
-    // FIXME: remove this unwrap
-    write_graph_label(tcx, body, &mut label).unwrap();
-    let g = mir_fn_to_generic_graph(tcx, body);
+    let g = mir_fn_to_generic_graph(tcx, body, only_cfg);
     let settings = GraphvizSettings {
         graph_attrs: Some(graph_attrs.join(" ")),
         node_attrs: Some(content_attrs.join(" ")),
@@ -2,6 +2,7 @@
 //!
 //! [rustc dev guide]: https://rustc-dev-guide.rust-lang.org/mir/index.html
 
+use crate::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use crate::mir::interpret::{AllocRange, ConstAllocation, Scalar};
 use crate::mir::visit::MirVisitable;
 use crate::ty::codec::{TyDecoder, TyEncoder};
@@ -17,7 +18,7 @@
 use rustc_errors::{DiagnosticArgValue, DiagnosticMessage, ErrorGuaranteed, IntoDiagnosticArg};
 use rustc_hir::def::{CtorKind, Namespace};
 use rustc_hir::def_id::{DefId, CRATE_DEF_ID};
-use rustc_hir::{self, CoroutineKind, ImplicitSelfKind};
+use rustc_hir::{self, CoroutineKind, ImplicitSelfKind, LangItem};
 use rustc_hir::{self as hir, HirId};
 use rustc_session::Session;
 use rustc_target::abi::{FieldIdx, VariantIdx};
@@ -27,6 +28,7 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::graph::dominators::Dominators;
+use rustc_index::bit_set::BitSet;
 use rustc_index::{Idx, IndexSlice, IndexVec};
 use rustc_serialize::{Decodable, Encodable};
 use rustc_span::symbol::Symbol;
@@ -68,7 +70,8 @@
 pub use self::generic_graph::graphviz_safe_def_name;
 pub use self::graphviz::write_mir_graphviz;
 pub use self::pretty::{
-    create_dump_file, display_allocation, dump_enabled, dump_mir, write_mir_pretty, PassWhere,
+    create_dump_file, display_allocation, dump_enabled, dump_mir, dump_mir_cfg, write_mir_pretty,
+    PassWhere,
 };
 pub use consts::*;
 use pretty::pretty_print_const_value;
@@ -612,6 +615,64 @@ pub fn caller_location_span<T>(
         // No inlined `SourceScope`s, or all of them were `#[track_caller]`.
         caller_location.unwrap_or_else(|| from_span(source_info.span))
     }
+
+    /// Returns the blocks which only lead to a panic or to `unreachable`. A block is cold if it is
+    /// `unreachable`, if it calls a `#[cold]` function or a panic lang item, or if all its
+    /// successors are cold. Unwind edges don't count, as cleanup code is never on the normal
+    /// flow, and blocks without successors, like `return`, are never cold.
+    pub fn cold_blocks(&self, tcx: TyCtxt<'tcx>) -> BitSet<BasicBlock> {
+        let basic_blocks = &self.basic_blocks;
+        let mut cold = BitSet::new_empty(basic_blocks.len());
+        for (bb, data) in basic_blocks.iter_enumerated() {
+            let is_cold = match data.terminator().kind {
+                TerminatorKind::Unreachable => true,
+                TerminatorKind::Call { ref func, .. } => {
+                    func.const_fn_def().is_some_and(|(def_id, _)| is_cold_fn(tcx, def_id))
+                }
+                _ => false,
+            };
+            if is_cold {
+                cold.insert(bb);
+            }
+        }
+
+        // Successors are mostly visited first in postorder, so this only loops again for cycles.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (bb, data) in traversal::postorder(self) {
+                if cold.contains(bb) {
+                    continue;
+                }
+                let mut successors = data
+                    .terminator()
+                    .successors()
+                    .filter(|&succ| data.is_cleanup || !basic_blocks[succ].is_cleanup)
+                    .peekable();
+                if successors.peek().is_some() && successors.all(|succ| cold.contains(succ)) {
+                    cold.insert(bb);
+                    changed = true;
+                }
+            }
+        }
+        cold
+    }
+}
+
+fn is_cold_fn(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    const PANICS: &[LangItem] = &[
+        LangItem::Panic,
+        LangItem::PanicNounwind,
+        LangItem::PanicFmt,
+        LangItem::PanicBoundsCheck,
+        LangItem::PanicMisalignedPointerDereference,
+        LangItem::PanicCannotUnwind,
+        LangItem::PanicInCleanup,
+        LangItem::BeginPanic,
+    ];
+    let lang_items = tcx.lang_items();
+    PANICS.iter().any(|&item| lang_items.get(item) == Some(def_id))
+        || tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::COLD)
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, TyEncodable, TyDecodable, HashStable)]
@@ -4,7 +4,7 @@
 use std::io::{self, Write as _};
 use std::path::{Path, PathBuf};
 
-use super::graphviz::write_mir_fn_graphviz;
+use super::graphviz::{write_mir_fn_cfg_graphviz, write_mir_fn_graphviz};
 use super::spanview::write_mir_fn_spanview;
 use either::Either;
 use rustc_ast::InlineAsmTemplatePiece;
@@ -92,6 +92,11 @@ pub fn dump_enabled(tcx: TyCtxt<'_>, pass_name: &str, def_id: DefId) -> bool {
     let Some(ref filters) = tcx.sess.opts.unstable_opts.dump_mir else {
         return false;
     };
+    filters_match(tcx, filters, pass_name, def_id)
+}
+
+/// Whether the filters given to `-Zdump-mir` or `-Zdump-mir-cfg` select the pass and the body.
+fn filters_match(tcx: TyCtxt<'_>, filters: &str, pass_name: &str, def_id: DefId) -> bool {
     // see notes on #41697 below
     let node_path = ty::print::with_forced_impl_filename_line!(tcx.def_path_str(def_id));
     filters.split('|').any(|or_filter| {
@@ -158,6 +163,38 @@ fn dump_matched_mir_node<'tcx, F>(
     }
 }
 
+/// If `-Zdump-mir-cfg` selects the pass and the body, dumps the control-flow graph of `body` to the
+/// `<fn>.<pass_name>.<disambiguator>.dot` graphviz file. Each block only shows its number of
+/// statements and its terminator, and the graph is labeled with the pass boundary.
+pub fn dump_mir_cfg<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    pass_name: &str,
+    disambiguator: &str,
+    body: &Body<'tcx>,
+) {
+    let Some(ref filters) = tcx.sess.opts.unstable_opts.dump_mir_cfg else {
+        return;
+    };
+    let source = body.source;
+    // Shims of different types share the `DefId` of their item, and would dump to the same file.
+    if !matches!(source.instance, ty::InstanceDef::Item(_))
+        || !filters_match(tcx, filters, pass_name, source.def_id())
+    {
+        return;
+    }
+
+    let _: io::Result<()> = try {
+        let item_name = tcx.def_path(source.def_id()).to_filename_friendly_no_crate();
+        let promotion_id = match source.promoted {
+            Some(id) => format!("-{id:?}"),
+            None => String::new(),
+        };
+        let file_basename = format!("{item_name}{promotion_id}.{pass_name}.{disambiguator}");
+        let mut file = create_dump_file_with_basename(tcx, &file_basename, "dot")?;
+        write_mir_fn_cfg_graphviz(tcx, body, &format!("{disambiguator} {pass_name}"), &mut file)?;
+    };
+}
+
 /// Returns the file basename portion (without extension) of a filename path
 /// where we should dump a MIR representation output files.
 fn dump_file_basename<'tcx>(
@@ -6,7 +6,7 @@
 use rustc_data_structures::unord::UnordSet;
 use rustc_errors::ErrorGuaranteed;
 use rustc_hir as hir;
-use rustc_hir::def_id::LocalDefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_index::bit_set::BitMatrix;
 use rustc_index::{Idx, IndexVec};
 use rustc_span::symbol::Symbol;
@@ -31,14 +31,31 @@ pub enum UnsafetyViolationKind {
 pub enum UnsafetyViolationDetails {
     CallToUnsafeFunction,
     UseOfInlineAssembly,
-    InitializingTypeWith,
-    CastOfPointerToInt,
+    InitializingTypeWith {
+        /// The invariant of the restricted field beyond its valid range, if it has one.
+        field_validity: Option<FieldValidity>,
+    },
+    CastOfPointerToInt {
+        /// Whether the pointer is cast to `usize`, rather than to some other integer type.
+        to_usize: bool,
+    },
     UseOfMutableStatic,
-    UseOfExternStatic,
-    DerefOfRawPointer,
+    UseOfExternStatic {
+        /// Whether the static is declared in an `unsafe extern` block, which does not make using
+        /// it safe.
+        in_unsafe_extern_block: bool,
+    },
+    DerefOfRawPointer {
+        /// The type the pointer points to, if the pointer was cast from an integer.
+        from_int_pointee: Option<String>,
+    },
     AccessToUnionField,
     MutationOfLayoutConstrainedField,
-    BorrowOfLayoutConstrainedField,
+    BorrowOfLayoutConstrainedField {
+        /// Whether the field is `Copy` and borrowed with `&`, so that a copy of it can be borrowed
+        /// instead.
+        can_borrow_copy: bool,
+    },
     CallToFunctionWith {
         /// Target features enabled in callee's `#[target_feature]` but missing in
         /// caller's `#[target_feature]`.
@@ -49,6 +66,15 @@ pub enum UnsafetyViolationDetails {
     },
 }
 
+/// The kinds of fields whose values must satisfy more than being in a range of integers.
+#[derive(Copy, Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
+pub enum FieldValidity {
+    Reference,
+    Bool,
+    Char,
+    Enum,
+}
+
 #[derive(Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
 pub struct UnsafetyViolation {
     pub source_info: SourceInfo,
@@ -77,6 +103,62 @@ pub struct UnsafetyCheckResult {
 
     /// This is `Some` iff the item is not a closure.
     pub unused_unsafes: Option<Vec<(hir::HirId, UnusedUnsafe)>>,
+
+    /// Target features enabled in the `#[target_feature]` of the functions called in this
+    /// function, sorted by name. Closures called by this function are not taken into account.
+    pub required_target_features: Vec<Symbol>,
+
+    /// Local `static mut`s read by this function.
+    pub read_mutable_statics: UnordSet<LocalDefId>,
+
+    /// Local `static mut`s this function may write to, directly or by taking their address.
+    pub written_mutable_statics: UnordSet<LocalDefId>,
+
+    /// All the operations in this function which require `unsafe`, including the ones in
+    /// `unsafe` blocks. Closures have their own. This is only collected for
+    /// `-Zemit-unsafety-json`.
+    pub unsafe_operations: Vec<UnsafeOperation>,
+}
+
+impl<'tcx> TyCtxt<'tcx> {
+    /// The unsafe operations in this `LocalDefId` that are not covered by an `unsafe` block,
+    /// as found by the MIR unsafety checker. No diagnostics are emitted.
+    ///
+    /// Closures and inline consts are checked together with their enclosing body, so for them
+    /// the violations of the typeck root are returned.
+    pub fn unsafety_violations(self, def_id: LocalDefId) -> &'tcx [UnsafetyViolation] {
+        let root = self.typeck_root_def_id(def_id.to_def_id()).expect_local();
+        &self.unsafety_check_result(root).violations
+    }
+}
+
+/// An operation which requires `unsafe`, whether or not it is in an `unsafe` block.
+#[derive(Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
+pub struct UnsafeOperation {
+    pub span: Span,
+    pub details: UnsafetyViolationDetails,
+    /// Whether the operation is in an `unsafe fn`, or in a closure in one.
+    pub in_unsafe_fn: bool,
+}
+
+/// A use of a `const` item that modifies the temporary created for it, which is linted by
+/// `CONST_ITEM_MUTATION`.
+#[derive(Copy, Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
+pub struct ConstItemMutation {
+    pub span: Span,
+    pub lint_root: hir::HirId,
+    /// The mutated `const` item.
+    pub const_item: DefId,
+    pub kind: ConstItemMutationKind,
+}
+
+#[derive(Copy, Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
+pub enum ConstItemMutationKind {
+    /// A field of the temporary is assigned to, e.g. `FOO.x = 0`.
+    Modify,
+    /// The temporary is mutably borrowed, e.g. `&mut FOO`, or by calling `FOO.method()` for the
+    /// `method` given here.
+    MutBorrow { method: Option<DefId> },
 }
 
 rustc_index::newtype_index! {
@@ -875,6 +875,22 @@
         cache_on_disk_if { true }
     }
 
+    /// The target features enabled in the `#[target_feature]` of the functions called in this
+    /// `LocalDefId`, as found by the MIR unsafety checker, sorted by name. This includes the
+    /// features which the function enables itself, and calls in closures are not taken into
+    /// account. No diagnostics are emitted.
+    query required_target_features(key: LocalDefId) -> &'tcx [Symbol] {
+        desc { |tcx| "collecting the target features required by `{}`", tcx.def_path_str(key) }
+    }
+
+    /// The uses of `const` items in this `LocalDefId` that modify the temporary created for the
+    /// `const`, as found by the `CONST_ITEM_MUTATION` lint, without emitting it. The body of a
+    /// `const` item itself can be checked this way before it is evaluated.
+    query const_item_mutations(key: LocalDefId) -> &'tcx [mir::ConstItemMutation] {
+        desc { |tcx| "collecting the mutations of `const` items in `{}`", tcx.def_path_str(key) }
+        cache_on_disk_if { true }
+    }
+
     /// Unsafety-check this `LocalDefId` with THIR unsafeck. This should be
     /// used with `-Zthir-unsafeck`.
     query thir_check_unsafety(key: LocalDefId) {
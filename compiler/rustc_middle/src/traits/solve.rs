@@ -59,8 +59,8 @@ pub enum Certainty {
 }
 
 impl Certainty {
-    pub const AMBIGUOUS: Certainty = Certainty::Maybe(MaybeCause::Ambiguity);
-    pub const OVERFLOW: Certainty = Certainty::Maybe(MaybeCause::Overflow);
+    pub const AMBIGUOUS: Certainty = Certainty::Maybe(MaybeCause::Ambiguity(AmbiguityCause::Goal));
+    pub const OVERFLOW: Certainty = Certainty::Maybe(MaybeCause::Overflow(OverflowOrigin::Goal));
 
     /// Use this function to merge the certainty of multiple nested subgoals.
     ///
@@ -73,20 +73,39 @@ impl Certainty {
     /// inside of the solver as we do not distinguish ambiguity from overflow. It does
     /// however matter for diagnostics. If `T: Foo` resulted in overflow and `T: Bar`
     /// in ambiguity without changing the inference state, we still want to tell the
-    /// user that `T: Baz` results in overflow.
+    /// user that `T: Baz` results in overflow. For the same reason, we keep the
+    /// more specific cause or origin when unifying two ambiguities or overflows.
     pub fn unify_with(self, other: Certainty) -> Certainty {
         match (self, other) {
             (Certainty::Yes, Certainty::Yes) => Certainty::Yes,
             (Certainty::Yes, Certainty::Maybe(_)) => other,
             (Certainty::Maybe(_), Certainty::Yes) => self,
-            (Certainty::Maybe(MaybeCause::Ambiguity), Certainty::Maybe(MaybeCause::Ambiguity)) => {
-                Certainty::Maybe(MaybeCause::Ambiguity)
-            }
-            (Certainty::Maybe(MaybeCause::Ambiguity), Certainty::Maybe(MaybeCause::Overflow))
-            | (Certainty::Maybe(MaybeCause::Overflow), Certainty::Maybe(MaybeCause::Ambiguity))
-            | (Certainty::Maybe(MaybeCause::Overflow), Certainty::Maybe(MaybeCause::Overflow)) => {
-                Certainty::Maybe(MaybeCause::Overflow)
-            }
+            (
+                Certainty::Maybe(MaybeCause::Ambiguity(cause)),
+                Certainty::Maybe(MaybeCause::Ambiguity(other_cause)),
+            ) => match (cause, other_cause) {
+                (AmbiguityCause::Goal, AmbiguityCause::Goal) => Certainty::AMBIGUOUS,
+                (AmbiguityCause::UnresolvedAlias, _) | (_, AmbiguityCause::UnresolvedAlias) => {
+                    Certainty::Maybe(MaybeCause::Ambiguity(AmbiguityCause::UnresolvedAlias))
+                }
+            },
+            (
+                Certainty::Maybe(MaybeCause::Ambiguity(_)),
+                Certainty::Maybe(MaybeCause::Overflow(_)),
+            ) => other,
+            (
+                Certainty::Maybe(MaybeCause::Overflow(_)),
+                Certainty::Maybe(MaybeCause::Ambiguity(_)),
+            ) => self,
+            (
+                Certainty::Maybe(MaybeCause::Overflow(origin)),
+                Certainty::Maybe(MaybeCause::Overflow(other_origin)),
+            ) => match (origin, other_origin) {
+                (OverflowOrigin::Goal, OverflowOrigin::Goal) => Certainty::OVERFLOW,
+                (OverflowOrigin::AliasRelate, _) | (_, OverflowOrigin::AliasRelate) => {
+                    Certainty::Maybe(MaybeCause::Overflow(OverflowOrigin::AliasRelate))
+                }
+            },
         }
     }
 }
@@ -97,9 +116,28 @@ pub enum MaybeCause {
     /// We failed due to ambiguity. This ambiguity can either
     /// be a true ambiguity, i.e. there are multiple different answers,
     /// or we hit a case where we just don't bother, e.g. `?x: Trait` goals.
-    Ambiguity,
+    Ambiguity(AmbiguityCause),
     /// We gave up due to an overflow, most often by hitting the recursion limit.
-    Overflow,
+    Overflow(OverflowOrigin),
+}
+
+/// Why we failed to find a unique answer, used to give more specific errors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, HashStable, TypeFoldable, TypeVisitable)]
+pub enum AmbiguityCause {
+    /// There are multiple candidates, or we don't know enough to choose between them.
+    Goal,
+    /// We failed to relate an alias of an `AliasRelate` goal whose generic arguments are still
+    /// unresolved inference variables, so more type annotations are needed.
+    UnresolvedAlias,
+}
+
+/// Where we gave up due to an overflow, used to give more specific errors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, HashStable, TypeFoldable, TypeVisitable)]
+pub enum OverflowOrigin {
+    /// We hit the recursion limit, or failed to reach a fixpoint, while evaluating a goal.
+    Goal,
+    /// We hit the recursion limit while normalizing one side of an `AliasRelate` goal.
+    AliasRelate,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, HashStable, TypeFoldable, TypeVisitable)]
@@ -33,7 +33,7 @@
 use crate::ty::{GenericArg, GenericArgs, GenericArgsRef};
 use rustc_ast::{self as ast, attr};
 use rustc_data_structures::fingerprint::Fingerprint;
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_data_structures::intern::Interned;
 use rustc_data_structures::profiling::SelfProfilerRef;
 use rustc_data_structures::sharded::{IntoPointer, ShardedHashMap};
@@ -76,6 +76,7 @@
 use std::iter;
 use std::mem;
 use std::ops::{Bound, Deref};
+use std::time::Duration;
 
 #[allow(rustc::usage_of_ty_tykind)]
 impl<'tcx> Interner for TyCtxt<'tcx> {
@@ -615,6 +616,14 @@ pub struct GlobalCtxt<'tcx> {
     pub new_solver_evaluation_cache: solve::EvaluationCache<'tcx>,
     pub new_solver_coherence_evaluation_cache: solve::EvaluationCache<'tcx>,
 
+    /// The maximum recursion depth reached by the new solver while proving the obligations of
+    /// each item, only recorded with `-Zreport-solver-depth`.
+    pub solver_depths: Lock<FxIndexMap<LocalDefId, usize>>,
+
+    /// The time spent in each MIR pass on the bodies of each item, only recorded with
+    /// `-Zmir-opt-timings`.
+    pub mir_opt_timings: Lock<FxIndexMap<DefId, FxIndexMap<&'static str, Duration>>>,
+
     /// Data layout specification for the current target.
     pub data_layout: TargetDataLayout,
 
@@ -779,6 +788,8 @@ pub fn create_global_ctxt(
             evaluation_cache: Default::default(),
             new_solver_evaluation_cache: Default::default(),
             new_solver_coherence_evaluation_cache: Default::default(),
+            solver_depths: Default::default(),
+            mir_opt_timings: Default::default(),
             data_layout,
             alloc_map: Lock::new(interpret::AllocMap::new()),
         }
@@ -149,6 +149,15 @@ pub trait DropElaborator<'a, 'tcx>: fmt::Debug {
     ///
     /// This is only relevant for array patterns, which can move out of individual array elements.
     fn array_subpath(&self, path: Self::Path, index: u64, size: u64) -> Option<Self::Path>;
+
+    /// Returns the variant the enum `path` is known to have when it is dropped (or `None` if it
+    /// may have any of them).
+    ///
+    /// If this returns a variant, only the fields of that variant are dropped, without switching
+    /// on the discriminant.
+    fn known_variant(&self, _path: Self::Path) -> Option<VariantIdx> {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -496,6 +505,10 @@ fn open_drop_for_multivariant(
         succ: BasicBlock,
         unwind: Unwind,
     ) -> (BasicBlock, Unwind) {
+        if let Some(variant_index) = self.elaborator.known_variant(self.path) {
+            return self.open_drop_for_known_variant(adt, args, variant_index, succ, unwind);
+        }
+
         let mut values = Vec::with_capacity(adt.variants().len());
         let mut normal_blocks = Vec::with_capacity(adt.variants().len());
         let mut unwind_blocks =
@@ -584,6 +597,35 @@ fn open_drop_for_multivariant(
         )
     }
 
+    /// Drops the fields of `variant_index`, which the enum is known to have.
+    fn open_drop_for_known_variant(
+        &mut self,
+        adt: ty::AdtDef<'tcx>,
+        args: GenericArgsRef<'tcx>,
+        variant_index: VariantIdx,
+        succ: BasicBlock,
+        unwind: Unwind,
+    ) -> (BasicBlock, Unwind) {
+        let tcx = self.tcx();
+        let variant = adt.variant(variant_index);
+        let Some(variant_path) = self.elaborator.downcast_subpath(self.path, variant_index) else {
+            // Nothing was moved out of the variant, so it is either dropped as a whole or has
+            // nothing to drop.
+            let param_env = self.elaborator.param_env();
+            if !variant.fields.iter().any(|field| field.ty(tcx, args).needs_drop(tcx, param_env)) {
+                return (succ, unwind);
+            }
+            let unwind_block = unwind.map(|unwind| self.complete_drop(unwind, Unwind::InCleanup));
+            return (self.complete_drop(succ, unwind), unwind_block);
+        };
+        let base_place = tcx.mk_place_elem(
+            self.place,
+            ProjectionElem::Downcast(Some(variant.name), variant_index),
+        );
+        let fields = self.move_paths_for_fields(base_place, variant_path, variant, args);
+        self.drop_ladder(fields, succ, unwind)
+    }
+
     fn adt_switch_block(
         &mut self,
         adt: ty::AdtDef<'tcx>,
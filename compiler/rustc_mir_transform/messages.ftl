@@ -4,6 +4,7 @@ mir_transform_call_to_unsafe_note = consult the function's documentation for inf
 mir_transform_const_defined_here = `const` item defined here
 
 mir_transform_const_modify = attempting to modify a `const` item
+    .label = this modifies a new temporary, not the `const` item
     .note = each usage of a `const` item creates a new temporary; the original `const` item will not be modified
 
 mir_transform_const_mut_borrow = taking a mutable reference to a `const` item
@@ -11,18 +12,47 @@ mir_transform_const_mut_borrow = taking a mutable reference to a `const` item
     .note2 = the mutable reference will refer to this temporary, not the original `const` item
     .note3 = mutable reference created due to call to this method
 
+mir_transform_const_mut_borrow_cell_help = interior mutability requires a `Cell` or `RefCell`; as `{$ty}` is `Copy`, consider using `Cell<{$ty}>`
+
+mir_transform_const_mut_borrow_interior_mutability_help = interior mutability requires a `Cell` or `RefCell`
+
+mir_transform_const_mut_borrow_static_suggestion = to refer to a single value instead of a new temporary on each use, declare it as a `static`
+
+mir_transform_const_ptr2int_help = {$to_usize ->
+    [true] use `ptr.addr()` to get the address of the pointer, or `ptr.expose_addr()` if it will be cast back into a pointer
+    *[false] get the address as a `usize` with `ptr.addr()`, or with `ptr.expose_addr()` if it will be cast back into a pointer, and convert that instead
+}
 mir_transform_const_ptr2int_label = cast of pointer to int
 mir_transform_const_ptr2int_note = casting pointers to integers in constants
+
+mir_transform_couldnt_emit_unsafety_json =
+    unexpected error occurred while emitting the unsafety JSON: {$error}
+
+mir_transform_deref_ptr_from_int_note = this pointer was cast from an integer, so its address may be null or not aligned for `{$pointee}`
 mir_transform_deref_ptr_label = dereference of raw pointer
 mir_transform_deref_ptr_note = raw pointers may be null, dangling or unaligned; they can violate aliasing rules and cause data races: all of these are undefined behavior
 mir_transform_ffi_unwind_call = call to {$foreign ->
     [true] foreign function
     *[false] function pointer
     } with FFI-unwind ABI
+    .note = the {$abi} ABI allows unwinding, so a panic or foreign exception raised by the callee propagates into Rust code instead of aborting
 
 mir_transform_fn_item_ref = taking a reference to a function item does not give a function pointer
-    .suggestion = cast `{$ident}` to obtain a function pointer
+mir_transform_fn_item_ref_add_call = call `{$ident}` to use its return value
+mir_transform_fn_item_ref_add_cast = cast `{$ident}` to obtain a function pointer
 
+mir_transform_implicit_fn_pointer_coercion = function item `{$ident}` is implicitly coerced to a function pointer
+    .suggestion = cast `{$ident}` to make the coercion explicit
+
+mir_transform_index_out_of_bounds = index {$index} out of bounds for length {$len}
+
+mir_transform_initializing_valid_range_field_note =
+    {$field_validity ->
+        [reference] the field is a reference, so it must also be non-null, aligned, and point to a valid value
+        [bool] the field is a `bool`, so it must also be either `false` or `true`
+        [char] the field is a `char`, so it must also be a valid Unicode scalar value
+        *[enum] the field is an enum, so it must also have the discriminant of one of its variants
+    }
 mir_transform_initializing_valid_range_label = initializing type with `rustc_layout_scalar_valid_range` attr
 mir_transform_initializing_valid_range_note = initializing a layout restricted type's field with a value outside the valid range is undefined behavior
 mir_transform_must_not_suspend = {$pre}`{$def_path}`{$post} held across a suspend point, but should not be
@@ -30,18 +60,43 @@ mir_transform_must_not_suspend = {$pre}`{$def_path}`{$post} held across a suspen
     .note = {$reason}
     .help = consider using a block (`{"{ ... }"}`) to shrink the value's scope, ending before the suspend point
 
+mir_transform_must_not_suspend_path_array_element = `{$outer}` holds elements of type `{$inner}`
+mir_transform_must_not_suspend_path_boxed = `{$outer}` holds a boxed value of type `{$inner}`
+mir_transform_must_not_suspend_path_field = `{$outer}` holds a value of type `{$inner}` in field `{$field}`
+mir_transform_must_not_suspend_path_reference = `{$outer}` refers to a value of type `{$inner}`
+mir_transform_must_not_suspend_path_tuple_element = `{$outer}` holds a value of type `{$inner}` in element {$index}
+mir_transform_must_not_suspend_path_variant_field = `{$outer}` holds a value of type `{$inner}` in field `{$field}` of variant `{$variant}`
+mir_transform_must_not_suspend_state = the value is held in state `{$state}` (variant {$variant}) of the coroutine
+
 mir_transform_mutation_layout_constrained_borrow_label = borrow of layout constrained field with interior mutability
 mir_transform_mutation_layout_constrained_borrow_note = references to fields of layout constrained fields lose the constraints. Coupled with interior mutability, the field can be changed to invalid values
+mir_transform_mutation_layout_constrained_borrow_suggestion = borrow a copy of the field instead
 mir_transform_mutation_layout_constrained_label = mutation of layout constrained field
 mir_transform_mutation_layout_constrained_note = mutating layout constrained fields cannot statically be checked for valid values
 mir_transform_operation_will_panic = this operation will panic at runtime
 
+mir_transform_redundant_unchecked_indexing = unchecked indexing with an index which is already checked against the length of the slice
+    .label = the index is checked against the length here
+
+mir_transform_redundant_unchecked_indexing_suggestion = index the slice instead
+
+mir_transform_required_target_features = required target features: {$features}
+
 mir_transform_requires_unsafe = {$details} is unsafe and requires unsafe {$op_in_unsafe_fn_allowed ->
     [true] function or block
     *[false] block
     }
     .not_inherited = items do not inherit unsafety from separate enclosing items
 
+mir_transform_requires_unsafe_reference = for more information on why this requires `unsafe`, see {$reference}
+
+mir_transform_static_mut_never_written = mutable static `{$name}` is never written
+    .label = this static is only ever read
+    .note = a `static` which is never written doesn't need to be `mut`, and can be read without `unsafe`
+    .suggestion = consider making it immutable
+
+mir_transform_target_feature_call_attr_label = add `{$feature}` to the features enabled here
+
 mir_transform_target_feature_call_help = in order for the call to be safe, the context requires the following additional target {$missing_target_features_count ->
     [1] feature
     *[count] features
@@ -56,23 +111,43 @@ mir_transform_target_feature_call_note = the {$build_target_features} target {$b
     *[count] them
     } in `#[target_feature]`
 
+mir_transform_target_feature_call_suggestion = consider adding `#[target_feature(enable = "{$missing_target_features_enable}")]` to the calling function{$runtime_detection ->
+    [true] , or checking for the {$missing_target_features_count ->
+        [1] feature
+        *[count] features
+        } at runtime with `is_x86_feature_detected!` before calling it in an `unsafe` block
+    *[false] {""}
+    }
+
 mir_transform_unaligned_packed_ref = reference to packed field is unaligned
     .note = packed structs are only aligned by one byte, and many modern architectures penalize unaligned field accesses
     .note_ub = creating a misaligned reference is undefined behavior (even if that reference is never dereferenced)
     .help = copy the field contents to a local variable, or replace the reference with a raw pointer and use `read_unaligned`/`write_unaligned` (loads and stores via `*p` must be properly aligned even when using raw pointers)
 
+mir_transform_unaligned_packed_ref_addr_of_suggestion = replace the reference with a raw pointer
+mir_transform_unaligned_packed_ref_copy_suggestion = copy the field contents into a temporary and borrow that instead
+mir_transform_unaligned_packed_ref_offset = field `{$field}` is at offset {$offset} but requires alignment {$required_align}
+
 mir_transform_union_access_label = access to union field
 mir_transform_union_access_note = the field may not be properly initialized: using uninitialized data will cause undefined behavior
 mir_transform_unsafe_op_in_unsafe_fn = {$details} is unsafe and requires unsafe block (error E0133)
     .suggestion = consider wrapping the function body in an unsafe block
+    .suggestion_allow = or allow unsafe operations in the whole function
     .note = an unsafe function restricts its caller, but its body is safe by default
 
 mir_transform_unused_unsafe = unnecessary `unsafe` block
-    .label = because it's nested under this `unsafe` block
+    .label = already inside this `unsafe` block
+    .suggestion = remove the `unsafe` block
+
+mir_transform_unused_unsafe_no_unsafe_ops = this block contains no unsafe operations
+
+mir_transform_unused_unsafe_post_mono = `unsafe` block is unnecessary after inlining
+mir_transform_unused_unsafe_post_mono_note = the `unsafe` functions called in this block were inlined, and none of them need `unsafe` themselves
 
 mir_transform_use_of_asm_label = use of inline assembly
 mir_transform_use_of_asm_note = inline assembly is entirely unchecked and can cause undefined behavior
 mir_transform_use_of_extern_static_label = use of extern static
 mir_transform_use_of_extern_static_note = extern statics are not controlled by the Rust type system: invalid data, aliasing violations or data races will cause undefined behavior
+mir_transform_use_of_extern_static_unsafe_extern_note = declaring the `extern` block `unsafe` asserts that its declarations are correct, but reading the static still requires `unsafe`, as it may be changed by foreign code
 mir_transform_use_of_static_mut_label = use of mutable static
 mir_transform_use_of_static_mut_note = mutable statics can be mutated by multiple threads: aliasing violations or data races will cause undefined behavior
@@ -0,0 +1,77 @@
+//! Reports the `arithmetic_overflow` and `unconditional_panic` lints for assertions which are
+//! known to fail.
+//!
+//! `ConstPropLint` reports these lints for the assertions it can evaluate. Other passes, including
+//! ones in drivers outside of the compiler, can report them with the same messages using
+//! [`AssertLintBuilder`]:
+//! ```ignore (needs a `TyCtxt`)
+//! use rustc_middle::mir::AssertKind;
+//! use rustc_mir_transform::assert_lint::AssertLintBuilder;
+//!
+//! let source_info = body.source_info(location);
+//! let lint_root = source_info.scope.lint_root(&body.source_scopes).unwrap();
+//! // error: this operation will panic at runtime
+//! //   = index out of bounds: the length is 3 but the index is 5
+//! AssertLintBuilder::new(source_info.span, AssertKind::BoundsCheck { len: 3, index: 5 })
+//!     .emit(tcx, lint_root);
+//! ```
+//! The values in the `AssertKind` are shown with their `Debug` implementation, so passes which
+//! evaluate them should use `ConstInt`, which shows them like literals.
+
+use std::fmt::Debug;
+
+use rustc_hir::HirId;
+use rustc_middle::mir::{AssertKind, BinOp};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Span;
+
+use crate::errors::{AssertLint, AssertLintKind};
+
+/// Builds and emits the lint for an assertion which always fails.
+pub struct AssertLintBuilder<P> {
+    lint: AssertLint<P>,
+}
+
+impl<P: Debug> AssertLintBuilder<P> {
+    /// Starts building the lint for the assertion `assert_kind` at `span`.
+    ///
+    /// Overflowing arithmetic is reported as `arithmetic_overflow`, every other assertion,
+    /// including overflowing divisions, as `unconditional_panic`.
+    pub fn new(span: Span, assert_kind: AssertKind<P>) -> Self {
+        let lint_kind = match assert_kind {
+            AssertKind::Overflow(BinOp::Div | BinOp::Rem, ..) => AssertLintKind::UnconditionalPanic,
+            AssertKind::Overflow(..) | AssertKind::OverflowNeg(_) => {
+                AssertLintKind::ArithmeticOverflow
+            }
+            _ => AssertLintKind::UnconditionalPanic,
+        };
+        AssertLintBuilder {
+            lint: AssertLint {
+                span,
+                assert_kind,
+                lint_kind,
+                const_eval_only: false,
+                index_span: None,
+            },
+        }
+    }
+
+    /// Marks the assertion as being in a body which is only evaluated at compile time when it is
+    /// used, like the default value of an associated const. Overflows in such bodies are only
+    /// warned about by default.
+    pub fn const_eval_only(mut self, const_eval_only: bool) -> Self {
+        self.lint.const_eval_only = const_eval_only;
+        self
+    }
+
+    /// Points at the index of an out of bounds indexing operation.
+    pub fn index_span(mut self, index_span: Span) -> Self {
+        self.lint.index_span = Some(index_span);
+        self
+    }
+
+    /// Emits the lint at the level it has at `lint_root`.
+    pub fn emit(self, tcx: TyCtxt<'_>, lint_root: HirId) {
+        self.lint.emit(tcx, lint_root);
+    }
+}
@@ -0,0 +1,290 @@
+//! Replaces `Box`es which never escape the body by locals.
+//!
+//! After `ElaborateBoxDerefs`, a `Box` which is only used through dereferences looks like this:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _2 = Box::<u32>::new(move _1) -> [return: bb1, unwind continue];
+//! }
+//!
+//! bb1: {
+//!     _3 = (((_2.0: std::ptr::Unique<u32>).0: std::ptr::NonNull<u32>).0: *const u32);
+//!     _0 = (*_3);
+//!     drop(_2) -> [return: bb2, unwind continue];
+//! }
+//! ```
+//! Nothing ever observes the address of the allocation beyond the lifetime of `_2`, so we can
+//! store the value in a new local `_4` instead, replace `(*_3)` by `_4`, and drop `_4` where `_2`
+//! was dropped:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _4 = move _1;
+//!     goto -> bb1;
+//! }
+//!
+//! bb1: {
+//!     _0 = _4;
+//!     drop(_4) -> [return: bb2, unwind continue];
+//! }
+//! ```
+//!
+//! This is very conservative. The `Box` must be a temporary which is only assigned by a single
+//! call to `Box::new`, and which is otherwise only dropped, or read to obtain the pointer to its
+//! contents. If the box is moved anywhere, be it into a call or into the return place, borrowed,
+//! or mentioned in debuginfo, we leave it alone. The pointers obtained from it may only be
+//! dereferenced. In particular, moving the contents out of the box changes how it is dropped, so
+//! those boxes are left alone as well.
+//!
+//! Raw pointers to the contents could outlive the `Box`, so taking one counts as an escape too.
+//! Otherwise, the new local has no storage markers, so it outlives every reference derived from
+//! the `Box`. Values larger than `MAX_SIZE` may have been boxed to keep them off the stack, so
+//! they stay where they are.
+
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::{
+    MutVisitor, MutatingUseContext, NonMutatingUseContext, NonUseContext, PlaceContext, Visitor,
+};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::sym;
+use rustc_target::abi::FieldIdx;
+
+use crate::MirPass;
+
+/// The size in bytes of the largest value we move out of a `Box`.
+const MAX_SIZE: u64 = 256;
+
+pub struct BoxToStack;
+
+impl<'tcx> MirPass<'tcx> for BoxToStack {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let Some(box_new) = tcx.get_diagnostic_item(sym::box_new) else { return };
+
+        // The block of the `Box::new` call assigning each box.
+        let mut allocations: IndexVec<Local, Option<BasicBlock>> =
+            IndexVec::from_elem(None, &body.local_decls);
+        let mut escaped = BitSet::new_empty(body.local_decls.len());
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Call { ref func, ref args, destination, target: Some(_), .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            if !matches!(func.const_fn_def(), Some((def_id, _)) if def_id == box_new) {
+                continue;
+            }
+            let Some(local) = destination.as_local() else { continue };
+            if body.local_kind(local) != LocalKind::Temp || args.len() != 1 {
+                continue;
+            }
+            if allocations[local].replace(bb).is_some() {
+                escaped.insert(local);
+            }
+        }
+        if allocations.iter().all(Option::is_none) {
+            return;
+        }
+
+        // The box each pointer to the contents of a box was read from.
+        let mut pointers: IndexVec<Local, Option<Local>> =
+            IndexVec::from_elem(None, &body.local_decls);
+        for data in body.basic_blocks.iter() {
+            for statement in &data.statements {
+                if let Some((pointer, boxed)) = box_pointer_read(statement)
+                    && allocations[boxed].is_some()
+                {
+                    if pointers[pointer].replace(boxed).is_some() {
+                        escaped.insert(boxed);
+                    }
+                }
+            }
+        }
+
+        let mut uses = BoxUses { allocations: &allocations, pointers: &pointers, escaped };
+        uses.visit_body(body);
+        let escaped = uses.escaped;
+
+        // The local replacing each box.
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let mut replacements: IndexVec<Local, Option<Local>> =
+            IndexVec::from_elem(None, &body.local_decls);
+        for (boxed, allocation) in allocations.iter_enumerated() {
+            if allocation.is_none() || escaped.contains(boxed) {
+                continue;
+            }
+            let decl = &body.local_decls[boxed];
+            let ty = decl.ty.boxed_ty();
+            if !tcx.layout_of(param_env.and(ty)).is_ok_and(|layout| layout.size.bytes() <= MAX_SIZE)
+            {
+                continue;
+            }
+            let value = LocalDecl::with_source_info(ty, decl.source_info);
+            let value = body.local_decls.push(value);
+            debug!(?boxed, ?value, "replacing box by a local");
+            replacements[boxed] = Some(value);
+        }
+        if replacements.iter().all(Option::is_none) {
+            return;
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut_preserves_cfg();
+        for data in basic_blocks.iter_mut() {
+            data.statements.retain(|statement| match box_pointer_read(statement) {
+                Some((_, boxed)) => replacements[boxed].is_none(),
+                None => true,
+            });
+
+            let terminator = data.terminator_mut();
+            match terminator.kind {
+                TerminatorKind::Drop { ref mut place, .. } => {
+                    if let Some(boxed) = place.as_local()
+                        && let Some(value) = replacements[boxed]
+                    {
+                        *place = value.into();
+                    }
+                }
+                TerminatorKind::Call { ref mut args, destination, target: Some(target), .. } => {
+                    if let Some(boxed) = destination.as_local()
+                        && let Some(value) = replacements[boxed]
+                    {
+                        let [arg] = &mut args[..] else { bug!("`Box::new` takes one argument") };
+                        let arg = std::mem::replace(arg, Operand::Copy(value.into()));
+                        let source_info = terminator.source_info;
+                        terminator.kind = TerminatorKind::Goto { target };
+                        data.statements.push(Statement {
+                            source_info,
+                            kind: StatementKind::Assign(Box::new((
+                                value.into(),
+                                Rvalue::Use(arg),
+                            ))),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut values = IndexVec::from_elem(None, &body.local_decls);
+        for (pointer, boxed) in pointers.iter_enumerated() {
+            if let &Some(boxed) = boxed {
+                values[pointer] = replacements[boxed];
+            }
+        }
+        ReplacePointers { tcx, values }.visit_body_preserves_cfg(body);
+    }
+}
+
+/// If `statement` reads the pointer to the contents of a box, like
+/// `_3 = (((_2.0: Unique<T>).0: NonNull<T>).0: *const T)`, returns the pointer and the box.
+fn box_pointer_read(statement: &Statement<'_>) -> Option<(Local, Local)> {
+    let StatementKind::Assign(box (lhs, Rvalue::Use(Operand::Copy(rhs)))) = statement.kind else {
+        return None;
+    };
+    let zero = FieldIdx::from_u32(0);
+    match rhs.projection[..] {
+        [
+            ProjectionElem::Field(a, _),
+            ProjectionElem::Field(b, _),
+            ProjectionElem::Field(c, _),
+        ] if a == zero && b == zero && c == zero => Some((lhs.as_local()?, rhs.local)),
+        _ => None,
+    }
+}
+
+/// Finds the boxes which may escape.
+struct BoxUses<'a> {
+    allocations: &'a IndexVec<Local, Option<BasicBlock>>,
+    pointers: &'a IndexVec<Local, Option<Local>>,
+    escaped: BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for BoxUses<'_> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        if let Some((pointer, boxed)) = box_pointer_read(statement)
+            && self.pointers[pointer] == Some(boxed)
+        {
+            return;
+        }
+        self.super_statement(statement, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        let allocations = self.allocations;
+        let is_box = |place: Place<'_>| place.as_local().map(|local| allocations[local]);
+        match terminator.kind {
+            TerminatorKind::Drop { place, .. } if matches!(is_box(place), Some(Some(_))) => {}
+            TerminatorKind::Call { ref func, ref args, destination, .. }
+                if is_box(destination) == Some(Some(location.block)) =>
+            {
+                self.visit_operand(func, location);
+                for arg in args {
+                    self.visit_operand(arg, location);
+                }
+            }
+            _ => self.super_terminator(terminator, location),
+        }
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        if let Some(boxed) = self.pointers[place.local]
+            && place.projection.first() == Some(&ProjectionElem::Deref)
+        {
+            // Moving out of the box means it is dropped differently, and a raw pointer to the
+            // contents may be used after the box is dropped.
+            if let PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::Move | NonMutatingUseContext::AddressOf,
+            )
+            | PlaceContext::MutatingUse(MutatingUseContext::AddressOf) = context
+            {
+                self.escaped.insert(boxed);
+            }
+            // Visit the locals used to index into the contents.
+            self.visit_projection(place.as_ref(), context, location);
+            return;
+        }
+        self.super_place(place, context, location);
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if let PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead) =
+            context
+        {
+            return;
+        }
+        if self.allocations[local].is_some() {
+            self.escaped.insert(local);
+        }
+        if let Some(boxed) = self.pointers[local] {
+            self.escaped.insert(boxed);
+        }
+    }
+}
+
+/// Replaces the dereferences of pointers to the contents of boxes by the locals replacing them.
+struct ReplacePointers<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    values: IndexVec<Local, Option<Local>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for ReplacePointers<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_place(&mut self, place: &mut Place<'tcx>, context: PlaceContext, location: Location) {
+        let projection = place.projection;
+        if let Some(value) = self.values[place.local]
+            && let [ProjectionElem::Deref, ref rest @ ..] = projection[..]
+        {
+            *place = Place::from(value).project_deeper(rest, self.tcx);
+        }
+        self.super_place(place, context, location);
+    }
+}
@@ -1,26 +1,99 @@
-use rustc_hir::HirId;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::LocalDefId;
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::*;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::query::Providers;
+use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::lint::builtin::CONST_ITEM_MUTATION;
 use rustc_span::def_id::DefId;
-use rustc_span::Span;
 
 use crate::{errors, MirLint};
 
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers { const_item_mutations, ..*providers };
+}
+
+fn const_item_mutations(tcx: TyCtxt<'_>, def: LocalDefId) -> &[ConstItemMutation] {
+    // N.B., this borrow is valid because `mir_const` forces this before stealing `mir_built`.
+    let body = &tcx.mir_built(def).borrow();
+    let mut checker = ConstMutationChecker { body, tcx, target_local: None, mutations: Vec::new() };
+    checker.visit_body(body);
+    tcx.arena.alloc_from_iter(checker.mutations)
+}
+
 pub struct CheckConstItemMutation;
 
 impl<'tcx> MirLint<'tcx> for CheckConstItemMutation {
     fn run_lint(&self, tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
-        let mut checker = ConstMutationChecker { body, tcx, target_local: None };
-        checker.visit_body(body);
+        let def = body.source.def_id().expect_local();
+        for mutation in tcx.const_item_mutations(def) {
+            let konst = tcx.def_span(mutation.const_item);
+            let decorator = match mutation.kind {
+                ConstItemMutationKind::Modify => {
+                    errors::ConstMutate::Modify { write: mutation.span, konst }
+                }
+                ConstItemMutationKind::MutBorrow { method } => errors::ConstMutate::MutBorrow {
+                    method_call: method.map(|method| tcx.def_span(method)),
+                    konst,
+                    interior_mutability: interior_mutability(tcx, mutation.const_item),
+                    static_suggestion: static_suggestion(tcx, mutation.const_item),
+                },
+            };
+            let (lint_root, span) = (mutation.lint_root, mutation.span);
+            tcx.emit_spanned_lint(CONST_ITEM_MUTATION, lint_root, span, decorator);
+        }
+    }
+}
+
+/// Points at `Cell` for `const` items of a `Copy` type, and at interior mutability in general
+/// for the others. Nothing is said for const parameters, whose type can't be changed to a `Cell`.
+fn interior_mutability(
+    tcx: TyCtxt<'_>,
+    const_item: DefId,
+) -> Option<errors::ConstMutateInteriorMutability<'_>> {
+    if !matches!(tcx.def_kind(const_item), DefKind::Const | DefKind::AssocConst) {
+        return None;
+    }
+    let ty = tcx.type_of(const_item).instantiate_identity();
+    if ty.is_copy_modulo_regions(tcx, tcx.param_env(const_item)) {
+        Some(errors::ConstMutateInteriorMutability::Cell { ty })
+    } else {
+        Some(errors::ConstMutateInteriorMutability::Other)
     }
 }
 
+/// Suggests turning `const_item` into a `static`. This is only possible for non-generic
+/// `const` items of the local crate.
+///
+/// A `static` can't hold a mutable reference, so nothing is suggested for `const` items of a
+/// `&mut` type.
+fn static_suggestion(
+    tcx: TyCtxt<'_>,
+    const_item: DefId,
+) -> Option<errors::ConstMutateStaticSuggestion> {
+    if tcx.def_kind(const_item) != DefKind::Const || tcx.generics_of(const_item).count() != 0 {
+        return None;
+    }
+    if let ty::Ref(_, _, Mutability::Mut) = tcx.type_of(const_item).instantiate_identity().kind() {
+        return None;
+    }
+    let item = tcx.hir().expect_item(const_item.as_local()?);
+    if item.span.from_expansion() {
+        return None;
+    }
+    // The `const` keyword is the only token between the visibility and the name of the item.
+    let source_map = tcx.sess.source_map();
+    let between = item.vis_span.between(item.ident.span);
+    let whitespace = source_map.span_take_while(between, |c| c.is_whitespace());
+    let span = source_map.span_until_whitespace(between.with_lo(whitespace.hi()));
+    Some(errors::ConstMutateStaticSuggestion { span })
+}
+
 struct ConstMutationChecker<'a, 'tcx> {
     body: &'a Body<'tcx>,
     tcx: TyCtxt<'tcx>,
     target_local: Option<Local>,
+    mutations: Vec<ConstItemMutation>,
 }
 
 impl<'tcx> ConstMutationChecker<'_, 'tcx> {
@@ -59,14 +132,15 @@ fn is_const_item_without_destructor(&self, local: Local) -> Option<DefId> {
         }
     }
 
-    /// If we should lint on this usage, return the [`HirId`], source [`Span`]
-    /// and [`Span`] of the const item to use in the lint.
-    fn should_lint_const_item_usage(
-        &self,
+    /// Records a mutation of `const_item` through `place`, unless it happens behind a
+    /// dereference.
+    fn record_const_item_usage(
+        &mut self,
         place: &Place<'tcx>,
         const_item: DefId,
         location: Location,
-    ) -> Option<(HirId, Span, Span)> {
+        kind: ConstItemMutationKind,
+    ) {
         // Don't lint on borrowing/assigning when a dereference is involved.
         // If we 'leave' the temporary via a dereference, we must
         // be modifying something else
@@ -82,9 +156,12 @@ fn should_lint_const_item_usage(
                 .assert_crate_local()
                 .lint_root;
 
-            Some((lint_root, source_info.span, self.tcx.def_span(const_item)))
-        } else {
-            None
+            self.mutations.push(ConstItemMutation {
+                span: source_info.span,
+                lint_root,
+                const_item,
+                kind,
+            });
         }
     }
 }
@@ -96,16 +173,8 @@ fn visit_statement(&mut self, stmt: &Statement<'tcx>, loc: Location) {
             // Assigning directly to a constant (e.g. `FOO = true;`) is a hard error,
             // so emitting a lint would be redundant.
             if !lhs.projection.is_empty() {
-                if let Some(def_id) = self.is_const_item_without_destructor(lhs.local)
-                    && let Some((lint_root, span, item)) =
-                        self.should_lint_const_item_usage(lhs, def_id, loc)
-                {
-                    self.tcx.emit_spanned_lint(
-                        CONST_ITEM_MUTATION,
-                        lint_root,
-                        span,
-                        errors::ConstMutate::Modify { konst: item },
-                    );
+                if let Some(def_id) = self.is_const_item_without_destructor(lhs.local) {
+                    self.record_const_item_usage(lhs, def_id, loc, ConstItemMutationKind::Modify);
                 }
             }
             // We are looking for MIR of the form:
@@ -137,21 +206,13 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, loc: Location) {
                 let lint_loc =
                     if method_did.is_some() { self.body.terminator_loc(loc.block) } else { loc };
 
-                let method_call = if let Some((method_did, _)) = method_did {
-                    Some(self.tcx.def_span(method_did))
-                } else {
-                    None
-                };
-                if let Some((lint_root, span, item)) =
-                    self.should_lint_const_item_usage(place, def_id, lint_loc)
-                {
-                    self.tcx.emit_spanned_lint(
-                        CONST_ITEM_MUTATION,
-                        lint_root,
-                        span,
-                        errors::ConstMutate::MutBorrow { method_call, konst: item },
-                    );
-                }
+                let method = method_did.map(|(method_did, _)| method_did);
+                self.record_const_item_usage(
+                    place,
+                    def_id,
+                    lint_loc,
+                    ConstItemMutationKind::MutBorrow { method },
+                );
             }
         }
         self.super_rvalue(rvalue, loc);
@@ -36,7 +36,7 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
         self.super_statement(statement, location);
     }
 
-    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location: Location) {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
         if context.is_borrow() {
             if util::is_disaligned(self.tcx, self.body, self.param_env, *place) {
                 let def_id = self.body.source.instance.def_id();
@@ -51,11 +51,115 @@ fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location:
                         "builtin derive created an unaligned reference"
                     );
                 } else {
-                    self.tcx
-                        .sess
-                        .emit_err(errors::UnalignedPackedRef { span: self.source_info.span });
+                    let offset = self.field_offset(*place);
+                    let suggestion = self.suggestion(*place, context, location);
+                    self.tcx.sess.emit_err(errors::UnalignedPackedRef {
+                        span: self.source_info.span,
+                        offset,
+                        suggestion,
+                    });
                 }
             }
         }
     }
 }
+
+impl<'tcx> PackedRefChecker<'_, 'tcx> {
+    /// Finds the field of a packed struct that `place` goes through and reports its offset
+    /// if that offset is not a multiple of the field's alignment.
+    ///
+    /// Returns `None` if the offset happens to be suitably aligned (the reference is then only
+    /// misaligned because the struct itself is under-aligned), or if the layouts involved cannot
+    /// be computed, e.g. because they depend on generic parameters.
+    fn field_offset(&self, place: Place<'tcx>) -> Option<errors::UnalignedPackedRefOffset> {
+        let (adt, base_ty, field, field_ty) = place
+            .iter_projections()
+            .rev()
+            // Stop at `Deref`; standard ABI alignment applies there.
+            .take_while(|(_base, elem)| !matches!(elem, ProjectionElem::Deref))
+            .find_map(|(base, elem)| {
+                let ProjectionElem::Field(field, field_ty) = elem else { return None };
+                let base_ty = base.ty(self.body, self.tcx).ty;
+                let adt = base_ty.ty_adt_def()?;
+                adt.repr().pack.is_some().then_some((adt, base_ty, field, field_ty))
+            })?;
+
+        let base_layout = self.tcx.layout_of(self.param_env.and(base_ty)).ok()?;
+        let field_layout = self.tcx.layout_of(self.param_env.and(field_ty)).ok()?;
+        if !field_layout.is_sized() {
+            return None;
+        }
+        let offset = base_layout.fields.offset(field.index()).bytes();
+        let required_align = field_layout.align.abi.bytes();
+        if offset % required_align == 0 {
+            return None;
+        }
+        Some(errors::UnalignedPackedRefOffset {
+            field: adt.non_enum_variant().fields[field].name,
+            offset,
+            required_align,
+        })
+    }
+
+    /// Reconstructs the borrowed place from the source of a `&place`/`&mut place` expression and
+    /// suggests either borrowing a copy of it or taking a raw pointer with `addr_of!`.
+    ///
+    /// Autoref'd borrows such as method receivers have no `&` in the source, so we don't try to
+    /// suggest anything for them.
+    fn suggestion(
+        &self,
+        place: Place<'tcx>,
+        context: PlaceContext,
+        location: Location,
+    ) -> Option<errors::UnalignedPackedRefSuggestion> {
+        let span = self.source_info.span;
+        if span.from_expansion() {
+            return None;
+        }
+        let snippet = self.tcx.sess.source_map().span_to_snippet(span).ok()?;
+        let is_mut = matches!(context, PlaceContext::MutatingUse(_));
+        let place_snippet = if is_mut {
+            snippet.strip_prefix("&mut").filter(|rest| rest.starts_with(char::is_whitespace))?
+        } else {
+            snippet.strip_prefix('&')?
+        };
+        let place_snippet = place_snippet.trim_start().to_string();
+
+        let ty = place.ty(self.body, self.tcx).ty;
+        // A pointer to the copy would dangle once the temporary is dropped.
+        if !is_mut
+            && ty.is_copy_modulo_regions(self.tcx, self.param_env)
+            && !self.is_cast_to_raw_pointer(location)
+        {
+            Some(errors::UnalignedPackedRefSuggestion::Copy { span, place: place_snippet })
+        } else {
+            Some(errors::UnalignedPackedRefSuggestion::AddrOf {
+                span,
+                macro_name: if is_mut { "addr_of_mut" } else { "addr_of" },
+                place: place_snippet,
+            })
+        }
+    }
+
+    /// Whether the reference assigned at `location` is cast or coerced to a raw pointer, which
+    /// MIR building turns into `&raw const (*_r)`.
+    fn is_cast_to_raw_pointer(&self, location: Location) -> bool {
+        let Some(statement) = self.body.stmt_at(location).left() else { return false };
+        let Some(reference) = statement.kind.as_assign().and_then(|(dest, _)| dest.as_local())
+        else {
+            return false;
+        };
+        self.body.basic_blocks.iter().flat_map(|data| &data.statements).any(|statement| {
+            match statement.kind.as_assign() {
+                Some((_, Rvalue::AddressOf(_, place))) => {
+                    place.local == reference && place.projection[..] == [ProjectionElem::Deref]
+                }
+                Some((_, Rvalue::Cast(_, operand, ty))) => {
+                    ty.is_unsafe_ptr()
+                        && operand.place().and_then(|place| place.as_local()) == Some(reference)
+                }
+                _ => false,
+            }
+        })
+    }
+}
@@ -0,0 +1,282 @@
+//! Lints the calls to `get_unchecked` and `get_unchecked_mut` on a slice with an index which was
+//! already checked against the length of that slice:
+//! ```ignore (illustrative)
+//! if i < v.len() {
+//!     unsafe { *v.get_unchecked(i) }
+//! }
+//! ```
+//! Indexing `v` is just as fast there, as the optimizer removes the second bounds check.
+//!
+//! The branch on which the index is in bounds has to dominate the call, and the comparison has to
+//! be between the same index and the length of the same slice. We only follow the copies and
+//! reborrows between SSA locals, which is enough to see through the temporaries of built MIR.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::intravisit;
+use rustc_index::{IndexSlice, IndexVec};
+use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCoercion;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::lint::builtin::REDUNDANT_UNCHECKED_INDEXING;
+use rustc_span::{sym, Span};
+
+use crate::ssa::SsaLocals;
+use crate::{errors, MirLint};
+
+pub struct CheckUncheckedIndexing;
+
+impl<'tcx> MirLint<'tcx> for CheckUncheckedIndexing {
+    fn run_lint(&self, tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
+        let get_unchecked = tcx.get_diagnostic_item(sym::slice_get_unchecked);
+        let get_unchecked_mut = tcx.get_diagnostic_item(sym::slice_get_unchecked_mut);
+        let calls: Vec<_> = body
+            .basic_blocks
+            .iter_enumerated()
+            .filter_map(|(block, data)| {
+                let TerminatorKind::Call { ref func, .. } = data.terminator().kind else {
+                    return None;
+                };
+                let (def_id, _) = func.const_fn_def()?;
+                if Some(def_id) == get_unchecked {
+                    Some((block, Mutability::Not))
+                } else if Some(def_id) == get_unchecked_mut {
+                    Some((block, Mutability::Mut))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if calls.is_empty() {
+            return;
+        }
+
+        let ssa = SsaLocals::new(body);
+        let mut defs = IndexVec::from_elem(None, &body.local_decls);
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                if let StatementKind::Assign(box (place, ref rvalue)) = statement.kind
+                    && let Some(local) = place.as_local()
+                    && ssa.is_ssa(local)
+                {
+                    defs[local] = Some(Def::Rvalue(rvalue, Location { block, statement_index }));
+                }
+            }
+            if let TerminatorKind::Call { ref func, ref args, destination, .. } =
+                data.terminator().kind
+                && let Some(local) = destination.as_local()
+                && ssa.is_ssa(local)
+            {
+                defs[local] = Some(Def::Call(func, args));
+            }
+        }
+        let values = Values { tcx, body, ssa: &ssa, defs: &defs };
+
+        let mut checks = Vec::new();
+        for data in body.basic_blocks.iter() {
+            let TerminatorKind::SwitchInt { ref discr, ref targets } = data.terminator().kind else {
+                continue;
+            };
+            let Some((index, slice, in_bounds, span)) = values.bounds_check(discr) else {
+                continue;
+            };
+            // The index is only known to be in bounds in the blocks which the other branches
+            // can't reach.
+            let block = targets.target_for_value(in_bounds.into());
+            if body.basic_blocks.predecessors()[block].len() == 1
+                && targets.all_targets().iter().filter(|&&target| target == block).count() == 1
+            {
+                checks.push(BoundsCheck { block, index, slice, span });
+            }
+        }
+        if checks.is_empty() {
+            return;
+        }
+
+        let dominators = body.basic_blocks.dominators();
+        let method_calls = method_calls(tcx, body);
+        for (block, mutability) in calls {
+            if !dominators.is_reachable(block) {
+                continue;
+            }
+            let terminator = body.basic_blocks[block].terminator();
+            let TerminatorKind::Call { ref args, .. } = terminator.kind else { bug!() };
+            // Ranges are checked against the length differently.
+            let [slice, index] = &args[..] else { continue };
+            if !index.ty(body, tcx).is_usize() {
+                continue;
+            }
+            let (Some(slice), Some(index)) = (values.pointee(slice), values.index(index)) else {
+                continue;
+            };
+            let Some(check) = checks.iter().find(|check| {
+                check.slice == slice
+                    && check.index == index
+                    && dominators.dominates(check.block, block)
+            }) else {
+                continue;
+            };
+
+            let source_info = terminator.source_info;
+            let lint_root = body.source_scopes[source_info.scope]
+                .local_data
+                .as_ref()
+                .assert_crate_local()
+                .lint_root;
+            let suggestion = method_calls.get(&source_info.span).and_then(|&(receiver, index)| {
+                let source_map = tcx.sess.source_map();
+                let receiver = source_map.span_to_snippet(receiver).ok()?;
+                let index = source_map.span_to_snippet(index).ok()?;
+                Some(errors::RedundantUncheckedIndexingSuggestion {
+                    span: source_info.span,
+                    code: format!("{}{receiver}[{index}]", mutability.ref_prefix_str()),
+                })
+            });
+            tcx.emit_spanned_lint(
+                REDUNDANT_UNCHECKED_INDEXING,
+                lint_root,
+                source_info.span,
+                errors::RedundantUncheckedIndexing { check_span: check.span, suggestion },
+            );
+        }
+    }
+}
+
+/// How an SSA local is assigned.
+#[derive(Copy, Clone)]
+enum Def<'a, 'tcx> {
+    Rvalue(&'a Rvalue<'tcx>, Location),
+    Call(&'a Operand<'tcx>, &'a [Operand<'tcx>]),
+}
+
+/// An index, which is either a constant or the value of an SSA local.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Index<'tcx> {
+    Const(Const<'tcx>),
+    Local(Local),
+}
+
+/// A comparison of `index` with the length of the slice at `slice`, which shows that the index
+/// is in bounds in `block`.
+struct BoundsCheck<'tcx> {
+    block: BasicBlock,
+    index: Index<'tcx>,
+    slice: Place<'tcx>,
+    /// The span of the comparison.
+    span: Span,
+}
+
+struct Values<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    ssa: &'a SsaLocals,
+    defs: &'a IndexSlice<Local, Option<Def<'a, 'tcx>>>,
+}
+
+impl<'tcx> Values<'_, 'tcx> {
+    fn ssa_local(&self, operand: &Operand<'tcx>) -> Option<Local> {
+        let local = operand.place()?.as_local()?;
+        self.ssa.is_ssa(local).then_some(local)
+    }
+
+    /// If `discr` compares an index with the length of a slice, returns the index, the slice,
+    /// and the value of `discr` when the index is in bounds, along with the span of the
+    /// comparison.
+    fn bounds_check(
+        &self,
+        discr: &Operand<'tcx>,
+    ) -> Option<(Index<'tcx>, Place<'tcx>, bool, Span)> {
+        let local = self.ssa_local(discr)?;
+        let Def::Rvalue(rvalue, location) = self.defs[local]? else { return None };
+        match *rvalue {
+            // `assert!` branches on the negated condition.
+            Rvalue::UnaryOp(UnOp::Not, ref operand) => {
+                let (index, slice, in_bounds, span) = self.bounds_check(operand)?;
+                Some((index, slice, !in_bounds, span))
+            }
+            Rvalue::BinaryOp(op, box (ref lhs, ref rhs)) => {
+                let (index, len, in_bounds) = match op {
+                    BinOp::Lt => (lhs, rhs, true),
+                    BinOp::Ge => (lhs, rhs, false),
+                    BinOp::Gt => (rhs, lhs, true),
+                    BinOp::Le => (rhs, lhs, false),
+                    _ => return None,
+                };
+                let span = self.body.source_info(location).span;
+                Some((self.index(index)?, self.len_of(len)?, in_bounds, span))
+            }
+            _ => None,
+        }
+    }
+
+    fn index(&self, operand: &Operand<'tcx>) -> Option<Index<'tcx>> {
+        if let Operand::Constant(constant) = operand {
+            return Some(Index::Const(constant.const_));
+        }
+        let local = self.ssa_local(operand)?;
+        match self.defs[local] {
+            Some(Def::Rvalue(Rvalue::Use(operand), _)) => self.index(operand),
+            _ => Some(Index::Local(local)),
+        }
+    }
+
+    /// If `operand` is the length of a slice, returns the place of that slice.
+    fn len_of(&self, operand: &Operand<'tcx>) -> Option<Place<'tcx>> {
+        let local = self.ssa_local(operand)?;
+        match self.defs[local]? {
+            Def::Rvalue(Rvalue::Use(operand), _) => self.len_of(operand),
+            Def::Rvalue(Rvalue::Len(place), _) => Some(*place),
+            Def::Call(func, [slice])
+                if func.const_fn_def().map(|(def_id, _)| def_id)
+                    == self.tcx.lang_items().slice_len_fn() =>
+            {
+                self.pointee(slice)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the place that the reference `operand` points to.
+    fn pointee(&self, operand: &Operand<'tcx>) -> Option<Place<'tcx>> {
+        let local = self.ssa_local(operand)?;
+        match self.defs[local] {
+            Some(Def::Rvalue(
+                Rvalue::Use(operand)
+                | Rvalue::Cast(CastKind::PointerCoercion(PointerCoercion::Unsize), operand, _),
+                _,
+            )) => self.pointee(operand),
+            // A reborrow points to the same place as the reference it goes through.
+            Some(Def::Rvalue(Rvalue::Ref(_, _, place), _))
+                if place.projection[..] == [ProjectionElem::Deref] =>
+            {
+                self.pointee(&Operand::Copy(Place::from(place.local)))
+            }
+            Some(Def::Rvalue(Rvalue::Ref(_, _, place), _)) if !place.is_indirect() => Some(*place),
+            _ => Some(self.tcx.mk_place_deref(Place::from(local))),
+        }
+    }
+}
+
+/// Collects the spans of the receiver and of the argument of the method calls with a single
+/// argument in the body, by the span of the call.
+fn method_calls(tcx: TyCtxt<'_>, body: &Body<'_>) -> FxHashMap<Span, (Span, Span)> {
+    let mut method_calls = MethodCalls::default();
+    if let Some(def_id) = body.source.def_id().as_local()
+        && let Some(body_id) = tcx.hir().maybe_body_owned_by(def_id)
+    {
+        intravisit::Visitor::visit_body(&mut method_calls, tcx.hir().body(body_id));
+    }
+    method_calls.0
+}
+
+#[derive(Default)]
+struct MethodCalls(FxHashMap<Span, (Span, Span)>);
+
+impl<'tcx> intravisit::Visitor<'tcx> for MethodCalls {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::MethodCall(_, receiver, [argument], _) = expr.kind {
+            self.0.insert(expr.span, (receiver.span, argument.span));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
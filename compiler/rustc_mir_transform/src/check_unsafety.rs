@@ -1,18 +1,27 @@
+use rustc_data_structures::fx::FxIndexSet;
 use rustc_data_structures::unord::{ExtendUnord, UnordItems, UnordSet};
 use rustc_hir as hir;
 use rustc_hir::def::DefKind;
-use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::def_id::{DefId, LocalDefId, LOCAL_CRATE};
 use rustc_hir::hir_id::HirId;
 use rustc_hir::intravisit;
 use rustc_hir::{BlockCheckMode, ExprKind, Node};
-use rustc_middle::mir::visit::{MutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
 use rustc_middle::mir::*;
 use rustc_middle::query::Providers;
-use rustc_middle::ty::{self, TyCtxt};
-use rustc_session::lint::builtin::{UNSAFE_OP_IN_UNSAFE_FN, UNUSED_UNSAFE};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::config::SwitchWithOptPath;
+use rustc_session::lint::builtin::{STATIC_MUT_NEVER_WRITTEN, UNSAFE_OP_IN_UNSAFE_FN, UNUSED_UNSAFE};
 use rustc_session::lint::Level;
+use rustc_span::symbol::sym;
+use rustc_span::{BytePos, Span, Symbol};
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
 
+use std::fs::{self, File};
+use std::io::BufWriter;
 use std::ops::Bound;
+use std::path::Path;
 
 use crate::errors;
 
@@ -26,6 +35,16 @@ pub struct UnsafetyChecker<'a, 'tcx> {
 
     /// Used `unsafe` blocks in this function. This is used for the "unused_unsafe" lint.
     used_unsafe_blocks: UnordSet<HirId>,
+
+    /// Target features of the functions called in this function.
+    required_target_features: FxIndexSet<Symbol>,
+
+    /// Local `static mut`s read and written by this function.
+    read_mutable_statics: UnordSet<LocalDefId>,
+    written_mutable_statics: UnordSet<LocalDefId>,
+
+    /// All the operations requiring `unsafe` in this function, for `-Zemit-unsafety-json`.
+    unsafe_operations: Option<Vec<UnsafeOperation>>,
 }
 
 impl<'a, 'tcx> UnsafetyChecker<'a, 'tcx> {
@@ -35,6 +54,7 @@ fn new(
         tcx: TyCtxt<'tcx>,
         param_env: ty::ParamEnv<'tcx>,
     ) -> Self {
+        let emit_unsafety_json = tcx.sess.opts.unstable_opts.emit_unsafety_json.enabled();
         Self {
             body,
             body_did,
@@ -43,6 +63,10 @@ fn new(
             tcx,
             param_env,
             used_unsafe_blocks: Default::default(),
+            required_target_features: Default::default(),
+            read_mutable_statics: Default::default(),
+            written_mutable_statics: Default::default(),
+            unsafe_operations: emit_unsafety_json.then(Vec::new),
         }
     }
 }
@@ -83,10 +107,21 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 }
             }
 
-            TerminatorKind::InlineAsm { .. } => self.require_unsafe(
-                UnsafetyViolationKind::General,
-                UnsafetyViolationDetails::UseOfInlineAssembly,
-            ),
+            TerminatorKind::InlineAsm { ref operands, .. } => {
+                // We don't know what the assembly does with the statics it refers to.
+                for operand in operands {
+                    if let InlineAsmOperand::SymStatic { def_id } = *operand
+                        && let Some(def_id) = def_id.as_local()
+                        && self.tcx.is_mutable_static(def_id.to_def_id())
+                    {
+                        self.written_mutable_statics.insert(def_id);
+                    }
+                }
+                self.require_unsafe(
+                    UnsafetyViolationKind::General,
+                    UnsafetyViolationDetails::UseOfInlineAssembly,
+                )
+            }
         }
         self.super_terminator(terminator, location);
     }
@@ -119,13 +154,20 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
         match rvalue {
             Rvalue::Aggregate(box ref aggregate, _) => match aggregate {
                 &AggregateKind::Array(..) | &AggregateKind::Tuple => {}
-                &AggregateKind::Adt(adt_did, ..) => {
+                &AggregateKind::Adt(adt_did, _, args, ..) => {
                     match self.tcx.layout_scalar_valid_range(adt_did) {
                         (Bound::Unbounded, Bound::Unbounded) => {}
-                        _ => self.require_unsafe(
-                            UnsafetyViolationKind::General,
-                            UnsafetyViolationDetails::InitializingTypeWith,
-                        ),
+                        _ => {
+                            let field_validity = self
+                                .tcx
+                                .adt_def(adt_did)
+                                .all_fields()
+                                .find_map(|field| field_validity(field.ty(self.tcx, args)));
+                            self.require_unsafe(
+                                UnsafetyViolationKind::General,
+                                UnsafetyViolationDetails::InitializingTypeWith { field_validity },
+                            )
+                        }
                     }
                 }
                 &AggregateKind::Closure(def_id, _) | &AggregateKind::Coroutine(def_id, _, _) => {
@@ -185,15 +227,22 @@ fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location:
             // (avoid the general "raw pointer" clause below, that would only be confusing).
             if let LocalInfo::StaticRef { def_id, .. } = *decl.local_info() {
                 if self.tcx.is_mutable_static(def_id) {
+                    if let Some(def_id) = def_id.as_local() {
+                        self.record_mutable_static_access(def_id, *place, context);
+                    }
                     self.require_unsafe(
                         UnsafetyViolationKind::General,
                         UnsafetyViolationDetails::UseOfMutableStatic,
                     );
                     return;
                 } else if self.tcx.is_foreign_item(def_id) {
+                    let in_unsafe_extern_block = def_id.as_local().is_some_and(|def_id| {
+                        let hir_id = self.tcx.local_def_id_to_hir_id(def_id);
+                        self.tcx.hir().is_in_unsafe_foreign_mod(hir_id)
+                    });
                     self.require_unsafe(
                         UnsafetyViolationKind::General,
-                        UnsafetyViolationDetails::UseOfExternStatic,
+                        UnsafetyViolationDetails::UseOfExternStatic { in_unsafe_extern_block },
                     );
                     return;
                 }
@@ -205,9 +254,13 @@ fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location:
             if proj == ProjectionElem::Deref {
                 let base_ty = base.ty(self.body, self.tcx).ty;
                 if base_ty.is_unsafe_ptr() {
+                    let from_int_pointee = self.is_cast_from_int(base).then(|| {
+                        let pointee = base_ty.builtin_deref(true).unwrap().ty;
+                        with_no_trimmed_paths!(pointee.to_string())
+                    });
                     self.require_unsafe(
                         UnsafetyViolationKind::General,
-                        UnsafetyViolationDetails::DerefOfRawPointer,
+                        UnsafetyViolationDetails::DerefOfRawPointer { from_int_pointee },
                     )
                 }
             }
@@ -264,11 +317,24 @@ fn require_unsafe(&mut self, kind: UnsafetyViolationKind, details: UnsafetyViola
         assert_ne!(kind, UnsafetyViolationKind::UnsafeFn);
 
         let source_info = self.source_info;
-        let lint_root = self.body.source_scopes[self.source_info.scope]
+        let scope_data = self.body.source_scopes[self.source_info.scope]
             .local_data
             .as_ref()
-            .assert_crate_local()
-            .lint_root;
+            .assert_crate_local();
+        let lint_root = scope_data.lint_root;
+        // The operations the compiler generated inside its own `unsafe` blocks aren't the user's.
+        if let Some(unsafe_operations) = &mut self.unsafe_operations
+            && !matches!(scope_data.safety, Safety::BuiltinUnsafe)
+        {
+            let operation = UnsafeOperation {
+                span: source_info.span,
+                details: details.clone(),
+                in_unsafe_fn: is_in_unsafe_fn(self.tcx, self.body_did),
+            };
+            if !unsafe_operations.contains(&operation) {
+                unsafe_operations.push(operation);
+            }
+        }
         self.register_violations(
             [&UnsafetyViolation { source_info, lint_root, kind, details }],
             UnordItems::empty(),
@@ -285,9 +351,21 @@ fn register_violations<'a>(
             .as_ref()
             .assert_crate_local()
             .safety;
+        let require_unsafe_blocks = self.tcx.sess.opts.unstable_opts.require_unsafe_blocks;
         match safety {
-            // `unsafe` blocks are required in safe code
-            Safety::Safe => violations.into_iter().for_each(|violation| {
+            // With the RFC 2585, no longer allow `unsafe` operations in `unsafe fn`s
+            Safety::FnUnsafe if !require_unsafe_blocks => {
+                violations.into_iter().for_each(|violation| {
+                    let mut violation = violation.clone();
+                    violation.kind = UnsafetyViolationKind::UnsafeFn;
+                    if !self.violations.contains(&violation) {
+                        self.violations.push(violation)
+                    }
+                })
+            }
+            // `unsafe` blocks are required in safe code, and in `unsafe fn`s with
+            // `-Zrequire-unsafe-blocks`
+            Safety::Safe | Safety::FnUnsafe => violations.into_iter().for_each(|violation| {
                 match violation.kind {
                     UnsafetyViolationKind::General => {}
                     UnsafetyViolationKind::UnsafeFn => {
@@ -298,14 +376,6 @@ fn register_violations<'a>(
                     self.violations.push(violation.clone())
                 }
             }),
-            // With the RFC 2585, no longer allow `unsafe` operations in `unsafe fn`s
-            Safety::FnUnsafe => violations.into_iter().for_each(|violation| {
-                let mut violation = violation.clone();
-                violation.kind = UnsafetyViolationKind::UnsafeFn;
-                if !self.violations.contains(&violation) {
-                    self.violations.push(violation)
-                }
-            }),
             Safety::BuiltinUnsafe => {}
             Safety::ExplicitUnsafe(hir_id) => violations.into_iter().for_each(|_violation| {
                 self.used_unsafe_blocks.insert(hir_id);
@@ -314,6 +384,58 @@ fn register_violations<'a>(
 
         self.used_unsafe_blocks.extend_unord(new_used_unsafe_blocks);
     }
+
+    /// Whether the pointer in `place` is the result of casting an integer to a pointer, possibly
+    /// copied to other locals in between.
+    fn is_cast_from_int(&self, place: PlaceRef<'tcx>) -> bool {
+        let Some(mut local) = place.as_local() else { return false };
+        // Bounds the search in case of a cycle of copies.
+        for _ in 0..self.body.local_decls.len() {
+            let Some(rvalue) = self.unique_assignment(local) else { return false };
+            match *rvalue {
+                Rvalue::Cast(CastKind::PointerFromExposedAddress, ..) => return true,
+                Rvalue::Use(Operand::Copy(source) | Operand::Move(source)) => {
+                    let Some(source) = source.as_local() else { return false };
+                    local = source;
+                }
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// Returns the value assigned to `local`, if it is only assigned once and can't be modified
+    /// through a borrow.
+    fn unique_assignment(&self, local: Local) -> Option<&Rvalue<'tcx>> {
+        let decl = &self.body.local_decls[local];
+        if decl.is_user_variable() && decl.mutability.is_mut() {
+            return None;
+        }
+        let mut assignments =
+            self.body.basic_blocks.iter().flat_map(|data| &data.statements).filter_map(
+                |statement| match statement.kind {
+                    StatementKind::Assign(box (place, ref rvalue))
+                        if place.as_local() == Some(local) =>
+                    {
+                        Some(rvalue)
+                    }
+                    _ => None,
+                },
+            );
+        let rvalue = assignments.next()?;
+        assignments.next().is_none().then_some(rvalue)
+    }
+    /// Whether `span` is a borrow expression `&place` written by the user.
+    fn is_shared_borrow_expr(&self, span: Span) -> bool {
+        !span.from_expansion()
+            && self.tcx.sess.source_map().span_to_snippet(span).is_ok_and(|snippet| {
+                snippet.strip_prefix('&').is_some_and(|place| {
+                    let place = place.trim_start();
+                    !place.starts_with("mut ") && !place.starts_with("raw ")
+                })
+            })
+    }
+
     fn check_mut_borrowing_layout_constrained_field(
         &mut self,
         place: Place<'tcx>,
@@ -340,7 +462,14 @@ fn check_mut_borrowing_layout_constrained_field(
                                 .ty
                                 .is_freeze(self.tcx, self.param_env)
                             {
-                                UnsafetyViolationDetails::BorrowOfLayoutConstrainedField
+                                let can_borrow_copy = place
+                                    .ty(self.body, self.tcx)
+                                    .ty
+                                    .is_copy_modulo_regions(self.tcx, self.param_env)
+                                    && self.is_shared_borrow_expr(self.source_info.span);
+                                UnsafetyViolationDetails::BorrowOfLayoutConstrainedField {
+                                    can_borrow_copy,
+                                }
                             } else {
                                 continue;
                             };
@@ -353,16 +482,42 @@ fn check_mut_borrowing_layout_constrained_field(
         }
     }
 
+    /// Records whether an access to a local `static mut` reads it. Borrows which may be used to
+    /// modify it, and taking its address, count as writes.
+    fn record_mutable_static_access(
+        &mut self,
+        def_id: LocalDefId,
+        place: Place<'tcx>,
+        context: PlaceContext,
+    ) {
+        let is_write = match context {
+            PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::SharedBorrow | NonMutatingUseContext::FakeBorrow,
+            ) => !place.ty(self.body, self.tcx).ty.is_freeze(self.tcx, self.param_env),
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::AddressOf) => true,
+            PlaceContext::NonMutatingUse(_) => false,
+            PlaceContext::MutatingUse(_) => true,
+            PlaceContext::NonUse(_) => false,
+        };
+        if is_write {
+            self.written_mutable_statics.insert(def_id);
+        } else {
+            self.read_mutable_statics.insert(def_id);
+        }
+    }
+
     /// Checks whether calling `func_did` needs an `unsafe` context or not, i.e. whether
     /// the called function has target features the calling function hasn't.
     fn check_target_features(&mut self, func_did: DefId) {
+        let callee_features = &self.tcx.codegen_fn_attrs(func_did).target_features;
+        self.required_target_features.extend(callee_features);
+
         // Unsafety isn't required on wasm targets. For more information see
         // the corresponding check in typeck/src/collect.rs
         if self.tcx.sess.target.options.is_like_wasm {
             return;
         }
 
-        let callee_features = &self.tcx.codegen_fn_attrs(func_did).target_features;
         // The body might be a constant, so it doesn't have codegen attributes.
         let self_features = &self.tcx.body_codegen_attrs(self.body_did.to_def_id()).target_features;
 
@@ -390,7 +545,11 @@ fn check_target_features(&mut self, func_did: DefId) {
 }
 
 pub(crate) fn provide(providers: &mut Providers) {
-    *providers = Providers { unsafety_check_result, ..*providers };
+    *providers = Providers {
+        unsafety_check_result,
+        required_target_features,
+        ..*providers
+    };
 }
 
 /// Context information for [`UnusedUnsafeVisitor`] traversal,
@@ -444,7 +603,7 @@ fn visit_fn(
         fk: intravisit::FnKind<'tcx>,
         _fd: &'tcx hir::FnDecl<'tcx>,
         b: hir::BodyId,
-        _s: rustc_span::Span,
+        _s: Span,
         _id: LocalDefId,
     ) {
         if matches!(fk, intravisit::FnKind::Closure) {
@@ -502,6 +661,32 @@ fn unsafety_check_result(tcx: TyCtxt<'_>, def: LocalDefId) -> &UnsafetyCheckResu
             violations: Vec::new(),
             used_unsafe_blocks: Default::default(),
             unused_unsafes: Some(Vec::new()),
+            required_target_features: Vec::new(),
+            read_mutable_statics: Default::default(),
+            written_mutable_statics: Default::default(),
+            unsafe_operations: Vec::new(),
+        });
+    }
+
+    // A body without any construct which may be an unsafe operation has no violations, and none of
+    // its `unsafe` blocks are used, so we don't need to look at its MIR.
+    let may_use_unsafe = tcx
+        .hir()
+        .maybe_body_owned_by(def)
+        .map_or(true, |body_id| tcx.hir().body(body_id).may_use_unsafe);
+    if !may_use_unsafe {
+        debug!("unsafety_violations({:?}) - no unsafe operations", def);
+        let used_unsafe_blocks = Default::default();
+        let unused_unsafes = (!tcx.is_typeck_child(def.to_def_id()))
+            .then(|| check_unused_unsafe(tcx, def, &used_unsafe_blocks));
+        return tcx.arena.alloc(UnsafetyCheckResult {
+            violations: Vec::new(),
+            used_unsafe_blocks,
+            unused_unsafes,
+            required_target_features: Vec::new(),
+            read_mutable_statics: Default::default(),
+            written_mutable_statics: Default::default(),
+            unsafe_operations: Vec::new(),
         });
     }
 
@@ -513,13 +698,27 @@ fn unsafety_check_result(tcx: TyCtxt<'_>, def: LocalDefId) -> &UnsafetyCheckResu
     let unused_unsafes = (!tcx.is_typeck_child(def.to_def_id()))
         .then(|| check_unused_unsafe(tcx, def, &checker.used_unsafe_blocks));
 
+    // Sort by name rather than by interning order, which isn't stable across sessions.
+    let mut required_target_features: Vec<_> =
+        checker.required_target_features.into_iter().collect();
+    required_target_features.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
     tcx.arena.alloc(UnsafetyCheckResult {
         violations: checker.violations,
         used_unsafe_blocks: checker.used_unsafe_blocks,
         unused_unsafes,
+        required_target_features,
+        read_mutable_statics: checker.read_mutable_statics,
+        written_mutable_statics: checker.written_mutable_statics,
+        unsafe_operations: checker.unsafe_operations.unwrap_or_default(),
     })
 }
 
+fn required_target_features(tcx: TyCtxt<'_>, def: LocalDefId) -> &[Symbol] {
+    let result = tcx.unsafety_check_result(def);
+    tcx.arena.alloc_from_iter(result.required_target_features.iter().copied())
+}
+
 fn report_unused_unsafe(tcx: TyCtxt<'_>, kind: UnusedUnsafe, id: HirId) {
     let span = tcx.sess.source_map().guess_head_span(tcx.hir().span(id));
     let nested_parent = if let UnusedUnsafe::InUnsafeBlock(id) = kind {
@@ -527,7 +726,90 @@ fn report_unused_unsafe(tcx: TyCtxt<'_>, kind: UnusedUnsafe, id: HirId) {
     } else {
         None
     };
-    tcx.emit_spanned_lint(UNUSED_UNSAFE, id, span, errors::UnusedUnsafe { span, nested_parent });
+    let removal = unused_unsafe_removal(tcx, id);
+    tcx.emit_spanned_lint(
+        UNUSED_UNSAFE,
+        id,
+        span,
+        errors::UnusedUnsafe { span, nested_parent, removal, post_mono: false },
+    );
+}
+
+/// Finds the `unsafe` keyword and the braces of the unused `unsafe` block `id`, along with the
+/// whitespace which would be left over on their lines once they are removed.
+fn unused_unsafe_removal(tcx: TyCtxt<'_>, id: HirId) -> Option<errors::UnusedUnsafeRemoval> {
+    let sm = tcx.sess.source_map();
+    let Node::Block(block) = tcx.hir().get(id) else { return None };
+    if block.span.from_expansion() {
+        return None;
+    }
+    let snippet = sm.span_to_snippet(block.span).ok()?;
+    let after_keyword = snippet.strip_prefix("unsafe")?;
+    let brace = snippet.len() - after_keyword.len() + whitespace_and_comments_len(after_keyword);
+    if !snippet[brace..].starts_with('{') || !snippet.ends_with('}') {
+        return None;
+    }
+    let lo = block.span.lo();
+    let part = |start: usize, end: usize| {
+        block.span.with_lo(lo + BytePos(start as u32)).with_hi(lo + BytePos(end as u32))
+    };
+
+    let expr_id = tcx.hir().parent_id(id);
+    let context = match tcx.hir().get_parent(expr_id) {
+        Node::Stmt(hir::Stmt { kind: hir::StmtKind::Expr(_), .. }) => {
+            errors::UnusedUnsafeContext::Statement { needs_semicolon: block.expr.is_some() }
+        }
+        Node::Stmt(hir::Stmt { kind: hir::StmtKind::Semi(_), .. }) => {
+            errors::UnusedUnsafeContext::Statement { needs_semicolon: false }
+        }
+        Node::Block(outer) if outer.expr.is_some_and(|expr| expr.hir_id == expr_id) => {
+            errors::UnusedUnsafeContext::Tail
+        }
+        _ => errors::UnusedUnsafeContext::Expression,
+    };
+
+    let is_inline_whitespace = |c: char| c == ' ' || c == '\t';
+    let mut keyword = sm.span_extend_while(part(0, "unsafe".len()), is_inline_whitespace).ok()?;
+    let mut open_brace = sm.span_extend_while(part(brace, brace + 1), is_inline_whitespace).ok()?;
+    let end = snippet.len();
+    let mut close_brace = sm.span_extend_prev_while(part(end - 1, end), is_inline_whitespace).ok()?;
+
+    if !matches!(context, errors::UnusedUnsafeContext::Expression) {
+        // Remove lines which only contained the `unsafe {` or the `}`, so that the statements of
+        // the block keep their own lines and indentation.
+        let ends_line = |span: Span| {
+            sm.span_extend_while(span, is_inline_whitespace)
+                .and_then(|span| sm.span_extend_while(span.shrink_to_hi(), |c| c == '\n'))
+                .is_ok_and(|newlines| !newlines.is_empty())
+        };
+        if keyword.hi() == open_brace.lo()
+            && sm.is_line_before_span_empty(keyword)
+            && ends_line(open_brace)
+        {
+            keyword = sm.span_extend_prev_while(keyword, is_inline_whitespace).ok()?;
+            open_brace = open_brace.with_hi(open_brace.hi() + BytePos(1));
+        }
+        if sm.is_line_before_span_empty(close_brace) && ends_line(close_brace) {
+            close_brace = close_brace.with_lo(close_brace.lo() - BytePos(1));
+        }
+    }
+
+    Some(errors::UnusedUnsafeRemoval { keyword, open_brace, close_brace, context })
+}
+
+/// Returns the length of the whitespace and comments at the start of `s`.
+fn whitespace_and_comments_len(s: &str) -> usize {
+    let mut rest = s;
+    loop {
+        let trimmed = rest.trim_start();
+        if let Some(comment) = trimmed.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, next)| next);
+        } else if let Some(comment) = trimmed.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, next)| next);
+        } else {
+            return s.len() - trimmed.len();
+        }
+    }
 }
 
 pub fn check_unsafety(tcx: TyCtxt<'_>, def_id: LocalDefId) {
@@ -541,33 +823,47 @@ pub fn check_unsafety(tcx: TyCtxt<'_>, def_id: LocalDefId) {
     let UnsafetyCheckResult { violations, unused_unsafes, .. } = tcx.unsafety_check_result(def_id);
     // Only suggest wrapping the entire function body in an unsafe block once
     let mut suggest_unsafe_block = true;
+    // With `-Zgroup-unsafety-violations`, the errors for operations of the same kind, along with
+    // whether `unsafe fn` would have sufficed for them, are buffered and reported together.
+    let group_violations = tcx.sess.opts.unstable_opts.group_unsafety_violations;
+    let mut grouped: Vec<(bool, Vec<errors::RequiresUnsafeDetail>)> = Vec::new();
+    let in_fn = matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn);
+    let x86_feature_detection = matches!(&*tcx.sess.target.arch, "x86" | "x86_64");
+    let target_feature_attr = in_fn
+        .then(|| tcx.get_attrs(def_id, sym::target_feature).last())
+        .flatten()
+        .map(|attr| attr.span);
+    let reference_help = tcx.sess.verbose();
+    // With `-Zrequire-unsafe-blocks`, being in an `unsafe fn` is never enough.
+    let require_unsafe_blocks = tcx.sess.opts.unstable_opts.require_unsafe_blocks;
 
     for &UnsafetyViolation { source_info, lint_root, kind, ref details } in violations.iter() {
-        let details =
-            errors::RequiresUnsafeDetail { violation: details.clone(), span: source_info.span };
+        // One activity per violation, for `-Zself-profile` to count the violations of each kind.
+        let _prof_timer = tcx.prof.generic_activity(violation_kind_name(details));
+        let details = errors::RequiresUnsafeDetail {
+            violation: details.clone(),
+            span: source_info.span,
+            in_fn,
+            x86_feature_detection,
+            target_feature_attr,
+            reference_help,
+        };
 
         match kind {
             UnsafetyViolationKind::General => {
-                let op_in_unsafe_fn_allowed = unsafe_op_in_unsafe_fn_allowed(tcx, lint_root);
-                let note_non_inherited = tcx.hir().parent_iter(lint_root).find(|(id, node)| {
-                    if let Node::Expr(block) = node
-                        && let ExprKind::Block(block, _) = block.kind
-                        && let BlockCheckMode::UnsafeBlock(_) = block.rules
-                    {
-                        true
-                    } else if let Some(sig) = tcx.hir().fn_sig_by_hir_id(*id)
-                        && sig.header.is_unsafe()
-                    {
-                        true
-                    } else {
-                        false
+                let op_in_unsafe_fn_allowed =
+                    !require_unsafe_blocks && unsafe_op_in_unsafe_fn_allowed(tcx, lint_root);
+                if group_violations {
+                    match grouped.iter_mut().find(|(allowed, group)| {
+                        *allowed == op_in_unsafe_fn_allowed
+                            && group[0].violation == details.violation
+                    }) {
+                        Some((_, group)) => group.push(details),
+                        None => grouped.push((op_in_unsafe_fn_allowed, vec![details])),
                     }
-                });
-                let enclosing = if let Some((id, _)) = note_non_inherited {
-                    Some(tcx.sess.source_map().guess_head_span(tcx.hir().span(id)))
-                } else {
-                    None
-                };
+                    continue;
+                }
+                let enclosing = enclosing_unsafe_not_inherited(tcx, def_id);
                 tcx.sess.emit_err(errors::RequiresUnsafe {
                     span: source_info.span,
                     enclosing,
@@ -576,6 +872,8 @@ pub fn check_unsafety(tcx: TyCtxt<'_>, def_id: LocalDefId) {
                 });
             }
             UnsafetyViolationKind::UnsafeFn => {
+                let hir_id = tcx.local_def_id_to_hir_id(def_id);
+                let source_map = tcx.sess.source_map();
                 tcx.emit_spanned_lint(
                     UNSAFE_OP_IN_UNSAFE_FN,
                     lint_root,
@@ -583,16 +881,21 @@ pub fn check_unsafety(tcx: TyCtxt<'_>, def_id: LocalDefId) {
                     errors::UnsafeOpInUnsafeFn {
                         details,
                         suggest_unsafe_block: suggest_unsafe_block.then(|| {
-                            let hir_id = tcx.local_def_id_to_hir_id(def_id);
                             let fn_sig = tcx
                                 .hir()
                                 .fn_sig_by_hir_id(hir_id)
                                 .expect("this violation only occurs in fn");
                             let body = tcx.hir().body_owned_by(def_id);
                             let body_span = tcx.hir().body(body).value.span;
-                            let start = tcx.sess.source_map().start_point(body_span).shrink_to_hi();
-                            let end = tcx.sess.source_map().end_point(body_span).shrink_to_lo();
-                            (start, end, fn_sig.span)
+                            let start = source_map.start_point(body_span).shrink_to_hi();
+                            let end = source_map.end_point(body_span).shrink_to_lo();
+                            let body = source_map.span_to_snippet(start.to(end)).ok();
+                            (start, end, fn_sig.span, body)
+                        }),
+                        suggest_allow_attr: suggest_unsafe_block.then(|| {
+                            let span = tcx.hir().span(hir_id).shrink_to_lo();
+                            let indent = source_map.indentation_before(span).unwrap_or_default();
+                            (span, indent)
                         }),
                     },
                 );
@@ -601,11 +904,241 @@ pub fn check_unsafety(tcx: TyCtxt<'_>, def_id: LocalDefId) {
         }
     }
 
+    for (op_in_unsafe_fn_allowed, mut details) in grouped {
+        let enclosing = enclosing_unsafe_not_inherited(tcx, def_id);
+        if let [_] = details[..] {
+            let details = details.pop().unwrap();
+            tcx.sess.emit_err(errors::RequiresUnsafe {
+                span: details.span,
+                enclosing,
+                details,
+                op_in_unsafe_fn_allowed,
+            });
+        } else {
+            tcx.sess.emit_err(errors::RequiresUnsafeGrouped {
+                details,
+                enclosing,
+                op_in_unsafe_fn_allowed,
+            });
+        }
+    }
+
+    // For unit testing: report the features collected by `required_target_features`.
+    if tcx.has_attr(def_id, sym::rustc_dump_required_target_features) {
+        let features: Vec<_> =
+            tcx.required_target_features(def_id).iter().map(|feature| feature.as_str()).collect();
+        tcx.sess.emit_err(errors::RequiredTargetFeatures {
+            span: tcx.def_span(def_id),
+            features: features.join(", "),
+        });
+    }
+
     for &(block_id, kind) in unused_unsafes.as_ref().unwrap() {
         report_unused_unsafe(tcx, kind, block_id);
     }
 }
 
+/// Lints the local `static mut`s which are read, but never written by any body of the crate.
+pub fn check_static_mut_never_written(tcx: TyCtxt<'_>) {
+    // Bodies with errors may not have been checked, so we may not have seen all the writes.
+    if tcx.sess.has_errors().is_some() {
+        return;
+    }
+
+    let mut read = UnordSet::default();
+    let mut written = UnordSet::default();
+    for def_id in tcx.hir().body_owners() {
+        let result = tcx.unsafety_check_result(def_id);
+        read.extend_unord(result.read_mutable_statics.items().copied());
+        written.extend_unord(result.written_mutable_statics.items().copied());
+    }
+
+    // Statics are body owners themselves, which gives us a stable order to report them in.
+    for def_id in tcx.hir().body_owners() {
+        if tcx.def_kind(def_id) != DefKind::Static(hir::Mutability::Mut)
+            || !read.contains(&def_id)
+            || written.contains(&def_id)
+        {
+            continue;
+        }
+        // Other crates, or foreign code through the symbol, may write to the static, and other
+        // expansions of the macro defining it may need it to be mutable.
+        let span = tcx.def_span(def_id);
+        if tcx.effective_visibilities(()).is_exported(def_id)
+            || tcx.codegen_fn_attrs(def_id).contains_extern_indicator()
+            || span.from_expansion()
+        {
+            continue;
+        }
+        // An immutable static has to be `Sync`, and one with interior mutability may still be
+        // written through a shared reference.
+        let ty = tcx.type_of(def_id).instantiate_identity();
+        let param_env = tcx.param_env(def_id);
+        if !ty.is_freeze(tcx, param_env) || !is_sync(tcx, ty, param_env) {
+            continue;
+        }
+
+        tcx.emit_spanned_lint(
+            STATIC_MUT_NEVER_WRITTEN,
+            tcx.local_def_id_to_hir_id(def_id),
+            span,
+            errors::StaticMutNeverWritten {
+                span,
+                name: tcx.item_name(def_id.to_def_id()),
+                mut_span: static_mut_keyword(tcx, def_id),
+            },
+        );
+    }
+}
+
+fn is_sync<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>, param_env: ty::ParamEnv<'tcx>) -> bool {
+    let Some(sync_trait) = tcx.lang_items().sync_trait() else { return false };
+    let infcx = tcx.infer_ctxt().build();
+    infcx.type_implements_trait(sync_trait, [ty], param_env).must_apply_modulo_regions()
+}
+
+/// Writes the operations requiring `unsafe` in all the bodies of the crate to
+/// `<crate name>.unsafety.json`, for `-Zemit-unsafety-json`.
+pub fn emit_unsafety_json(tcx: TyCtxt<'_>) {
+    let SwitchWithOptPath::Enabled(ref directory) = tcx.sess.opts.unstable_opts.emit_unsafety_json
+    else {
+        return;
+    };
+    if let Err(err) = write_unsafety_json(tcx, directory.as_deref()) {
+        tcx.sess.emit_fatal(errors::CouldntEmitUnsafetyJson { error: err.to_string() });
+    }
+}
+
+fn write_unsafety_json(
+    tcx: TyCtxt<'_>,
+    directory: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let directory = if let Some(directory) = directory {
+        fs::create_dir_all(directory)?;
+        directory
+    } else {
+        Path::new(".")
+    };
+    let filename = format!("{}.unsafety.json", tcx.crate_name(LOCAL_CRATE));
+    let file = BufWriter::new(File::create(directory.join(filename))?);
+
+    // Lines and columns start at 1, and the end is exclusive, as in JSON diagnostics.
+    #[derive(serde::Serialize)]
+    struct JsonSpan {
+        file_name: String,
+        line_start: usize,
+        column_start: usize,
+        line_end: usize,
+        column_end: usize,
+    }
+
+    #[derive(serde::Serialize)]
+    struct JsonOperation {
+        span: JsonSpan,
+        kind: &'static str,
+        in_unsafe_fn: bool,
+    }
+
+    let source_map = tcx.sess.source_map();
+    let mut operations = Vec::new();
+    for def_id in tcx.hir().body_owners() {
+        for operation in &tcx.unsafety_check_result(def_id).unsafe_operations {
+            let lo = source_map.lookup_char_pos(operation.span.lo());
+            let hi = source_map.lookup_char_pos(operation.span.hi());
+            let span = JsonSpan {
+                file_name: source_map.filename_for_diagnostics(&lo.file.name).to_string(),
+                line_start: lo.line,
+                column_start: lo.col.0 + 1,
+                line_end: hi.line,
+                column_end: hi.col.0 + 1,
+            };
+            operations.push(JsonOperation {
+                span,
+                kind: violation_kind_name(&operation.details),
+                in_unsafe_fn: operation.in_unsafe_fn,
+            });
+        }
+    }
+    serde_json::to_writer(file, &operations)?;
+    Ok(())
+}
+
+/// The span of the `mut` keyword of the `static mut` item `def_id`, along with the whitespace
+/// following it.
+fn static_mut_keyword(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<Span> {
+    let item = tcx.hir().expect_item(def_id);
+    let between = item.vis_span.between(item.ident.span);
+    let snippet = tcx.sess.source_map().span_to_snippet(between).ok()?;
+    let keyword = snippet.find("mut")?;
+    Some(between.with_lo(between.lo() + BytePos(keyword as u32)))
+}
+
+/// What values of type `ty` must satisfy, if that is more than being in a range of integers.
+fn field_validity(ty: Ty<'_>) -> Option<FieldValidity> {
+    match ty.kind() {
+        ty::Ref(..) => Some(FieldValidity::Reference),
+        ty::Bool => Some(FieldValidity::Bool),
+        ty::Char => Some(FieldValidity::Char),
+        ty::Adt(adt, _) if adt.is_enum() => Some(FieldValidity::Enum),
+        _ => None,
+    }
+}
+
+/// Whether `def_id` is an `unsafe fn`, or a closure or inline const in one.
+fn is_in_unsafe_fn(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
+    let root = tcx.typeck_root_def_id(def_id.to_def_id());
+    matches!(tcx.def_kind(root), DefKind::Fn | DefKind::AssocFn)
+        && tcx.fn_sig(root).skip_binder().unsafety() == hir::Unsafety::Unsafe
+}
+
+/// The label of the self-profiler activity reporting a violation of this kind, so that the
+/// number of these events counts the violations of each kind.
+fn violation_kind_name(details: &UnsafetyViolationDetails) -> &'static str {
+    use UnsafetyViolationDetails::*;
+    match details {
+        CallToUnsafeFunction => "unsafety_violation_call_to_unsafe_function",
+        UseOfInlineAssembly => "unsafety_violation_use_of_inline_assembly",
+        InitializingTypeWith { .. } => "unsafety_violation_initializing_type_with",
+        CastOfPointerToInt { .. } => "unsafety_violation_cast_of_pointer_to_int",
+        UseOfMutableStatic => "unsafety_violation_use_of_mutable_static",
+        UseOfExternStatic { .. } => "unsafety_violation_use_of_extern_static",
+        DerefOfRawPointer { .. } => "unsafety_violation_deref_of_raw_pointer",
+        AccessToUnionField => "unsafety_violation_access_to_union_field",
+        MutationOfLayoutConstrainedField => {
+            "unsafety_violation_mutation_of_layout_constrained_field"
+        }
+        BorrowOfLayoutConstrainedField { .. } => {
+            "unsafety_violation_borrow_of_layout_constrained_field"
+        }
+        CallToFunctionWith { .. } => "unsafety_violation_call_to_function_with",
+    }
+}
+
+/// Returns the head span of the closest `unsafe` block or `unsafe fn` around the body of
+/// `def_id`, whose unsafety the body does not inherit.
+///
+/// Closures and inline consts are checked together with their parent and inherit its safety
+/// context, so the boundary which fails to inherit unsafety is always the typeck root itself,
+/// however deeply the operation is nested inside of it.
+fn enclosing_unsafe_not_inherited(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<Span> {
+    let hir_id = tcx.local_def_id_to_hir_id(def_id);
+    let (id, _) = tcx.hir().parent_iter(hir_id).find(|&(id, node)| {
+        if let Node::Expr(block) = node
+            && let ExprKind::Block(block, _) = block.kind
+            && let BlockCheckMode::UnsafeBlock(hir::UnsafeSource::UserProvided) = block.rules
+        {
+            true
+        } else if let Some(sig) = tcx.hir().fn_sig_by_hir_id(id)
+            && sig.header.is_unsafe()
+        {
+            true
+        } else {
+            false
+        }
+    })?;
+    Some(tcx.sess.source_map().guess_head_span(tcx.hir().span(id)))
+}
+
 fn unsafe_op_in_unsafe_fn_allowed(tcx: TyCtxt<'_>, id: HirId) -> bool {
     tcx.lint_level_at_node(UNSAFE_OP_IN_UNSAFE_FN, id).0 == Level::Allow
 }
@@ -0,0 +1,189 @@
+//! Lints `unsafe` blocks which have become unnecessary once the calls in them were inlined.
+//!
+//! An `unsafe` block is often only needed to call an `unsafe fn`. Once the call is inlined, we
+//! can see what the callee does in this particular instantiation. If none of the inlined callees
+//! perform any operation which needs `unsafe` themselves, and nothing else in the block does, the
+//! block could have been safe code here.
+//!
+//! The block is still required to call an `unsafe fn`, and may be needed for other
+//! instantiations, so this is an allow-by-default lint which only helps finding `unsafe`
+//! functions which could be safe. It reuses the `UnusedUnsafe` diagnostic, without suggesting
+//! the removal of the block.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir as hir;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_hir::hir_id::HirId;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::lint::builtin::UNUSED_UNSAFE_AFTER_INLINING;
+use std::ops::Bound;
+
+use crate::errors;
+use crate::MirPass;
+
+pub struct CheckUnsafetyAfterInlining;
+
+impl<'tcx> MirPass<'tcx> for CheckUnsafetyAfterInlining {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() > 0 && !sess.opts.unstable_opts.thir_unsafeck
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let body = &*body;
+        let Some(def_id) = body.source.def_id().as_local() else { return };
+        debug!(?def_id);
+
+        // Only inlining can make an `unsafe` block unnecessary here.
+        if body.source_scopes.iter().all(|scope| scope.inlined.is_none()) {
+            return;
+        }
+
+        let mut checker = UnsafeOperations {
+            tcx,
+            body,
+            source_info: SourceInfo::outermost(body.span),
+            used: FxHashSet::default(),
+        };
+        checker.visit_body(body);
+        let used = checker.used;
+
+        let used_before_inlining = &tcx.unsafety_check_result(def_id).used_unsafe_blocks;
+        let mut reported = FxHashSet::default();
+        for scope in body.source_scopes.iter() {
+            if scope.inlined.is_some() || scope.inlined_parent_scope.is_some() {
+                continue;
+            }
+            let ClearCrossCrate::Set(SourceScopeLocalData {
+                safety: Safety::ExplicitUnsafe(hir_id),
+                ..
+            }) = scope.local_data
+            else {
+                continue;
+            };
+            if used.contains(&hir_id)
+                || !used_before_inlining.contains(&hir_id)
+                || !reported.insert(hir_id)
+            {
+                continue;
+            }
+            let span = tcx.sess.source_map().guess_head_span(tcx.hir().span(hir_id));
+            debug!(?hir_id, "unsafe block is unnecessary after inlining");
+            tcx.emit_spanned_lint(
+                UNUSED_UNSAFE_AFTER_INLINING,
+                hir_id,
+                span,
+                errors::UnusedUnsafe { span, nested_parent: None, removal: None, post_mono: true },
+            );
+        }
+    }
+}
+
+/// Finds the `unsafe` blocks of the body which contain an operation needing them.
+struct UnsafeOperations<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    source_info: SourceInfo,
+    used: FxHashSet<HirId>,
+}
+
+impl<'tcx> UnsafeOperations<'_, 'tcx> {
+    /// Records that the current operation needs `unsafe`.
+    fn require_unsafe(&mut self) {
+        if let Some(hir_id) = self.covering_block(self.source_info.scope) {
+            self.used.insert(hir_id);
+        }
+    }
+
+    /// Returns the `unsafe` block of the body itself which an unsafe operation in `scope` needs.
+    ///
+    /// Operations inlined from a safe function are covered by that function's own `unsafe`
+    /// blocks. Operations inlined from an `unsafe fn` need the `unsafe` block around its call.
+    fn covering_block(&self, mut scope: SourceScope) -> Option<HirId> {
+        loop {
+            let data = &self.body.source_scopes[scope];
+            let inlined = data.inlined.is_some() || data.inlined_parent_scope.is_some();
+            if let ClearCrossCrate::Set(SourceScopeLocalData { safety, .. }) = data.local_data {
+                match safety {
+                    Safety::ExplicitUnsafe(hir_id) => return (!inlined).then_some(hir_id),
+                    Safety::BuiltinUnsafe => return None,
+                    Safety::Safe | Safety::FnUnsafe => {}
+                }
+            }
+            if let Some((callee, _)) = data.inlined
+                && !self.is_unsafe_fn(callee.def_id())
+            {
+                return None;
+            }
+            scope = data.parent_scope?;
+        }
+    }
+
+    fn is_unsafe_fn(&self, def_id: DefId) -> bool {
+        matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            && (self.tcx.fn_sig(def_id).skip_binder().unsafety() == hir::Unsafety::Unsafe
+                || !self.tcx.codegen_fn_attrs(def_id).target_features.is_empty())
+    }
+
+    fn is_layout_constrained(&self, def_id: DefId) -> bool {
+        self.tcx.layout_scalar_valid_range(def_id) != (Bound::Unbounded, Bound::Unbounded)
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UnsafeOperations<'_, 'tcx> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        self.source_info = statement.source_info;
+        if let StatementKind::Intrinsic(_) = statement.kind {
+            self.require_unsafe();
+        }
+        self.super_statement(statement, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        self.source_info = terminator.source_info;
+        match terminator.kind {
+            TerminatorKind::Call { ref func, .. } => {
+                let unsafe_call = match *func.ty(self.body, self.tcx).kind() {
+                    ty::FnDef(def_id, _) => self.is_unsafe_fn(def_id),
+                    ty::FnPtr(sig) => sig.unsafety() == hir::Unsafety::Unsafe,
+                    _ => false,
+                };
+                if unsafe_call {
+                    self.require_unsafe();
+                }
+            }
+            TerminatorKind::InlineAsm { .. } => self.require_unsafe(),
+            _ => {}
+        }
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        if let Rvalue::Aggregate(box AggregateKind::Adt(def_id, ..), _) = *rvalue
+            && self.is_layout_constrained(def_id)
+        {
+            self.require_unsafe();
+        }
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        for (base, elem) in place.iter_projections() {
+            let base_ty = base.ty(self.body, self.tcx).ty;
+            let unsafe_projection = match (elem, base_ty.kind()) {
+                (ProjectionElem::Deref, _) => base_ty.is_unsafe_ptr(),
+                (ProjectionElem::Field(..), ty::Adt(adt, _)) => {
+                    adt.is_union() || self.is_layout_constrained(adt.did())
+                }
+                _ => false,
+            };
+            if unsafe_projection {
+                self.require_unsafe();
+            }
+        }
+        self.super_place(place, context, location);
+    }
+}
@@ -0,0 +1,143 @@
+//! Replaces reads of the discriminant of an enum which has just been constructed by a constant.
+//!
+//! ```ignore (MIR)
+//! _2 = Option::<u32>::Some(_1);
+//! _3 = discriminant(_2);
+//! ```
+//! becomes
+//! ```ignore (MIR)
+//! _2 = Option::<u32>::Some(_1);
+//! _3 = const 1_isize;
+//! ```
+//! We only track whole locals written by an `Aggregate` or `SetDiscriminant`, within a block and
+//! into the blocks it jumps to with a `goto` if they have no other predecessor. Anything else
+//! which could change the discriminant forgets what we know about the local: any other write to it
+//! or to one of its fields, which may hold the niche of the discriminant, moving out of one of its
+//! fields, which may leave the niche uninitialized, and storage markers. Locals which are ever
+//! borrowed may be changed behind our back, so we don't track them at all.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::{NonMutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, ScalarInt, TyCtxt};
+use rustc_mir_dataflow::impls::borrowed_locals;
+use rustc_target::abi::VariantIdx;
+
+use crate::MirPass;
+
+pub struct ConstDiscriminant;
+
+impl<'tcx> MirPass<'tcx> for ConstDiscriminant {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let borrowed = borrowed_locals(body);
+
+        // The block whose known variants hold at the start of each block, if any.
+        let predecessors = body.basic_blocks.predecessors();
+        let goto_predecessor: IndexVec<BasicBlock, Option<BasicBlock>> = predecessors
+            .iter()
+            .map(|predecessors| match predecessors[..] {
+                [predecessor] => {
+                    let terminator = body.basic_blocks[predecessor].terminator();
+                    matches!(terminator.kind, TerminatorKind::Goto { .. }).then_some(predecessor)
+                }
+                _ => None,
+            })
+            .collect();
+        let reverse_postorder = body.basic_blocks.reverse_postorder().to_vec();
+
+        let mut exit_variants = IndexVec::from_elem(None, &body.basic_blocks);
+        let basic_blocks = body.basic_blocks.as_mut_preserves_cfg();
+        for bb in reverse_postorder {
+            let mut known = goto_predecessor[bb]
+                .and_then(|predecessor| exit_variants[predecessor].clone())
+                .unwrap_or_default();
+            for (statement_index, statement) in basic_blocks[bb].statements.iter_mut().enumerate() {
+                let location = Location { block: bb, statement_index };
+                if let StatementKind::Assign(box (_, ref mut rvalue)) = statement.kind
+                    && let Rvalue::Discriminant(place) = *rvalue
+                    && let Some(local) = place.as_local()
+                    && let Some(&variant) = known.get(&local)
+                    && let Some(constant) = discriminant_constant(
+                        tcx,
+                        param_env,
+                        &body.local_decls,
+                        statement.source_info,
+                        place,
+                        variant,
+                    )
+                {
+                    debug!(?location, ?local, ?variant, "replacing discriminant read");
+                    *rvalue = Rvalue::Use(constant);
+                }
+
+                Forget { known: &mut known }.visit_statement(statement, location);
+
+                let written = match statement.kind {
+                    StatementKind::Assign(box (
+                        place,
+                        Rvalue::Aggregate(box AggregateKind::Adt(def_id, variant, ..), _),
+                    )) if tcx.adt_def(def_id).is_enum() => Some((place, variant)),
+                    StatementKind::SetDiscriminant { box place, variant_index } => {
+                        Some((place, variant_index))
+                    }
+                    _ => None,
+                };
+                if let Some((place, variant)) = written
+                    && let Some(local) = place.as_local()
+                    && !borrowed.contains(local)
+                {
+                    known.insert(local, variant);
+                }
+            }
+            exit_variants[bb] = Some(known);
+        }
+    }
+}
+
+/// The discriminant of `variant` of the type of `place`, as an operand.
+fn discriminant_constant<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    local_decls: &LocalDecls<'tcx>,
+    source_info: SourceInfo,
+    place: Place<'tcx>,
+    variant: VariantIdx,
+) -> Option<Operand<'tcx>> {
+    let enum_ty = place.ty(local_decls, tcx).ty;
+    let discr = enum_ty.discriminant_for_variant(tcx, variant)?;
+    let discr_layout = tcx.layout_of(param_env.and(discr.ty)).ok()?;
+    let scalar = ScalarInt::try_from_uint(discr.val, discr_layout.size)?;
+    Some(Operand::const_from_scalar(tcx, discr.ty, scalar.into(), source_info.span))
+}
+
+/// Forgets the variant of the locals a statement may change the discriminant of.
+struct Forget<'a> {
+    known: &'a mut FxHashMap<Local, VariantIdx>,
+}
+
+impl<'tcx> Visitor<'tcx> for Forget<'_> {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _: Location) {
+        // Moving out of a field is a use of a projection of the local, so we look at the context
+        // of the whole place.
+        if context.is_mutating_use()
+            || context == PlaceContext::NonMutatingUse(NonMutatingUseContext::Move)
+        {
+            self.known.remove(&place.local);
+        }
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_storage_marker() {
+            self.known.remove(&local);
+        }
+    }
+}
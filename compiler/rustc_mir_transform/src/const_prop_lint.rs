@@ -26,7 +26,7 @@
 use crate::const_prop::CanConstProp;
 use crate::const_prop::ConstPropMachine;
 use crate::const_prop::ConstPropMode;
-use crate::errors::AssertLint;
+use crate::errors::{AssertLint, AssertLintKind};
 use crate::MirLint;
 
 /// The maximum number of bytes that we'll allocate space for a local or the return value.
@@ -86,6 +86,9 @@ struct ConstPropagator<'mir, 'tcx> {
     param_env: ParamEnv<'tcx>,
     worklist: Vec<BasicBlock>,
     visited_blocks: BitSet<BasicBlock>,
+    /// Whether this body is only evaluated at compile time when it is used, see
+    /// [`AssertLint::const_eval_only`].
+    const_eval_only: bool,
 }
 
 impl<'tcx> LayoutOfHelpers<'tcx> for ConstPropagator<'_, 'tcx> {
@@ -171,6 +174,8 @@ fn new(body: &'mir Body<'tcx>, tcx: TyCtxt<'tcx>) -> ConstPropagator<'mir, 'tcx>
             param_env,
             worklist: vec![START_BLOCK],
             visited_blocks: BitSet::new_empty(body.basic_blocks.len()),
+            const_eval_only: tcx.def_kind(def_id) == DefKind::AssocConst
+                && tcx.trait_of_item(def_id).is_some(),
         }
     }
 
@@ -276,9 +281,23 @@ fn eval_operand(&mut self, op: &Operand<'tcx>, location: Location) -> Option<OpT
         }
     }
 
-    fn report_assert_as_lint(&self, source_info: &SourceInfo, lint: AssertLint<impl Debug>) {
+    fn report_assert_as_lint(
+        &self,
+        source_info: &SourceInfo,
+        lint_kind: AssertLintKind,
+        assert_kind: AssertKind<impl Debug>,
+        index_span: Option<Span>,
+    ) {
         if let Some(lint_root) = self.lint_root(*source_info) {
-            self.tcx.emit_spanned_lint(lint.lint(), lint_root, source_info.span, lint);
+            let span = source_info.span;
+            let lint = AssertLint {
+                span,
+                assert_kind,
+                lint_kind,
+                const_eval_only: self.const_eval_only,
+                index_span,
+            };
+            lint.emit(self.tcx, lint_root);
         }
     }
 
@@ -294,10 +313,9 @@ fn check_unary_op(&mut self, op: UnOp, arg: &Operand<'tcx>, location: Location)
             let source_info = self.body().source_info(location);
             self.report_assert_as_lint(
                 source_info,
-                AssertLint::ArithmeticOverflow(
-                    source_info.span,
-                    AssertKind::OverflowNeg(val.to_const_int()),
-                ),
+                AssertLintKind::ArithmeticOverflow,
+                AssertKind::OverflowNeg(val.to_const_int()),
+                None,
             );
             return None;
         }
@@ -344,7 +362,9 @@ fn check_binary_op(
                 );
                 self.report_assert_as_lint(
                     source_info,
-                    AssertLint::ArithmeticOverflow(source_info.span, panic),
+                    AssertLintKind::ArithmeticOverflow,
+                    panic,
+                    None,
                 );
                 return None;
             }
@@ -359,10 +379,9 @@ fn check_binary_op(
                 let source_info = self.body().source_info(location);
                 self.report_assert_as_lint(
                     source_info,
-                    AssertLint::ArithmeticOverflow(
-                        source_info.span,
-                        AssertKind::Overflow(op, l.to_const_int(), r.to_const_int()),
-                    ),
+                    AssertLintKind::ArithmeticOverflow,
+                    AssertKind::Overflow(op, l.to_const_int(), r.to_const_int()),
+                    None,
                 );
                 return None;
             }
@@ -466,6 +485,14 @@ fn check_assertion(
                 Self::remove_const(&mut self.ecx, place.local);
             }
 
+            let source_info = self.body().source_info(location);
+            let index_span = match msg {
+                AssertKind::BoundsCheck { ref index, .. } => {
+                    self.operand_span(index, source_info.span)
+                }
+                _ => None,
+            };
+
             enum DbgVal<T> {
                 Val(T),
                 Underscore,
@@ -503,16 +530,32 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 // Need proper const propagator for these.
                 _ => return None,
             };
-            let source_info = self.body().source_info(location);
+            // Only point at the index if we know its value.
+            let index_span = index_span
+                .filter(|_| matches!(msg, AssertKind::BoundsCheck { index: DbgVal::Val(_), .. }));
             self.report_assert_as_lint(
                 source_info,
-                AssertLint::UnconditionalPanic(source_info.span, msg),
+                AssertLintKind::UnconditionalPanic,
+                msg,
+                index_span,
             );
         }
 
         None
     }
 
+    /// Returns the span of the expression `operand` was computed from, if it is a part of `span`
+    /// and not all of it.
+    fn operand_span(&self, operand: &Operand<'tcx>, span: Span) -> Option<Span> {
+        let operand_span = match operand {
+            Operand::Constant(constant) => constant.span,
+            Operand::Copy(place) | Operand::Move(place) => {
+                self.local_decls()[place.local].source_info.span
+            }
+        };
+        (operand_span != span && span.contains(operand_span)).then_some(operand_span)
+    }
+
     fn ensure_not_propagated(&self, local: Local) {
         if cfg!(debug_assertions) {
             assert!(
@@ -74,6 +74,8 @@
 };
 use rustc_mir_dataflow::storage::always_storage_live_locals;
 use rustc_mir_dataflow::{self, Analysis};
+use rustc_session::lint::builtin::MUST_NOT_SUSPEND;
+use rustc_session::lint::Level;
 use rustc_span::def_id::{DefId, LocalDefId};
 use rustc_span::symbol::sym;
 use rustc_span::Span;
@@ -1800,19 +1802,25 @@ fn check_suspend_tys<'tcx>(tcx: TyCtxt<'tcx>, layout: &CoroutineLayout<'tcx>, bo
     // We want a user-facing param-env.
     let param_env = tcx.param_env(body.source.def_id());
 
-    for (variant, yield_source_info) in
-        layout.variant_fields.iter().zip(&layout.variant_source_info)
+    for ((variant_index, variant), yield_source_info) in
+        layout.variant_fields.iter_enumerated().zip(&layout.variant_source_info)
     {
         debug!(?variant);
         for &local in variant {
             let decl = &layout.field_tys[local];
             debug!(?decl);
 
-            if !decl.ignore_for_traits && linted_tys.insert(decl.ty) {
-                let Some(hir_id) = decl.source_info.scope.lint_root(&body.source_scopes) else {
-                    continue;
-                };
-
+            if decl.ignore_for_traits {
+                continue;
+            }
+            // The lint root of a binding is the `let` declaring it, so the lint can be allowed
+            // for a single held value. That value must not hide others of the same type, so the
+            // type only counts as linted once the lint is not allowed.
+            let Some(hir_id) = decl.source_info.scope.lint_root(&body.source_scopes) else {
+                continue;
+            };
+            let (level, _) = tcx.lint_level_at_node(MUST_NOT_SUSPEND, hir_id);
+            if level != Level::Allow && linted_tys.insert(decl.ty) {
                 check_must_not_suspend_ty(
                     tcx,
                     decl.ty,
@@ -1821,6 +1829,7 @@ fn check_suspend_tys<'tcx>(tcx: TyCtxt<'tcx>, layout: &CoroutineLayout<'tcx>, bo
                     SuspendCheckData {
                         source_span: decl.source_info.span,
                         yield_span: yield_source_info.span,
+                        state: tcx.sess.verbose().then_some(variant_index),
                         plural_len: 1,
                         ..Default::default()
                     },
@@ -1830,13 +1839,21 @@ fn check_suspend_tys<'tcx>(tcx: TyCtxt<'tcx>, layout: &CoroutineLayout<'tcx>, bo
     }
 }
 
-#[derive(Default)]
-struct SuspendCheckData<'a> {
+/// The maximum number of steps from the held value to the `#[must_not_suspend]` type that are
+/// noted in the diagnostic.
+const MUST_NOT_SUSPEND_MAX_PATH_LEN: usize = 5;
+
+#[derive(Default, Clone, Copy)]
+struct SuspendCheckData<'a, 'tcx> {
     source_span: Span,
     yield_span: Span,
+    /// The variant of the coroutine in which the value is held, if it should be noted.
+    state: Option<VariantIdx>,
     descr_pre: &'a str,
     descr_post: &'a str,
     plural_len: usize,
+    /// How the value held across the suspend point contains the type being checked.
+    path: &'a [errors::MustNotSuspendPathStep<'tcx>],
 }
 
 // Returns whether it emitted a diagnostic or not
@@ -1850,7 +1867,7 @@ fn check_must_not_suspend_ty<'tcx>(
     ty: Ty<'tcx>,
     hir_id: hir::HirId,
     param_env: ty::ParamEnv<'tcx>,
-    data: SuspendCheckData<'_>,
+    data: SuspendCheckData<'_, 'tcx>,
 ) -> bool {
     if ty.is_unit() {
         return false;
@@ -1864,15 +1881,72 @@ fn check_must_not_suspend_ty<'tcx>(
         ty::Adt(..) if ty.is_box() => {
             let boxed_ty = ty.boxed_ty();
             let descr_pre = &format!("{}boxed ", data.descr_pre);
+            let step = errors::MustNotSuspendPathStep::Boxed { outer: ty, inner: boxed_ty };
+            let path = &[data.path, &[step]].concat();
             check_must_not_suspend_ty(
                 tcx,
                 boxed_ty,
                 hir_id,
                 param_env,
-                SuspendCheckData { descr_pre, ..data },
+                SuspendCheckData { descr_pre, path, ..data },
             )
         }
-        ty::Adt(def, _) => check_must_not_suspend_def(tcx, def.did(), hir_id, data),
+        ty::Adt(def, args) => {
+            if check_must_not_suspend_def(tcx, def.did(), hir_id, data) {
+                return true;
+            }
+            // Look through the fields of the ADT, unless we are already looking through it,
+            // which happens for recursive types.
+            let is_recursive = data.path.iter().any(|step| match *step {
+                errors::MustNotSuspendPathStep::Field { outer, .. }
+                | errors::MustNotSuspendPathStep::VariantField { outer, .. } => outer == ty,
+                _ => false,
+            });
+            if is_recursive || !tcx.recursion_limit().value_within_limit(data.path.len()) {
+                return false;
+            }
+            let mut has_emitted = false;
+            for variant in def.variants() {
+                for field in &variant.fields {
+                    let field_ty = field.ty(tcx, args);
+                    let field_ty =
+                        tcx.try_normalize_erasing_regions(param_env, field_ty).unwrap_or(field_ty);
+                    let step = if def.is_enum() {
+                        errors::MustNotSuspendPathStep::VariantField {
+                            outer: ty,
+                            inner: field_ty,
+                            variant: variant.name,
+                            field: field.name,
+                        }
+                    } else {
+                        errors::MustNotSuspendPathStep::Field {
+                            outer: ty,
+                            inner: field_ty,
+                            field: field.name,
+                        }
+                    };
+                    let path = &[data.path, &[step]].concat();
+                    // The notes for the path describe where the value is, so the message only
+                    // names the `#[must_not_suspend]` type.
+                    if check_must_not_suspend_ty(
+                        tcx,
+                        field_ty,
+                        hir_id,
+                        param_env,
+                        SuspendCheckData {
+                            descr_pre: "",
+                            descr_post: "",
+                            plural_len: 1,
+                            path,
+                            ..data
+                        },
+                    ) {
+                        has_emitted = true;
+                    }
+                }
+            }
+            has_emitted
+        }
         // FIXME: support adding the attribute to TAITs
         ty::Alias(ty::Opaque, ty::AliasTy { def_id: def, .. }) => {
             let mut has_emitted = false;
@@ -1917,73 +1991,103 @@ fn check_must_not_suspend_ty<'tcx>(
         }
         ty::Tuple(fields) => {
             let mut has_emitted = false;
-            for (i, ty) in fields.iter().enumerate() {
+            for (i, field_ty) in fields.iter().enumerate() {
                 let descr_post = &format!(" in tuple element {i}");
+                let step = errors::MustNotSuspendPathStep::TupleElement {
+                    outer: ty,
+                    inner: field_ty,
+                    index: i,
+                };
+                let path = &[data.path, &[step]].concat();
                 if check_must_not_suspend_ty(
                     tcx,
-                    ty,
+                    field_ty,
                     hir_id,
                     param_env,
-                    SuspendCheckData { descr_post, ..data },
+                    SuspendCheckData { descr_post, path, ..data },
                 ) {
                     has_emitted = true;
                 }
             }
             has_emitted
         }
-        ty::Array(ty, len) => {
+        ty::Array(elem_ty, len) => {
             let descr_pre = &format!("{}array{} of ", data.descr_pre, plural_suffix);
+            let step = errors::MustNotSuspendPathStep::ArrayElement { outer: ty, inner: elem_ty };
+            let path = &[data.path, &[step]].concat();
             check_must_not_suspend_ty(
                 tcx,
-                ty,
+                elem_ty,
                 hir_id,
                 param_env,
                 SuspendCheckData {
                     descr_pre,
                     plural_len: len.try_eval_target_usize(tcx, param_env).unwrap_or(0) as usize + 1,
+                    path,
                     ..data
                 },
             )
         }
         // If drop tracking is enabled, we want to look through references, since the referent
         // may not be considered live across the await point.
-        ty::Ref(_region, ty, _mutability) => {
+        ty::Ref(_region, pointee_ty, _mutability) => {
             let descr_pre = &format!("{}reference{} to ", data.descr_pre, plural_suffix);
+            let step = errors::MustNotSuspendPathStep::Reference { outer: ty, inner: pointee_ty };
+            let path = &[data.path, &[step]].concat();
             check_must_not_suspend_ty(
                 tcx,
-                ty,
+                pointee_ty,
                 hir_id,
                 param_env,
-                SuspendCheckData { descr_pre, ..data },
+                SuspendCheckData { descr_pre, path, ..data },
             )
         }
         _ => false,
     }
 }
 
-fn check_must_not_suspend_def(
-    tcx: TyCtxt<'_>,
+fn check_must_not_suspend_def<'tcx>(
+    tcx: TyCtxt<'tcx>,
     def_id: DefId,
     hir_id: hir::HirId,
-    data: SuspendCheckData<'_>,
+    data: SuspendCheckData<'_, 'tcx>,
 ) -> bool {
     if let Some(attr) = tcx.get_attr(def_id, sym::must_not_suspend) {
-        let reason = attr.value_str().map(|s| errors::MustNotSuspendReason {
+        // Either `#[must_not_suspend = "reason"]`, or
+        // `#[must_not_suspend(reason = "...", label = "...")]` with both keys optional, where the
+        // label defaults to the reason.
+        let (reason, label) = match attr.meta_item_list() {
+            Some(items) => {
+                let value = |name| {
+                    items.iter().find(|item| item.has_name(name)).and_then(|item| item.value_str())
+                };
+                let reason = value(sym::reason);
+                (reason, value(sym::label).or(reason))
+            }
+            None => (attr.value_str(), None),
+        };
+        let reason = reason.map(|s| errors::MustNotSuspendReason {
             span: data.source_span,
             reason: s.as_str().to_string(),
         });
         tcx.emit_spanned_lint(
-            rustc_session::lint::builtin::MUST_NOT_SUSPEND,
+            MUST_NOT_SUSPEND,
             hir_id,
             data.source_span,
             errors::MustNotSupend {
                 tcx,
                 yield_sp: data.yield_span,
+                label: label.map(|s| s.as_str().to_string()),
                 reason,
                 src_sp: data.source_span,
                 pre: data.descr_pre,
                 def_id,
                 post: data.descr_post,
+                path: &data.path[..data.path.len().min(MUST_NOT_SUSPEND_MAX_PATH_LEN)],
+                state: data.state.map(|variant| errors::MustNotSuspendState {
+                    state: CoroutineArgs::variant_name(variant).into_owned(),
+                    variant: variant.as_usize(),
+                }),
             },
         );
 
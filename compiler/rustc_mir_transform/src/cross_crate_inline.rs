@@ -45,7 +45,8 @@ fn cross_crate_inlinable(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
 
     // Don't do any inference if codegen optimizations are disabled and also MIR inlining is not
     // enabled. This ensures that we do inference even if someone only passes -Zinline-mir,
-    // which is less confusing than having to also enable -Copt-level=1.
+    // which is less confusing than having to also enable -Copt-level=1. Inlining only the trivial
+    // accessors doesn't count, as it doesn't need the callee to be cross-crate inlinable.
     if matches!(tcx.sess.opts.optimize, OptLevel::No) && !pm::should_run_pass(tcx, &inline::Inline)
     {
         return false;
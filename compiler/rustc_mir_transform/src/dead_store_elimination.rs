@@ -13,9 +13,11 @@
 //!
 
 use crate::util::is_within_packed;
-use rustc_middle::mir::visit::Visitor;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
 use rustc_middle::mir::*;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
 use rustc_mir_dataflow::debuginfo::debuginfo_locals;
 use rustc_mir_dataflow::impls::{
     borrowed_locals, LivenessTransferFunction, MaybeTransitiveLiveLocals,
@@ -28,6 +30,7 @@
 /// can be generated via the [`borrowed_locals`] function.
 pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     let borrowed_locals = borrowed_locals(body);
+    let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
 
     // If the user requests complete debuginfo, mark the locals that appear in it as live, so
     // we don't remove assignements to them.
@@ -43,6 +46,8 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     // record it as (block, argument index).
     let mut call_operands_to_move = Vec::new();
     let mut patch = Vec::new();
+    // The discriminant writes to live locals, which may still be overwritten before being read.
+    let mut set_discriminants = Vec::new();
 
     for (bb, bb_data) in traversal::preorder(body) {
         if let TerminatorKind::Call { ref args, .. } = bb_data.terminator().kind {
@@ -88,6 +93,8 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
                         live.seek_before_primary_effect(loc);
                         if !live.get().contains(place.local) {
                             patch.push(loc);
+                        } else if let StatementKind::SetDiscriminant { .. } = statement.kind {
+                            set_discriminants.push((loc, *place));
                         }
                     }
                 }
@@ -107,6 +114,15 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         }
     }
 
+    // Look for overwrites once we know which statements are removed, so that running this pass
+    // again doesn't find more discriminant writes to remove.
+    let removed: FxHashSet<Location> = patch.iter().copied().collect();
+    for (loc, place) in set_discriminants {
+        if is_discriminant_overwritten(tcx, param_env, body, &removed, loc, place) {
+            patch.push(loc);
+        }
+    }
+
     if patch.is_empty() && call_operands_to_move.is_empty() {
         return;
     }
@@ -127,6 +143,109 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     crate::simplify::simplify_locals(body, tcx)
 }
 
+/// Whether the discriminant set at `loc` is overwritten or goes out of scope before anything can
+/// read it.
+///
+/// We walk forward from `loc`, following gotos and no-op drops into blocks which can only be
+/// reached from the one we come from. Writes to fields of `place` don't read its discriminant, so
+/// we step over them. Any other mention of the local counts as a read, including a `Drop` which
+/// runs drop glue, since the glue of an enum depends on its variant. Statements in `removed` are
+/// ignored.
+fn is_discriminant_overwritten<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &Body<'tcx>,
+    removed: &FxHashSet<Location>,
+    loc: Location,
+    place: Place<'tcx>,
+) -> bool {
+    let local = place.local;
+    let mentions_local = |statement: &Statement<'tcx>, location| {
+        let mut visitor = MentionsLocal { local, found: false };
+        visitor.visit_statement(statement, location);
+        visitor.found
+    };
+    let predecessors = body.basic_blocks.predecessors();
+    let mut visited = BitSet::new_empty(body.basic_blocks.len());
+
+    let mut block = loc.block;
+    let mut first_statement = loc.statement_index + 1;
+    loop {
+        if !visited.insert(block) {
+            return false;
+        }
+        let data = &body.basic_blocks[block];
+        for (statement_index, statement) in data.statements.iter().enumerate().skip(first_statement)
+        {
+            let location = Location { block, statement_index };
+            if removed.contains(&location) {
+                continue;
+            }
+            match &statement.kind {
+                StatementKind::StorageDead(dead) if *dead == local => return true,
+                StatementKind::SetDiscriminant { place: set, .. } if **set == place => {
+                    return true;
+                }
+                StatementKind::Deinit(box deinit)
+                    if deinit.local == local && is_prefix(*deinit, place) =>
+                {
+                    return true;
+                }
+                StatementKind::Assign(box (dest, rvalue))
+                    if dest.local == local && !dest.is_indirect() =>
+                {
+                    let mut visitor = MentionsLocal { local, found: false };
+                    visitor.visit_rvalue(rvalue, location);
+                    if visitor.found {
+                        return false;
+                    }
+                    if is_prefix(*dest, place) {
+                        return true;
+                    }
+                    // The write only changes a field, which doesn't observe the discriminant.
+                }
+                _ if mentions_local(statement, location) => return false,
+                _ => {}
+            }
+        }
+
+        let next = match data.terminator().kind {
+            TerminatorKind::Goto { target } => target,
+            TerminatorKind::Drop { place: dropped, target, .. }
+                if !dropped.ty(body, tcx).ty.needs_drop(tcx, param_env) =>
+            {
+                target
+            }
+            // Locals other than the return place are dead once the function returns.
+            TerminatorKind::Return => return local != RETURN_PLACE,
+            _ => return false,
+        };
+        if predecessors[next].len() != 1 {
+            return false;
+        }
+        block = next;
+        first_statement = 0;
+    }
+}
+
+/// Whether `prefix` is `place` or one of its parents.
+fn is_prefix<'tcx>(prefix: Place<'tcx>, place: Place<'tcx>) -> bool {
+    prefix.local == place.local && place.projection.starts_with(&prefix.projection)
+}
+
+struct MentionsLocal {
+    local: Local,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for MentionsLocal {
+    fn visit_local(&mut self, local: Local, _: PlaceContext, _: Location) {
+        if local == self.local {
+            self.found = true;
+        }
+    }
+}
+
 pub struct DeadStoreElimination;
 
 impl<'tcx> MirPass<'tcx> for DeadStoreElimination {
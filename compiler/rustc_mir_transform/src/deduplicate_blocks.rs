@@ -155,7 +155,7 @@ fn operand_hash<H: Hasher>(hasher: &mut H, operand: &Operand<'_>) {
     };
 }
 
-fn statement_eq<'tcx>(lhs: &StatementKind<'tcx>, rhs: &StatementKind<'tcx>) -> bool {
+pub(crate) fn statement_eq<'tcx>(lhs: &StatementKind<'tcx>, rhs: &StatementKind<'tcx>) -> bool {
     let res = match (lhs, rhs) {
         (
             StatementKind::Assign(box (place, rvalue)),
@@ -0,0 +1,86 @@
+//! Makes equal constants of a body point to the same allocation.
+//!
+//! Allocations are interned, but each byte string literal, and each constant GVN puts into
+//! memory, gets its own `AllocId`. Two occurrences of `b"abc"` in a body are thus different
+//! constants, even though they point to the same data. The addresses of constants are not
+//! guaranteed to be unique, so we can make them all use the first `AllocId` we see for that
+//! data. Equal constants then compare equal, and later passes can treat them as the same value.
+//!
+//! Constants of different types are kept separate, even if their bytes are equal. Only immutable
+//! memory without provenance is shared, as pointers lose part of their identity in codegen
+//! (#79738).
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir::interpret::{
+    AllocId, ConstAllocation, CtfeProvenance, GlobalAlloc, Pointer, Scalar,
+};
+use rustc_middle::mir::visit::MutVisitor;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{Ty, TyCtxt};
+
+use crate::MirPass;
+
+pub struct DeduplicateConsts;
+
+impl<'tcx> MirPass<'tcx> for DeduplicateConsts {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 1
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let mut deduplicator = ConstDeduplicator { tcx, alloc_ids: FxHashMap::default() };
+        deduplicator.visit_body_preserves_cfg(body);
+    }
+}
+
+struct ConstDeduplicator<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The first `AllocId` seen for each allocation, by the type of the constants using it.
+    alloc_ids: FxHashMap<(Ty<'tcx>, ConstAllocation<'tcx>), AllocId>,
+}
+
+impl<'tcx> ConstDeduplicator<'tcx> {
+    /// The `AllocId` all the constants of type `ty` with the data of `alloc_id` use.
+    fn canonical_alloc_id(&mut self, ty: Ty<'tcx>, alloc_id: AllocId) -> AllocId {
+        let Some(GlobalAlloc::Memory(alloc)) = self.tcx.try_get_global_alloc(alloc_id) else {
+            return alloc_id;
+        };
+        let inner = alloc.inner();
+        if inner.mutability.is_mut() || !inner.provenance().ptrs().is_empty() {
+            return alloc_id;
+        }
+        *self.alloc_ids.entry((ty, alloc)).or_insert(alloc_id)
+    }
+}
+
+impl<'tcx> MutVisitor<'tcx> for ConstDeduplicator<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_constant(&mut self, constant: &mut ConstOperand<'tcx>, _: Location) {
+        let Const::Val(value, ty) = constant.const_ else { return };
+        let value = match value {
+            ConstValue::Scalar(Scalar::Ptr(pointer, size)) => {
+                let (prov, offset) = pointer.into_parts();
+                let alloc_id = self.canonical_alloc_id(ty, prov.alloc_id());
+                let mut new_prov = CtfeProvenance::from(alloc_id);
+                if prov.immutable() {
+                    new_prov = new_prov.as_immutable();
+                }
+                ConstValue::Scalar(Scalar::Ptr(Pointer::new(new_prov, offset), size))
+            }
+            ConstValue::Indirect { alloc_id, offset } => {
+                ConstValue::Indirect { alloc_id: self.canonical_alloc_id(ty, alloc_id), offset }
+            }
+            // Slices point to their allocation directly, which is already interned.
+            ConstValue::ZeroSized
+            | ConstValue::Scalar(Scalar::Int(_))
+            | ConstValue::Slice { .. } => return,
+        };
+        constant.const_ = Const::Val(value, ty);
+    }
+}
@@ -0,0 +1,144 @@
+//! Replaces calls through a trait object by direct calls, when the type behind it is known.
+//!
+//! Coercing a reference to a trait object and immediately calling a method on it looks like
+//! this:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _2 = _1 as &dyn Trait (PointerCoercion(Unsize));
+//!     _0 = <dyn Trait as Trait>::method(move _2) -> [return: bb1, unwind continue];
+//! }
+//! ```
+//! The method is looked up in the vtable of `S`, the type `_1` points to, so we can call
+//! `<S as Trait>::method` directly instead. We give `_2` the type `&S`, and assign `_1` to it
+//! without the coercion:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _2 = _1;
+//!     _0 = <S as Trait>::method(move _2) -> [return: bb1, unwind continue];
+//! }
+//! ```
+//!
+//! This only handles the simplest case: the trait object must be a temporary reference which is
+//! assigned once by the coercion, in the same block as the call, and only used as the receiver
+//! of that call. Upcasts from one trait object to another are left alone.
+
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::{NonUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCoercion;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+
+use crate::MirPass;
+
+pub struct Devirtualize;
+
+impl<'tcx> MirPass<'tcx> for Devirtualize {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let mut uses = UseCounts(IndexVec::from_elem(0, &body.local_decls));
+        uses.visit_body(body);
+        let uses = uses.0;
+
+        let mut candidates = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Call { ref func, ref args, .. } = data.terminator().kind else {
+                continue;
+            };
+            let Some((def_id, generic_args)) = func.const_fn_def() else { continue };
+            if tcx.trait_of_item(def_id).is_none() {
+                continue;
+            }
+            let Some(receiver) = args.first().and_then(Operand::place) else { continue };
+            let Some(object) = receiver.as_local() else { continue };
+            // The assignment, and the use as the receiver.
+            if body.local_kind(object) != LocalKind::Temp || uses[object] != 2 {
+                continue;
+            }
+
+            let Some(index) = data.statements.iter().rposition(|statement| {
+                matches!(
+                    statement.kind,
+                    StatementKind::Assign(box (lhs, _)) if lhs.as_local() == Some(object)
+                )
+            }) else {
+                continue;
+            };
+            let StatementKind::Assign(box (
+                _,
+                Rvalue::Cast(CastKind::PointerCoercion(PointerCoercion::Unsize), ref source, _),
+            )) = data.statements[index].kind
+            else {
+                continue;
+            };
+
+            let source_ty = source.ty(&body.local_decls, tcx);
+            let object_ty = body.local_decls[object].ty;
+            let Some(concrete) = concrete_type(source_ty, object_ty, generic_args.type_at(0))
+            else {
+                continue;
+            };
+
+            debug!(?bb, ?object, ?concrete, "devirtualizing call");
+            let generic_args = tcx.mk_args_from_iter(
+                std::iter::once(concrete.into()).chain(generic_args.iter().skip(1)),
+            );
+            candidates.push((bb, index, object, source.clone(), source_ty, def_id, generic_args));
+        }
+
+        for (bb, index, object, source, source_ty, def_id, generic_args) in candidates {
+            body.local_decls[object].ty = source_ty;
+
+            let data = &mut body.basic_blocks.as_mut_preserves_cfg()[bb];
+            let StatementKind::Assign(box (_, ref mut rvalue)) = data.statements[index].kind else {
+                bug!("devirtualized a call whose receiver is not assigned");
+            };
+            *rvalue = Rvalue::Use(source);
+
+            let TerminatorKind::Call { ref mut func, .. } = data.terminator_mut().kind else {
+                bug!("devirtualized a terminator which is not a call");
+            };
+            let span = match func {
+                Operand::Constant(constant) => constant.span,
+                _ => bug!("devirtualized a call which is not to a known function"),
+            };
+            *func = Operand::function_handle(tcx, def_id, generic_args, span);
+        }
+    }
+}
+
+/// If `source_ty` is a reference to `S` which is coerced into `object_ty`, a reference to the
+/// trait object `dyn_ty` with the same mutability, returns `S`.
+fn concrete_type<'tcx>(
+    source_ty: Ty<'tcx>,
+    object_ty: Ty<'tcx>,
+    dyn_ty: Ty<'tcx>,
+) -> Option<Ty<'tcx>> {
+    let ty::Ref(_, concrete, source_mutbl) = *source_ty.kind() else { return None };
+    let ty::Ref(_, pointee, object_mutbl) = *object_ty.kind() else { return None };
+    // Upcasting one trait object to another still needs the vtable of the source.
+    if source_mutbl != object_mutbl || pointee != dyn_ty || concrete.is_trait() {
+        return None;
+    }
+    let ty::Dynamic(_, _, ty::Dyn) = *dyn_ty.kind() else { return None };
+    Some(concrete)
+}
+
+/// Counts the uses of each local, not including storage markers.
+struct UseCounts(IndexVec<Local, usize>);
+
+impl<'tcx> Visitor<'tcx> for UseCounts {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if let PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead) =
+            context
+        {
+            return;
+        }
+        self.0[local] += 1;
+    }
+}
@@ -7,7 +7,9 @@
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_mir_dataflow::elaborate_drops::{elaborate_drop, DropFlagState, Unwind};
 use rustc_mir_dataflow::elaborate_drops::{DropElaborator, DropFlagMode, DropStyle};
-use rustc_mir_dataflow::impls::{MaybeInitializedPlaces, MaybeUninitializedPlaces};
+use rustc_mir_dataflow::impls::{
+    borrowed_locals, MaybeInitializedPlaces, MaybeUninitializedPlaces,
+};
 use rustc_mir_dataflow::move_paths::{LookupResult, MoveData, MovePathIndex};
 use rustc_mir_dataflow::on_all_children_bits;
 use rustc_mir_dataflow::on_lookup_result_bits;
@@ -85,6 +87,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
                 init_data: InitializationData { inits, uninits },
                 drop_flags,
                 patch: MirPatch::new(body),
+                borrowed_locals: borrowed_locals(body),
             }
             .elaborate()
         };
@@ -119,6 +122,91 @@ fn compute_dead_unwinds<'mir, 'tcx>(
     dead_unwinds
 }
 
+/// Returns the variant the enum `local` is known to have at the terminator of `bb`.
+///
+/// This is the case when the only path to `bb` assigns a variant to `local`, or switches on
+/// its discriminant, with no other write to `local` afterwards. `local` must not be borrowed,
+/// so that its assignments are the only statements that may modify it.
+fn known_variant<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    local: Local,
+    mut bb: BasicBlock,
+) -> Option<VariantIdx> {
+    let ty::Adt(adt, _) = *body.local_decls[local].ty.kind() else { return None };
+    if !adt.is_enum() {
+        return None;
+    }
+
+    // The value of the discriminant the block we come from switched on, and the local holding it.
+    let mut switched_on: Option<(u128, Local)> = None;
+    for _ in 0..body.basic_blocks.len() {
+        for statement in body.basic_blocks[bb].statements.iter().rev() {
+            match statement.kind {
+                StatementKind::Assign(box (place, ref rvalue)) if place.local == local => {
+                    return match *rvalue {
+                        Rvalue::Aggregate(box AggregateKind::Adt(_, variant, ..), _)
+                            if place.projection.is_empty() =>
+                        {
+                            Some(variant)
+                        }
+                        _ => None,
+                    };
+                }
+                StatementKind::Assign(box (place, ref rvalue))
+                    if let Some((value, discr_local)) = switched_on
+                        && place.local == discr_local =>
+                {
+                    return match *rvalue {
+                        Rvalue::Discriminant(discr_place) if discr_place == Place::from(local) => {
+                            adt.discriminants(tcx)
+                                .find(|(_, discr)| discr.val == value)
+                                .map(|(variant, _)| variant)
+                        }
+                        _ => None,
+                    };
+                }
+                StatementKind::SetDiscriminant { box place, variant_index }
+                    if place.local == local =>
+                {
+                    return place.projection.is_empty().then_some(variant_index);
+                }
+                StatementKind::Deinit(box place) if place.local == local => return None,
+                StatementKind::StorageLive(l) | StatementKind::StorageDead(l) if l == local => {
+                    return None;
+                }
+                _ => {}
+            }
+        }
+        // The discriminant is read in the block that switches on it.
+        if switched_on.is_some() {
+            return None;
+        }
+
+        let [pred] = body.basic_blocks.predecessors()[bb][..] else { return None };
+        match body.basic_blocks[pred].terminator().kind {
+            TerminatorKind::Goto { .. } | TerminatorKind::Assert { .. } => {}
+            TerminatorKind::Drop { place, .. } if place.local != local => {}
+            TerminatorKind::Call { destination, .. } if destination.local != local => {}
+            TerminatorKind::SwitchInt { discr: Operand::Move(discr), ref targets }
+                if let Some(discr) = discr.as_local() =>
+            {
+                let mut values = targets.iter().filter(|&(_, target)| target == bb);
+                let (Some((value, _)), None) = (values.next(), values.next()) else {
+                    return None;
+                };
+                if targets.otherwise() == bb {
+                    return None;
+                }
+                switched_on = Some((value, discr));
+            }
+            _ => return None,
+        }
+        bb = pred;
+    }
+    None
+}
+
 struct InitializationData<'mir, 'tcx> {
     inits: ResultsCursor<'mir, 'tcx, MaybeInitializedPlaces<'mir, 'tcx>>,
     uninits: ResultsCursor<'mir, 'tcx, MaybeUninitializedPlaces<'mir, 'tcx>>,
@@ -137,6 +225,8 @@ fn maybe_live_dead(&self, path: MovePathIndex) -> (bool, bool) {
 
 struct Elaborator<'a, 'b, 'tcx> {
     ctxt: &'a mut ElaborateDropsCtxt<'b, 'tcx>,
+    /// The variant the dropped place is known to have, with the move path of that place.
+    known_variant: Option<(MovePathIndex, VariantIdx)>,
 }
 
 impl fmt::Debug for Elaborator<'_, '_, '_> {
@@ -237,6 +327,11 @@ fn downcast_subpath(&self, path: Self::Path, variant: VariantIdx) -> Option<Self
     fn get_drop_flag(&mut self, path: Self::Path) -> Option<Operand<'tcx>> {
         self.ctxt.drop_flag(path).map(Operand::Copy)
     }
+
+    fn known_variant(&self, path: Self::Path) -> Option<VariantIdx> {
+        let (dropped_path, variant) = self.known_variant?;
+        (dropped_path == path).then_some(variant)
+    }
 }
 
 struct ElaborateDropsCtxt<'a, 'tcx> {
@@ -246,6 +341,8 @@ struct ElaborateDropsCtxt<'a, 'tcx> {
     init_data: InitializationData<'a, 'tcx>,
     drop_flags: IndexVec<MovePathIndex, Option<Local>>,
     patch: MirPatch<'tcx>,
+    /// The locals whose address is taken, which may be modified outside of their assignments.
+    borrowed_locals: BitSet<Local>,
 }
 
 impl<'b, 'tcx> ElaborateDropsCtxt<'b, 'tcx> {
@@ -368,8 +465,13 @@ fn elaborate_drops(&mut self) {
                         }
                     };
                     self.init_data.seek_before(self.body.terminator_loc(bb));
+                    let known_variant = place
+                        .as_local()
+                        .filter(|&local| !self.borrowed_locals.contains(local))
+                        .and_then(|local| known_variant(self.tcx, self.body, local, bb))
+                        .map(|variant| (path, variant));
                     elaborate_drop(
-                        &mut Elaborator { ctxt: self },
+                        &mut Elaborator { ctxt: self, known_variant },
                         terminator.source_info,
                         place,
                         path,
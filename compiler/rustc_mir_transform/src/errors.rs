@@ -2,22 +2,28 @@
 
 use rustc_errors::{
     Applicability, DecorateLint, DiagnosticArgValue, DiagnosticBuilder, DiagnosticMessage,
-    EmissionGuarantee, ErrorGuaranteed, Handler, IntoDiagnostic,
+    EmissionGuarantee, ErrorGuaranteed, Handler, IntoDiagnostic, MultiSpan,
 };
 use rustc_macros::{Diagnostic, LintDiagnostic, Subdiagnostic};
-use rustc_middle::mir::{AssertKind, UnsafetyViolationDetails};
-use rustc_middle::ty::TyCtxt;
-use rustc_session::lint::{self, Lint};
+use rustc_hir::HirId;
+use rustc_middle::lint::{struct_lint_level, LintLevelSource};
+use rustc_middle::mir::{AssertKind, FieldValidity, UnsafetyViolationDetails};
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_session::lint::{self, Level, Lint, LintId};
 use rustc_span::def_id::DefId;
-use rustc_span::Span;
+use rustc_span::{BytePos, Span, Symbol};
+use rustc_target::spec::abi::Abi;
 
 use crate::fluent_generated as fluent;
 
 #[derive(LintDiagnostic)]
-pub(crate) enum ConstMutate {
+pub(crate) enum ConstMutate<'tcx> {
     #[diag(mir_transform_const_modify)]
     #[note]
     Modify {
+        /// The statement writing to the temporary, which is also the primary span.
+        #[label]
+        write: Span,
         #[note(mir_transform_const_defined_here)]
         konst: Span,
     },
@@ -29,9 +35,36 @@ pub(crate) enum ConstMutate {
         method_call: Option<Span>,
         #[note(mir_transform_const_defined_here)]
         konst: Span,
+        #[subdiagnostic]
+        interior_mutability: Option<ConstMutateInteriorMutability<'tcx>>,
+        #[subdiagnostic]
+        static_suggestion: Option<ConstMutateStaticSuggestion>,
     },
 }
 
+/// Points at `Cell` and `RefCell` for mutating a value in place. These are only mentioned, as
+/// changing the type of the `const` item affects all its uses.
+#[derive(Subdiagnostic)]
+pub(crate) enum ConstMutateInteriorMutability<'tcx> {
+    /// The type of the `const` item is `Copy`, so it fits in a `Cell`.
+    #[help(mir_transform_const_mut_borrow_cell_help)]
+    Cell { ty: Ty<'tcx> },
+    #[help(mir_transform_const_mut_borrow_interior_mutability_help)]
+    Other,
+}
+
+#[derive(Subdiagnostic)]
+#[suggestion(
+    mir_transform_const_mut_borrow_static_suggestion,
+    code = "static",
+    applicability = "maybe-incorrect",
+    style = "verbose"
+)]
+pub(crate) struct ConstMutateStaticSuggestion {
+    #[primary_span]
+    pub span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(mir_transform_unaligned_packed_ref, code = "E0793")]
 #[note]
@@ -40,15 +73,126 @@ pub(crate) enum ConstMutate {
 pub(crate) struct UnalignedPackedRef {
     #[primary_span]
     pub span: Span,
+    #[subdiagnostic]
+    pub offset: Option<UnalignedPackedRefOffset>,
+    #[subdiagnostic]
+    pub suggestion: Option<UnalignedPackedRefSuggestion>,
+}
+
+#[derive(Subdiagnostic)]
+#[note(mir_transform_unaligned_packed_ref_offset)]
+pub(crate) struct UnalignedPackedRefOffset {
+    pub field: Symbol,
+    pub offset: u64,
+    pub required_align: u64,
+}
+
+#[derive(Subdiagnostic)]
+pub(crate) enum UnalignedPackedRefSuggestion {
+    #[suggestion(
+        mir_transform_unaligned_packed_ref_copy_suggestion,
+        code = "&{{ {place} }}",
+        applicability = "machine-applicable",
+        style = "verbose"
+    )]
+    Copy {
+        #[primary_span]
+        span: Span,
+        place: String,
+    },
+    #[suggestion(
+        mir_transform_unaligned_packed_ref_addr_of_suggestion,
+        code = "core::ptr::{macro_name}!({place})",
+        applicability = "maybe-incorrect",
+        style = "verbose"
+    )]
+    AddrOf {
+        #[primary_span]
+        span: Span,
+        macro_name: &'static str,
+        place: String,
+    },
 }
 
-#[derive(LintDiagnostic)]
-#[diag(mir_transform_unused_unsafe)]
 pub(crate) struct UnusedUnsafe {
-    #[label(mir_transform_unused_unsafe)]
     pub span: Span,
-    #[label]
+    /// The `unsafe` block this one is nested in, if that's why it is unnecessary. Otherwise it
+    /// contains no unsafe operations at all.
     pub nested_parent: Option<Span>,
+    pub removal: Option<UnusedUnsafeRemoval>,
+    /// Whether the block only became unnecessary once the calls in it were inlined.
+    pub post_mono: bool,
+}
+
+/// The parts of an unused `unsafe` block which make it one, along with the whitespace around
+/// them which can go too.
+pub(crate) struct UnusedUnsafeRemoval {
+    pub keyword: Span,
+    pub open_brace: Span,
+    pub close_brace: Span,
+    pub context: UnusedUnsafeContext,
+}
+
+/// Where the unused `unsafe` block is, which decides whether its braces can be removed.
+pub(crate) enum UnusedUnsafeContext {
+    /// The block is a statement. If it ends with an expression but isn't followed by a
+    /// semicolon, that expression needs one once the braces are gone.
+    Statement { needs_semicolon: bool },
+    /// The block is the trailing expression of another block.
+    Tail,
+    /// The block is used as a value, so its statements can't be moved out of it.
+    Expression,
+}
+
+impl<'a> DecorateLint<'a, ()> for UnusedUnsafe {
+    fn decorate_lint<'b>(
+        self,
+        diag: &'b mut DiagnosticBuilder<'a, ()>,
+    ) -> &'b mut DiagnosticBuilder<'a, ()> {
+        let label = if self.nested_parent.is_none() && !self.post_mono {
+            fluent::mir_transform_unused_unsafe_no_unsafe_ops
+        } else {
+            fluent::mir_transform_unused_unsafe
+        };
+        diag.span_label(self.span, label);
+        if self.post_mono {
+            diag.note(fluent::mir_transform_unused_unsafe_post_mono_note);
+        }
+        if let Some(nested_parent) = self.nested_parent {
+            diag.span_label(nested_parent, fluent::_subdiag::label);
+        }
+
+        if let Some(removal) = self.removal {
+            let mut parts = vec![(removal.keyword, String::new())];
+            match removal.context {
+                UnusedUnsafeContext::Statement { needs_semicolon } => {
+                    let close = if needs_semicolon { ";" } else { "" };
+                    parts.push((removal.open_brace, String::new()));
+                    parts.push((removal.close_brace, close.to_string()));
+                }
+                UnusedUnsafeContext::Tail => {
+                    parts.push((removal.open_brace, String::new()));
+                    parts.push((removal.close_brace, String::new()));
+                }
+                UnusedUnsafeContext::Expression => {}
+            }
+            diag.tool_only_multipart_suggestion(
+                fluent::mir_transform_suggestion,
+                parts,
+                Applicability::MaybeIncorrect,
+            );
+        }
+
+        diag
+    }
+
+    fn msg(&self) -> DiagnosticMessage {
+        if self.post_mono {
+            fluent::mir_transform_unused_unsafe_post_mono
+        } else {
+            fluent::mir_transform_unused_unsafe
+        }
+    }
 }
 
 pub(crate) struct RequiresUnsafe {
@@ -80,10 +224,49 @@ fn into_diagnostic(self, handler: &'sess Handler) -> DiagnosticBuilder<'sess, Er
     }
 }
 
+/// Several operations of the same kind in one body which require `unsafe`, reported together
+/// with `-Zgroup-unsafety-violations`.
+pub(crate) struct RequiresUnsafeGrouped {
+    pub details: Vec<RequiresUnsafeDetail>,
+    pub enclosing: Option<Span>,
+    pub op_in_unsafe_fn_allowed: bool,
+}
+
+impl<'sess> IntoDiagnostic<'sess> for RequiresUnsafeGrouped {
+    #[track_caller]
+    fn into_diagnostic(self, handler: &'sess Handler) -> DiagnosticBuilder<'sess, ErrorGuaranteed> {
+        let [first, ..] = &self.details[..] else { bug!("no unsafety violations to report") };
+        let mut diag = handler.struct_diagnostic(fluent::mir_transform_requires_unsafe);
+        diag.code(rustc_errors::DiagnosticId::Error("E0133".to_string()));
+        diag.set_span(MultiSpan::from_spans(self.details.iter().map(|d| d.span).collect()));
+        for details in &self.details {
+            diag.span_label(details.span, details.label());
+        }
+        let desc = handler.eagerly_translate_to_string(first.label(), [].into_iter());
+        diag.set_arg("details", desc);
+        diag.set_arg("op_in_unsafe_fn_allowed", self.op_in_unsafe_fn_allowed);
+        first.add_subdiagnostics(&mut diag);
+        if let Some(sp) = self.enclosing {
+            diag.span_label(sp, fluent::mir_transform_not_inherited);
+        }
+        diag
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct RequiresUnsafeDetail {
     pub span: Span,
     pub violation: UnsafetyViolationDetails,
+    /// Whether the operation is in a function, which could enable missing target features.
+    pub in_fn: bool,
+    /// Whether target features can be detected with `is_x86_feature_detected!`.
+    pub x86_feature_detection: bool,
+    /// The last `#[target_feature]` attribute of the function, to which missing target features
+    /// can be added.
+    pub target_feature_attr: Option<Span>,
+    /// Whether to point to the section of the reference explaining why the operation is unsafe,
+    /// which is only done with `-Zverbose`.
+    pub reference_help: bool,
 }
 
 impl RequiresUnsafeDetail {
@@ -96,20 +279,41 @@ fn add_subdiagnostics<G: EmissionGuarantee>(&self, diag: &mut DiagnosticBuilder<
             UseOfInlineAssembly => {
                 diag.note(fluent::mir_transform_use_of_asm_note);
             }
-            InitializingTypeWith => {
+            InitializingTypeWith { field_validity } => {
                 diag.note(fluent::mir_transform_initializing_valid_range_note);
+                if let Some(field_validity) = field_validity {
+                    diag.note(fluent::mir_transform_initializing_valid_range_field_note);
+                    diag.set_arg(
+                        "field_validity",
+                        match field_validity {
+                            FieldValidity::Reference => "reference",
+                            FieldValidity::Bool => "bool",
+                            FieldValidity::Char => "char",
+                            FieldValidity::Enum => "enum",
+                        },
+                    );
+                }
             }
-            CastOfPointerToInt => {
+            CastOfPointerToInt { to_usize } => {
                 diag.note(fluent::mir_transform_const_ptr2int_note);
+                diag.help(fluent::mir_transform_const_ptr2int_help);
+                diag.set_arg("to_usize", to_usize);
             }
             UseOfMutableStatic => {
                 diag.note(fluent::mir_transform_use_of_static_mut_note);
             }
-            UseOfExternStatic => {
+            UseOfExternStatic { in_unsafe_extern_block } => {
                 diag.note(fluent::mir_transform_use_of_extern_static_note);
+                if in_unsafe_extern_block {
+                    diag.note(fluent::mir_transform_use_of_extern_static_unsafe_extern_note);
+                }
             }
-            DerefOfRawPointer => {
+            DerefOfRawPointer { ref from_int_pointee } => {
                 diag.note(fluent::mir_transform_deref_ptr_note);
+                if let Some(pointee) = from_int_pointee {
+                    diag.note(fluent::mir_transform_deref_ptr_from_int_note);
+                    diag.set_arg("pointee", pointee.clone());
+                }
             }
             AccessToUnionField => {
                 diag.note(fluent::mir_transform_union_access_note);
@@ -117,8 +321,20 @@ fn add_subdiagnostics<G: EmissionGuarantee>(&self, diag: &mut DiagnosticBuilder<
             MutationOfLayoutConstrainedField => {
                 diag.note(fluent::mir_transform_mutation_layout_constrained_note);
             }
-            BorrowOfLayoutConstrainedField => {
+            BorrowOfLayoutConstrainedField { can_borrow_copy } => {
                 diag.note(fluent::mir_transform_mutation_layout_constrained_borrow_note);
+                if can_borrow_copy {
+                    // `&place` becomes `&{ place }`, which borrows a temporary copy of the field.
+                    let after_ref = self.span.lo() + BytePos(1);
+                    diag.multipart_suggestion_verbose(
+                        fluent::mir_transform_mutation_layout_constrained_borrow_suggestion,
+                        vec![
+                            (self.span.with_lo(after_ref).with_hi(after_ref), "{ ".into()),
+                            (self.span.shrink_to_hi(), " }".into()),
+                        ],
+                        Applicability::MachineApplicable,
+                    );
+                }
             }
             CallToFunctionWith { ref missing, ref build_enabled } => {
                 diag.help(fluent::mir_transform_target_feature_call_help);
@@ -142,6 +358,69 @@ fn add_subdiagnostics<G: EmissionGuarantee>(&self, diag: &mut DiagnosticBuilder<
                     );
                     diag.set_arg("build_target_features_count", build_enabled.len());
                 }
+                if self.in_fn {
+                    diag.help(fluent::mir_transform_target_feature_call_suggestion);
+                    let enable: Vec<_> = missing.iter().map(|feature| feature.as_str()).collect();
+                    diag.set_arg("missing_target_features_enable", enable.join(","));
+                    diag.set_arg("runtime_detection", self.x86_feature_detection);
+                }
+                if let Some(attr) = self.target_feature_attr
+                    && let Some(handler) = diag.handler()
+                {
+                    // Each feature gets its own label, so the message must be translated eagerly.
+                    let labels: Vec<_> = missing
+                        .iter()
+                        .map(|feature| {
+                            let args = [(
+                                "feature".into(),
+                                DiagnosticArgValue::Str(feature.to_string().into()),
+                            )];
+                            handler.eagerly_translate_to_string(
+                                fluent::mir_transform_target_feature_call_attr_label,
+                                args.iter().map(|(a, b)| (a, b)),
+                            )
+                        })
+                        .collect();
+                    for label in labels {
+                        diag.span_label(attr, label);
+                    }
+                }
+            }
+        }
+        if self.reference_help {
+            diag.help(fluent::mir_transform_requires_unsafe_reference);
+            diag.set_arg("reference", self.reference_url());
+        }
+    }
+
+    /// The section of the reference explaining why the operation requires `unsafe`.
+    fn reference_url(&self) -> &'static str {
+        use UnsafetyViolationDetails::*;
+        match self.violation {
+            CallToUnsafeFunction => {
+                "https://doc.rust-lang.org/reference/unsafe-keyword.html#unsafe-functions-unsafe-fn"
+            }
+            UseOfInlineAssembly => "https://doc.rust-lang.org/reference/inline-assembly.html",
+            InitializingTypeWith { .. }
+            | MutationOfLayoutConstrainedField
+            | BorrowOfLayoutConstrainedField { .. } => {
+                "https://doc.rust-lang.org/reference/behavior-considered-undefined.html"
+            }
+            CastOfPointerToInt { .. } => "https://doc.rust-lang.org/reference/const_eval.html",
+            UseOfMutableStatic => {
+                "https://doc.rust-lang.org/reference/items/static-items.html#mutable-statics"
+            }
+            UseOfExternStatic { .. } => {
+                "https://doc.rust-lang.org/reference/items/external-blocks.html#statics"
+            }
+            DerefOfRawPointer { .. } => {
+                "https://doc.rust-lang.org/reference/types/pointer.html#raw-pointers-const-and-mut"
+            }
+            AccessToUnionField => {
+                "https://doc.rust-lang.org/reference/items/unions.html#reading-and-writing-union-fields"
+            }
+            CallToFunctionWith { .. } => {
+                "https://doc.rust-lang.org/reference/attributes/codegen.html#the-target_feature-attribute"
             }
         }
     }
@@ -151,16 +430,16 @@ fn label(&self) -> DiagnosticMessage {
         match self.violation {
             CallToUnsafeFunction => fluent::mir_transform_call_to_unsafe_label,
             UseOfInlineAssembly => fluent::mir_transform_use_of_asm_label,
-            InitializingTypeWith => fluent::mir_transform_initializing_valid_range_label,
-            CastOfPointerToInt => fluent::mir_transform_const_ptr2int_label,
+            InitializingTypeWith { .. } => fluent::mir_transform_initializing_valid_range_label,
+            CastOfPointerToInt { .. } => fluent::mir_transform_const_ptr2int_label,
             UseOfMutableStatic => fluent::mir_transform_use_of_static_mut_label,
-            UseOfExternStatic => fluent::mir_transform_use_of_extern_static_label,
-            DerefOfRawPointer => fluent::mir_transform_deref_ptr_label,
+            UseOfExternStatic { .. } => fluent::mir_transform_use_of_extern_static_label,
+            DerefOfRawPointer { .. } => fluent::mir_transform_deref_ptr_label,
             AccessToUnionField => fluent::mir_transform_union_access_label,
             MutationOfLayoutConstrainedField => {
                 fluent::mir_transform_mutation_layout_constrained_label
             }
-            BorrowOfLayoutConstrainedField => {
+            BorrowOfLayoutConstrainedField { .. } => {
                 fluent::mir_transform_mutation_layout_constrained_borrow_label
             }
             CallToFunctionWith { .. } => fluent::mir_transform_target_feature_call_label,
@@ -175,7 +454,12 @@ pub(crate) struct UnsafeOpInUnsafeFn {
     ///  1. the start of the function body
     ///  2. the end of the function body
     ///  3. the function signature
-    pub suggest_unsafe_block: Option<(Span, Span, Span)>,
+    ///
+    /// along with the source text between the braces of the body, if it is available.
+    pub suggest_unsafe_block: Option<(Span, Span, Span, Option<String>)>,
+    /// The start of the function, where `#[allow(unsafe_op_in_unsafe_fn)]` can be inserted
+    /// instead, along with the indentation of the function. Only set with `suggest_unsafe_block`.
+    pub suggest_allow_attr: Option<(Span, String)>,
 }
 
 impl<'a> DecorateLint<'a, ()> for UnsafeOpInUnsafeFn {
@@ -190,11 +474,27 @@ fn decorate_lint<'b>(
         diag.span_label(self.details.span, self.details.label());
         self.details.add_subdiagnostics(diag);
 
-        if let Some((start, end, fn_sig)) = self.suggest_unsafe_block {
+        if let Some((start, end, fn_sig, body)) = self.suggest_unsafe_block {
             diag.span_note(fn_sig, fluent::mir_transform_note);
+            // Indenting the lines of the body could change the contents of multi-line string
+            // literals, so this is still only a guess.
+            let edits = match body.as_deref().and_then(wrap_in_unsafe_block) {
+                Some(wrapped) => vec![(start.to(end), wrapped)],
+                None => vec![(start, " unsafe {".into()), (end, "}".into())],
+            };
             diag.tool_only_multipart_suggestion(
                 fluent::mir_transform_suggestion,
-                vec![(start, " unsafe {".into()), (end, "}".into())],
+                edits,
+                Applicability::MaybeIncorrect,
+            );
+        }
+        // Wrapping the body stays the first suggestion, this is the alternative for those who
+        // would rather keep the whole function unsafe.
+        if let Some((span, indent)) = self.suggest_allow_attr {
+            diag.tool_only_span_suggestion(
+                span,
+                fluent::mir_transform_suggestion_allow,
+                format!("#[allow(unsafe_op_in_unsafe_fn)]\n{indent}"),
                 Applicability::MaybeIncorrect,
             );
         }
@@ -207,9 +507,50 @@ fn msg(&self) -> DiagnosticMessage {
     }
 }
 
-pub(crate) enum AssertLint<P> {
-    ArithmeticOverflow(Span, AssertKind<P>),
-    UnconditionalPanic(Span, AssertKind<P>),
+/// Wraps `body`, the text between the braces of a function body, in an `unsafe` block, indenting
+/// its lines by one more level.
+///
+/// Returns `None` unless the braces of the body are on lines of their own, like rustfmt puts them.
+fn wrap_in_unsafe_block(body: &str) -> Option<String> {
+    let (first, rest) = body.split_once('\n')?;
+    let (lines, last) = rest.rsplit_once('\n')?;
+    // The closing brace is indented by `last`, the first statement by one more level.
+    if !first.trim().is_empty() || !last.trim().is_empty() {
+        return None;
+    }
+    let first_statement = lines.lines().find(|line| !line.trim().is_empty())?;
+    let indent = &first_statement[..first_statement.len() - first_statement.trim_start().len()];
+    let level = indent.strip_prefix(last).filter(|level| !level.is_empty())?;
+
+    let mut wrapped = format!("{first}\n{indent}unsafe {{\n");
+    for line in lines.lines() {
+        if !line.trim().is_empty() {
+            wrapped.push_str(level);
+            wrapped.push_str(line);
+        }
+        wrapped.push('\n');
+    }
+    wrapped.push_str(&format!("{indent}}}\n{last}"));
+    Some(wrapped)
+}
+
+pub(crate) struct AssertLint<P> {
+    pub span: Span,
+    pub assert_kind: AssertKind<P>,
+    pub lint_kind: AssertLintKind,
+    /// Whether the body containing the assertion is only ever evaluated at compile time, and
+    /// only if it is actually used, i.e. the default value of an associated const in a trait.
+    /// Every impl may override such a default; if it does get evaluated, CTFE reports a hard
+    /// error anyway.
+    pub const_eval_only: bool,
+    /// The span of the index of an out of bounds indexing operation, if it is known.
+    pub index_span: Option<Span>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum AssertLintKind {
+    ArithmeticOverflow,
+    UnconditionalPanic,
 }
 
 impl<'a, P: std::fmt::Debug> DecorateLint<'a, ()> for AssertLint<P> {
@@ -217,69 +558,198 @@ fn decorate_lint<'b>(
         self,
         diag: &'b mut DiagnosticBuilder<'a, ()>,
     ) -> &'b mut DiagnosticBuilder<'a, ()> {
-        let span = self.span();
-        let assert_kind = self.panic();
-        let message = assert_kind.diagnostic_message();
-        assert_kind.add_args(&mut |name, value| {
+        let message = self.assert_kind.diagnostic_message();
+        self.assert_kind.add_args(&mut |name, value| {
             diag.set_arg(name, value);
         });
-        diag.span_label(span, message);
+        diag.span_label(self.span, message);
+        if let Some(index_span) = self.index_span {
+            diag.span_label(index_span, fluent::mir_transform_index_out_of_bounds);
+        }
 
         diag
     }
 
     fn msg(&self) -> DiagnosticMessage {
-        match self {
-            AssertLint::ArithmeticOverflow(..) => fluent::mir_transform_arithmetic_overflow,
-            AssertLint::UnconditionalPanic(..) => fluent::mir_transform_operation_will_panic,
+        match self.lint_kind {
+            AssertLintKind::ArithmeticOverflow => fluent::mir_transform_arithmetic_overflow,
+            AssertLintKind::UnconditionalPanic => fluent::mir_transform_operation_will_panic,
         }
     }
 }
 
 impl<P> AssertLint<P> {
     pub fn lint(&self) -> &'static Lint {
-        match self {
-            AssertLint::ArithmeticOverflow(..) => lint::builtin::ARITHMETIC_OVERFLOW,
-            AssertLint::UnconditionalPanic(..) => lint::builtin::UNCONDITIONAL_PANIC,
+        self.lint_kind.lint()
+    }
+
+    /// Emits the lint at `lint_root`, at the level adjusted by [`AssertLint::level`].
+    ///
+    /// Nothing is emitted if the same lint was already reported at the same span, so that an
+    /// overflow found in several bodies made from the same source is only reported once. Where
+    /// the lint is allowed, it isn't recorded as reported.
+    pub fn emit(self, tcx: TyCtxt<'_>, lint_root: HirId)
+    where
+        P: std::fmt::Debug,
+    {
+        let lint = LintId::of(self.lint());
+        if tcx.sess.assert_lint_reported(self.span, lint) {
+            return;
+        }
+        let (level, src) = tcx.lint_level_at_node(self.lint(), lint_root);
+        let level = self.level(level, src);
+        if level == Level::Allow {
+            return;
         }
+        let span = self.span;
+        struct_lint_level(
+            tcx.sess,
+            self.lint(),
+            level,
+            src,
+            Some(self.span.into()),
+            self.msg(),
+            |diag| self.decorate_lint(diag),
+        );
+        tcx.sess.mark_assert_lint_reported(span, lint);
     }
-    pub fn span(&self) -> Span {
-        match self {
-            AssertLint::ArithmeticOverflow(sp, _) | AssertLint::UnconditionalPanic(sp, _) => *sp,
+
+    /// Adjusts the level the lint would be emitted at, given the `level` and `src` of the lint
+    /// at the assertion's lint root.
+    ///
+    /// Overflows in const-eval-only bodies are downgraded to a warning, unless the user asked for
+    /// a specific level themselves.
+    pub fn level(&self, level: Level, src: LintLevelSource) -> Level {
+        match (self.lint_kind, level, src) {
+            (AssertLintKind::ArithmeticOverflow, Level::Deny, LintLevelSource::Default)
+                if self.const_eval_only =>
+            {
+                Level::Warn
+            }
+            _ => level,
         }
     }
-    pub fn panic(self) -> AssertKind<P> {
+}
+
+impl AssertLintKind {
+    pub fn lint(&self) -> &'static Lint {
         match self {
-            AssertLint::ArithmeticOverflow(_, p) | AssertLint::UnconditionalPanic(_, p) => p,
+            AssertLintKind::ArithmeticOverflow => lint::builtin::ARITHMETIC_OVERFLOW,
+            AssertLintKind::UnconditionalPanic => lint::builtin::UNCONDITIONAL_PANIC,
         }
     }
 }
 
 #[derive(LintDiagnostic)]
 #[diag(mir_transform_ffi_unwind_call)]
+#[note]
 pub(crate) struct FfiUnwindCall {
     #[label(mir_transform_ffi_unwind_call)]
     pub span: Span,
     pub foreign: bool,
+    pub abi: Abi,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(mir_transform_static_mut_never_written)]
+#[note]
+pub(crate) struct StaticMutNeverWritten {
+    #[label]
+    pub span: Span,
+    pub name: Symbol,
+    #[suggestion(code = "", applicability = "maybe-incorrect", style = "verbose")]
+    pub mut_span: Option<Span>,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_transform_couldnt_emit_unsafety_json)]
+pub(crate) struct CouldntEmitUnsafetyJson {
+    pub error: String,
 }
 
 #[derive(LintDiagnostic)]
 #[diag(mir_transform_fn_item_ref)]
 pub(crate) struct FnItemRef {
-    #[suggestion(code = "{sugg}", applicability = "unspecified")]
+    #[subdiagnostic]
+    pub sugg: FnItemRefSuggestionKind,
+}
+
+#[derive(Subdiagnostic)]
+pub(crate) enum FnItemRefSuggestionKind {
+    #[suggestion(
+        mir_transform_fn_item_ref_add_call,
+        code = "{sugg}",
+        applicability = "machine-applicable"
+    )]
+    AddCall {
+        #[primary_span]
+        span: Span,
+        sugg: String,
+        ident: String,
+    },
+    #[suggestion(
+        mir_transform_fn_item_ref_add_cast,
+        code = "{sugg}",
+        applicability = "unspecified"
+    )]
+    AddCast {
+        #[primary_span]
+        span: Span,
+        sugg: String,
+        ident: String,
+    },
+}
+
+#[derive(LintDiagnostic)]
+#[diag(mir_transform_implicit_fn_pointer_coercion)]
+pub(crate) struct ImplicitFnPointerCoercion {
+    #[suggestion(code = "{sugg}", applicability = "maybe-incorrect")]
     pub span: Span,
     pub sugg: String,
     pub ident: String,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(mir_transform_redundant_unchecked_indexing)]
+pub(crate) struct RedundantUncheckedIndexing {
+    #[label]
+    pub check_span: Span,
+    #[subdiagnostic]
+    pub suggestion: Option<RedundantUncheckedIndexingSuggestion>,
+}
+
+#[derive(Subdiagnostic)]
+#[suggestion(
+    mir_transform_redundant_unchecked_indexing_suggestion,
+    code = "{code}",
+    applicability = "maybe-incorrect"
+)]
+pub(crate) struct RedundantUncheckedIndexingSuggestion {
+    #[primary_span]
+    pub span: Span,
+    pub code: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_transform_required_target_features)]
+pub(crate) struct RequiredTargetFeatures {
+    #[primary_span]
+    pub span: Span,
+    pub features: String,
+}
+
 pub(crate) struct MustNotSupend<'tcx, 'a> {
     pub tcx: TyCtxt<'tcx>,
     pub yield_sp: Span,
+    /// Replaces the default label of the suspend point, if given in the attribute.
+    pub label: Option<String>,
     pub reason: Option<MustNotSuspendReason>,
     pub src_sp: Span,
     pub pre: &'a str,
     pub def_id: DefId,
     pub post: &'a str,
+    pub path: &'a [MustNotSuspendPathStep<'tcx>],
+    pub state: Option<MustNotSuspendState>,
 }
 
 // Needed for def_path_str
@@ -288,10 +758,19 @@ fn decorate_lint<'b>(
         self,
         diag: &'b mut rustc_errors::DiagnosticBuilder<'a, ()>,
     ) -> &'b mut rustc_errors::DiagnosticBuilder<'a, ()> {
-        diag.span_label(self.yield_sp, fluent::_subdiag::label);
+        match self.label {
+            Some(label) => diag.span_label(self.yield_sp, label),
+            None => diag.span_label(self.yield_sp, fluent::_subdiag::label),
+        };
         if let Some(reason) = self.reason {
             diag.subdiagnostic(reason);
         }
+        for &step in self.path {
+            diag.subdiagnostic(step);
+        }
+        if let Some(state) = self.state {
+            diag.subdiagnostic(state);
+        }
         diag.span_help(self.src_sp, fluent::_subdiag::help);
         diag.set_arg("pre", self.pre);
         diag.set_arg("def_path", self.tcx.def_path_str(self.def_id));
@@ -311,3 +790,28 @@ pub(crate) struct MustNotSuspendReason {
     pub span: Span,
     pub reason: String,
 }
+
+/// How a value held across a suspend point contains the `#[must_not_suspend]` type.
+#[derive(Subdiagnostic, Clone, Copy)]
+pub(crate) enum MustNotSuspendPathStep<'tcx> {
+    #[note(mir_transform_must_not_suspend_path_array_element)]
+    ArrayElement { outer: Ty<'tcx>, inner: Ty<'tcx> },
+    #[note(mir_transform_must_not_suspend_path_boxed)]
+    Boxed { outer: Ty<'tcx>, inner: Ty<'tcx> },
+    #[note(mir_transform_must_not_suspend_path_field)]
+    Field { outer: Ty<'tcx>, inner: Ty<'tcx>, field: Symbol },
+    #[note(mir_transform_must_not_suspend_path_reference)]
+    Reference { outer: Ty<'tcx>, inner: Ty<'tcx> },
+    #[note(mir_transform_must_not_suspend_path_tuple_element)]
+    TupleElement { outer: Ty<'tcx>, inner: Ty<'tcx>, index: usize },
+    #[note(mir_transform_must_not_suspend_path_variant_field)]
+    VariantField { outer: Ty<'tcx>, inner: Ty<'tcx>, variant: Symbol, field: Symbol },
+}
+
+/// The state of the coroutine in which the value is held, only noted with `-Zverbose`.
+#[derive(Subdiagnostic)]
+#[note(mir_transform_must_not_suspend_state)]
+pub(crate) struct MustNotSuspendState {
+    pub state: String,
+    pub variant: usize,
+}
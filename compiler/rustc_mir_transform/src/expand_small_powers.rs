@@ -0,0 +1,182 @@
+//! Replaces `x.pow(c)` on integers by multiplications when `c` is a small constant.
+//!
+//! `pow` computes the power by squaring in a loop, which is only unrolled by LLVM, and only once
+//! the call has been inlined. For a constant exponent, we multiply `x` by itself `c - 1` times
+//! instead, checking each multiplication for overflow the way `x * x` does:
+//! ```ignore (MIR)
+//! _0 = core::num::<impl u32>::pow(move _2, const 3_u32) -> [return: bb1, unwind continue];
+//! ```
+//! becomes
+//! ```ignore (MIR)
+//! bb0: {
+//!     _3 = CheckedMul(_2, _2);
+//!     assert(!move (_3.1: bool), "attempt to compute `{} * {}`, ...", _2, _2) -> bb2;
+//! }
+//!
+//! bb2: {
+//!     _4 = CheckedMul((_3.0: u32), _2);
+//!     assert(!move (_4.1: bool), "attempt to compute `{} * {}`, ...", (_3.0: u32), _2) -> bb3;
+//! }
+//!
+//! bb3: {
+//!     _0 = move (_4.0: u32);
+//!     goto -> bb1;
+//! }
+//! ```
+//! `pow` is `#[rustc_inherit_overflow_checks]`, so its own multiplications are also checked
+//! unless overflow checks are disabled in the crate it ends up in, which is what happens to these
+//! assertions too. The intermediate products are never larger than the result, so they overflow
+//! exactly when `pow` does. The panic then reports the location of the call instead of one in the
+//! body of `pow`.
+//!
+//! This has to run before inlining, which replaces the call to `pow` by its body.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::sym;
+use rustc_target::abi::FieldIdx;
+
+use crate::MirPass;
+
+/// The largest exponent for which we expand `pow`.
+const MAX_EXP: u128 = 8;
+
+pub struct ExpandSmallPowers;
+
+impl<'tcx> MirPass<'tcx> for ExpandSmallPowers {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let mut powers = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Call {
+                ref func,
+                ref args,
+                destination,
+                target: Some(target),
+                unwind,
+                ..
+            } = data.terminator().kind
+            else {
+                continue;
+            };
+            let Some((def_id, _)) = func.const_fn_def() else { continue };
+            if !is_integer_pow(tcx, def_id) {
+                continue;
+            }
+            let [base, exp] = &args[..] else { continue };
+            let Some(exp) = exp.constant().and_then(|exp| exp.const_.try_eval_bits(tcx, param_env))
+            else {
+                continue;
+            };
+            if exp > MAX_EXP {
+                continue;
+            }
+            debug!(?bb, ?exp, "expanding power");
+            powers.push(Power {
+                bb,
+                base: base.clone(),
+                exp: exp as u32,
+                destination,
+                target,
+                unwind,
+            });
+        }
+
+        for Power { bb, base, exp, destination, target, unwind } in powers {
+            let ty = base.ty(&body.local_decls, tcx);
+            let data = &body.basic_blocks[bb];
+            let source_info = data.terminator().source_info;
+            let span = source_info.span;
+            let is_cleanup = data.is_cleanup;
+
+            // The block which assigns the result, after the checks of the multiplications.
+            let mut block = bb;
+            let value = match exp {
+                0 => {
+                    let const_ = Const::from_bits(tcx, 1, param_env.and(ty));
+                    let constant = ConstOperand { span, user_ty: None, const_ };
+                    Operand::Constant(Box::new(constant))
+                }
+                1 => base,
+                _ => {
+                    let checked_ty = Ty::new_tup(tcx, &[ty, tcx.types.bool]);
+                    let mut product = None;
+                    for _ in 1..exp {
+                        let lhs = product.map_or_else(|| base.to_copy(), Operand::Copy);
+                        let rhs = base.to_copy();
+                        let checked = body.local_decls.push(LocalDecl::new(checked_ty, span));
+                        let checked = Place::from(checked);
+                        let next = body.basic_blocks.as_mut().push(BasicBlockData {
+                            statements: Vec::new(),
+                            terminator: None,
+                            is_cleanup,
+                        });
+                        let overflow =
+                            tcx.mk_place_field(checked, FieldIdx::from_u32(1), tcx.types.bool);
+                        let data = &mut body.basic_blocks.as_mut()[block];
+                        data.statements.push(Statement {
+                            source_info,
+                            kind: StatementKind::Assign(Box::new((
+                                checked,
+                                Rvalue::CheckedBinaryOp(
+                                    BinOp::Mul,
+                                    Box::new((lhs.clone(), rhs.clone())),
+                                ),
+                            ))),
+                        });
+                        data.terminator = Some(Terminator {
+                            source_info,
+                            kind: TerminatorKind::Assert {
+                                cond: Operand::Move(overflow),
+                                expected: false,
+                                msg: Box::new(AssertKind::Overflow(BinOp::Mul, lhs, rhs)),
+                                target: next,
+                                unwind,
+                            },
+                        });
+                        product = Some(tcx.mk_place_field(checked, FieldIdx::from_u32(0), ty));
+                        block = next;
+                    }
+                    Operand::Move(product.unwrap())
+                }
+            };
+
+            let data = &mut body.basic_blocks.as_mut()[block];
+            data.statements.push(Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((destination, Rvalue::Use(value)))),
+            });
+            let kind = TerminatorKind::Goto { target };
+            data.terminator = Some(Terminator { source_info, kind });
+        }
+    }
+}
+
+struct Power<'tcx> {
+    /// The block calling `pow`.
+    bb: BasicBlock,
+    base: Operand<'tcx>,
+    exp: u32,
+    destination: Place<'tcx>,
+    target: BasicBlock,
+    unwind: UnwindAction,
+}
+
+/// Whether `def_id` is the `pow` method of an integer type.
+fn is_integer_pow(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let Some(impl_def_id) = tcx.impl_of_method(def_id) else { return false };
+    tcx.trait_id_of_impl(impl_def_id).is_none()
+        && tcx.item_name(def_id) == sym::pow
+        && matches!(
+            tcx.type_of(impl_def_id).instantiate_identity().kind(),
+            ty::Int(_) | ty::Uint(_)
+        )
+}
@@ -116,7 +116,7 @@ fn has_ffi_unwind_calls(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> bool {
                 FFI_UNWIND_CALLS,
                 lint_root,
                 span,
-                errors::FfiUnwindCall { span, foreign },
+                errors::FfiUnwindCall { span, foreign, abi: sig.abi() },
             );
 
             tainted = true;
@@ -0,0 +1,178 @@
+//! Folds the sums of the elements of small arrays of integer constants:
+//! ```ignore (illustrative)
+//! let weights = [1_u32, 2, 3, 4];
+//! weights.iter().sum::<u32>()
+//! ```
+//! Once inlined, `Iterator::sum` is a loop over the slice iterator, which const propagation
+//! does not see through. Before inlining, the whole computation is a call to `sum` on the
+//! iterator that `slice::iter` returns for the array:
+//! ```ignore (MIR)
+//! _1 = [const 1_u32, const 2_u32, const 3_u32, const 4_u32];
+//! _4 = &_1;
+//! _3 = move _4 as &[u32] (PointerCoercion(Unsize));
+//! _2 = core::slice::<impl [u32]>::iter(move _3) -> [return: bb1, ...];
+//! _0 = <std::slice::Iter<'_, u32> as Iterator>::sum::<u32>(move _2) -> [return: bb2, ...];
+//! ```
+//! We replace the call to `sum` by the assignment of its result, unless the sum overflows. The
+//! array has to be assigned once, and otherwise only be read or borrowed immutably, so that its
+//! elements are the constants it was built with.
+//!
+//! This has to run before inlining, which replaces the call to `sum` by its body.
+
+use rustc_hir::def_id::DefId;
+use rustc_index::IndexSlice;
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext};
+use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCoercion;
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt};
+use rustc_span::sym;
+use rustc_target::abi::FieldIdx;
+
+use crate::ssa::{SsaLocals, SsaValues};
+use crate::MirPass;
+
+/// The maximum length of the arrays whose sums we fold.
+const MAX_LEN: usize = 32;
+
+pub struct FoldConstantArraySums;
+
+impl<'tcx> MirPass<'tcx> for FoldConstantArraySums {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let (Some(sum), Some(iter)) = (
+            tcx.get_diagnostic_item(sym::iterator_sum_fn),
+            tcx.get_diagnostic_item(sym::slice_iter),
+        ) else {
+            return;
+        };
+
+        let ssa = SsaLocals::new(body);
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let values = Values { tcx, param_env, body, values: SsaValues::new(body, &ssa), iter };
+
+        let mut folded = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Call { ref func, ref args, destination, target: Some(target), .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            if !func.const_fn_def().is_some_and(|(def_id, _)| def_id == sum) {
+                continue;
+            }
+            let [iterator] = &args[..] else { continue };
+            let ty = destination.ty(&body.local_decls, tcx).ty;
+            if let Some(value) = values.array_sum(iterator, ty) {
+                folded.push((bb, destination, ty, value, target));
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, destination, ty, value, target) in folded {
+            debug!(?bb, ?value, "folding sum of constant array");
+            let data = &mut basic_blocks[bb];
+            let source_info = data.terminator().source_info;
+            let const_ = Const::from_bits(tcx, value, param_env.and(ty));
+            let constant = ConstOperand { span: source_info.span, user_ty: None, const_ };
+            data.statements.push(Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((
+                    destination,
+                    Rvalue::Use(Operand::Constant(Box::new(constant))),
+                ))),
+            });
+            data.terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+struct Values<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    values: SsaValues<'a, 'tcx>,
+    iter: DefId,
+}
+
+impl<'tcx> Values<'_, 'tcx> {
+    /// If `iterator` iterates over an array of constants of the integer type `ty`, returns the
+    /// bits of the sum of its elements, unless it overflows.
+    fn array_sum(&self, iterator: &Operand<'tcx>, ty: Ty<'tcx>) -> Option<u128> {
+        // The partial sums of 128-bit integers don't fit in an `i128`.
+        if !ty.is_integral() || ty.primitive_size(self.tcx).bits() > 64 {
+            return None;
+        }
+        let iterator = self.values.ssa_value(iterator)?;
+        let (def_id, [slice]) = self.values.call(iterator)? else { return None };
+        if def_id != self.iter {
+            return None;
+        }
+        let slice = self.values.ssa_value(slice)?;
+        let Rvalue::Cast(CastKind::PointerCoercion(PointerCoercion::Unsize), array, _) =
+            self.values.assignment(slice)?
+        else {
+            return None;
+        };
+        let array = self.values.ssa_value(array)?;
+        let Rvalue::Ref(_, BorrowKind::Shared, array) = self.values.assignment(array)? else {
+            return None;
+        };
+        let elements = self.elements(array.as_local()?, ty)?;
+
+        let (size, signed) = ty.int_size_and_signed(self.tcx);
+        let mut sum: i128 = 0;
+        for element in elements {
+            let bits = element.constant()?.const_.try_eval_bits(self.tcx, self.param_env)?;
+            let element = if signed { size.sign_extend(bits) as i128 } else { bits as i128 };
+            sum += element;
+            let in_range = if signed {
+                size.signed_int_min() <= sum && sum <= size.signed_int_max()
+            } else {
+                sum as u128 <= size.unsigned_int_max()
+            };
+            if !in_range {
+                return None;
+            }
+        }
+        Some(size.truncate(sum as u128))
+    }
+
+    /// If the array in `array` is built once from at most `MAX_LEN` elements of type `ty`, and
+    /// can't be changed afterwards, returns these elements.
+    fn elements(
+        &self,
+        array: Local,
+        ty: Ty<'tcx>,
+    ) -> Option<&IndexSlice<FieldIdx, Operand<'tcx>>> {
+        let mut assignment = None;
+        for &(location, place, context) in self.values.uses(array) {
+            match context {
+                PlaceContext::MutatingUse(MutatingUseContext::Store)
+                    if place.projection.is_empty() && assignment.is_none() =>
+                {
+                    assignment = Some(location);
+                }
+                // A raw pointer could be used to write to the array.
+                PlaceContext::MutatingUse(_)
+                | PlaceContext::NonMutatingUse(NonMutatingUseContext::AddressOf) => return None,
+                PlaceContext::NonMutatingUse(_) | PlaceContext::NonUse(_) => {}
+            }
+        }
+        let statement = self.body.stmt_at(assignment?).left()?;
+        let (_, rvalue) = statement.kind.as_assign()?;
+        match rvalue {
+            Rvalue::Aggregate(box AggregateKind::Array(element_ty), elements)
+                if *element_ty == ty && elements.len() <= MAX_LEN =>
+            {
+                Some(elements)
+            }
+            _ => None,
+        }
+    }
+}
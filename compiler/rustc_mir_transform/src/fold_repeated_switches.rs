@@ -0,0 +1,210 @@
+//! Folds consecutive `match`es on the same `Option` or `Result` into a single switch.
+//!
+//! Code like `if let Some(x) = opt { ... } if let Some(y) = opt { ... }` reads the discriminant
+//! and branches on it twice:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _2 = discriminant(_1);
+//!     switchInt(move _2) -> [1: bb1, otherwise: bb2];
+//! }
+//!
+//! bb1: {
+//!     _3 = ((_1 as Some).0: u32);
+//!     goto -> bb2;
+//! }
+//!
+//! bb2: {
+//!     _4 = discriminant(_1);
+//!     switchInt(move _4) -> [1: bb3, otherwise: bb4];
+//! }
+//! ```
+//! `bb1` is only reached when `_1` is `Some`, and doesn't modify `_1`, so it can jump to `bb3`
+//! directly. The `otherwise` edge of a switch on a two-variant enum also tells us which variant
+//! we have, so `bb0` can jump to `bb4` instead of `bb2`. This leaves a single switch on `_1`.
+//!
+//! The `match`ed place must be a local which is never borrowed, so that only the statements we
+//! skip over could modify it.
+
+use rustc_middle::mir::visit::{NonUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_mir_dataflow::impls::borrowed_locals;
+use rustc_span::sym;
+
+use crate::MirPass;
+
+pub struct FoldRepeatedSwitches;
+
+impl<'tcx> MirPass<'tcx> for FoldRepeatedSwitches {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let borrowed = borrowed_locals(body);
+        let predecessors = body.basic_blocks.predecessors();
+
+        // Edges of switches, and `goto`s, that can jump past the next switch.
+        let mut edges = Vec::new();
+        let mut gotos = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let Some((place, targets)) = discriminant_switch(data, false) else { continue };
+            if !place.projection.is_empty() || borrowed.contains(place.local) {
+                continue;
+            }
+            let ty = body.local_decls[place.local].ty;
+            let Some(discriminants) = option_or_result_discriminants(tcx, ty) else { continue };
+
+            for (value, edge, target) in known_targets(targets, discriminants) {
+                let target_data = &body.basic_blocks[target];
+                if let Some((next_place, next_targets)) = discriminant_switch(target_data, true)
+                    && next_place == place
+                {
+                    let new_target = next_targets.target_for_value(value);
+                    debug!(?bb, ?target, ?new_target, "folding repeated switch");
+                    edges.push((bb, edge, new_target));
+                    continue;
+                }
+
+                if predecessors[target].as_slice() != [bb] {
+                    continue;
+                }
+                let TerminatorKind::Goto { target: next } = target_data.terminator().kind else {
+                    continue;
+                };
+                if modifies(target_data, place.local) {
+                    continue;
+                }
+                if let Some((next_place, next_targets)) =
+                    discriminant_switch(&body.basic_blocks[next], true)
+                    && next_place == place
+                {
+                    let new_target = next_targets.target_for_value(value);
+                    debug!(?bb, ?target, ?next, ?new_target, "folding repeated switch");
+                    gotos.push((target, new_target));
+                }
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, edge, new_target) in edges {
+            let TerminatorKind::SwitchInt { ref mut targets, .. } =
+                basic_blocks[bb].terminator_mut().kind
+            else {
+                bug!("folded an edge of a terminator which is not a `SwitchInt`");
+            };
+            targets.all_targets_mut()[edge] = new_target;
+        }
+        for (bb, target) in gotos {
+            basic_blocks[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// If `data` ends with a switch on the discriminant of a place it reads, returns that place and
+/// the targets of the switch.
+///
+/// With `only_switch`, the block must do nothing else, so that it can be skipped entirely.
+fn discriminant_switch<'a, 'tcx>(
+    data: &'a BasicBlockData<'tcx>,
+    only_switch: bool,
+) -> Option<(Place<'tcx>, &'a SwitchTargets)> {
+    let TerminatorKind::SwitchInt { ref discr, ref targets } = data.terminator().kind else {
+        return None;
+    };
+    let discr = match discr {
+        Operand::Move(discr) => discr.as_local()?,
+        Operand::Copy(discr) if !only_switch => discr.as_local()?,
+        _ => return None,
+    };
+
+    let (index, place) = data.statements.iter().enumerate().rev().find_map(|(index, statement)| {
+        match statement.kind {
+            StatementKind::Assign(box (lhs, Rvalue::Discriminant(place)))
+                if lhs.as_local() == Some(discr) =>
+            {
+                Some((index, place))
+            }
+            _ => None,
+        }
+    })?;
+
+    let is_ignored = |statement: &Statement<'_>| match statement.kind {
+        StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+            local == discr || (!only_switch && local != place.local)
+        }
+        StatementKind::Nop => true,
+        _ => false,
+    };
+    if !data.statements[index + 1..].iter().all(is_ignored)
+        || (only_switch && !data.statements[..index].iter().all(is_ignored))
+    {
+        return None;
+    }
+    Some((place, targets))
+}
+
+/// Returns the values of the discriminants of `Option` or `Result`.
+fn option_or_result_discriminants<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<[u128; 2]> {
+    let ty::Adt(adt, _) = ty.kind() else { return None };
+    if !tcx.is_diagnostic_item(sym::Option, adt.did())
+        && !tcx.is_diagnostic_item(sym::Result, adt.did())
+    {
+        return None;
+    }
+    let mut discriminants = adt.discriminants(tcx).map(|(_, discr)| discr.val);
+    Some([discriminants.next()?, discriminants.next()?])
+}
+
+/// Returns the edges of `targets` for which we know the discriminant, with that discriminant and
+/// the index of the edge.
+fn known_targets(
+    targets: &SwitchTargets,
+    discriminants: [u128; 2],
+) -> Vec<(u128, usize, BasicBlock)> {
+    let mut known: Vec<_> =
+        targets.iter().enumerate().map(|(edge, (value, target))| (value, edge, target)).collect();
+    if let [(value, _, _)] = known[..]
+        && let Some(other) = discriminants.into_iter().find(|&discr| discr != value)
+    {
+        known.push((other, 1, targets.otherwise()));
+    }
+    // A block which is reached for several values tells us nothing.
+    known.retain(|&(_, _, target)| {
+        targets.all_targets().iter().filter(|&&bb| bb == target).count() == 1
+    });
+    known
+}
+
+/// Whether the statements of `data` may modify `local`, or end its storage.
+fn modifies(data: &BasicBlockData<'_>, local: Local) -> bool {
+    struct Modifies {
+        local: Local,
+        found: bool,
+    }
+
+    impl<'tcx> Visitor<'tcx> for Modifies {
+        fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+            if local == self.local
+                && (context.is_mutating_use()
+                    || matches!(
+                        context,
+                        PlaceContext::NonUse(
+                            NonUseContext::StorageLive | NonUseContext::StorageDead
+                        )
+                    ))
+            {
+                self.found = true;
+            }
+        }
+    }
+
+    let mut visitor = Modifies { local, found: false };
+    for (statement_index, statement) in data.statements.iter().enumerate() {
+        visitor.visit_statement(statement, Location { block: START_BLOCK, statement_index });
+    }
+    visitor.found
+}
@@ -1,11 +1,16 @@
 use itertools::Itertools;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit;
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCoercion;
 use rustc_middle::ty::{self, EarlyBinder, GenericArgsRef, Ty, TyCtxt};
-use rustc_session::lint::builtin::FUNCTION_ITEM_REFERENCES;
+use rustc_session::lint::builtin::{FUNCTION_ITEM_REFERENCES, IMPLICIT_FN_POINTER_COERCIONS};
 use rustc_span::{symbol::sym, Span};
 use rustc_target::spec::abi::Abi;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
 
 use crate::{errors, MirLint};
 
@@ -13,14 +18,37 @@
 
 impl<'tcx> MirLint<'tcx> for FunctionItemReferences {
     fn run_lint(&self, tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
-        let mut checker = FunctionItemRefChecker { tcx, body };
+        let mut explicit_casts = ExplicitCasts::default();
+        if let Some(def_id) = body.source.def_id().as_local()
+            && let Some(body_id) = tcx.hir().maybe_body_owned_by(def_id)
+        {
+            intravisit::Visitor::visit_body(&mut explicit_casts, tcx.hir().body(body_id));
+        }
+        let mut checker = FunctionItemRefChecker { tcx, body, explicit_casts: explicit_casts.0 };
         checker.visit_body(body);
     }
 }
 
+/// Collects the spans of the expressions cast with `as`. Function items cast to function pointers
+/// or integers are coerced to function pointers the same way as implicitly coerced ones, so this
+/// is how we tell them apart.
+#[derive(Default)]
+struct ExplicitCasts(FxHashSet<Span>);
+
+impl<'tcx> intravisit::Visitor<'tcx> for ExplicitCasts {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::Cast(source, _) = expr.kind {
+            self.0.insert(source.span);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
 struct FunctionItemRefChecker<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     body: &'a Body<'tcx>,
+    /// The spans of the expressions cast with `as` in the body.
+    explicit_casts: FxHashSet<Span>,
 }
 
 impl<'tcx> Visitor<'tcx> for FunctionItemRefChecker<'_, 'tcx> {
@@ -48,7 +76,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                         if let Some((fn_id, fn_args)) = FunctionItemRefChecker::is_fn_ref(inner_ty)
                         {
                             let span = self.nth_arg_span(args, 0);
-                            self.emit_lint(fn_id, fn_args, source_info, span);
+                            self.emit_lint(fn_id, fn_args, source_info, span, false);
                         }
                     }
                 } else {
@@ -58,6 +86,29 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
         }
         self.super_terminator(terminator, location);
     }
+
+    /// Emits a lint for function items implicitly coerced to function pointers, outside macro
+    /// expansions.
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        if let Rvalue::Cast(CastKind::PointerCoercion(coercion), operand, _) = rvalue
+            && let PointerCoercion::ReifyFnPointer = coercion
+            && let ty::FnDef(fn_id, fn_args) = *operand.ty(self.body, self.tcx).kind()
+        {
+            let source_info = *self.body.source_info(location);
+            let span = source_info.span;
+            if !span.from_expansion() && !self.explicit_casts.contains(&span) {
+                let (_, sugg) = self.fn_ptr_cast(fn_id, fn_args);
+                let ident = self.tcx.item_name(fn_id).to_ident_string();
+                self.tcx.emit_spanned_lint(
+                    IMPLICIT_FN_POINTER_COERCIONS,
+                    self.lint_root(source_info),
+                    span,
+                    errors::ImplicitFnPointerCoercion { span, sugg, ident },
+                );
+            }
+        }
+        self.super_rvalue(rvalue, location);
+    }
 }
 
 impl<'tcx> FunctionItemRefChecker<'_, 'tcx> {
@@ -95,7 +146,7 @@ fn check_bound_args(
                                     let callsite_ctxt = span.source_callsite().ctxt();
                                     span = span.with_ctxt(callsite_ctxt);
                                 }
-                                self.emit_lint(fn_id, fn_args, source_info, span);
+                                self.emit_lint(fn_id, fn_args, source_info, span, true);
                             }
                         }
                     }
@@ -143,18 +194,29 @@ fn nth_arg_span(&self, args: &[Operand<'tcx>], n: usize) -> Span {
         }
     }
 
-    fn emit_lint(
-        &self,
-        fn_id: DefId,
-        fn_args: GenericArgsRef<'tcx>,
-        source_info: SourceInfo,
-        span: Span,
-    ) {
-        let lint_root = self.body.source_scopes[source_info.scope]
+    /// Returns `true` if the return value of a function with the signature `fn_sig` can itself be
+    /// formatted as a pointer.
+    fn returns_pointer(&self, fn_sig: ty::PolyFnSig<'tcx>) -> bool {
+        let Some(pointer_trait) = self.tcx.get_diagnostic_item(sym::Pointer) else {
+            return false;
+        };
+        let output = self.tcx.instantiate_bound_regions_with_erased(fn_sig.output());
+        let param_env = self.tcx.param_env(self.body.source.def_id());
+        let infcx = self.tcx.infer_ctxt().build();
+        infcx.type_implements_trait(pointer_trait, [output], param_env).must_apply_modulo_regions()
+    }
+
+    fn lint_root(&self, source_info: SourceInfo) -> hir::HirId {
+        self.body.source_scopes[source_info.scope]
             .local_data
             .as_ref()
             .assert_crate_local()
-            .lint_root;
+            .lint_root
+    }
+
+    /// Returns the path to the function item `fn_id` instantiated with `fn_args`, and the cast of
+    /// that path to the matching function pointer type.
+    fn fn_ptr_cast(&self, fn_id: DefId, fn_args: GenericArgsRef<'tcx>) -> (String, String) {
         // FIXME: use existing printing routines to print the function signature
         let fn_sig = self.tcx.fn_sig(fn_id).instantiate(self.tcx, fn_args);
         let unsafety = fn_sig.unsafety().prefix_str();
@@ -174,21 +236,52 @@ fn emit_lint(
         let num_args = fn_sig.inputs().map_bound(|inputs| inputs.len()).skip_binder();
         let variadic = if fn_sig.c_variadic() { ", ..." } else { "" };
         let ret = if fn_sig.output().skip_binder().is_unit() { "" } else { " -> _" };
-        let sugg = format!(
+        let path = if params.is_empty() { ident } else { format!("{ident}::<{params}>") };
+        let cast = format!(
             "{} as {}{}fn({}{}){}",
-            if params.is_empty() { ident.clone() } else { format!("{ident}::<{params}>") },
+            path,
             unsafety,
             abi,
             vec!["_"; num_args].join(", "),
             variadic,
             ret,
         );
+        (path, cast)
+    }
+
+    /// Emits the lint for a reference to the function item `fn_id`. `bound_by_pointer` is set if
+    /// the reference is passed where a `fmt::Pointer` is expected, in which case the user may
+    /// have meant to format the pointer returned by calling the function instead.
+    fn emit_lint(
+        &self,
+        fn_id: DefId,
+        fn_args: GenericArgsRef<'tcx>,
+        source_info: SourceInfo,
+        span: Span,
+        bound_by_pointer: bool,
+    ) {
+        let fn_sig = self.tcx.fn_sig(fn_id).instantiate(self.tcx, fn_args);
+        let ident = self.tcx.item_name(fn_id).to_ident_string();
+        let num_args = fn_sig.inputs().map_bound(|inputs| inputs.len()).skip_binder();
+        let (path, cast) = self.fn_ptr_cast(fn_id, fn_args);
+
+        // Only suggest calling the function if we are sure the call is all that's missing.
+        let sugg = if bound_by_pointer
+            && num_args == 0
+            && !fn_sig.c_variadic()
+            && fn_sig.unsafety() == hir::Unsafety::Normal
+            && self.returns_pointer(fn_sig)
+        {
+            errors::FnItemRefSuggestionKind::AddCall { span, sugg: format!("{path}()"), ident }
+        } else {
+            errors::FnItemRefSuggestionKind::AddCast { span, sugg: cast, ident }
+        };
 
         self.tcx.emit_spanned_lint(
             FUNCTION_ITEM_REFERENCES,
-            lint_root,
+            self.lint_root(source_info),
             span,
-            errors::FnItemRef { span, sugg, ident },
+            errors::FnItemRef { sugg },
         );
     }
 }
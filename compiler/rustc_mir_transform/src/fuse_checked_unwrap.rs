@@ -0,0 +1,213 @@
+//! Replaces `x.checked_add(y).unwrap()` on integers by an addition with an overflow check.
+//!
+//! Before inlining, the idiom is a call to `checked_add` building an `Option`, which is
+//! immediately unwrapped:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _3 = core::num::<impl u32>::checked_add(move _4, move _5) -> [return: bb1, unwind continue];
+//! }
+//!
+//! bb1: {
+//!     _0 = Option::<u32>::unwrap(move _3) -> [return: bb2, unwind continue];
+//! }
+//! ```
+//! We compute the addition the way `x + y` does with overflow checks instead, so the `Option`
+//! and the branch on its discriminant never exist:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _3 = CheckedAdd(_4, _5);
+//!     assert(!move (_3.1: bool), "attempt to compute `{} + {}`, ...", move _4, move _5) -> bb1;
+//! }
+//!
+//! bb1: {
+//!     _0 = move (_3.0: u32);
+//!     goto -> bb2;
+//! }
+//! ```
+//! `checked_sub` and `checked_mul` are handled the same way.
+//!
+//! We recognize the functions by their names, and rely on them behaving the way they are
+//! documented to. The panic message changes from the one of `unwrap` to the one of an overflowing
+//! operation, which is observable, so this is only enabled with `-Zunsound-mir-opts`.
+//!
+//! The `Option` must be a temporary which is only used by the call to `unwrap`, and the block of
+//! that call must only be reached from the call to `checked_add`.
+
+use rustc_hir::def_id::DefId;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::sym;
+use rustc_target::abi::FieldIdx;
+
+use crate::MirPass;
+
+pub struct FuseCheckedUnwrap;
+
+impl<'tcx> MirPass<'tcx> for FuseCheckedUnwrap {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3 && sess.opts.unstable_opts.unsound_mir_opts
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let mut uses = UseCounts(IndexVec::from_elem(0, &body.local_decls));
+        uses.visit_body(body);
+
+        let predecessors = body.basic_blocks.predecessors();
+        let mut fusions = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Call {
+                ref func,
+                ref args,
+                destination,
+                target: Some(unwrap_bb),
+                ..
+            } = data.terminator().kind
+            else {
+                continue;
+            };
+            let Some((def_id, _)) = func.const_fn_def() else { continue };
+            let Some(op) = checked_op(tcx, def_id) else { continue };
+            let [lhs, rhs] = &args[..] else { continue };
+            let Some(option) = destination.as_local() else { continue };
+            // The `Option` is only written by this call and moved into `unwrap`.
+            if body.local_kind(option) != LocalKind::Temp
+                || uses.0[option] != 2
+                || predecessors[unwrap_bb].as_slice() != [bb]
+            {
+                continue;
+            }
+
+            let unwrap_data = &body.basic_blocks[unwrap_bb];
+            let TerminatorKind::Call {
+                ref func,
+                ref args,
+                destination: result,
+                target,
+                unwind,
+                ..
+            } = unwrap_data.terminator().kind
+            else {
+                continue;
+            };
+            let Some((def_id, _)) = func.const_fn_def() else { continue };
+            if !is_option_unwrap(tcx, def_id)
+                || !matches!(args[..], [Operand::Move(place)] if place.as_local() == Some(option))
+                || !unwrap_data.statements.iter().all(|statement| {
+                    matches!(statement.kind, StatementKind::StorageDead(local) if local != option)
+                })
+            {
+                continue;
+            }
+
+            let ty = lhs.ty(&body.local_decls, tcx);
+            debug!(?bb, ?op, ?ty, "fusing checked operation with unwrap");
+            fusions.push(Fusion {
+                bb,
+                unwrap_bb,
+                op,
+                ty,
+                lhs: lhs.clone(),
+                rhs: rhs.clone(),
+                option,
+                result,
+                target,
+                unwind,
+            });
+        }
+
+        for Fusion { bb, unwrap_bb, op, ty, lhs, rhs, option, result, target, unwind } in fusions {
+            // The `Option` becomes the result of the checked operation.
+            body.local_decls[option].ty = Ty::new_tup(tcx, &[ty, tcx.types.bool]);
+            let checked = Place::from(option);
+            let basic_blocks = body.basic_blocks.as_mut();
+
+            let data = &mut basic_blocks[bb];
+            let source_info = data.terminator().source_info;
+            data.statements.push(Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((
+                    checked,
+                    Rvalue::CheckedBinaryOp(op, Box::new((lhs.to_copy(), rhs.to_copy()))),
+                ))),
+            });
+            let overflow = tcx.mk_place_field(checked, FieldIdx::from_u32(1), tcx.types.bool);
+            data.terminator_mut().kind = TerminatorKind::Assert {
+                cond: Operand::Move(overflow),
+                expected: false,
+                msg: Box::new(AssertKind::Overflow(op, lhs, rhs)),
+                target: unwrap_bb,
+                unwind,
+            };
+
+            let data = &mut basic_blocks[unwrap_bb];
+            let source_info = data.terminator().source_info;
+            let value = tcx.mk_place_field(checked, FieldIdx::from_u32(0), ty);
+            data.statements.push(Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((result, Rvalue::Use(Operand::Move(value))))),
+            });
+            data.terminator_mut().kind = match target {
+                Some(target) => TerminatorKind::Goto { target },
+                None => TerminatorKind::Unreachable,
+            };
+        }
+    }
+}
+
+struct Fusion<'tcx> {
+    /// The block calling the checked operation.
+    bb: BasicBlock,
+    /// The block calling `unwrap`.
+    unwrap_bb: BasicBlock,
+    op: BinOp,
+    ty: Ty<'tcx>,
+    lhs: Operand<'tcx>,
+    rhs: Operand<'tcx>,
+    option: Local,
+    result: Place<'tcx>,
+    target: Option<BasicBlock>,
+    unwind: UnwindAction,
+}
+
+/// The operation `def_id` computes, if it is one of the checked operations of an integer type.
+fn checked_op(tcx: TyCtxt<'_>, def_id: DefId) -> Option<BinOp> {
+    let impl_def_id = tcx.impl_of_method(def_id)?;
+    let self_ty = tcx.type_of(impl_def_id).instantiate_identity();
+    if tcx.trait_id_of_impl(impl_def_id).is_some()
+        || !matches!(self_ty.kind(), ty::Int(_) | ty::Uint(_))
+    {
+        return None;
+    }
+    match tcx.item_name(def_id) {
+        sym::checked_add => Some(BinOp::Add),
+        sym::checked_sub => Some(BinOp::Sub),
+        sym::checked_mul => Some(BinOp::Mul),
+        _ => None,
+    }
+}
+
+fn is_option_unwrap(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let Some(impl_def_id) = tcx.impl_of_method(def_id) else { return false };
+    tcx.trait_id_of_impl(impl_def_id).is_none()
+        && tcx.item_name(def_id) == sym::unwrap
+        && matches!(
+            tcx.type_of(impl_def_id).instantiate_identity().kind(),
+            ty::Adt(adt, _) if tcx.is_diagnostic_item(sym::Option, adt.did())
+        )
+}
+
+/// Counts the uses of each local, except for storage markers.
+struct UseCounts(IndexVec<Local, u32>);
+
+impl<'tcx> Visitor<'tcx> for UseCounts {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if !context.is_storage_marker() {
+            self.0[local] += 1;
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Fuses assignments copying each field of a struct or tuple into the same field of another one
+//! into a single assignment of the whole value.
+//!
+//! ```ignore (MIR)
+//! (_2.0: u32) = (_1.0: u32);
+//! (_2.1: i64) = (_1.1: i64);
+//! ```
+//! becomes
+//! ```ignore (MIR)
+//! _2 = _1;
+//! nop;
+//! ```
+//! The assignments must be consecutive, and assign all the fields in order. Both places must be
+//! based on different locals without any dereference, so that writing to the fields of one of
+//! them can't change the other.
+
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_target::abi::FieldIdx;
+
+use crate::MirPass;
+
+pub struct FuseFieldCopies;
+
+impl<'tcx> MirPass<'tcx> for FuseFieldCopies {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let local_decls = &body.local_decls;
+        for (bb, data) in body.basic_blocks.as_mut_preserves_cfg().iter_enumerated_mut() {
+            let statements = &mut data.statements;
+            let mut start = 0;
+            while start < statements.len() {
+                let Some(copy) = field_copy(tcx, &statements[start], FieldIdx::from_u32(0)) else {
+                    start += 1;
+                    continue;
+                };
+                let Some(field_count) = field_count(tcx, local_decls, &copy) else {
+                    start += 1;
+                    continue;
+                };
+
+                // The other fields must be copied right after the first one, the same way.
+                let end = start + field_count;
+                if end > statements.len()
+                    || !(1..field_count).all(|field| {
+                        let statement = &statements[start + field];
+                        field_copy(tcx, statement, FieldIdx::from_usize(field)) == Some(copy)
+                    })
+                {
+                    start += 1;
+                    continue;
+                }
+
+                debug!(?bb, ?start, ?copy, "fusing field copies");
+                let value =
+                    if copy.is_move { Operand::Move(copy.src) } else { Operand::Copy(copy.src) };
+                statements[start].kind =
+                    StatementKind::Assign(Box::new((copy.dest, Rvalue::Use(value))));
+                for statement in &mut statements[start + 1..end] {
+                    statement.make_nop();
+                }
+                start = end;
+            }
+        }
+    }
+}
+
+/// An assignment of a field of `src` to the same field of `dest`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct FieldCopy<'tcx> {
+    dest: Place<'tcx>,
+    src: Place<'tcx>,
+    /// Whether the field is moved rather than copied.
+    is_move: bool,
+}
+
+/// The copy `statement` does, if it assigns field `field` of some place to the same field of
+/// another one.
+fn field_copy<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    statement: &Statement<'tcx>,
+    field: FieldIdx,
+) -> Option<FieldCopy<'tcx>> {
+    let StatementKind::Assign(box (dest, Rvalue::Use(ref operand))) = statement.kind else {
+        return None;
+    };
+    let (is_move, src) = match *operand {
+        Operand::Copy(src) => (false, src),
+        Operand::Move(src) => (true, src),
+        Operand::Constant(_) => return None,
+    };
+    let (dest, ProjectionElem::Field(dest_field, _)) = dest.as_ref().last_projection()? else {
+        return None;
+    };
+    let (src, ProjectionElem::Field(src_field, _)) = src.as_ref().last_projection()? else {
+        return None;
+    };
+    if dest_field != field || src_field != field {
+        return None;
+    }
+    let dest = dest.project_deeper(&[], tcx);
+    let src = src.project_deeper(&[], tcx);
+    Some(FieldCopy { dest, src, is_move })
+}
+
+/// The number of fields of the type `copy` copies a field of, if copying all of them can be done
+/// by copying the whole value.
+fn field_count<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_decls: &LocalDecls<'tcx>,
+    copy: &FieldCopy<'tcx>,
+) -> Option<usize> {
+    if copy.dest.local == copy.src.local || copy.dest.is_indirect() || copy.src.is_indirect() {
+        return None;
+    }
+    let dest_ty = copy.dest.ty(local_decls, tcx);
+    let src_ty = copy.src.ty(local_decls, tcx);
+    // Fields of enum variants are only a part of the value.
+    if dest_ty.ty != src_ty.ty || dest_ty.variant_index.is_some() || src_ty.variant_index.is_some()
+    {
+        return None;
+    }
+    match dest_ty.ty.kind() {
+        ty::Adt(adt, _) if adt.is_struct() => Some(adt.non_enum_variant().fields.len()),
+        ty::Tuple(tys) => Some(tys.len()),
+        _ => None,
+    }
+}
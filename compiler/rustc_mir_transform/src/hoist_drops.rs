@@ -0,0 +1,196 @@
+//! Moves `Drop` terminators up to the last use of the dropped local.
+//!
+//! A local is dropped at the end of its scope, which can be long after it was last used:
+//! ```ignore (MIR)
+//! bb1: {
+//!     _3 = Vec::<u32>::len(move _4) -> [return: bb2, unwind: bb4];
+//! }
+//!
+//! bb2: {
+//!     _5 = _3;
+//!     _6 = _5;
+//!     goto -> bb3;
+//! }
+//!
+//! bb3: {
+//!     _0 = _6;
+//!     drop(_2) -> [return: bb5, unwind: bb4];
+//! }
+//! ```
+//! Nothing after the call looks at `_2`, so we drop it at the start of `bb2` instead, which
+//! splits off the rest of `bb2` into a new block:
+//! ```ignore (MIR)
+//! bb2: {
+//!     drop(_2) -> [return: bb6, unwind: bb4];
+//! }
+//!
+//! bb3: {
+//!     _0 = _6;
+//!     goto -> bb5;
+//! }
+//!
+//! bb6: {
+//!     _5 = _3;
+//!     _6 = _5;
+//!     goto -> bb3;
+//! }
+//! ```
+//!
+//! Running a destructor earlier must not be observable, so the drop only moves over statements
+//! which neither the destructor nor its unwinding path can tell apart from running after it. They
+//! may only use locals which are never borrowed and never dereference anything, so that the
+//! destructor can't read or write what they access. They may not move or assign anything which
+//! needs to be dropped, so that the unwind path still drops the same locals. The drop never moves
+//! over a terminator other than a `goto`, so the order of drops and calls is preserved.
+
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
+use rustc_mir_dataflow::impls::borrowed_locals;
+
+use crate::MirPass;
+
+pub struct HoistDrops;
+
+impl<'tcx> MirPass<'tcx> for HoistDrops {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let borrowed = borrowed_locals(body);
+        let predecessors = body.basic_blocks.predecessors();
+
+        let mut hoists = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Drop { place, replace: false, .. } = data.terminator().kind else {
+                continue;
+            };
+            let Some(local) = place.as_local() else { continue };
+            // References to the local may still be copied around, but can't be dereferenced in
+            // the statements we move the drop over.
+            if data.is_cleanup {
+                continue;
+            }
+
+            let skippable = |statement: &Statement<'tcx>| {
+                is_skippable(tcx, param_env, body, &borrowed, local, statement)
+            };
+            // The earliest statement we can move the drop in front of.
+            let mut earliest = None;
+            let mut block = bb;
+            let mut index = data.statements.len();
+            loop {
+                let statements = &body.basic_blocks[block].statements;
+                if index > 0 {
+                    if !skippable(&statements[index - 1]) {
+                        break;
+                    }
+                    index -= 1;
+                    earliest = Some((block, index));
+                    continue;
+                }
+                let &[pred] = predecessors[block].as_slice() else { break };
+                let pred_data = &body.basic_blocks[pred];
+                if pred_data.is_cleanup
+                    || !matches!(pred_data.terminator().kind, TerminatorKind::Goto { .. })
+                {
+                    break;
+                }
+                block = pred;
+                index = pred_data.statements.len();
+            }
+
+            if let Some((block, index)) = earliest {
+                debug!(?bb, ?local, ?block, ?index, "hoisting drop");
+                hoists.push((bb, block, index));
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, block, index) in hoists {
+            let terminator = basic_blocks[bb].terminator_mut();
+            let source_info = terminator.source_info;
+            let TerminatorKind::Drop { place, target, unwind, replace } = terminator.kind else {
+                bug!("hoisted a terminator which is not a `Drop`");
+            };
+            terminator.kind = TerminatorKind::Goto { target };
+
+            let data = &mut basic_blocks[block];
+            let rest = BasicBlockData {
+                statements: data.statements.split_off(index),
+                terminator: data.terminator.take(),
+                is_cleanup: false,
+            };
+            let rest = basic_blocks.push(rest);
+            basic_blocks[block].terminator = Some(Terminator {
+                source_info,
+                kind: TerminatorKind::Drop { place, target: rest, unwind, replace },
+            });
+        }
+    }
+}
+
+/// Whether dropping `dropped` before `statement` instead of after it can't be observed.
+fn is_skippable<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &Body<'tcx>,
+    borrowed: &BitSet<Local>,
+    dropped: Local,
+    statement: &Statement<'tcx>,
+) -> bool {
+    match statement.kind {
+        StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => local != dropped,
+        StatementKind::Nop => true,
+        StatementKind::Assign(box (lhs, _)) => {
+            if lhs.ty(body, tcx).ty.needs_drop(tcx, param_env) {
+                return false;
+            }
+            let mut visitor =
+                Skippable { tcx, param_env, body, borrowed, dropped, skippable: true };
+            visitor.visit_statement(statement, START_BLOCK.start_location());
+            visitor.skippable
+        }
+        _ => false,
+    }
+}
+
+/// Checks the places used by an assignment.
+struct Skippable<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    borrowed: &'a BitSet<Local>,
+    dropped: Local,
+    skippable: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for Skippable<'_, 'tcx> {
+    fn visit_operand(&mut self, operand: &Operand<'tcx>, location: Location) {
+        if let Operand::Move(place) = operand
+            && place.ty(self.body, self.tcx).ty.needs_drop(self.tcx, self.param_env)
+        {
+            self.skippable = false;
+        }
+        self.super_operand(operand, location);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        if place.is_indirect() {
+            self.skippable = false;
+        }
+        self.super_place(place, context, location);
+    }
+
+    fn visit_local(&mut self, local: Local, _: PlaceContext, _: Location) {
+        if local == self.dropped || self.borrowed.contains(local) {
+            self.skippable = false;
+        }
+    }
+}
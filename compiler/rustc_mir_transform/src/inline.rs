@@ -35,6 +35,36 @@ struct CallSite<'tcx> {
     source_info: SourceInfo,
 }
 
+/// The cost of a callee, and the threshold it has to stay under to be inlined.
+#[derive(Copy, Clone, Debug)]
+struct CostEstimate {
+    cost: usize,
+    threshold: usize,
+}
+
+/// A callsite which has been inlined.
+#[derive(Debug)]
+struct Inlined {
+    /// The basic blocks containing the inlined body.
+    new_blocks: Range<BasicBlock>,
+    /// The cost of the callee, unless it was inlined without looking at it.
+    estimate: Option<CostEstimate>,
+}
+
+/// Why a callsite has not been inlined.
+#[derive(Copy, Clone, Debug)]
+struct NotInlined {
+    reason: &'static str,
+    /// The cost of the callee, if we got as far as computing it.
+    estimate: Option<CostEstimate>,
+}
+
+impl From<&'static str> for NotInlined {
+    fn from(reason: &'static str) -> Self {
+        NotInlined { reason, estimate: None }
+    }
+}
+
 impl<'tcx> MirPass<'tcx> for Inline {
     fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
         if let Some(enabled) = sess.opts.unstable_opts.inline_mir {
@@ -55,7 +85,7 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         let span = trace_span!("inline", body = %tcx.def_path_str(body.source.def_id()));
         let _guard = span.enter();
-        if inline(tcx, body) {
+        if inline(tcx, body, false) {
             debug!("running simplify cfg on {:?}", body.source);
             CfgSimplifier::new(body).simplify();
             remove_dead_blocks(body);
@@ -64,7 +94,40 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     }
 }
 
-fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
+/// Inlines the trivial accessors, see [`is_trivial_accessor`], when `Inline` doesn't run.
+///
+/// This is the only inlining done in unoptimized builds, so it has to stay cheap. Like `Inline`,
+/// it needs `mir_inliner_callees` for the caller, which is a single walk over its terminators, to
+/// avoid query cycles. For each call, it gives up on callees with more than one block before
+/// looking at their statements, and never runs the cost checker. It can be disabled with
+/// `-Zinline-mir=no`.
+pub struct InlineTrivialAccessors;
+
+impl<'tcx> MirPass<'tcx> for InlineTrivialAccessors {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        // `-Zinline-mir=yes` enables `Inline`, which inlines the accessors too.
+        sess.opts.unstable_opts.inline_mir.is_none()
+            && sess.mir_opt_level() >= 1
+            && !Inline.is_enabled(sess)
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        // `Inline` may still have been enabled by `-Zmir-enable-passes`.
+        if crate::pass_manager::should_run_pass(tcx, &Inline) {
+            return;
+        }
+        let def_path = tcx.def_path_str(body.source.def_id());
+        let span = trace_span!("inline_trivial_accessors", body = %def_path);
+        let _guard = span.enter();
+        if inline(tcx, body, true) {
+            CfgSimplifier::new(body).simplify();
+            remove_dead_blocks(body);
+            deref_finder(tcx, body);
+        }
+    }
+}
+
+fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, only_accessors: bool) -> bool {
     let def_id = body.source.def_id().expect_local();
 
     // Only do inlining into fn bodies.
@@ -87,6 +150,7 @@ fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
         tcx,
         param_env,
         codegen_fn_attrs: tcx.codegen_fn_attrs(def_id),
+        only_accessors,
         history: Vec::new(),
         changed: false,
     };
@@ -100,6 +164,8 @@ struct Inliner<'tcx> {
     param_env: ParamEnv<'tcx>,
     /// Caller codegen attributes.
     codegen_fn_attrs: &'tcx CodegenFnAttrs,
+    /// Whether to only inline trivial accessors, for `InlineTrivialAccessors`.
+    only_accessors: bool,
     /// Stack of inlined instances.
     /// We only check the `DefId` and not the args because we want to
     /// avoid inlining cases of polymorphic recursion.
@@ -133,12 +199,14 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
             let span = trace_span!("process_blocks", %callsite.callee, ?bb);
             let _guard = span.enter();
 
-            match self.try_inlining(caller_body, &callsite) {
-                Err(reason) => {
-                    debug!("not-inlined {} [{}]", callsite.callee, reason);
+            let decision = self.try_inlining(caller_body, &callsite);
+            self.report(caller_body, &callsite, &decision);
+            match decision {
+                Err(not_inlined) => {
+                    debug!("not-inlined {} [{}]", callsite.callee, not_inlined.reason);
                     continue;
                 }
-                Ok(new_blocks) => {
+                Ok(Inlined { new_blocks, .. }) => {
                     debug!("inlined {}", callsite.callee);
                     self.changed = true;
 
@@ -156,6 +224,42 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
         }
     }
 
+    /// Prints the decision taken for `callsite` to stderr, if requested by `-Zmir-inline-report`.
+    ///
+    /// Each decision is printed on its own line, as `key=value` pairs for the caller, the callee,
+    /// the span of the call, the decision (`inlined` or `not-inlined`), the cost of the callee and
+    /// the threshold it was compared against (`-` if we didn't get that far), and the reason.
+    fn report(
+        &self,
+        caller_body: &Body<'tcx>,
+        callsite: &CallSite<'tcx>,
+        decision: &Result<Inlined, NotInlined>,
+    ) {
+        if !self.tcx.sess.opts.unstable_opts.mir_inline_report {
+            return;
+        }
+        let (decision, estimate, reason) = match decision {
+            Ok(Inlined { estimate: None, .. }) => ("inlined", None, "trivial accessor"),
+            Ok(inlined) => ("inlined", inlined.estimate, "cost within threshold"),
+            Err(not_inlined) => ("not-inlined", not_inlined.estimate, not_inlined.reason),
+        };
+        let (cost, threshold) = match estimate {
+            Some(CostEstimate { cost, threshold }) => (cost.to_string(), threshold.to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        eprintln!(
+            "mir-inline-report: caller={} callee={} site={} decision={} cost={} threshold={} \
+            reason={}",
+            self.tcx.def_path_str(caller_body.source.def_id()),
+            callsite.callee,
+            self.tcx.sess.source_map().span_to_diagnostic_string(callsite.source_info.span),
+            decision,
+            cost,
+            threshold,
+            reason,
+        );
+    }
+
     /// Attempts to inline a callsite into the caller body. When successful returns basic blocks
     /// containing the inlined body. Otherwise returns an error describing why inlining didn't take
     /// place.
@@ -163,11 +267,20 @@ fn try_inlining(
         &self,
         caller_body: &mut Body<'tcx>,
         callsite: &CallSite<'tcx>,
-    ) -> Result<std::ops::Range<BasicBlock>, &'static str> {
+    ) -> Result<Inlined, NotInlined> {
         self.check_mir_is_available(caller_body, &callsite.callee)?;
 
+        let callee_body = try_instance_mir(self.tcx, callsite.callee.def)?;
+        let trivial_accessor = is_trivial_accessor(callee_body);
+        if self.only_accessors && !trivial_accessor {
+            return Err("not a trivial accessor".into());
+        }
+
         let callee_attrs = self.tcx.codegen_fn_attrs(callsite.callee.def_id());
-        let cross_crate_inlinable = self.tcx.cross_crate_inlinable(callsite.callee.def_id());
+        // A trivial accessor doesn't mention any other item, so it can't reference a symbol
+        // which isn't exported either.
+        let cross_crate_inlinable =
+            trivial_accessor || self.tcx.cross_crate_inlinable(callsite.callee.def_id());
         self.check_codegen_attributes(callsite, callee_attrs, cross_crate_inlinable)?;
 
         let terminator = caller_body[callsite.block].terminator.as_ref().unwrap();
@@ -177,17 +290,21 @@ fn try_inlining(
             if !arg.ty(&caller_body.local_decls, self.tcx).is_sized(self.tcx, self.param_env) {
                 // We do not allow inlining functions with unsized params. Inlining these functions
                 // could create unsized locals, which are unsound and being phased out.
-                return Err("Call has unsized argument");
+                return Err("Call has unsized argument".into());
             }
         }
 
-        let callee_body = try_instance_mir(self.tcx, callsite.callee.def)?;
-        self.check_mir_body(callsite, callee_body, callee_attrs, cross_crate_inlinable)?;
+        // Trivial accessors are always cheaper than the call, so skip the cost model for them.
+        let estimate = if trivial_accessor {
+            None
+        } else {
+            Some(self.check_mir_body(callsite, callee_body, callee_attrs, cross_crate_inlinable)?)
+        };
 
         if !self.tcx.consider_optimizing(|| {
             format!("Inline {:?} into {:?}", callsite.callee, caller_body.source)
         }) {
-            return Err("optimization fuel exhausted");
+            return Err("optimization fuel exhausted".into());
         }
 
         let Ok(callee_body) = callsite.callee.try_instantiate_mir_and_normalize_erasing_regions(
@@ -195,7 +312,7 @@ fn try_inlining(
             self.param_env,
             ty::EarlyBinder::bind(callee_body.clone()),
         ) else {
-            return Err("failed to normalize callee body");
+            return Err("failed to normalize callee body".into());
         };
 
         // Normally, this shouldn't be required, but trait normalization failure can create a
@@ -208,7 +325,7 @@ fn try_inlining(
         )
         .is_empty()
         {
-            return Err("failed to validate callee body");
+            return Err("failed to validate callee body".into());
         }
 
         // Check call signature compatibility.
@@ -223,14 +340,14 @@ fn try_inlining(
             destination_ty,
         ) {
             trace!(?output_type, ?destination_ty);
-            return Err("failed to normalize return type");
+            return Err("failed to normalize return type".into());
         }
         if callsite.fn_sig.abi() == Abi::RustCall {
             // FIXME: Don't inline user-written `extern "rust-call"` functions,
             // since this is generally perf-negative on rustc, and we hope that
             // LLVM will inline these functions instead.
             if callee_body.spread_arg.is_some() {
-                return Err("do not inline user-written rust-call functions");
+                return Err("do not inline user-written rust-call functions".into());
             }
 
             let (self_arg, arg_tuple) = match &args[..] {
@@ -259,7 +376,7 @@ fn try_inlining(
                     arg_ty,
                 ) {
                     trace!(?arg_ty, ?input_type);
-                    return Err("failed to normalize tuple argument type");
+                    return Err("failed to normalize tuple argument type".into());
                 }
             }
         } else {
@@ -274,7 +391,7 @@ fn try_inlining(
                     arg_ty,
                 ) {
                     trace!(?arg_ty, ?input_type);
-                    return Err("failed to normalize argument type");
+                    return Err("failed to normalize argument type".into());
                 }
             }
         }
@@ -283,7 +400,7 @@ fn try_inlining(
         self.inline_call(caller_body, callsite, callee_body);
         let new_blocks = old_blocks..caller_body.basic_blocks.next_index();
 
-        Ok(new_blocks)
+        Ok(Inlined { new_blocks, estimate })
     }
 
     fn check_mir_is_available(
@@ -458,7 +575,7 @@ fn check_mir_body(
         callee_body: &Body<'tcx>,
         callee_attrs: &CodegenFnAttrs,
         cross_crate_inlinable: bool,
-    ) -> Result<(), &'static str> {
+    ) -> Result<CostEstimate, NotInlined> {
         let tcx = self.tcx;
 
         let mut threshold = if cross_crate_inlinable {
@@ -513,7 +630,7 @@ fn check_mir_body(
                 // assign one. However, during this stage we require an exact match when any
                 // inline-asm is detected. LLVM will still possibly do an inline later on
                 // if the no-attribute function ends up with the same instruction set anyway.
-                return Err("Cannot move inline-asm across instruction sets");
+                return Err("Cannot move inline-asm across instruction sets".into());
             } else {
                 work_list.extend(term.successors())
             }
@@ -524,12 +641,13 @@ fn check_mir_body(
         // generous) inlining threshold. Such functions are very poor MIR inlining candidates.
         // Always inlining #[inline(always)] functions in MIR, on net, slows down the compiler.
         let cost = checker.cost();
+        let estimate = CostEstimate { cost, threshold };
         if cost <= threshold {
             debug!("INLINING {:?} [cost={} <= threshold={}]", callsite, cost, threshold);
-            Ok(())
+            Ok(estimate)
         } else {
             debug!("NOT inlining {:?} [cost={} > threshold={}]", callsite, cost, threshold);
-            Err("cost above threshold")
+            Err(NotInlined { reason: "cost above threshold", estimate: Some(estimate) })
         }
     }
 
@@ -1021,6 +1139,38 @@ fn visit_terminator(&mut self, terminator: &mut Terminator<'tcx>, loc: Location)
     }
 }
 
+/// Whether `body` is a trivial accessor, like `fn x(&self) -> u32 { self.x }`: a single block
+/// which only writes a field of an argument, or a reference to it, to the return place.
+fn is_trivial_accessor(body: &Body<'_>) -> bool {
+    if body.basic_blocks.len() != 1 {
+        return false;
+    }
+    let data = &body.basic_blocks[START_BLOCK];
+    if !matches!(data.terminator().kind, TerminatorKind::Return) {
+        return false;
+    }
+
+    let is_argument_field = |place: &Place<'_>| {
+        body.args_iter().any(|arg| arg == place.local)
+            && place
+                .projection
+                .iter()
+                .all(|elem| matches!(elem, ProjectionElem::Deref | ProjectionElem::Field(..)))
+    };
+    data.statements.iter().all(|statement| match statement.kind {
+        StatementKind::StorageLive(_) | StatementKind::StorageDead(_) | StatementKind::Nop => true,
+        StatementKind::Assign(box (dest, ref rvalue)) => {
+            dest == Place::return_place()
+                && match rvalue {
+                    Rvalue::Use(Operand::Copy(place) | Operand::Move(place))
+                    | Rvalue::Ref(_, _, place) => is_argument_field(place),
+                    _ => false,
+                }
+        }
+        _ => false,
+    })
+}
+
 #[instrument(skip(tcx), level = "debug")]
 fn try_instance_mir<'tcx>(
     tcx: TyCtxt<'tcx>,
@@ -3,11 +3,15 @@
 use crate::simplify::simplify_duplicate_switch_targets;
 use crate::MirPass;
 use rustc_hir::Mutability;
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::{NonMutatingUseContext, NonUseContext, PlaceContext, Visitor};
 use rustc_middle::mir::*;
 use rustc_middle::ty::layout::ValidityRequirement;
 use rustc_middle::ty::{self, GenericArgsRef, ParamEnv, Ty, TyCtxt};
+use rustc_mir_dataflow::impls::borrowed_locals;
 use rustc_span::symbol::Symbol;
-use rustc_target::abi::FieldIdx;
+use rustc_target::abi::{FieldIdx, FIRST_VARIANT};
 
 pub struct InstSimplify;
 
@@ -17,6 +21,9 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let mut occurrences = LocalOccurrences(IndexVec::from_elem(0, &body.local_decls));
+        occurrences.visit_body(body);
+        let borrowed = borrowed_locals(body);
         let ctx = InstSimplifyContext {
             tcx,
             local_decls: &body.local_decls,
@@ -30,12 +37,16 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
                         ctx.simplify_ref_deref(&statement.source_info, rvalue);
                         ctx.simplify_len(&statement.source_info, rvalue);
                         ctx.simplify_cast(&statement.source_info, rvalue);
+                        ctx.simplify_pow2_rem(&statement.source_info, rvalue);
                     }
                     _ => {}
                 }
             }
+            ctx.combine_bit_op_constants(&occurrences, &mut block.statements);
+            ctx.combine_int_casts(&occurrences, &borrowed, &mut block.statements);
 
             ctx.simplify_primitive_clone(block.terminator.as_mut().unwrap(), &mut block.statements);
+            ctx.simplify_rem_by_zero_assert(block.terminator.as_mut().unwrap(), &block.statements);
             ctx.simplify_intrinsic_assert(
                 block.terminator.as_mut().unwrap(),
                 &mut block.statements,
@@ -106,11 +117,20 @@ fn try_eval_bool(&self, a: &Operand<'_>) -> Option<bool> {
         if a.const_.ty().is_bool() { a.const_.try_to_bool() } else { None }
     }
 
-    /// Transform "&(*a)" ==> "a".
+    /// Transform "&(*a)" ==> "a", and "&mut (*a)" ==> "a".
+    ///
+    /// Only reborrows of a reference with the same mutability are folded, so this never turns
+    /// a shared borrow into a mutable one or the other way around. Borrowck has already run, so
+    /// two-phase borrows are folded like any other mutable borrow.
     fn simplify_ref_deref(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
-        if let Rvalue::Ref(_, _, place) = rvalue {
+        if let Rvalue::Ref(_, borrow_kind, place) = rvalue {
             if let Some((base, ProjectionElem::Deref)) = place.as_ref().last_projection() {
-                if rvalue.ty(self.local_decls, self.tcx) != base.ty(self.local_decls, self.tcx).ty {
+                let base_ty = base.ty(self.local_decls, self.tcx).ty;
+                let ty::Ref(_, _, mutbl) = *base_ty.kind() else { return };
+                if *borrow_kind == BorrowKind::Fake || borrow_kind.to_mutbl_lossy() != mutbl {
+                    return;
+                }
+                if rvalue.ty(self.local_decls, self.tcx) != base_ty {
                     return;
                 }
 
@@ -162,10 +182,14 @@ fn simplify_cast(&self, _source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
                     return;
                 }
 
-                // Transmuting a transparent struct/union to a field's type is a projection
+                // Transmuting a transparent struct/union to a field's type is a projection, and
+                // so is transmuting any other struct with a single field and the same layout.
                 if let ty::Adt(adt_def, args) = operand_ty.kind()
-                    && adt_def.repr().transparent()
                     && (adt_def.is_struct() || adt_def.is_union())
+                    && (adt_def.repr().transparent()
+                        || (adt_def.is_struct()
+                            && adt_def.non_enum_variant().fields.len() == 1
+                            && self.same_layout(operand_ty, *cast_ty)))
                     && let Some(place) = operand.place()
                 {
                     let variant = adt_def.non_enum_variant();
@@ -186,10 +210,320 @@ fn simplify_cast(&self, _source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
                         }
                     }
                 }
+
+                // Transmuting a type to a struct with a single field of that type and the same
+                // layout builds the struct. Types with a validity invariant, like `NonZeroU32`,
+                // have a different layout than their field, so their transmutes are kept.
+                if let ty::Adt(adt_def, args) = cast_ty.kind()
+                    && adt_def.is_struct()
+                    && let [field] = &adt_def.non_enum_variant().fields.raw[..]
+                    && field.ty(self.tcx, args) == operand_ty
+                    && self.same_layout(operand_ty, *cast_ty)
+                {
+                    let kind = AggregateKind::Adt(adt_def.did(), FIRST_VARIANT, args, None, None);
+                    let fields = IndexVec::from_raw(vec![operand.clone()]);
+                    *rvalue = Rvalue::Aggregate(Box::new(kind), fields);
+                    return;
+                }
             }
         }
     }
 
+    /// Whether values of `a` and `b` are represented the same way, including the valid ranges of
+    /// their scalars.
+    fn same_layout(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> bool {
+        let (Ok(a), Ok(b)) =
+            (self.tcx.layout_of(self.param_env.and(a)), self.tcx.layout_of(self.param_env.and(b)))
+        else {
+            return false;
+        };
+        a.size == b.size && a.align.abi == b.align.abi && a.abi == b.abi
+    }
+
+    /// Transform "Rem(a, const 2^k)" ==> "BitAnd(a, const 2^k - 1)" for unsigned integers.
+    ///
+    /// This does not hold for signed integers, as the remainder takes the sign of the dividend.
+    fn simplify_pow2_rem(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+        let Rvalue::BinaryOp(BinOp::Rem, box (lhs, rhs)) = rvalue else { return };
+        let ty = rhs.ty(self.local_decls, self.tcx);
+        if !matches!(ty.kind(), ty::Uint(_)) {
+            return;
+        }
+        let Some(divisor) =
+            rhs.constant().and_then(|c| c.const_.try_eval_bits(self.tcx, self.param_env))
+        else {
+            return;
+        };
+        if !divisor.is_power_of_two() {
+            return;
+        }
+
+        let const_ = Const::from_bits(self.tcx, divisor - 1, self.param_env.and(ty));
+        let mask = ConstOperand { span: source_info.span, const_, user_ty: None };
+        let new = Rvalue::BinaryOp(
+            BinOp::BitAnd,
+            Box::new((lhs.clone(), Operand::Constant(Box::new(mask)))),
+        );
+        if self.should_simplify(source_info, rvalue) {
+            *rvalue = new;
+        }
+    }
+
+    /// Combine the constants of chained `BitOr`s, or of chained `BitAnd`s.
+    ///
+    /// ```ignore (MIR)
+    /// _2 = BitOr(_1, const 1_u32);
+    /// _3 = BitOr(move _2, const 2_u32);
+    /// ```
+    /// becomes
+    /// ```ignore (MIR)
+    /// _2 = BitOr(_1, const 3_u32);
+    /// _3 = move _2;
+    /// ```
+    /// The intermediate results must only be used by the next operation of the chain, as they
+    /// now hold the result of the whole chain.
+    fn combine_bit_op_constants(
+        &self,
+        occurrences: &LocalOccurrences,
+        statements: &mut [Statement<'tcx>],
+    ) {
+        for index in 0..statements.len() {
+            let statement = &statements[index];
+            let StatementKind::Assign(box (_, ref rvalue)) = statement.kind else { continue };
+            let Rvalue::BinaryOp(op @ (BinOp::BitOr | BinOp::BitAnd), box (ref lhs, ref rhs)) =
+                *rvalue
+            else {
+                continue;
+            };
+            let ty = lhs.ty(self.local_decls, self.tcx);
+            if !ty.is_integral() && !ty.is_bool() {
+                continue;
+            }
+            let Some((operand, bits)) = self.split_bit_op_constant(lhs, rhs) else { continue };
+            let Some(mut local) = operand.place().and_then(|place| place.as_local()) else {
+                continue;
+            };
+
+            // Find the start of the chain, going through the moves left by previous iterations.
+            let root = loop {
+                // The assignment and the use by the next operation.
+                if occurrences.0[local] != 2 {
+                    break None;
+                }
+                let Some(def) = statements[..index].iter().rposition(|statement| {
+                    matches!(
+                        statement.kind,
+                        StatementKind::Assign(box (place, _)) if place.as_local() == Some(local)
+                    )
+                }) else {
+                    break None;
+                };
+                let StatementKind::Assign(box (_, ref def_rvalue)) = statements[def].kind else {
+                    bug!()
+                };
+                match *def_rvalue {
+                    Rvalue::Use(Operand::Copy(place) | Operand::Move(place))
+                        if let Some(moved) = place.as_local() =>
+                    {
+                        local = moved;
+                    }
+                    Rvalue::BinaryOp(def_op, box (ref a, ref b)) if def_op == op => {
+                        let root_bits = self.split_bit_op_constant(a, b).map(|(_, bits)| bits);
+                        break root_bits.map(|root_bits| (def, root_bits));
+                    }
+                    _ => break None,
+                }
+            };
+            let Some((root, root_bits)) = root else { continue };
+            if !self.should_simplify(&statement.source_info, rvalue) {
+                continue;
+            }
+
+            let bits = if op == BinOp::BitOr { root_bits | bits } else { root_bits & bits };
+            let new_rvalue = Rvalue::Use(operand.clone());
+            let StatementKind::Assign(box (_, ref mut root_rvalue)) = statements[root].kind else {
+                bug!()
+            };
+            let Rvalue::BinaryOp(_, box (ref mut a, ref mut b)) = *root_rvalue else { bug!() };
+            let constant = if self.try_eval_bits(b).is_some() { b } else { a };
+            let Operand::Constant(ref mut constant) = *constant else { bug!() };
+            constant.const_ = Const::from_bits(self.tcx, bits, self.param_env.and(ty));
+
+            let StatementKind::Assign(box (_, ref mut rvalue)) = statements[index].kind else {
+                bug!()
+            };
+            *rvalue = new_rvalue;
+        }
+    }
+
+    /// Fold an integer cast of an integer cast into a single cast, when the intermediate type is
+    /// wider than the original one and casting through it doesn't change the result.
+    ///
+    /// ```ignore (MIR)
+    /// _2 = _1 as u64 (IntToInt);
+    /// _3 = move _2 as u32 (IntToInt);
+    /// ```
+    /// becomes
+    /// ```ignore (MIR)
+    /// _2 = _1 as u64 (IntToInt);
+    /// _3 = _1 as u32 (IntToInt);
+    /// ```
+    /// Truncating the widened value keeps the same bits as truncating the original one. Extending
+    /// it again does too, unless a signed value was widened to an unsigned type: that is
+    /// sign-extended, then zero-extended, so `-1_i8 as u16 as u32` is `0xffff`, whereas
+    /// `-1_i8 as u32` is `0xffff_ffff`.
+    fn combine_int_casts(
+        &self,
+        occurrences: &LocalOccurrences,
+        borrowed: &BitSet<Local>,
+        statements: &mut [Statement<'tcx>],
+    ) {
+        for index in 0..statements.len() {
+            let statement = &statements[index];
+            let StatementKind::Assign(box (_, ref rvalue)) = statement.kind else { continue };
+            let Rvalue::Cast(CastKind::IntToInt, ref operand, ty) = *rvalue else { continue };
+            let Some(intermediate) = operand.place().and_then(|place| place.as_local()) else {
+                continue;
+            };
+            // The assignment and this cast.
+            if occurrences.0[intermediate] != 2 {
+                continue;
+            }
+            let Some(def) = find_assignment(&statements[..index], intermediate) else { continue };
+            let StatementKind::Assign(box (_, ref def_rvalue)) = statements[def].kind else {
+                bug!()
+            };
+            let Rvalue::Cast(CastKind::IntToInt, ref source, intermediate_ty) = *def_rvalue else {
+                continue;
+            };
+            let source_ty = source.ty(self.local_decls, self.tcx);
+            if !self.is_lossless_int_cast_chain(source_ty, intermediate_ty, ty) {
+                continue;
+            }
+
+            // The source is read again by the new cast, so it must still hold the same value.
+            // Go through the copy MIR building makes of it, as the copy is dead by then.
+            let (mut source, mut source_def) = (source.clone(), def);
+            if let Some(copy) = source.place().and_then(|place| place.as_local())
+                && occurrences.0[copy] == 2
+                && let Some(copy_def) = find_assignment(&statements[..def], copy)
+                && let StatementKind::Assign(box (_, Rvalue::Use(Operand::Copy(copied)))) =
+                    statements[copy_def].kind
+                && copied.as_local().is_some()
+            {
+                (source, source_def) = (Operand::Copy(copied), copy_def);
+            }
+            if let Some(place) = source.place() {
+                let Some(local) = place.as_local() else { continue };
+                if borrowed.contains(local)
+                    || is_changed(&statements[source_def + 1..index], local)
+                    || (source_def == def && source.is_move())
+                {
+                    continue;
+                }
+                source = Operand::Copy(place);
+            }
+            if !self.should_simplify(&statement.source_info, rvalue) {
+                continue;
+            }
+
+            let StatementKind::Assign(box (_, ref mut rvalue)) = statements[index].kind else {
+                bug!()
+            };
+            *rvalue = Rvalue::Cast(CastKind::IntToInt, source, ty);
+        }
+    }
+
+    /// Whether casting an integer of type `source` to `intermediate`, then to `target`, gives the
+    /// same value as casting it to `target` directly, if `intermediate` is wider than `source`.
+    fn is_lossless_int_cast_chain(
+        &self,
+        source: Ty<'tcx>,
+        intermediate: Ty<'tcx>,
+        target: Ty<'tcx>,
+    ) -> bool {
+        let is_int = |ty: Ty<'tcx>| matches!(ty.kind(), ty::Int(_) | ty::Uint(_));
+        if !is_int(source) || !is_int(intermediate) || !is_int(target) {
+            return false;
+        }
+        let (source_size, source_signed) = source.int_size_and_signed(self.tcx);
+        let (intermediate_size, intermediate_signed) = intermediate.int_size_and_signed(self.tcx);
+        let (target_size, _) = target.int_size_and_signed(self.tcx);
+        intermediate_size > source_size
+            && (target_size <= intermediate_size || !source_signed || intermediate_signed)
+    }
+
+    /// Splits the operands of a `BitOr` or a `BitAnd` into the non-constant one and the bits of
+    /// the constant one.
+    fn split_bit_op_constant<'a>(
+        &self,
+        lhs: &'a Operand<'tcx>,
+        rhs: &'a Operand<'tcx>,
+    ) -> Option<(&'a Operand<'tcx>, u128)> {
+        match (self.try_eval_bits(lhs), self.try_eval_bits(rhs)) {
+            (None, Some(bits)) => Some((lhs, bits)),
+            (Some(bits), None) => Some((rhs, bits)),
+            _ => None,
+        }
+    }
+
+    fn try_eval_bits(&self, operand: &Operand<'tcx>) -> Option<u128> {
+        operand.constant()?.const_.try_eval_bits(self.tcx, self.param_env)
+    }
+
+    /// Remove the "remainder by zero" assertion when the divisor is a nonzero constant.
+    ///
+    /// MIR building checks the divisor even when it is a constant:
+    /// ```ignore (MIR)
+    /// _3 = Eq(const 8_u32, const 0_u32);
+    /// assert(!move _3, "attempt to calculate the remainder of `{}` with a divisor of zero", _2)
+    /// ```
+    fn simplify_rem_by_zero_assert(
+        &self,
+        terminator: &mut Terminator<'tcx>,
+        statements: &[Statement<'tcx>],
+    ) {
+        let TerminatorKind::Assert {
+            ref cond,
+            expected: false,
+            msg: box AssertKind::RemainderByZero(_),
+            target,
+            ..
+        } = terminator.kind
+        else {
+            return;
+        };
+        let Some(cond) = cond.place().and_then(|place| place.as_local()) else { return };
+
+        // Find the comparison computing the condition.
+        let Some(Rvalue::BinaryOp(BinOp::Eq, box (divisor, zero))) =
+            statements.iter().rev().find_map(|statement| match &statement.kind {
+                StatementKind::Assign(box (place, rvalue)) if place.as_local() == Some(cond) => {
+                    Some(rvalue)
+                }
+                _ => None,
+            })
+        else {
+            return;
+        };
+        let eval = |operand: &Operand<'tcx>| {
+            operand.constant()?.const_.try_eval_bits(self.tcx, self.param_env)
+        };
+        if eval(zero) != Some(0) || !matches!(eval(divisor), Some(1..)) {
+            return;
+        }
+
+        if !self.tcx.consider_optimizing(|| {
+            format!(
+                "InstSimplify - Assert: {:?} SourceInfo: {:?}",
+                terminator.kind, terminator.source_info
+            )
+        }) {
+            return;
+        }
+        terminator.kind = TerminatorKind::Goto { target };
+    }
+
     fn simplify_primitive_clone(
         &self,
         terminator: &mut Terminator<'tcx>,
@@ -291,6 +625,60 @@ fn simplify_intrinsic_assert(
     }
 }
 
+/// The index of the last statement assigning to `local`.
+fn find_assignment(statements: &[Statement<'_>], local: Local) -> Option<usize> {
+    statements.iter().rposition(|statement| {
+        matches!(
+            statement.kind,
+            StatementKind::Assign(box (place, _)) if place.as_local() == Some(local)
+        )
+    })
+}
+
+/// Whether `statements` may change the value of `local`, by writing to it, moving out of it, or
+/// ending its storage.
+fn is_changed(statements: &[Statement<'_>], local: Local) -> bool {
+    struct ChangeFinder {
+        local: Local,
+        changed: bool,
+    }
+
+    impl<'tcx> Visitor<'tcx> for ChangeFinder {
+        fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+            if local == self.local
+                && (context.is_mutating_use()
+                    || matches!(
+                        context,
+                        PlaceContext::NonMutatingUse(NonMutatingUseContext::Move)
+                            | PlaceContext::NonUse(NonUseContext::StorageDead)
+                    ))
+            {
+                self.changed = true;
+            }
+        }
+    }
+
+    let mut finder = ChangeFinder { local, changed: false };
+    for statement in statements {
+        finder.visit_statement(statement, Location::START);
+    }
+    finder.changed
+}
+
+/// Counts the occurrences of each local, storage statements excepted.
+struct LocalOccurrences(IndexVec<Local, u32>);
+
+impl<'tcx> Visitor<'tcx> for LocalOccurrences {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if !matches!(
+            context,
+            PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead)
+        ) {
+            self.0[local] += 1;
+        }
+    }
+}
+
 fn intrinsic_assert_panics<'tcx>(
     tcx: TyCtxt<'tcx>,
     param_env: ty::ParamEnv<'tcx>,
@@ -47,18 +47,25 @@
 
 use pass_manager::{self as pm, Lint, MirLint, WithMinOptLevel};
 
+pub use pass_manager::print_mir_opt_timings;
+
 mod abort_unwinding_calls;
 mod add_call_guards;
 mod add_moves_for_packed_drops;
 mod add_retag;
 mod check_const_item_mutation;
 mod check_packed_ref;
+mod check_unchecked_indexing;
 pub mod check_unsafety;
+mod check_unsafety_after_inlining;
 mod remove_place_mention;
 // This pass is public to allow external drivers to perform MIR cleanup
 mod add_subtyping_projections;
+pub mod assert_lint;
+mod box_to_stack;
 pub mod cleanup_post_borrowck;
 mod const_debuginfo;
+mod const_discriminant;
 mod const_goto;
 mod const_prop;
 mod const_prop_lint;
@@ -72,34 +79,53 @@
 mod dead_store_elimination;
 mod deduce_param_attrs;
 mod deduplicate_blocks;
+mod deduplicate_consts;
 mod deref_separator;
 mod dest_prop;
+mod devirtualize;
 pub mod dump_mir;
 mod early_otherwise_branch;
 mod elaborate_box_derefs;
 mod elaborate_drops;
 mod errors;
+mod expand_small_powers;
 mod ffi_unwind_calls;
+mod fold_constant_array_sums;
+mod fold_repeated_switches;
 mod function_item_references;
+mod fuse_checked_unwrap;
+mod fuse_field_copies;
 mod gvn;
+mod hoist_drops;
 pub mod inline;
 mod instsimplify;
 mod jump_threading;
 mod large_enums;
+mod loop_invariant_len;
 mod lower_intrinsics;
 mod lower_slice_len;
 mod match_branches;
+mod merge_switch_targets;
 mod multiple_return_terminators;
 mod normalize_array_len;
 mod nrvo;
 mod prettify;
+mod prune_range_branches;
 mod ref_prop;
+mod remove_duplicate_asserts;
+mod remove_exact_chunk_bounds_checks;
 mod remove_noop_landing_pads;
+mod remove_null_checks;
+mod remove_range_loop_bounds_checks;
+mod remove_redundant_bounds_checks;
+mod remove_redundant_clones;
 mod remove_storage_markers;
 mod remove_uninit_drops;
 mod remove_unneeded_drops;
 mod remove_zsts;
 mod required_consts;
+mod reserve_vec_capacity;
+mod reuse_field_reads;
 mod reveal_all;
 mod separate_const_switch;
 mod shim;
@@ -109,7 +135,12 @@
 pub mod simplify;
 mod simplify_branches;
 mod simplify_comparison_integral;
+mod sink_cold_blocks;
 mod sroa;
+mod strength_reduction;
+mod switch_to_select;
+mod tail_recursion;
+mod thread_option_matches;
 mod uninhabited_enum_branching;
 mod unreachable_prop;
 
@@ -121,6 +152,7 @@
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
 
 pub fn provide(providers: &mut Providers) {
+    check_const_item_mutation::provide(providers);
     check_unsafety::provide(providers);
     coverage::query::provide(providers);
     ffi_unwind_calls::provide(providers);
@@ -292,6 +324,9 @@ fn mir_const(tcx: TyCtxt<'_>, def: LocalDefId) -> &Steal<Body<'_>> {
     // has_ffi_unwind_calls query uses the raw mir, so make sure it is run.
     tcx.ensure_with_value().has_ffi_unwind_calls(def);
 
+    // The `CheckConstItemMutation` lint below reads the result of this query.
+    tcx.ensure_with_value().const_item_mutations(def);
+
     let mut body = tcx.mir_built(def).steal();
 
     pass_manager::dump_mir_for_phase_change(tcx, &body);
@@ -303,6 +338,7 @@ fn mir_const(tcx: TyCtxt<'_>, def: LocalDefId) -> &Steal<Body<'_>> {
             // MIR-level lints.
             &Lint(check_packed_ref::CheckPackedRef),
             &Lint(check_const_item_mutation::CheckConstItemMutation),
+            &Lint(check_unchecked_indexing::CheckUncheckedIndexing),
             &Lint(function_item_references::FunctionItemReferences),
             // What we need to do constant evaluation.
             &simplify::SimplifyCfg::Initial,
@@ -403,7 +439,9 @@ fn mir_drops_elaborated_and_const_checked(tcx: TyCtxt<'_>, def: LocalDefId) -> &
     let is_fn_like = tcx.def_kind(def).is_fn_like();
     if is_fn_like {
         // Do not compute the mir call graph without said call graph actually being used.
-        if pm::should_run_pass(tcx, &inline::Inline) {
+        if pm::should_run_pass(tcx, &inline::Inline)
+            || pm::should_run_pass(tcx, &inline::InlineTrivialAccessors)
+        {
             tcx.ensure_with_value().mir_inliner_callees(ty::InstanceDef::Item(def.to_def_id()));
         }
     }
@@ -565,35 +603,76 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
         &[
             &check_alignment::CheckAlignment,
             &lower_slice_len::LowerSliceLenCalls, // has to be done before inlining, otherwise actual call will be almost always inlined. Also simple, so can just do first
+            // Has to run before inlining, which would inline `Box::new`.
+            &box_to_stack::BoxToStack,
+            // Has to run before inlining, like `BoxToStack`.
+            &reserve_vec_capacity::ReserveVecCapacity,
+            // These have to run before inlining, which would inline `unwrap` and `clone`.
+            &fuse_checked_unwrap::FuseCheckedUnwrap,
+            &remove_redundant_clones::RemoveRedundantClones,
+            // Has to run before inlining, which would inline `Range::next`.
+            &remove_range_loop_bounds_checks::RemoveRangeLoopBoundsChecks,
+            // Has to run before inlining, like `RemoveRangeLoopBoundsChecks`.
+            &remove_exact_chunk_bounds_checks::RemoveExactChunkBoundsChecks,
+            // Has to run before inlining, which would inline `Iterator::sum`.
+            &fold_constant_array_sums::FoldConstantArraySums,
+            // Has to run before inlining, which would inline `pow`.
+            &expand_small_powers::ExpandSmallPowers,
             &inline::Inline,
+            &inline::InlineTrivialAccessors,
+            &check_unsafety_after_inlining::CheckUnsafetyAfterInlining,
             // Substitutions during inlining may introduce switch on enums with uninhabited branches.
             &uninhabited_enum_branching::UninhabitedEnumBranching,
             &unreachable_prop::UnreachablePropagation,
             &o1(simplify::SimplifyCfg::AfterUninhabitedEnumBranching),
+            // Inlined combinators build `Option`s that are matched on right away.
+            &thread_option_matches::ThreadOptionMatches,
             &remove_storage_markers::RemoveStorageMarkers,
             &remove_zsts::RemoveZsts,
             &normalize_array_len::NormalizeArrayLen, // has to run after `slice::len` lowering
+            &remove_redundant_bounds_checks::RemoveRedundantBoundsChecks,
             &const_goto::ConstGoto,
             &remove_unneeded_drops::RemoveUnneededDrops,
+            &hoist_drops::HoistDrops,
             &ref_prop::ReferencePropagation,
             &sroa::ScalarReplacementOfAggregates,
             &match_branches::MatchBranchSimplification,
+            // Leaves choosing between `bool`s to `MatchBranchSimplification`.
+            &switch_to_select::SwitchToSelect,
             // inst combine is after MatchBranchSimplification to clean up Ne(_1, false)
             &multiple_return_terminators::MultipleReturnTerminators,
+            // The argument copies are cleaned up by `CopyProp`.
+            &tail_recursion::TailRecursionToLoop,
             &instsimplify::InstSimplify,
+            &fuse_field_copies::FuseFieldCopies,
+            // The copies it leaves are removed by `CopyProp`.
+            &reuse_field_reads::ReuseFieldReads,
             &simplify::SimplifyLocals::BeforeConstProp,
             &copy_prop::CopyProp,
+            // Only recognizes the receiver once reborrows are propagated.
+            &devirtualize::Devirtualize,
             // Perform `SeparateConstSwitch` after SSA-based analyses, as cloning blocks may
             // destroy the SSA property. It should still happen before const-propagation, so the
             // latter pass will leverage the created opportunities.
             &separate_const_switch::SeparateConstSwitch,
+            &const_discriminant::ConstDiscriminant,
             &const_prop::ConstProp,
             &gvn::GVN,
+            // GVN gives each constant it creates its own allocation.
+            &deduplicate_consts::DeduplicateConsts,
+            &remove_duplicate_asserts::RemoveDuplicateAsserts,
+            // GVN folds `ptr::null()` to a constant first.
+            &remove_null_checks::RemoveNullChecks,
             &simplify::SimplifyLocals::AfterGVN,
             &dataflow_const_prop::DataflowConstProp,
+            // After const propagation has found the constant factors.
+            &strength_reduction::StrengthReduction,
             &const_debuginfo::ConstDebugInfo,
+            // Uses the constants found by const propagation.
+            &prune_range_branches::PruneRangeBranches,
             &o1(simplify_branches::SimplifyConstCondition::AfterConstProp),
             &jump_threading::JumpThreading,
+            &fold_repeated_switches::FoldRepeatedSwitches,
             &early_otherwise_branch::EarlyOtherwiseBranch,
             &simplify_comparison_integral::SimplifyComparisonIntegral,
             &dead_store_elimination::DeadStoreElimination,
@@ -601,6 +680,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &o1(simplify_branches::SimplifyConstCondition::Final),
             &o1(remove_noop_landing_pads::RemoveNoopLandingPads),
             &o1(simplify::SimplifyCfg::Final),
+            &merge_switch_targets::MergeSwitchTargets,
+            &loop_invariant_len::LoopInvariantLen,
             &nrvo::RenameReturnPlace,
             &simplify::SimplifyLocals::Final,
             &multiple_return_terminators::MultipleReturnTerminators,
@@ -608,6 +689,7 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &large_enums::EnumSizeOpt { discrepancy: 128 },
             // Some cleanup necessary at least for LLVM and potentially other codegen backends.
             &add_call_guards::CriticalCallEdges,
+            &sink_cold_blocks::SinkColdBlocks,
             // Cleanup for human readability, off by default.
             &prettify::ReorderBasicBlocks,
             &prettify::ReorderLocals,
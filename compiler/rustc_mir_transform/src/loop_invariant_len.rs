@@ -0,0 +1,229 @@
+//! Hoists loop-invariant `Len` computations out of loops.
+//!
+//! Indexing a slice in a loop recomputes the length for the bounds check on every iteration:
+//! ```ignore (MIR)
+//! bb0: {
+//!     goto -> bb1;
+//! }
+//!
+//! bb1: {
+//!     _4 = Len((*_1));
+//!     _5 = Lt(_3, _4);
+//!     assert(move _5, "index out of bounds: ...", move _4, _3) -> bb2;
+//! }
+//!
+//! bb2: {
+//!     ...
+//!     goto -> bb1;
+//! }
+//! ```
+//! If `_1` cannot change while the loop runs, we move `_4 = Len((*_1))` to the end of `bb0`, the
+//! only block which enters the loop from the outside, and turn the moves of `_4` into copies.
+//!
+//! The length of a slice is stored in the pointer to it, so we only consider places which are
+//! either a local array, or a single dereference of a reference or `Box` held in a local (or in
+//! a field of one). The local must not be borrowed anywhere in the body, and must not be
+//! modified in the loop. The destination of the `Len` must be an SSA local without storage
+//! markers, so that it keeps its value for the whole loop once assigned.
+//!
+//! Loops are processed from the innermost outwards, so a `Len` can be hoisted through several
+//! nested loops.
+
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{MutVisitor, NonUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_mir_dataflow::impls::borrowed_locals;
+
+use crate::ssa::SsaLocals;
+use crate::MirPass;
+
+pub struct LoopInvariantLen;
+
+impl<'tcx> MirPass<'tcx> for LoopInvariantLen {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        if !body.basic_blocks.is_cfg_cyclic() {
+            return;
+        }
+
+        let loops = find_loops(body);
+        if loops.is_empty() {
+            return;
+        }
+
+        let ssa = SsaLocals::new(body);
+        let borrowed = borrowed_locals(body);
+        let mut with_storage = StorageMarked(BitSet::new_empty(body.local_decls.len()));
+        with_storage.visit_body(body);
+        let with_storage = with_storage.0;
+
+        let mut hoisted = BitSet::new_empty(body.local_decls.len());
+        for lp in &loops {
+            let mut modified = Modified(BitSet::new_empty(body.local_decls.len()));
+            for bb in lp.blocks.iter() {
+                modified.visit_basic_block_data(bb, &body.basic_blocks[bb]);
+            }
+            let modified = modified.0;
+
+            let mut candidates = Vec::new();
+            for bb in lp.blocks.iter() {
+                for (statement_index, statement) in
+                    body.basic_blocks[bb].statements.iter().enumerate()
+                {
+                    let StatementKind::Assign(box (dest, Rvalue::Len(place))) = statement.kind
+                    else {
+                        continue;
+                    };
+                    let Some(dest) = dest.as_local() else { continue };
+                    if !ssa.is_ssa(dest)
+                        || with_storage.contains(dest)
+                        || borrowed.contains(place.local)
+                        || modified.contains(place.local)
+                        || !is_length_in_local(tcx, body, place)
+                    {
+                        continue;
+                    }
+                    candidates.push((bb, statement_index, dest));
+                }
+            }
+
+            // Remove the statements back to front, so that the indices stay valid.
+            let basic_blocks = body.basic_blocks.as_mut_preserves_cfg();
+            let mut hoisted_statements = Vec::with_capacity(candidates.len());
+            for &(bb, statement_index, dest) in candidates.iter().rev() {
+                debug!(?bb, ?dest, preheader = ?lp.preheader, "hoisting loop-invariant length");
+                hoisted_statements.push(basic_blocks[bb].statements.remove(statement_index));
+                hoisted.insert(dest);
+            }
+            basic_blocks[lp.preheader].statements.extend(hoisted_statements.into_iter().rev());
+        }
+
+        if hoisted.is_empty() {
+            return;
+        }
+
+        // The hoisted values are now live across iterations, so they must not be moved from.
+        CopyHoisted { tcx, hoisted }.visit_body_preserves_cfg(body);
+    }
+}
+
+struct Loop {
+    /// The only block outside of the loop which jumps to its header.
+    preheader: BasicBlock,
+    blocks: BitSet<BasicBlock>,
+}
+
+/// Finds the natural loops of `body` which have a preheader, innermost first.
+fn find_loops(body: &Body<'_>) -> Vec<Loop> {
+    let basic_blocks = &body.basic_blocks;
+    let dominators = basic_blocks.dominators();
+    let predecessors = basic_blocks.predecessors();
+
+    let mut loops = Vec::new();
+    // An inner loop header is dominated by the outer one, so it comes later in reverse postorder.
+    for &header in basic_blocks.reverse_postorder().iter().rev() {
+        let (latches, entries): (Vec<_>, Vec<_>) = predecessors[header]
+            .iter()
+            .copied()
+            .partition(|&pred| dominators.is_reachable(pred) && dominators.dominates(header, pred));
+        if latches.is_empty() {
+            continue;
+        }
+
+        let mut blocks = BitSet::new_empty(basic_blocks.len());
+        blocks.insert(header);
+        let mut worklist = latches;
+        while let Some(bb) = worklist.pop() {
+            if blocks.insert(bb) {
+                worklist.extend(predecessors[bb].iter().copied());
+            }
+        }
+
+        let &[preheader] = &entries[..] else { continue };
+        if !matches!(
+            basic_blocks[preheader].terminator().kind,
+            TerminatorKind::Goto { target } if target == header
+        ) {
+            continue;
+        }
+        loops.push(Loop { preheader, blocks });
+    }
+    loops
+}
+
+/// Whether the length of `place` is stored in its local, rather than behind a pointer which
+/// could be modified through some alias.
+fn is_length_in_local<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>, place: Place<'tcx>) -> bool {
+    let mut seen_deref = false;
+    for (base, elem) in place.iter_projections() {
+        match elem {
+            ProjectionElem::Field(..) if !seen_deref => {}
+            ProjectionElem::Deref if !seen_deref => {
+                let ty = base.ty(body, tcx).ty;
+                if !ty.is_ref() && !ty.is_box() {
+                    return false;
+                }
+                seen_deref = true;
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Collects the locals which have storage markers.
+struct StorageMarked(BitSet<Local>);
+
+impl<'tcx> Visitor<'tcx> for StorageMarked {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if let PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead) =
+            context
+        {
+            self.0.insert(local);
+        }
+    }
+}
+
+/// Collects the locals which may be modified, or lose their storage.
+struct Modified(BitSet<Local>);
+
+impl<'tcx> Visitor<'tcx> for Modified {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_mutating_use()
+            || matches!(
+                context,
+                PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead)
+            )
+        {
+            self.0.insert(local);
+        }
+    }
+}
+
+struct CopyHoisted<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    hoisted: BitSet<Local>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for CopyHoisted<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, location: Location) {
+        if let Operand::Move(place) = *operand
+            && let Some(local) = place.as_local()
+            && self.hoisted.contains(local)
+        {
+            *operand = Operand::Copy(place);
+        }
+        self.super_operand(operand, location);
+    }
+}
@@ -0,0 +1,156 @@
+//! Merges the targets of a `SwitchInt` whose blocks do the same thing.
+//!
+//! Large `match`es often end up with several arms whose blocks are equal apart from the storage
+//! markers of the temporaries used in them:
+//! ```ignore (MIR)
+//! bb0: {
+//!     switchInt(_1) -> [0: bb1, 1: bb2, otherwise: bb3];
+//! }
+//!
+//! bb1: {
+//!     _0 = const 1_i32;
+//!     StorageDead(_2);
+//!     return;
+//! }
+//!
+//! bb2: {
+//!     _0 = const 1_i32;
+//!     return;
+//! }
+//! ```
+//! Here, `bb1` and `bb2` can be merged by dropping the `StorageDead(_2)`, which only lengthens the
+//! live range of `_2`: keeping its storage live for longer is always allowed. We keep one of the
+//! blocks, strip the `StorageDead`s that are not present in every merged block from it, and point
+//! all the equivalent targets to it.
+//!
+//! `StorageLive`s are compared like any other statement: removing one could make a later use of
+//! the local undefined behaviour. Source information is not compared, which means that merged
+//! blocks lose the spans and scopes of all but one of them.
+//!
+//! Unlike `DeduplicateBlocks`, this only looks at the successors of `SwitchInt` terminators.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+use crate::deduplicate_blocks::statement_eq;
+use crate::simplify::{simplify_cfg, simplify_duplicate_switch_targets};
+use crate::MirPass;
+
+pub struct MergeSwitchTargets;
+
+impl<'tcx> MirPass<'tcx> for MergeSwitchTargets {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        // For each `SwitchInt`, its targets that can be replaced by an equivalent one.
+        let mut replacements = Vec::new();
+        // For each kept block, the `StorageDead`s that all blocks merged into it agree on.
+        let mut common_storage_dead: FxHashMap<BasicBlock, BitSet<Local>> = FxHashMap::default();
+
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::SwitchInt { ref targets, .. } = data.terminator().kind else {
+                continue;
+            };
+
+            let mut classes: Vec<BasicBlock> = Vec::new();
+            let mut merged = FxHashMap::default();
+            for &target in targets.all_targets() {
+                if classes.contains(&target) || merged.contains_key(&target) {
+                    continue;
+                }
+                let target_data = &body.basic_blocks[target];
+                let equivalent = classes
+                    .iter()
+                    .copied()
+                    .find(|&kept| blocks_eq(&body.basic_blocks[kept], target_data));
+                let Some(kept) = equivalent else {
+                    classes.push(target);
+                    continue;
+                };
+                debug!(?bb, ?target, ?kept, "merging switch target");
+                merged.insert(target, kept);
+                let target_dead = storage_dead(body, target);
+                common_storage_dead
+                    .entry(kept)
+                    .or_insert_with(|| storage_dead(body, kept))
+                    .intersect(&target_dead);
+            }
+
+            if !merged.is_empty() {
+                replacements.push((bb, merged));
+            }
+        }
+
+        if replacements.is_empty() {
+            return;
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, merged) in replacements {
+            let terminator = basic_blocks[bb].terminator_mut();
+            let TerminatorKind::SwitchInt { ref mut targets, .. } = terminator.kind else {
+                bug!("merged targets of a terminator which is not a `SwitchInt`");
+            };
+            for target in targets.all_targets_mut() {
+                if let Some(&kept) = merged.get(target) {
+                    *target = kept;
+                }
+            }
+            simplify_duplicate_switch_targets(terminator);
+        }
+
+        for (kept, common) in common_storage_dead {
+            for statement in &mut basic_blocks[kept].statements {
+                if let StatementKind::StorageDead(local) = statement.kind
+                    && !common.contains(local)
+                {
+                    statement.make_nop();
+                }
+            }
+        }
+
+        simplify_cfg(tcx, body);
+    }
+}
+
+/// Whether two blocks are equal once `StorageDead`s and `Nop`s are disregarded.
+fn blocks_eq<'tcx>(lhs: &BasicBlockData<'tcx>, rhs: &BasicBlockData<'tcx>) -> bool {
+    if lhs.is_cleanup != rhs.is_cleanup || lhs.terminator().kind != rhs.terminator().kind {
+        return false;
+    }
+    let mut lhs = compared_statements(lhs);
+    let mut rhs = compared_statements(rhs);
+    loop {
+        match (lhs.next(), rhs.next()) {
+            (None, None) => return true,
+            (Some(x), Some(y)) if statement_eq(x, y) => {}
+            _ => return false,
+        }
+    }
+}
+
+fn compared_statements<'a, 'tcx>(
+    data: &'a BasicBlockData<'tcx>,
+) -> impl Iterator<Item = &'a StatementKind<'tcx>> {
+    data.statements
+        .iter()
+        .map(|statement| &statement.kind)
+        .filter(|kind| !matches!(kind, StatementKind::StorageDead(_) | StatementKind::Nop))
+}
+
+fn storage_dead(body: &Body<'_>, bb: BasicBlock) -> BitSet<Local> {
+    let mut locals = BitSet::new_empty(body.local_decls.len());
+    for statement in &body.basic_blocks[bb].statements {
+        if let StatementKind::StorageDead(local) = statement.kind {
+            locals.insert(local);
+        }
+    }
+    locals
+}
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use rustc_middle::mir::{self, Body, MirPhase, RuntimePhase};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
@@ -114,6 +116,7 @@ fn run_passes_inner<'tcx>(
     trace!(?overridden_passes);
 
     let prof_arg = tcx.sess.prof.enabled().then(|| format!("{:?}", body.source.def_id()));
+    let record_timings = tcx.sess.opts.unstable_opts.mir_opt_timings.is_some();
 
     if !body.should_skip() {
         for pass in passes {
@@ -132,6 +135,7 @@ fn run_passes_inner<'tcx>(
                 validate_body(tcx, body, format!("before pass {name}"));
             }
 
+            let start = record_timings.then(Instant::now);
             if let Some(prof_arg) = &prof_arg {
                 tcx.sess
                     .prof
@@ -140,6 +144,12 @@ fn run_passes_inner<'tcx>(
             } else {
                 pass.run_pass(tcx, body);
             }
+            if let Some(start) = start {
+                let elapsed = start.elapsed();
+                let mut timings = tcx.mir_opt_timings.lock();
+                let passes = timings.entry(body.source.def_id()).or_default();
+                *passes.entry(name).or_default() += elapsed;
+            }
 
             if dump_enabled {
                 dump_mir_for_pass(tcx, body, name, true);
@@ -169,6 +179,33 @@ fn run_passes_inner<'tcx>(
     }
 }
 
+/// Prints the functions which spent the most time in MIR passes to stderr, if requested by
+/// `-Zmir-opt-timings`, along with the time taken by each pass on them.
+///
+/// The times of all the bodies of a function, like its promoteds, are added up. Each function is
+/// printed as a `body=... total=...` line, followed by a `body=... pass=... time=...` line for
+/// each pass, from the slowest one.
+pub fn print_mir_opt_timings(tcx: TyCtxt<'_>) {
+    let Some(count) = tcx.sess.opts.unstable_opts.mir_opt_timings else { return };
+    let timings = tcx.mir_opt_timings.lock();
+    let mut bodies: Vec<_> = timings
+        .iter()
+        .map(|(&def_id, passes)| (def_id, passes, passes.values().sum::<Duration>()))
+        .collect();
+    bodies.sort_by(|(_, _, a), (_, _, b)| b.cmp(a));
+
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    for (def_id, passes, total) in bodies.into_iter().take(count) {
+        let body = tcx.def_path_str(def_id);
+        eprintln!("mir-opt-timings: body={body} total={:.3}ms", ms(total));
+        let mut passes: Vec<_> = passes.iter().collect();
+        passes.sort_by(|(_, a), (_, b)| b.cmp(a));
+        for (pass, &time) in passes {
+            eprintln!("mir-opt-timings: body={body} pass={pass} time={:.3}ms", ms(time));
+        }
+    }
+}
+
 pub fn validate_body<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, when: String) {
     validate::Validator { when, mir_phase: body.phase }.run_pass(tcx, body);
 }
@@ -179,14 +216,9 @@ pub fn dump_mir_for_pass<'tcx>(
     pass_name: &str,
     is_after: bool,
 ) {
-    mir::dump_mir(
-        tcx,
-        true,
-        pass_name,
-        if is_after { &"after" } else { &"before" },
-        body,
-        |_, _| Ok(()),
-    );
+    let disambiguator = if is_after { "after" } else { "before" };
+    mir::dump_mir(tcx, true, pass_name, &disambiguator, body, |_, _| Ok(()));
+    mir::dump_mir_cfg(tcx, pass_name, disambiguator, body);
 }
 
 pub fn dump_mir_for_phase_change<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
@@ -29,14 +29,23 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             return;
         }
 
-        let mut updater = BasicBlockUpdater { map: rpo.invert_bijective_mapping(), tcx };
-        debug_assert_eq!(updater.map[START_BLOCK], START_BLOCK);
-        updater.visit_body(body);
-
-        permute(body.basic_blocks.as_mut(), &updater.map);
+        permute_basic_blocks(tcx, body, rpo.invert_bijective_mapping());
     }
 }
 
+/// Moves each block `bb` of `body` to `map[bb]`. The start block must stay in place.
+pub(super) fn permute_basic_blocks<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    map: IndexVec<BasicBlock, BasicBlock>,
+) {
+    let mut updater = BasicBlockUpdater { map, tcx };
+    debug_assert_eq!(updater.map[START_BLOCK], START_BLOCK);
+    updater.visit_body(body);
+
+    permute(body.basic_blocks.as_mut(), &updater.map);
+}
+
 /// Rearranges the locals into *use* order.
 ///
 /// Thus after this pass, a local with a smaller [`Location`] where it was first
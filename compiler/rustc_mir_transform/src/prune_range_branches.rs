@@ -0,0 +1,270 @@
+//! Removes the branches that can't be taken given the range of values the switched integer can
+//! have.
+//!
+//! Range patterns are lowered to comparisons with their bounds, which are often already decided
+//! by an earlier comparison:
+//! ```ignore (illustrative)
+//! if x < 5 {
+//!     match x {
+//!         0..=9 => a,
+//!         10..=19 => b,
+//!         _ => c,
+//!     }
+//! }
+//! ```
+//! In the arm of the `if`, `x` is in `0..=4`, so `x <= 9` always holds and only `a` can be
+//! reached.
+//!
+//! We track intervals of SSA integer locals. A local starts out with the range of its type, or
+//! with its value if it is assigned a constant. Each edge out of a `switchInt` on a comparison of
+//! the local with a constant, or on the local itself, narrows that interval in the blocks that
+//! only this edge leads to, and in the blocks they dominate. A comparison whose outcome is known
+//! from the interval becomes a `goto`, and the values that a `switchInt` on the local itself can't
+//! see are removed from its targets.
+
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt};
+
+use crate::simplify::simplify_cfg;
+use crate::ssa::{SsaLocals, SsaValues};
+use crate::MirPass;
+
+pub struct PruneRangeBranches;
+
+impl<'tcx> MirPass<'tcx> for PruneRangeBranches {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let ssa = SsaLocals::new(body);
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let values = Values { tcx, param_env, body, values: SsaValues::new(body, &ssa) };
+
+        // Blocks at whose entry a local is known to be in an interval.
+        let mut facts = Vec::new();
+        let predecessors = body.basic_blocks.predecessors();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::SwitchInt { ref discr, ref targets } = data.terminator().kind else {
+                continue;
+            };
+            let only_from_switch = |target: BasicBlock| predecessors[target].as_slice() == [bb];
+            if let Some(local) = values.integer(discr) {
+                for (value, target) in targets.iter() {
+                    if only_from_switch(target) {
+                        let value = values.order(value, local);
+                        facts.push((target, local, Interval { start: value, end: value }));
+                    }
+                }
+            } else if let Some((0, else_, then)) = targets.as_static_if()
+                && let Some(comparison) = values.comparison(discr)
+            {
+                for (target, holds) in [(then, true), (else_, false)] {
+                    if only_from_switch(target)
+                        && let Some(interval) = comparison.restrict(holds)
+                    {
+                        facts.push((target, comparison.local, interval));
+                    }
+                }
+            }
+        }
+
+        let dominators = body.basic_blocks.dominators();
+        let interval_at = |local: Local, bb: BasicBlock| {
+            let mut interval = values.initial_interval(local);
+            for &(start, fact_local, ref fact) in &facts {
+                if fact_local == local && dominators.dominates(start, bb) {
+                    interval = interval.intersect(fact)?;
+                }
+            }
+            Some(interval)
+        };
+
+        let mut pruned = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::SwitchInt { ref discr, ref targets } = data.terminator().kind else {
+                continue;
+            };
+            if !dominators.is_reachable(bb) {
+                continue;
+            }
+            if let Some(local) = values.integer(discr) {
+                let Some(interval) = interval_at(local, bb) else { continue };
+                let (kept, removed): (Vec<_>, Vec<_>) = targets
+                    .iter()
+                    .partition(|&(value, _)| interval.contains(values.order(value, local)));
+                if interval.start == interval.end {
+                    // The only value is either one of the listed ones, or goes to `otherwise`.
+                    let target = kept.first().map_or(targets.otherwise(), |&(_, target)| target);
+                    pruned.push((bb, TerminatorKind::Goto { target }));
+                } else if !removed.is_empty() {
+                    let targets = SwitchTargets::new(kept.into_iter(), targets.otherwise());
+                    let discr = discr.clone();
+                    pruned.push((bb, TerminatorKind::SwitchInt { discr, targets }));
+                }
+            } else if let Some((0, else_, then)) = targets.as_static_if()
+                && let Some(comparison) = values.comparison(discr)
+                && let Some(interval) = interval_at(comparison.local, bb)
+                && let Some(holds) = comparison.evaluate(interval)
+            {
+                let target = if holds { then } else { else_ };
+                pruned.push((bb, TerminatorKind::Goto { target }));
+            }
+        }
+
+        if pruned.is_empty() {
+            return;
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, kind) in pruned {
+            debug!(?bb, ?kind, "pruning unreachable branches");
+            basic_blocks[bb].terminator_mut().kind = kind;
+        }
+        simplify_cfg(tcx, body);
+    }
+}
+
+/// An inclusive interval of integers, in the order given by [`Values::order`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Interval {
+    start: u128,
+    end: u128,
+}
+
+impl Interval {
+    fn contains(&self, value: u128) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start <= end).then_some(Interval { start, end })
+    }
+}
+
+/// The comparison `local op value`, with `value` a constant.
+#[derive(Copy, Clone, Debug)]
+struct Comparison {
+    local: Local,
+    op: BinOp,
+    value: u128,
+    /// The interval of all the values of the type of `local`.
+    range: Interval,
+}
+
+impl Comparison {
+    /// Returns whether the comparison holds for all the values in `interval`, or for none of
+    /// them. Returns `None` if it depends on the value.
+    fn evaluate(&self, interval: Interval) -> Option<bool> {
+        let value = self.value;
+        match self.op {
+            BinOp::Eq | BinOp::Ne if interval.start == value && interval.end == value => {
+                Some(self.op == BinOp::Eq)
+            }
+            BinOp::Eq | BinOp::Ne if !interval.contains(value) => Some(self.op == BinOp::Ne),
+            BinOp::Lt | BinOp::Ge if interval.end < value => Some(self.op == BinOp::Lt),
+            BinOp::Lt | BinOp::Ge if interval.start >= value => Some(self.op == BinOp::Ge),
+            BinOp::Le | BinOp::Gt if interval.end <= value => Some(self.op == BinOp::Le),
+            BinOp::Le | BinOp::Gt if interval.start > value => Some(self.op == BinOp::Gt),
+            _ => None,
+        }
+    }
+
+    /// Returns the interval of the values for which the comparison has the result `holds`, unless
+    /// it is empty or not an interval.
+    fn restrict(&self, holds: bool) -> Option<Interval> {
+        let Comparison { op, value, range, .. } = *self;
+        let (start, end) = match (op, holds) {
+            (BinOp::Eq, true) | (BinOp::Ne, false) => (value, value),
+            (BinOp::Lt, true) | (BinOp::Ge, false) => (range.start, value.checked_sub(1)?),
+            (BinOp::Le, true) | (BinOp::Gt, false) => (range.start, value),
+            (BinOp::Gt, true) | (BinOp::Le, false) => (value.checked_add(1)?, range.end),
+            (BinOp::Ge, true) | (BinOp::Lt, false) => (value, range.end),
+            _ => return None,
+        };
+        (start <= end).then_some(Interval { start, end })
+    }
+}
+
+struct Values<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    values: SsaValues<'a, 'tcx>,
+}
+
+impl<'tcx> Values<'_, 'tcx> {
+    /// If `operand` is a copy of an SSA local of an integer type, returns that local.
+    fn integer(&self, operand: &Operand<'tcx>) -> Option<Local> {
+        let local = self.values.ssa_value(operand)?;
+        self.body.local_decls[local].ty.is_integral().then_some(local)
+    }
+
+    /// Maps the bits of a value of the type of `local` to a `u128`, such that the order of the
+    /// results is the order of the values.
+    fn order(&self, bits: u128, local: Local) -> u128 {
+        let (size, signed) = self.body.local_decls[local].ty.int_size_and_signed(self.tcx);
+        if signed { size.sign_extend(bits) ^ (1 << 127) } else { bits }
+    }
+
+    /// Returns the interval of all the values of `ty`.
+    fn type_range(&self, ty: Ty<'tcx>) -> Interval {
+        let (size, signed) = ty.int_size_and_signed(self.tcx);
+        if signed {
+            let flip = |value: i128| value as u128 ^ (1 << 127);
+            Interval { start: flip(size.signed_int_min()), end: flip(size.signed_int_max()) }
+        } else {
+            Interval { start: 0, end: size.unsigned_int_max() }
+        }
+    }
+
+    /// Returns the interval `local` is in before we know anything about the branches taken.
+    fn initial_interval(&self, local: Local) -> Interval {
+        if let Some(Rvalue::Use(operand)) = self.values.assignment(local)
+            && let Some(value) = self.constant(operand, local)
+        {
+            return Interval { start: value, end: value };
+        }
+        self.type_range(self.body.local_decls[local].ty)
+    }
+
+    /// If `operand` is a constant of the type of `local`, returns its value, as in
+    /// [`Values::order`].
+    fn constant(&self, operand: &Operand<'tcx>, local: Local) -> Option<u128> {
+        let constant = operand.constant()?;
+        let bits = constant.const_.try_eval_bits(self.tcx, self.param_env)?;
+        Some(self.order(bits, local))
+    }
+
+    /// If `operand` is the result of comparing an SSA integer local with a constant, returns that
+    /// comparison, with the local on the left side.
+    fn comparison(&self, operand: &Operand<'tcx>) -> Option<Comparison> {
+        let result = self.values.ssa_value(operand)?;
+        let Rvalue::BinaryOp(op, box (lhs, rhs)) = self.values.assignment(result)? else {
+            return None;
+        };
+        let (local, value, op) = if let Some(local) = self.integer(lhs) {
+            (local, self.constant(rhs, local)?, *op)
+        } else {
+            let local = self.integer(rhs)?;
+            let op = match op {
+                BinOp::Lt => BinOp::Gt,
+                BinOp::Le => BinOp::Ge,
+                BinOp::Gt => BinOp::Lt,
+                BinOp::Ge => BinOp::Le,
+                op => *op,
+            };
+            (local, self.constant(lhs, local)?, op)
+        };
+        if !matches!(op, BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge) {
+            return None;
+        }
+        let range = self.type_range(self.body.local_decls[local].ty);
+        Some(Comparison { local, op, value, range })
+    }
+}
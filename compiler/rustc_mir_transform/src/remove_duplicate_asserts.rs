@@ -0,0 +1,165 @@
+//! Removes assertions which check the same condition as an earlier assertion.
+//!
+//! Indexing a slice twice with the same index, which is common once small accessors have been
+//! inlined into a loop body, checks the bounds twice:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _4 = _2;
+//!     _5 = Len((*_1));
+//!     _6 = Lt(_4, _5);
+//!     assert(move _6, "index out of bounds: ...", move _5, _4) -> bb1;
+//! }
+//!
+//! bb1: {
+//!     _3 = (*_1)[_4];
+//!     _8 = _2;
+//!     _9 = Len((*_1));
+//!     _10 = Lt(_8, _9);
+//!     assert(move _10, "index out of bounds: ...", move _9, _8) -> bb2;
+//! }
+//! ```
+//! Every path to `bb1` goes through the first `assert`, which has checked the same condition, so
+//! the second one can never fail and we replace it by a `goto`.
+//!
+//! Like in `RemoveRedundantBoundsChecks`, we only compare the values of SSA locals. Two of them
+//! are the same if they are in the same copy class, or if they are computed by the same operation
+//! on the same values. The length of a slice behind an SSA pointer can't change, so we also
+//! consider `Len((*_1))` the same everywhere.
+
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+use crate::ssa::{SsaLocals, SsaValues};
+use crate::MirPass;
+
+pub struct RemoveDuplicateAsserts;
+
+impl<'tcx> MirPass<'tcx> for RemoveDuplicateAsserts {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, _tcx, body))]
+    fn run_pass(&self, _tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let ssa = SsaLocals::new(body);
+        let values = Values(SsaValues::new(body, &ssa));
+
+        let dominators = body.basic_blocks.dominators();
+        let asserts: Vec<_> = body
+            .basic_blocks
+            .iter_enumerated()
+            .filter(|(bb, data)| !data.is_cleanup && dominators.is_reachable(*bb))
+            .filter_map(|(bb, data)| match data.terminator().kind {
+                TerminatorKind::Assert { ref cond, expected, ref msg, target, .. } => {
+                    Some(Assert { bb, cond, expected, msg, target })
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut duplicates = Vec::new();
+        for assert in &asserts {
+            // A failing assertion unwinds to a cleanup block, which can't lead back to `bb`, so
+            // an assertion dominating `bb` has succeeded whenever we get there.
+            let duplicate = asserts.iter().any(|earlier| {
+                earlier.bb != assert.bb
+                    && dominators.dominates(earlier.bb, assert.bb)
+                    && earlier.expected == assert.expected
+                    && values.same_operand(earlier.cond, assert.cond)
+                    && values.same_message(earlier.msg, assert.msg)
+            });
+            if duplicate {
+                debug!(bb = ?assert.bb, "removing duplicate assertion");
+                duplicates.push((assert.bb, assert.target));
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, target) in duplicates {
+            basic_blocks[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+struct Assert<'a, 'tcx> {
+    bb: BasicBlock,
+    cond: &'a Operand<'tcx>,
+    expected: bool,
+    msg: &'a AssertMessage<'tcx>,
+    target: BasicBlock,
+}
+
+struct Values<'a, 'tcx>(SsaValues<'a, 'tcx>);
+
+impl<'tcx> Values<'_, 'tcx> {
+    /// Whether `a` and `b` are known to evaluate to the same value.
+    fn same_operand(&self, a: &Operand<'tcx>, b: &Operand<'tcx>) -> bool {
+        match (a, b) {
+            (Operand::Constant(a), Operand::Constant(b)) => a.const_ == b.const_,
+            (Operand::Copy(a) | Operand::Move(a), Operand::Copy(b) | Operand::Move(b)) => {
+                let (Some(a), Some(b)) = (a.as_local(), b.as_local()) else { return false };
+                self.same_local(a, b)
+            }
+            _ => false,
+        }
+    }
+
+    fn same_local(&self, a: Local, b: Local) -> bool {
+        let (Some(a), Some(b)) = (self.0.ssa_head(a), self.0.ssa_head(b)) else { return false };
+        if a == b {
+            return true;
+        }
+        match (self.0.assignment(a), self.0.assignment(b)) {
+            (
+                Some(Rvalue::BinaryOp(a_op, box (a_lhs, a_rhs))),
+                Some(Rvalue::BinaryOp(b_op, box (b_lhs, b_rhs))),
+            ) => a_op == b_op && self.same_operand(a_lhs, b_lhs) && self.same_operand(a_rhs, b_rhs),
+            (Some(Rvalue::UnaryOp(a_op, a)), Some(Rvalue::UnaryOp(b_op, b))) => {
+                a_op == b_op && self.same_operand(a, b)
+            }
+            (Some(Rvalue::Len(a)), Some(Rvalue::Len(b))) => self.same_slice(*a, *b),
+            _ => false,
+        }
+    }
+
+    /// Whether `a` and `b` are known to have the same length.
+    fn same_slice(&self, a: Place<'tcx>, b: Place<'tcx>) -> bool {
+        match (&a.projection[..], &b.projection[..]) {
+            // The length of an array is part of its type.
+            ([], []) => a.local == b.local,
+            ([ProjectionElem::Deref], [ProjectionElem::Deref]) => {
+                self.same_local(a.local, b.local)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `a` and `b` are the same message with the same arguments.
+    fn same_message(&self, a: &AssertMessage<'tcx>, b: &AssertMessage<'tcx>) -> bool {
+        use AssertKind::*;
+        let same_kind = match (a, b) {
+            (Overflow(a_op, ..), Overflow(b_op, ..)) => a_op == b_op,
+            (ResumedAfterReturn(a_kind), ResumedAfterReturn(b_kind))
+            | (ResumedAfterPanic(a_kind), ResumedAfterPanic(b_kind)) => a_kind == b_kind,
+            _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+        };
+        let (a, b) = (arguments(a), arguments(b));
+        same_kind
+            && a.len() == b.len()
+            && std::iter::zip(a, b).all(|(a, b)| self.same_operand(a, b))
+    }
+}
+
+/// The operands shown in the message of a failed assertion.
+fn arguments<'a, 'tcx>(msg: &'a AssertMessage<'tcx>) -> Vec<&'a Operand<'tcx>> {
+    use AssertKind::*;
+    match msg {
+        BoundsCheck { len, index } => vec![len, index],
+        Overflow(_, lhs, rhs) => vec![lhs, rhs],
+        OverflowNeg(operand) | DivisionByZero(operand) | RemainderByZero(operand) => vec![operand],
+        MisalignedPointerDereference { required, found } => vec![required, found],
+        ResumedAfterReturn(_) | ResumedAfterPanic(_) => vec![],
+    }
+}
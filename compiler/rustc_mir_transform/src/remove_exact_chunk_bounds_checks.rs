@@ -0,0 +1,199 @@
+//! Removes the bounds checks of constant indices into the chunks of a slice:
+//! ```ignore (illustrative)
+//! for chunk in slice.chunks_exact(4) {
+//!     sum += chunk[0] * chunk[3];
+//! }
+//! ```
+//! The iterators returned by the functions marked `#[rustc_exact_chunks]`, like
+//! `slice::chunks_exact`, only yield slices whose length is the last argument of the function.
+//! When that argument is a constant, indexing the chunks with a smaller constant is always in
+//! bounds.
+//!
+//! In MIR, the loop calls `next` on the iterator through a reference, and takes the chunk out of
+//! the `Some` it returns:
+//! ```ignore (MIR)
+//! _3 = core::slice::<impl [u32]>::chunks_exact(move _4, const 4_usize) -> bb1;
+//! _2 = <ChunksExact<'_, u32> as IntoIterator>::into_iter(move _3) -> bb2;
+//! _5 = move _2;
+//!
+//! bb3: {
+//!     _8 = &mut _5;
+//!     _7 = &mut (*_8);
+//!     _6 = <ChunksExact<'_, u32> as Iterator>::next(move _7) -> [return: bb4, ...];
+//! }
+//!
+//! bb5: {
+//!     _10 = ((_6 as Some).0: &[u32]);
+//!     _12 = const 0_usize;
+//!     _13 = Len((*_10));
+//!     _14 = Lt(_12, _13);
+//!     assert(move _14, "index out of bounds: ...", move _13, _12) -> bb6;
+//! }
+//! ```
+//! As in `RemoveRangeLoopBoundsChecks`, the iterator has to be assigned once, from the iterator
+//! the loop was started with, and otherwise only be borrowed to be passed to `next`, so that it
+//! is still the one the marked function returned.
+//!
+//! This has to run before inlining, which replaces the calls to `next` by their body.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::visit::{MutatingUseContext, PlaceContext};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
+use rustc_span::sym;
+use rustc_target::abi::{FieldIdx, VariantIdx};
+
+use crate::ssa::{SsaLocals, SsaValues};
+use crate::MirPass;
+
+pub struct RemoveExactChunkBoundsChecks;
+
+impl<'tcx> MirPass<'tcx> for RemoveExactChunkBoundsChecks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let lang_items = tcx.lang_items();
+        let (Some(next), Some(into_iter)) = (lang_items.next_fn(), lang_items.into_iter_fn())
+        else {
+            return;
+        };
+
+        let ssa = SsaLocals::new(body);
+        let values = Values {
+            tcx,
+            param_env: tcx.param_env_reveal_all_normalized(body.source.def_id()),
+            body,
+            values: SsaValues::new(body, &ssa),
+            next,
+            into_iter,
+        };
+
+        let mut redundant = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Assert { ref cond, expected: true, ref msg, target, .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            if !matches!(**msg, AssertKind::BoundsCheck { .. }) {
+                continue;
+            }
+            if values.is_exact_chunk_index_check(cond) {
+                debug!(?bb, "removing bounds check of exact chunk index");
+                redundant.push((bb, target));
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, target) in redundant {
+            basic_blocks[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+struct Values<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    values: SsaValues<'a, 'tcx>,
+    next: DefId,
+    into_iter: DefId,
+}
+
+impl<'tcx> Values<'_, 'tcx> {
+    /// If `operand` is a `usize` constant, or an SSA local assigned one, returns its value.
+    fn constant(&self, operand: &Operand<'tcx>) -> Option<u64> {
+        let constant = match operand {
+            Operand::Constant(constant) => constant,
+            operand => match self.values.assignment(self.values.ssa_value(operand)?)? {
+                Rvalue::Use(Operand::Constant(constant)) => constant,
+                _ => return None,
+            },
+        };
+        constant.const_.try_eval_target_usize(self.tcx, self.param_env)
+    }
+
+    /// Whether `cond` compares a constant index with the length of a chunk that is known to be
+    /// longer.
+    fn is_exact_chunk_index_check(&self, cond: &Operand<'tcx>) -> bool {
+        let Some(cond) = self.values.ssa_value(cond) else { return false };
+        let Some(Rvalue::BinaryOp(BinOp::Lt, box (index, len))) = self.values.assignment(cond)
+        else {
+            return false;
+        };
+        let Some(len) = self.values.ssa_value(len) else { return false };
+        let Some(Rvalue::Len(place)) = self.values.assignment(len) else { return false };
+        let Some(chunk) = self.values.slice_reference(*place) else { return false };
+        let Some(index) = self.constant(index) else { return false };
+        self.chunk_len(chunk).is_some_and(|len| index < len)
+    }
+
+    /// If `chunk` is the payload of the `Some` returned by `next` on an iterator over exact
+    /// chunks, returns the length of these chunks.
+    fn chunk_len(&self, chunk: Local) -> Option<u64> {
+        let Rvalue::Use(Operand::Copy(place) | Operand::Move(place)) =
+            self.values.assignment(chunk)?
+        else {
+            return None;
+        };
+        let [ProjectionElem::Downcast(_, variant), ProjectionElem::Field(field, _)] =
+            place.projection[..]
+        else {
+            return None;
+        };
+        if variant != VariantIdx::from_u32(1) || field != FieldIdx::from_u32(0) {
+            return None;
+        }
+        let (def_id, [iterator]) = self.values.call(place.local)? else { return None };
+        if def_id != self.next {
+            return None;
+        }
+        self.iterator_chunk_len(self.values.borrowed(self.values.ssa_value(iterator)?)?)
+    }
+
+    /// If the iterator in `iterator` was returned by a function marked `#[rustc_exact_chunks]`,
+    /// and is only changed by `next`, returns the length of the chunks it yields.
+    fn iterator_chunk_len(&self, iterator: Local) -> Option<u64> {
+        let mut assignment = None;
+        for &(location, place, context) in self.values.uses(iterator) {
+            match context {
+                PlaceContext::NonUse(_) => {}
+                PlaceContext::MutatingUse(MutatingUseContext::Store)
+                    if place.projection.is_empty() && assignment.is_none() =>
+                {
+                    assignment = Some(location);
+                }
+                PlaceContext::MutatingUse(MutatingUseContext::Borrow)
+                    if place.projection.is_empty()
+                        && self.values.is_only_advanced(location, self.next) => {}
+                _ => return None,
+            }
+        }
+
+        let statement = self.body.stmt_at(assignment?).left()?;
+        let (_, Rvalue::Use(operand)) = statement.kind.as_assign()? else { return None };
+        self.exact_chunks_len(self.values.ssa_value(operand)?)
+    }
+
+    /// If the SSA local `iterator` is assigned the result of a function marked
+    /// `#[rustc_exact_chunks]`, possibly through `IntoIterator::into_iter`, returns the constant
+    /// length of the chunks it yields.
+    fn exact_chunks_len(&self, iterator: Local) -> Option<u64> {
+        let (def_id, args) = self.values.call(iterator)?;
+        if def_id == self.into_iter {
+            // `for` loops start with what `IntoIterator::into_iter` returns, which is the
+            // iterator itself.
+            let [iterator] = args else { return None };
+            return self.exact_chunks_len(self.values.ssa_value(iterator)?);
+        }
+        if !self.tcx.has_attr(def_id, sym::rustc_exact_chunks) {
+            return None;
+        }
+        self.constant(args.last()?)
+    }
+}
@@ -0,0 +1,185 @@
+//! Folds the comparisons with the null pointer of pointers which come from a reference or a
+//! `NonNull`, and so can't be null.
+//!
+//! Generic code often checks whether a pointer is null, even where, once inlined, the pointer is
+//! known to come from a reference:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _2 = &raw const (*_1);
+//!     _3 = const 0_usize as *const u8 (Transmute);
+//!     _4 = Eq(_2, _3);
+//!     switchInt(move _4) -> [0: bb2, otherwise: bb1];
+//! }
+//! ```
+//! `_1` is a reference, which is never null, so `_4` is `false` and the `switchInt` always goes
+//! to `bb2`. We replace the comparison by its value, and a `switchInt` on it by a `goto`.
+//!
+//! The pointer has to be known to have the address of a reference or of a `NonNull` through all
+//! the casts in between. We only look at SSA locals, and only follow the casts between pointers,
+//! which keep the address. Casts from integers, and transmutes from types which may be zero, can
+//! make up any address, so they end the search.
+
+use std::ops::Bound;
+
+use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCoercion;
+use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt};
+
+use crate::simplify::simplify_cfg;
+use crate::ssa::{SsaLocals, SsaValues};
+use crate::MirPass;
+
+pub struct RemoveNullChecks;
+
+impl<'tcx> MirPass<'tcx> for RemoveNullChecks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let ssa = SsaLocals::new(body);
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let values = Values { tcx, param_env, body, values: SsaValues::new(body, &ssa) };
+
+        let mut null_checks = Vec::new();
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                let StatementKind::Assign(box (_, Rvalue::BinaryOp(op, box (ref lhs, ref rhs)))) =
+                    statement.kind
+                else {
+                    continue;
+                };
+                if !matches!(op, BinOp::Eq | BinOp::Ne) {
+                    continue;
+                }
+                if (values.is_null(lhs) && values.is_non_null(rhs))
+                    || (values.is_non_null(lhs) && values.is_null(rhs))
+                {
+                    null_checks.push((Location { block, statement_index }, op == BinOp::Ne));
+                }
+            }
+        }
+
+        if null_checks.is_empty() {
+            return;
+        }
+
+        let mut folded_branch = false;
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (location, value) in null_checks {
+            debug!(?location, ?value, "folding null check");
+            let data = &mut basic_blocks[location.block];
+            let statement = &mut data.statements[location.statement_index];
+            let StatementKind::Assign(box (place, ref mut rvalue)) = statement.kind else { bug!() };
+            *rvalue = Rvalue::Use(Operand::Constant(Box::new(ConstOperand {
+                span: statement.source_info.span,
+                user_ty: None,
+                const_: Const::from_bool(tcx, value),
+            })));
+
+            // The comparison is usually only there to decide the branch at the end of its block.
+            // As the result is SSA, it can't change before the terminator.
+            let terminator = data.terminator_mut();
+            if let TerminatorKind::SwitchInt { ref discr, ref targets } = terminator.kind
+                && discr.place() == Some(place)
+                && place.as_local().is_some_and(|local| ssa.is_ssa(local))
+            {
+                let target = targets.target_for_value(value.into());
+                terminator.kind = TerminatorKind::Goto { target };
+                folded_branch = true;
+            }
+        }
+
+        // Remove the blocks which only the null case went to.
+        if folded_branch {
+            simplify_cfg(tcx, body);
+        }
+    }
+}
+
+struct Values<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    values: SsaValues<'a, 'tcx>,
+}
+
+impl<'tcx> Values<'_, 'tcx> {
+    /// Whether `operand` is the null pointer, or zero.
+    fn is_null(&self, operand: &Operand<'tcx>) -> bool {
+        if let Operand::Constant(constant) = operand {
+            return constant
+                .const_
+                .try_eval_scalar_int(self.tcx, self.param_env)
+                .is_some_and(|int| int.is_null());
+        }
+        let Some(local) = self.values.ssa_value(operand) else { return false };
+        match self.values.assignment(local) {
+            Some(
+                Rvalue::Use(operand)
+                | Rvalue::Cast(
+                    CastKind::PtrToPtr | CastKind::PointerFromExposedAddress | CastKind::Transmute,
+                    operand,
+                    _,
+                ),
+            ) => self.is_null(operand),
+            _ => false,
+        }
+    }
+
+    /// Whether `operand` is a pointer with the address of a reference or of a `NonNull`.
+    fn is_non_null(&self, operand: &Operand<'tcx>) -> bool {
+        let Some(place) = operand.place() else { return false };
+        if let Some((base, ProjectionElem::Field(..))) = place.iter_projections().last()
+            && self.is_non_null_ty(base.ty(self.body, self.tcx).ty)
+        {
+            // The pointer inside a `NonNull`.
+            return true;
+        }
+        let Some(local) = self.values.ssa_value(operand) else { return false };
+        match self.values.assignment(local) {
+            Some(Rvalue::AddressOf(_, place)) => self.has_non_null_address(*place),
+            Some(
+                Rvalue::Use(operand)
+                | Rvalue::Cast(
+                    CastKind::PtrToPtr
+                    | CastKind::PointerCoercion(
+                        PointerCoercion::MutToConstPointer
+                        | PointerCoercion::ArrayToPointer
+                        | PointerCoercion::Unsize,
+                    ),
+                    operand,
+                    _,
+                ),
+            ) => self.is_non_null(operand),
+            Some(Rvalue::Cast(CastKind::Transmute, operand, _)) => {
+                self.is_non_null_ty(operand.ty(self.body, self.tcx))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether values of type `ty` are never zero: references, and the types like `NonNull`
+    /// whose valid range excludes zero.
+    fn is_non_null_ty(&self, ty: Ty<'tcx>) -> bool {
+        match *ty.kind() {
+            ty::Ref(..) => true,
+            ty::Adt(def, _) => matches!(
+                self.tcx.layout_scalar_valid_range(def.did()),
+                (Bound::Included(start), _) if start > 0
+            ),
+            _ => false,
+        }
+    }
+
+    /// Whether `place` is a local, or is only reached through references, which makes its
+    /// address not null.
+    fn has_non_null_address(&self, place: Place<'tcx>) -> bool {
+        place.iter_projections().all(|(base, elem)| {
+            elem != ProjectionElem::Deref || base.ty(self.body, self.tcx).ty.is_ref()
+        })
+    }
+}
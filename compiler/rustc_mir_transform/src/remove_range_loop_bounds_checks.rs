@@ -0,0 +1,203 @@
+//! Removes the bounds checks in loops over the indices of a slice:
+//! ```ignore (illustrative)
+//! for i in 0..slice.len() {
+//!     sum += slice[i];
+//! }
+//! ```
+//! The indices come from a `Range<usize>` which ends at the length of the slice. `Range::next`
+//! only returns values below the end of the range, and the length of a slice cannot change
+//! behind a reference, so indexing the slice with them is always in bounds.
+//!
+//! In MIR, the loop calls `next` on the range through a reference, and indexes the slice with
+//! the payload of the `Some` it returns:
+//! ```ignore (MIR)
+//! bb3: {
+//!     _13 = &mut _8;
+//!     _12 = &mut (*_13);
+//!     _11 = <std::ops::Range<usize> as Iterator>::next(move _12) -> [return: bb4, ...];
+//! }
+//!
+//! bb5: {
+//!     _16 = ((_11 as Some).0: usize);
+//!     _18 = _16;
+//!     _19 = Len((*_1));
+//!     _20 = Lt(_18, _19);
+//!     assert(move _20, "index out of bounds: ...", move _19, _18) -> bb8;
+//! }
+//! ```
+//! As in `RemoveRedundantBoundsChecks`, the index and the slice are identified by SSA locals. The
+//! range itself cannot be SSA, as `next` changes it: it has to be assigned once, from the range
+//! the loop was started with, and otherwise only be borrowed to be passed to `next`. Nothing else
+//! can then move its end.
+//!
+//! This has to run before inlining, which replaces the calls to `next` by their body.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::visit::{MutatingUseContext, PlaceContext};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_target::abi::{FieldIdx, VariantIdx};
+
+use crate::ssa::{SsaLocals, SsaValues};
+use crate::MirPass;
+
+pub struct RemoveRangeLoopBoundsChecks;
+
+impl<'tcx> MirPass<'tcx> for RemoveRangeLoopBoundsChecks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let lang_items = tcx.lang_items();
+        let (Some(next), Some(into_iter), Some(range)) =
+            (lang_items.next_fn(), lang_items.into_iter_fn(), lang_items.range_struct())
+        else {
+            return;
+        };
+
+        let ssa = SsaLocals::new(body);
+        let values = Values {
+            body,
+            values: SsaValues::new(body, &ssa),
+            next,
+            into_iter,
+            range,
+        };
+
+        let mut redundant = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Assert { ref cond, expected: true, ref msg, target, .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            if !matches!(**msg, AssertKind::BoundsCheck { .. }) {
+                continue;
+            }
+            if values.is_range_loop_index_check(cond) {
+                debug!(?bb, "removing bounds check of range loop index");
+                redundant.push((bb, target));
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, target) in redundant {
+            basic_blocks[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+struct Values<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    values: SsaValues<'a, 'tcx>,
+    next: DefId,
+    into_iter: DefId,
+    range: DefId,
+}
+
+impl<'tcx> Values<'_, 'tcx> {
+    /// Whether `cond` compares an index returned by `next` on a range with the length of the
+    /// slice at which that range ends.
+    fn is_range_loop_index_check(&self, cond: &Operand<'tcx>) -> bool {
+        let Some(cond) = self.values.ssa_value(cond) else { return false };
+        let Some(Rvalue::BinaryOp(BinOp::Lt, box (index, len))) = self.values.assignment(cond)
+        else {
+            return false;
+        };
+        let Some(len) = self.values.ssa_value(len) else { return false };
+        let Some(Rvalue::Len(place)) = self.values.assignment(len) else { return false };
+        let Some(slice) = self.values.slice_reference(*place) else { return false };
+        self.values
+            .ssa_value(index)
+            .and_then(|index| self.range_of_index(index))
+            .and_then(|range| self.range_end(range))
+            .is_some_and(|end| end == slice)
+    }
+
+    /// If `index` is the payload of the `Some` returned by `next` on a `Range<usize>`, returns
+    /// the local of that range.
+    fn range_of_index(&self, index: Local) -> Option<Local> {
+        let Rvalue::Use(Operand::Copy(place) | Operand::Move(place)) =
+            self.values.assignment(index)?
+        else {
+            return None;
+        };
+        let [ProjectionElem::Downcast(_, variant), ProjectionElem::Field(field, _)] =
+            place.projection[..]
+        else {
+            return None;
+        };
+        if variant != VariantIdx::from_u32(1) || field != FieldIdx::from_u32(0) {
+            return None;
+        }
+        let (def_id, [iterator]) = self.values.call(place.local)? else { return None };
+        if def_id != self.next {
+            return None;
+        }
+        let range = self.values.borrowed(self.values.ssa_value(iterator)?)?;
+        self.is_usize_range(range).then_some(range)
+    }
+
+    fn is_usize_range(&self, local: Local) -> bool {
+        let ty = self.body.local_decls[local].ty;
+        matches!(
+            *ty.kind(),
+            ty::Adt(def, args) if def.did() == self.range && args.type_at(0).is_usize()
+        )
+    }
+
+    /// If the range in `range` ends at the length of a slice, and is only changed by `next`,
+    /// returns the reference to that slice, as in [`SsaValues::slice_reference`].
+    fn range_end(&self, range: Local) -> Option<Local> {
+        let mut assignment = None;
+        for &(location, place, context) in self.values.uses(range) {
+            match context {
+                PlaceContext::NonUse(_) => {}
+                PlaceContext::MutatingUse(MutatingUseContext::Store)
+                    if place.projection.is_empty() && assignment.is_none() =>
+                {
+                    assignment = Some(location);
+                }
+                PlaceContext::MutatingUse(MutatingUseContext::Borrow)
+                    if place.projection.is_empty()
+                        && self.values.is_only_advanced(location, self.next) => {}
+                _ => return None,
+            }
+        }
+
+        let statement = self.body.stmt_at(assignment?).left()?;
+        let (_, rvalue) = statement.kind.as_assign()?;
+        let end = match rvalue {
+            Rvalue::Use(operand) => {
+                // `for` loops start with what `IntoIterator::into_iter` returns, which is the
+                // range itself.
+                let iterator = self.values.ssa_value(operand)?;
+                let (def_id, [range]) = self.values.call(iterator)? else { return None };
+                if def_id != self.into_iter {
+                    return None;
+                }
+                self.range_end_operand(self.values.assignment(self.values.ssa_value(range)?)?)?
+            }
+            rvalue => self.range_end_operand(rvalue)?,
+        };
+        let end = self.values.ssa_value(end)?;
+        let Rvalue::Len(place) = self.values.assignment(end)? else { return None };
+        self.values.slice_reference(*place)
+    }
+
+    /// If `rvalue` builds a range, returns its end.
+    fn range_end_operand<'b>(&self, rvalue: &'b Rvalue<'tcx>) -> Option<&'b Operand<'tcx>> {
+        match rvalue {
+            Rvalue::Aggregate(box AggregateKind::Adt(def_id, ..), fields)
+                if *def_id == self.range =>
+            {
+                Some(&fields[FieldIdx::from_u32(1)])
+            }
+            _ => None,
+        }
+    }
+}
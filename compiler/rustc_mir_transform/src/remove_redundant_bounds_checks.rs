@@ -0,0 +1,114 @@
+//! Removes bounds checks which are implied by an earlier explicit comparison with the length.
+//!
+//! A common pattern is to check the index against the length before indexing:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _4 = Len((*_2));
+//!     _3 = Lt(_1, move _4);
+//!     switchInt(move _3) -> [0: bb2, otherwise: bb1];
+//! }
+//!
+//! bb1: {
+//!     _6 = Len((*_2));
+//!     _7 = Lt(_1, _6);
+//!     assert(move _7, "index out of bounds: ...", move _6, _1) -> bb3;
+//! }
+//! ```
+//! Every path to `bb1` goes through the `otherwise` edge of the `switchInt`, so we know that
+//! `_1 < Len((*_2))` holds there and the `assert` can never fail. We replace it by a `goto`.
+//!
+//! To recognize that two comparisons are about the same values, we only look at SSA locals: the
+//! index is identified by its copy class, and the length by the (copy class of the) reference to
+//! the slice, looking through reborrows. A slice's length cannot change behind a reference, so
+//! equal references always have equal lengths.
+
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+use crate::ssa::{SsaLocals, SsaValues};
+use crate::MirPass;
+
+pub struct RemoveRedundantBoundsChecks;
+
+impl<'tcx> MirPass<'tcx> for RemoveRedundantBoundsChecks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, _tcx, body))]
+    fn run_pass(&self, _tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let ssa = SsaLocals::new(body);
+        let values = SsaValues::new(body, &ssa);
+
+        // Blocks at whose entry `index < Len(*slice)` is known to hold.
+        let mut known = Vec::new();
+        let predecessors = body.basic_blocks.predecessors();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::SwitchInt { ref discr, ref targets } = data.terminator().kind else {
+                continue;
+            };
+            let Some((0, _, then)) = targets.as_static_if() else { continue };
+            // If `then` can be reached some other way, the comparison tells us nothing.
+            if predecessors[then].as_slice() != [bb] {
+                continue;
+            }
+            if let Some(check) = length_check(&values, discr) {
+                debug!(?bb, ?then, ?check, "found length comparison");
+                known.push((then, check));
+            }
+        }
+
+        if known.is_empty() {
+            return;
+        }
+
+        let dominators = body.basic_blocks.dominators();
+        let mut redundant = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Assert { ref cond, expected: true, ref msg, target, .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            if !matches!(**msg, AssertKind::BoundsCheck { .. }) || !dominators.is_reachable(bb) {
+                continue;
+            }
+            let Some(check) = length_check(&values, cond) else { continue };
+            if known.iter().any(|&(start, fact)| fact == check && dominators.dominates(start, bb)) {
+                debug!(?bb, ?check, "removing redundant bounds check");
+                redundant.push((bb, target));
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (bb, target) in redundant {
+            basic_blocks[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// A comparison `index < Len(*slice)`, identified by SSA locals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct LengthCheck {
+    index: Local,
+    slice: Local,
+}
+
+/// If `operand` is the result of comparing some index with the length of a slice, returns the
+/// values involved.
+fn length_check<'tcx>(
+    values: &SsaValues<'_, 'tcx>,
+    operand: &Operand<'tcx>,
+) -> Option<LengthCheck> {
+    let cond = values.ssa_value(operand)?;
+    let Rvalue::BinaryOp(BinOp::Lt, box (index, len)) = values.assignment(cond)? else {
+        return None;
+    };
+    let index = values.ssa_value(index)?;
+    let len = values.ssa_value(len)?;
+    let Rvalue::Len(place) = values.assignment(len)? else { return None };
+    let slice = values.slice_reference(*place)?;
+    Some(LengthCheck { index, slice })
+}
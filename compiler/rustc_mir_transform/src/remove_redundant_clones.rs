@@ -0,0 +1,212 @@
+//! Replaces a call to `Clone::clone` by a move of the cloned value when that value is only dropped
+//! after the call.
+//!
+//! ```ignore (MIR)
+//! bb0: {
+//!     _3 = &_1;
+//!     _2 = <String as Clone>::clone(move _3) -> [return: bb1, unwind continue];
+//! }
+//!
+//! bb1: {
+//!     drop(_1) -> [return: bb2, unwind continue];
+//! }
+//! ```
+//! becomes
+//! ```ignore (MIR)
+//! bb0: {
+//!     nop;
+//!     _2 = move _1;
+//!     goto -> bb1;
+//! }
+//!
+//! bb1: {
+//!     goto -> bb2;
+//! }
+//! ```
+//! Neither the call to `clone` nor the drop of the original value happen anymore, which is
+//! observable if they have side effects, so this is only enabled with `-Zunsound-mir-opts`.
+//!
+//! To stay conservative, the cloned local may only be borrowed by the argument of the call, and it
+//! may only be dropped once the call returned. Those drops are removed, so they must not be
+//! reachable without going through the call, and the call must not be inside of a loop.
+
+use rustc_hir::def_id::DefId;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+use crate::MirPass;
+
+pub struct RemoveRedundantClones;
+
+impl<'tcx> MirPass<'tcx> for RemoveRedundantClones {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3 && sess.opts.unstable_opts.unsound_mir_opts
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let Some(clone_trait) = tcx.lang_items().clone_trait() else { return };
+        for bb in body.basic_blocks.indices() {
+            // Each replacement removes drops, so we look at the body as it was left by the
+            // previous one.
+            let Some(clone) = find_redundant_clone(tcx, clone_trait, body, bb) else { continue };
+            debug!(?bb, ?clone, "replacing clone by a move");
+
+            let basic_blocks = body.basic_blocks.as_mut();
+            let data = &mut basic_blocks[bb];
+            data.statements[clone.borrow_index].make_nop();
+            let source_info = data.terminator().source_info;
+            data.statements.push(Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((
+                    clone.destination,
+                    Rvalue::Use(Operand::Move(clone.cloned.into())),
+                ))),
+            });
+            data.terminator_mut().kind = TerminatorKind::Goto { target: clone.target };
+
+            for drop in clone.drops {
+                let terminator = basic_blocks[drop].terminator_mut();
+                let TerminatorKind::Drop { target, .. } = terminator.kind else {
+                    bug!("expected a drop of {:?} in {:?}", clone.cloned, drop)
+                };
+                terminator.kind = TerminatorKind::Goto { target };
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RedundantClone<'tcx> {
+    /// The index of the statement borrowing the cloned local, in the block of the call.
+    borrow_index: usize,
+    cloned: Local,
+    destination: Place<'tcx>,
+    target: BasicBlock,
+    /// The blocks dropping the cloned local after the call.
+    drops: Vec<BasicBlock>,
+}
+
+fn find_redundant_clone<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    clone_trait: DefId,
+    body: &Body<'tcx>,
+    bb: BasicBlock,
+) -> Option<RedundantClone<'tcx>> {
+    let data = &body.basic_blocks[bb];
+    let TerminatorKind::Call { ref func, ref args, destination, target: Some(target), .. } =
+        data.terminator().kind
+    else {
+        return None;
+    };
+    let (def_id, _) = func.const_fn_def()?;
+    if tcx.trait_of_item(def_id) != Some(clone_trait) {
+        return None;
+    }
+    let [Operand::Move(reference)] = args[..] else { return None };
+    let reference = reference.as_local()?;
+
+    // The argument must be a temporary borrowing the whole cloned local right before the call.
+    let borrow_index = data.statements.len().checked_sub(1)?;
+    let StatementKind::Assign(box (place, Rvalue::Ref(_, BorrowKind::Shared, borrowed))) =
+        data.statements[borrow_index].kind
+    else {
+        return None;
+    };
+    let cloned = borrowed.as_local()?;
+    if place.as_local() != Some(reference)
+        || body.local_kind(reference) != LocalKind::Temp
+        || body.local_kind(cloned) == LocalKind::ReturnPointer
+        || destination.local == cloned
+    {
+        return None;
+    }
+
+    // Any other borrow of the cloned local could be used after it is moved, and any other use of
+    // the reference could leak that borrow.
+    let mut uses = BorrowCounts { cloned, reference, borrows: 0, reference_uses: 0 };
+    uses.visit_body(body);
+    if uses.borrows != 1 || uses.reference_uses != 2 {
+        return None;
+    }
+
+    let after = reachable(body, target, bb);
+    if after.contains(bb) {
+        return None;
+    }
+    let before = reachable(body, START_BLOCK, bb);
+
+    let mut drops = Vec::new();
+    let mut used = UsedAfter { cloned, used: false };
+    for block in after.iter() {
+        let data = &body.basic_blocks[block];
+        for (statement_index, statement) in data.statements.iter().enumerate() {
+            used.visit_statement(statement, Location { block, statement_index });
+        }
+        let terminator = data.terminator();
+        match terminator.kind {
+            TerminatorKind::Drop { place, .. } if place.as_local() == Some(cloned) => {
+                // This drop is still needed on the paths which don't call `clone`.
+                if before.contains(block) {
+                    return None;
+                }
+                drops.push(block);
+            }
+            _ => used.visit_terminator(terminator, body.terminator_loc(block)),
+        }
+        if used.used {
+            return None;
+        }
+    }
+
+    Some(RedundantClone { borrow_index, cloned, destination, target, drops })
+}
+
+/// The blocks reachable from `start`, not going further than the block of the call.
+fn reachable(body: &Body<'_>, start: BasicBlock, call: BasicBlock) -> BitSet<BasicBlock> {
+    let mut reachable = BitSet::new_empty(body.basic_blocks.len());
+    let mut worklist = vec![start];
+    while let Some(bb) = worklist.pop() {
+        if !reachable.insert(bb) || bb == call {
+            continue;
+        }
+        worklist.extend(body.basic_blocks[bb].terminator().successors());
+    }
+    reachable
+}
+
+struct BorrowCounts {
+    cloned: Local,
+    reference: Local,
+    borrows: u32,
+    reference_uses: u32,
+}
+
+impl<'tcx> Visitor<'tcx> for BorrowCounts {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if local == self.cloned && (context.is_borrow() || context.is_address_of()) {
+            self.borrows += 1;
+        }
+        if local == self.reference && !context.is_storage_marker() {
+            self.reference_uses += 1;
+        }
+    }
+}
+
+/// Looks for uses of the cloned local other than storage markers.
+struct UsedAfter {
+    cloned: Local,
+    used: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for UsedAfter {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if local == self.cloned && !context.is_storage_marker() {
+            self.used = true;
+        }
+    }
+}
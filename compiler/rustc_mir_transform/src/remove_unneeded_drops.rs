@@ -3,6 +3,10 @@
 //! When the MIR is built, we check `needs_drop` before emitting a `Drop` for a place. This pass is
 //! useful because (unlike MIR building) it runs after type checking, so it can make use of
 //! `Reveal::All` to provide more precise type information.
+//!
+//! Drops of `Copy` values can still be left by inlining, e.g. of `mem::drop`. Nothing adds them
+//! after this pass, which the MIR validator checks in optimized MIR unless this pass is disabled
+//! or out of fuel.
 
 use crate::MirPass;
 use rustc_middle::mir::*;
@@ -0,0 +1,162 @@
+//! Allocates `Vec`s which are created empty and then immediately pushed to with the right
+//! capacity.
+//!
+//! Building a vector element by element looks like this:
+//! ```ignore (MIR)
+//! bb0: {
+//!     _1 = Vec::<u32>::new() -> [return: bb1, unwind continue];
+//! }
+//!
+//! bb1: {
+//!     _3 = &mut _1;
+//!     _2 = Vec::<u32>::push(move _3, const 1_u32) -> [return: bb2, unwind: bb4];
+//! }
+//!
+//! bb2: {
+//!     _5 = &mut _1;
+//!     _4 = Vec::<u32>::push(move _5, const 2_u32) -> [return: bb3, unwind: bb4];
+//! }
+//! ```
+//! We know that at least two elements are pushed before anything else can look at the vector,
+//! so we create it with `Vec::<u32>::with_capacity(const 2_usize)` instead, which avoids
+//! growing it while pushing.
+//!
+//! This relies on `Vec::new`, `Vec::with_capacity` and `Vec::push` behaving the way they are
+//! documented to, which we can only check by their diagnostic items. The capacity of a `Vec` is
+//! observable, and making it exact can cause more reallocations if more elements are pushed
+//! later on, which is why this is only enabled with `-Zunsound-mir-opts`. A proper version of
+//! this would have the library opt into it with an attribute on these functions.
+//!
+//! The pushes must follow the call to `Vec::new` in a straight line of blocks, each only reached
+//! from the previous one. Their blocks may not mention the vector except to borrow it for the
+//! call to `push`.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::sym;
+
+use crate::MirPass;
+
+pub struct ReserveVecCapacity;
+
+impl<'tcx> MirPass<'tcx> for ReserveVecCapacity {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3 && sess.opts.unstable_opts.unsound_mir_opts
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let (Some(vec_new), Some(vec_with_capacity), Some(vec_push)) = (
+            tcx.get_diagnostic_item(sym::vec_new),
+            tcx.get_diagnostic_item(sym::vec_with_capacity),
+            tcx.get_diagnostic_item(sym::vec_push),
+        ) else {
+            return;
+        };
+
+        let predecessors = body.basic_blocks.predecessors();
+        let mut reservations = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Call { ref func, destination, target: Some(target), .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            let Some((def_id, generic_args)) = func.const_fn_def() else { continue };
+            if def_id != vec_new {
+                continue;
+            }
+            let Some(vec) = destination.as_local() else { continue };
+
+            let mut pushes = 0;
+            let mut prev = bb;
+            let mut next = target;
+            while predecessors[next].as_slice() == [prev] {
+                let Some(after) = push_target(&body.basic_blocks[next], vec, vec_push) else {
+                    break;
+                };
+                pushes += 1;
+                prev = next;
+                next = after;
+            }
+
+            if pushes >= 2 {
+                debug!(?bb, ?vec, ?pushes, "reserving capacity for pushes");
+                reservations.push((bb, generic_args, pushes));
+            }
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut_preserves_cfg();
+        for (bb, generic_args, pushes) in reservations {
+            let TerminatorKind::Call { ref mut func, ref mut args, .. } =
+                basic_blocks[bb].terminator_mut().kind
+            else {
+                bug!("reserved capacity for a terminator which is not a call");
+            };
+            let span = match func {
+                Operand::Constant(constant) => constant.span,
+                _ => bug!("reserved capacity for a call which is not to `Vec::new`"),
+            };
+            *func = Operand::function_handle(tcx, vec_with_capacity, generic_args, span);
+            let capacity =
+                ConstOperand { span, user_ty: None, const_: Const::from_usize(tcx, pushes) };
+            *args = vec![Operand::Constant(Box::new(capacity))];
+        }
+    }
+}
+
+/// If `data` only borrows `vec` to push to it, returns the block the call to `Vec::push` returns
+/// to.
+fn push_target(data: &BasicBlockData<'_>, vec: Local, vec_push: DefId) -> Option<BasicBlock> {
+    let TerminatorKind::Call { ref func, ref args, target: Some(target), .. } =
+        data.terminator().kind
+    else {
+        return None;
+    };
+    if !matches!(func.const_fn_def(), Some((def_id, _)) if def_id == vec_push) {
+        return None;
+    }
+    let Some(Operand::Move(receiver)) = args.first() else { return None };
+    let receiver = receiver.as_local()?;
+
+    let mut borrowed = false;
+    for statement in &data.statements {
+        match statement.kind {
+            StatementKind::Assign(box (lhs, Rvalue::Ref(_, BorrowKind::Mut { .. }, place)))
+                if lhs.as_local() == Some(receiver) && place.as_local() == Some(vec) =>
+            {
+                borrowed = true;
+            }
+            _ if mentions(statement, vec) => return None,
+            _ => {}
+        }
+    }
+    if !borrowed || args[1..].iter().any(|arg| arg.place().is_some_and(|arg| arg.local == vec)) {
+        return None;
+    }
+    Some(target)
+}
+
+/// Whether `statement` mentions `local` at all.
+fn mentions(statement: &Statement<'_>, local: Local) -> bool {
+    struct Mentions {
+        local: Local,
+        found: bool,
+    }
+
+    impl<'tcx> Visitor<'tcx> for Mentions {
+        fn visit_local(&mut self, local: Local, _: PlaceContext, _: Location) {
+            if local == self.local {
+                self.found = true;
+            }
+        }
+    }
+
+    let mut visitor = Mentions { local, found: false };
+    visitor.visit_statement(statement, START_BLOCK.start_location());
+    visitor.found
+}
@@ -0,0 +1,129 @@
+//! Reuses the value read from a field when the same field is read again in the same block, and
+//! can't have changed in between.
+//!
+//! ```ignore (MIR)
+//! _2 = (((*_1).0: Config).0: bool);
+//! _3 = Not(_2);
+//! _4 = (((*_1).0: Config).0: bool);
+//! ```
+//! becomes
+//! ```ignore (MIR)
+//! _2 = (((*_1).0: Config).0: bool);
+//! _3 = Not(_2);
+//! _4 = _2;
+//! ```
+//! `CopyProp` then replaces `_4` with `_2`, if it can.
+//!
+//! Only the copies of places made of dereferences and fields are reused. The reads are in the
+//! same block, so no call can change the place in between. The other statements end the reuse if
+//! they write through a dereference, write to a borrowed local, or write to, move out of, or end
+//! the storage of the local the place is based on or the local holding its value.
+
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{NonMutatingUseContext, NonUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_mir_dataflow::impls::borrowed_locals;
+
+use crate::MirPass;
+
+pub struct ReuseFieldReads;
+
+impl<'tcx> MirPass<'tcx> for ReuseFieldReads {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let borrowed = borrowed_locals(body);
+        for (bb, data) in body.basic_blocks.as_mut_preserves_cfg().iter_enumerated_mut() {
+            // The places read so far in this block, with the local holding the value read.
+            let mut reads: Vec<(Place<'tcx>, Local)> = Vec::new();
+            for statement in &mut data.statements {
+                if let StatementKind::Assign(box (_, Rvalue::Use(Operand::Copy(ref mut place)))) =
+                    statement.kind
+                    && let Some(&(_, value)) = reads.iter().find(|(read, _)| *read == *place)
+                {
+                    debug!(?bb, ?place, ?value, "reusing field read");
+                    *place = value.into();
+                }
+
+                forget_changed_reads(&mut reads, &borrowed, statement);
+
+                if let StatementKind::Assign(box (dest, Rvalue::Use(Operand::Copy(place)))) =
+                    statement.kind
+                    && let Some(dest) = dest.as_local()
+                    && dest != place.local
+                    && is_field_path(place)
+                {
+                    reads.push((place, dest));
+                }
+            }
+        }
+    }
+}
+
+/// Whether `place` reads a field, through dereferences and fields only.
+fn is_field_path(place: Place<'_>) -> bool {
+    !place.projection.is_empty()
+        && place
+            .projection
+            .iter()
+            .all(|elem| matches!(elem, ProjectionElem::Deref | ProjectionElem::Field(..)))
+}
+
+/// Forgets the `reads` whose place or value `statement` may change.
+fn forget_changed_reads<'tcx>(
+    reads: &mut Vec<(Place<'tcx>, Local)>,
+    borrowed: &BitSet<Local>,
+    statement: &Statement<'tcx>,
+) {
+    if reads.is_empty() {
+        return;
+    }
+    if let StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(_))
+    | StatementKind::Retag(..) = statement.kind
+    {
+        reads.clear();
+        return;
+    }
+
+    let mut changes = Changes { locals: Vec::new(), indirect: false };
+    changes.visit_statement(statement, Location::START);
+    if changes.indirect || changes.locals.iter().any(|&local| borrowed.contains(local)) {
+        reads.clear();
+    } else {
+        reads.retain(|&(place, value)| {
+            !changes.locals.contains(&place.local) && !changes.locals.contains(&value)
+        });
+    }
+}
+
+/// The locals a statement changes.
+struct Changes {
+    locals: Vec<Local>,
+    /// Whether the statement writes through a dereference, which may change any place.
+    indirect: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for Changes {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _: Location) {
+        if context.is_mutating_use()
+            || context == PlaceContext::NonMutatingUse(NonMutatingUseContext::Move)
+        {
+            self.indirect |= place.is_indirect();
+            self.locals.push(place.local);
+        }
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if let PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead) =
+            context
+        {
+            self.locals.push(local);
+        }
+    }
+}
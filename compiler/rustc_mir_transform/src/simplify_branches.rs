@@ -1,3 +1,4 @@
+use crate::ssa::SsaLocals;
 use crate::MirPass;
 use rustc_middle::mir::*;
 use rustc_middle::ty::TyCtxt;
@@ -7,6 +8,8 @@ pub enum SimplifyConstCondition {
     Final,
 }
 /// A pass that replaces a branch with a goto when its condition is known.
+/// It also removes the `assume`s of conditions known to be true, and makes the blocks assuming
+/// `false` unreachable.
 impl<'tcx> MirPass<'tcx> for SimplifyConstCondition {
     fn name(&self) -> &'static str {
         match self {
@@ -18,6 +21,7 @@ fn name(&self) -> &'static str {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         trace!("Running SimplifyConstCondition on {:?}", body.source);
         let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        remove_dominated_assumes(body);
         'blocks: for block in body.basic_blocks_mut() {
             for stmt in block.statements.iter_mut() {
                 if let StatementKind::Intrinsic(box ref intrinsic) = stmt.kind
@@ -59,3 +63,54 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         }
     }
 }
+
+/// Removes the `assume(x)` statements which are dominated by another `assume(x)`, or by the
+/// success edge of an `assert(x)`. `x` must be an SSA local, so that it is still true when the
+/// dominated `assume` is reached.
+fn remove_dominated_assumes(body: &mut Body<'_>) {
+    let ssa = SsaLocals::new(body);
+    let predecessors = body.basic_blocks.predecessors();
+    let dominators = body.basic_blocks.dominators();
+
+    // For each local known to be true, the locations from which it is known.
+    let mut known_true = Vec::new();
+    let mut assumes = Vec::new();
+    // We only visit reachable blocks: computing `dominates` on an unreachable one ICEs.
+    for (block, data) in traversal::reverse_postorder(body) {
+        for (statement_index, stmt) in data.statements.iter().enumerate() {
+            if let StatementKind::Intrinsic(box NonDivergingIntrinsic::Assume(ref cond)) = stmt.kind
+                && let Some(local) = cond.place().and_then(|place| place.as_local())
+                && ssa.is_ssa(local)
+            {
+                assumes.push((local, Location { block, statement_index }));
+                known_true.push((local, Location { block, statement_index: statement_index + 1 }));
+            }
+        }
+        if let TerminatorKind::Assert { ref cond, expected: true, target, .. } =
+            data.terminator().kind
+            && let Some(local) = cond.place().and_then(|place| place.as_local())
+            && ssa.is_ssa(local)
+            && predecessors[target].as_slice() == [block]
+        {
+            known_true.push((local, Location { block: target, statement_index: 0 }));
+        }
+    }
+
+    let redundant: Vec<_> = assumes
+        .into_iter()
+        .filter(|&(local, location)| {
+            known_true
+                .iter()
+                .any(|&(known, from)| known == local && from.dominates(location, dominators))
+        })
+        .map(|(_, location)| location)
+        .collect();
+    if redundant.is_empty() {
+        return;
+    }
+
+    let basic_blocks = body.basic_blocks.as_mut_preserves_cfg();
+    for Location { block, statement_index } in redundant {
+        basic_blocks[block].statements[statement_index].make_nop();
+    }
+}
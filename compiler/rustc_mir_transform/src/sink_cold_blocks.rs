@@ -0,0 +1,83 @@
+//! Moves the blocks that only lead to a panic or to `unreachable` after all the other blocks, so
+//! that the normal flow of a function reads straight through.
+//!
+//! ```ignore (MIR)
+//! bb0: {
+//!     switchInt(_1) -> [0: bb1, otherwise: bb2];
+//! }
+//!
+//! bb1: {
+//!     _2 = panic(const "...") -> unwind continue;
+//! }
+//!
+//! bb2: {
+//!     _0 = Div(const 10_u32, _1);
+//!     return;
+//! }
+//! ```
+//! becomes
+//! ```ignore (MIR)
+//! bb0: {
+//!     switchInt(_1) -> [0: bb2, otherwise: bb1];
+//! }
+//!
+//! bb1: {
+//!     _0 = Div(const 10_u32, _1);
+//!     return;
+//! }
+//!
+//! bb2: {
+//!     _2 = panic(const "...") -> unwind continue;
+//! }
+//! ```
+//! A block is cold if it is `unreachable`, if it calls a `#[cold]` function or a panic lang item,
+//! or if all its successors are cold, see `Body::cold_blocks`. Unwind edges don't count, as
+//! cleanup code is never on the normal flow.
+//!
+//! MIR has no notion of a cold block: the panicking functions are already `#[cold]`, and codegen
+//! marks the branches to the same blocks as unlikely when optimizing. This only orders the blocks
+//! of MIR the same way. The start block never moves, and the other blocks keep their relative
+//! order.
+//!
+//! `ReorderBasicBlocks`, which the mir-opt tests enable, puts the blocks back in reverse
+//! postorder, so the new order only shows in the dumps of this pass.
+
+use rustc_index::IndexVec;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+use crate::prettify::permute_basic_blocks;
+use crate::MirPass;
+
+pub struct SinkColdBlocks;
+
+impl<'tcx> MirPass<'tcx> for SinkColdBlocks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let mut cold = body.cold_blocks(tcx);
+        cold.remove(START_BLOCK);
+        if cold.is_empty() {
+            return;
+        }
+
+        let mut order: Vec<BasicBlock> =
+            body.basic_blocks.indices().filter(|&bb| !cold.contains(bb)).collect();
+        order.extend(cold.iter());
+        if order.iter().copied().eq(body.basic_blocks.indices()) {
+            return;
+        }
+        debug!(?order, "sinking cold blocks");
+
+        let mut map = IndexVec::from_elem(START_BLOCK, &body.basic_blocks);
+        for (new, old) in order.into_iter().enumerate() {
+            map[old] = BasicBlock::from_usize(new);
+        }
+        permute_basic_blocks(tcx, body, map);
+    }
+}
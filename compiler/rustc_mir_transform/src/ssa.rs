@@ -5,7 +5,10 @@
 //! As a consequence of rule 2, we consider that borrowed locals are not SSA, even if they are
 //! `Freeze`, as we do not track that the assignment dominates all uses of the borrow.
 
+use std::cell::OnceCell;
+
 use rustc_data_structures::graph::dominators::Dominators;
+use rustc_hir::def_id::DefId;
 use rustc_index::bit_set::BitSet;
 use rustc_index::{IndexSlice, IndexVec};
 use rustc_middle::middle::resolve_bound_vars::Set1;
@@ -383,3 +386,139 @@ pub(crate) fn has_single_storage(&self, local: Local) -> bool {
         matches!(self.storage_live[local], Set1::One(_))
     }
 }
+
+/// The values of the SSA locals of a body, for the passes which look at how a value was computed
+/// to learn something about it.
+pub(crate) struct SsaValues<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    ssa: &'a SsaLocals,
+    assignments: IndexVec<Local, Option<&'a Rvalue<'tcx>>>,
+    /// The functions called to assign SSA locals, and their arguments.
+    calls: IndexVec<Local, Option<(DefId, &'a [Operand<'tcx>])>>,
+    /// Where and how each local is mentioned. This is only collected if a pass asks for it.
+    uses: OnceCell<IndexVec<Local, Vec<(Location, Place<'tcx>, PlaceContext)>>>,
+}
+
+impl<'a, 'tcx> SsaValues<'a, 'tcx> {
+    pub(crate) fn new(body: &'a Body<'tcx>, ssa: &'a SsaLocals) -> SsaValues<'a, 'tcx> {
+        let mut assignments = IndexVec::from_elem(None, &body.local_decls);
+        for (local, rvalue, _) in ssa.assignments(body) {
+            assignments[local] = Some(rvalue);
+        }
+        let mut calls = IndexVec::from_elem(None, &body.local_decls);
+        for data in body.basic_blocks.iter() {
+            if let TerminatorKind::Call { ref func, ref args, destination, .. } =
+                data.terminator().kind
+                && let Some(local) = destination.as_local()
+                && ssa.is_ssa(local)
+                && let Some((def_id, _)) = func.const_fn_def()
+            {
+                calls[local] = Some((def_id, &args[..]));
+            }
+        }
+        SsaValues { body, ssa, assignments, calls, uses: OnceCell::new() }
+    }
+
+    /// Returns the SSA local whose value `local` is a copy of.
+    pub(crate) fn ssa_head(&self, local: Local) -> Option<Local> {
+        let head = self.ssa.copy_classes()[local];
+        self.ssa.is_ssa(head).then_some(head)
+    }
+
+    /// Returns the SSA local whose value `operand` is a copy of.
+    pub(crate) fn ssa_value(&self, operand: &Operand<'tcx>) -> Option<Local> {
+        self.ssa_head(operand.place()?.as_local()?)
+    }
+
+    /// Returns the rvalue assigned to the SSA local `local`, unless it is an argument or the
+    /// destination of a call.
+    pub(crate) fn assignment(&self, local: Local) -> Option<&'a Rvalue<'tcx>> {
+        self.assignments[local]
+    }
+
+    /// If the SSA local `local` is the destination of a call, returns the function called and the
+    /// arguments.
+    pub(crate) fn call(&self, local: Local) -> Option<(DefId, &'a [Operand<'tcx>])> {
+        self.calls[local]
+    }
+
+    /// Returns where and how `local` is mentioned, including as an index.
+    pub(crate) fn uses(&self, local: Local) -> &[(Location, Place<'tcx>, PlaceContext)] {
+        let uses = self.uses.get_or_init(|| {
+            let mut uses = Uses(IndexVec::from_elem(Vec::new(), &self.body.local_decls));
+            uses.visit_body(self.body);
+            uses.0
+        });
+        &uses[local]
+    }
+
+    /// If the SSA reference `reference` mutably (re)borrows a local, returns that local.
+    pub(crate) fn borrowed(&self, reference: Local) -> Option<Local> {
+        let Rvalue::Ref(_, BorrowKind::Mut { .. }, place) = self.assignments[reference]? else {
+            return None;
+        };
+        match place.projection[..] {
+            [] => Some(place.local),
+            [ProjectionElem::Deref] if self.ssa.is_ssa(place.local) => self.borrowed(place.local),
+            _ => None,
+        }
+    }
+
+    /// Whether the reference assigned at `location` is only reborrowed and passed to the method
+    /// `next`.
+    pub(crate) fn is_only_advanced(&self, location: Location, next: DefId) -> bool {
+        let Some(statement) = self.body.stmt_at(location).left() else { return false };
+        let Some((place, _)) = statement.kind.as_assign() else { return false };
+        let Some(reference) = place.as_local() else { return false };
+        if !self.ssa.is_ssa(reference) {
+            return false;
+        }
+        self.uses(reference).iter().all(|&(location, place, context)| match context {
+            PlaceContext::NonUse(_) => true,
+            PlaceContext::MutatingUse(MutatingUseContext::Store) => place.projection.is_empty(),
+            PlaceContext::MutatingUse(MutatingUseContext::Borrow) => {
+                place.projection[..] == [ProjectionElem::Deref]
+                    && self.is_only_advanced(location, next)
+            }
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::Move) => {
+                let Some(terminator) = self.body.stmt_at(location).right() else { return false };
+                matches!(
+                    terminator.kind,
+                    TerminatorKind::Call { ref func, ref args, .. }
+                        if func.const_fn_def().is_some_and(|(def_id, _)| def_id == next)
+                            && args[..] == [Operand::Move(place)]
+                )
+            }
+            _ => false,
+        })
+    }
+
+    /// If `place` is `(*_r)` where `_r` is an SSA reference to a slice, returns the SSA local that
+    /// `_r` was (re)borrowed from.
+    pub(crate) fn slice_reference(&self, place: Place<'tcx>) -> Option<Local> {
+        let [ProjectionElem::Deref] = place.projection[..] else { return None };
+        let head = self.ssa_head(place.local)?;
+        if !self.body.local_decls[head].ty.is_ref() {
+            return None;
+        }
+        match self.assignments[head] {
+            Some(Rvalue::Ref(_, _, reborrowed)) => self.slice_reference(*reborrowed),
+            _ => Some(head),
+        }
+    }
+}
+
+/// Collects where and how each local is mentioned.
+struct Uses<'tcx>(IndexVec<Local, Vec<(Location, Place<'tcx>, PlaceContext)>>);
+
+impl<'tcx> Visitor<'tcx> for Uses<'tcx> {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        self.0[place.local].push((location, *place, context));
+        // Record the locals used as indices too.
+        self.visit_projection(place.as_ref(), context, location);
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, location: Location) {
+        self.0[local].push((location, Place::from(local), context));
+    }
+}
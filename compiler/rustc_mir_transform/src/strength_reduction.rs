@@ -0,0 +1,186 @@
+//! Replaces multiplications by constants next to a power of two by shifts and additions.
+//!
+//! Without overflow checks, `x * 9` is computed with a wrapping multiplication, which gives the
+//! same result as a shift and a wrapping addition:
+//! ```ignore (MIR)
+//! _0 = Mul(_1, const 9_u32);
+//! ```
+//! becomes
+//! ```ignore (MIR)
+//! StorageLive(_2);
+//! _2 = Shl(_1, const 3_u32);
+//! _0 = Add(move _2, _1);
+//! StorageDead(_2);
+//! ```
+//! We handle factors of the form `2^k`, `2^k + 1` and `2^k - 1`. Since wrapping arithmetic only
+//! depends on the bits of the factor, this works for signed integers too.
+//!
+//! A `CheckedMul` has to report overflow exactly when the multiplication overflows, which a shift
+//! doesn't, so we only replace the ones whose overflow we can still compute: `x * 2` overflows
+//! exactly when `x + x` does, so it becomes a `CheckedAdd` and keeps its assertion. Multiplying by
+//! `0` or `1` never overflows, so we compute the result directly and remove the assertion.
+
+use rustc_middle::mir::interpret::Scalar;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, ParamEnv, TyCtxt};
+use rustc_target::abi::FieldIdx;
+
+use crate::MirPass;
+
+pub struct StrengthReduction;
+
+impl<'tcx> MirPass<'tcx> for StrengthReduction {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let local_decls = &mut body.local_decls;
+        for data in body.basic_blocks.as_mut() {
+            // The result of the last statement if it is a checked multiplication which can't
+            // overflow.
+            let mut without_overflow = None;
+            for statement in std::mem::take(&mut data.statements) {
+                let reduced = match statement.kind {
+                    StatementKind::Assign(box (place, ref rvalue)) => {
+                        reduce(tcx, param_env, local_decls, statement.source_info, place, rvalue)
+                    }
+                    _ => None,
+                };
+                let Some(reduced) = reduced else {
+                    data.statements.push(statement);
+                    without_overflow = None;
+                    continue;
+                };
+                debug!(?statement, ?reduced.statements, "reducing multiplication");
+                data.statements.extend(reduced.statements);
+                without_overflow = reduced.without_overflow;
+            }
+
+            let Some(result) = without_overflow else { continue };
+            let TerminatorKind::Assert {
+                cond: Operand::Copy(cond) | Operand::Move(cond),
+                expected: false,
+                msg: box AssertKind::Overflow(BinOp::Mul, ..),
+                target,
+                ..
+            } = data.terminator().kind
+            else {
+                continue;
+            };
+            if cond == tcx.mk_place_field(result, FieldIdx::from_u32(1), tcx.types.bool) {
+                debug!(?result, "removing overflow assertion");
+                data.terminator_mut().kind = TerminatorKind::Goto { target };
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Reduced<'tcx> {
+    /// The statements replacing the multiplication.
+    statements: Vec<Statement<'tcx>>,
+    /// The place holding the result of a checked multiplication which can't overflow.
+    without_overflow: Option<Place<'tcx>>,
+}
+
+fn reduce<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    local_decls: &mut LocalDecls<'tcx>,
+    source_info: SourceInfo,
+    place: Place<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+) -> Option<Reduced<'tcx>> {
+    let (Rvalue::BinaryOp(op, box (lhs, rhs)) | Rvalue::CheckedBinaryOp(op, box (lhs, rhs))) =
+        rvalue
+    else {
+        return None;
+    };
+    if *op != BinOp::Mul {
+        return None;
+    }
+    let ty = lhs.ty(local_decls, tcx);
+    if !matches!(ty.kind(), ty::Int(_) | ty::Uint(_)) {
+        return None;
+    }
+    let eval = |operand: &Operand<'tcx>| operand.constant()?.const_.try_eval_bits(tcx, param_env);
+    let (operand, factor) = match (eval(lhs), eval(rhs)) {
+        (_, Some(factor)) => (lhs.clone(), factor),
+        (Some(factor), None) => (rhs.clone(), factor),
+        (None, None) => return None,
+    };
+
+    let span = source_info.span;
+    let assign = |place, rvalue| Statement {
+        source_info,
+        kind: StatementKind::Assign(Box::new((place, rvalue))),
+    };
+    let size = ty.primitive_size(tcx);
+
+    if let Rvalue::CheckedBinaryOp(..) = rvalue {
+        let result = match factor {
+            0 => Operand::const_from_scalar(tcx, ty, Scalar::from_uint(0u128, size), span),
+            1 => operand,
+            2 => {
+                let add =
+                    Rvalue::CheckedBinaryOp(BinOp::Add, Box::new((operand.to_copy(), operand)));
+                return Some(Reduced {
+                    statements: vec![assign(place, add)],
+                    without_overflow: None,
+                });
+            }
+            _ => return None,
+        };
+        let overflow =
+            Operand::const_from_scalar(tcx, tcx.types.bool, Scalar::from_bool(false), span);
+        let tuple = Rvalue::Aggregate(
+            Box::new(AggregateKind::Tuple),
+            [result, overflow].into_iter().collect(),
+        );
+        return Some(Reduced {
+            statements: vec![assign(place, tuple)],
+            without_overflow: Some(place),
+        });
+    }
+
+    // `factor` is smaller than `2^size`, so all shifts are smaller than the size of the type,
+    // except for `2^size - 1`, which we don't handle.
+    let (shift, combine) = if factor > 1 && factor.is_power_of_two() {
+        (factor.trailing_zeros(), None)
+    } else if factor > 2 && (factor - 1).is_power_of_two() {
+        ((factor - 1).trailing_zeros(), Some(BinOp::Add))
+    } else if factor > 2
+        && let Some(next) = factor.checked_add(1)
+        && next.is_power_of_two()
+        && u64::from(next.trailing_zeros()) < size.bits()
+    {
+        (next.trailing_zeros(), Some(BinOp::Sub))
+    } else {
+        return None;
+    };
+    let shift = Operand::const_from_scalar(tcx, tcx.types.u32, Scalar::from_u32(shift), span);
+    let shl = |operand| Rvalue::BinaryOp(BinOp::Shl, Box::new((operand, shift.clone())));
+
+    let Some(combine) = combine else {
+        return Some(Reduced {
+            statements: vec![assign(place, shl(operand))],
+            without_overflow: None,
+        });
+    };
+    let temp = local_decls.push(LocalDecl::new(ty, span));
+    // Only the last use of `operand` may move it.
+    let shifted = shl(operand.to_copy());
+    let combined = Rvalue::BinaryOp(combine, Box::new((Operand::Move(temp.into()), operand)));
+    let statements = vec![
+        Statement { source_info, kind: StatementKind::StorageLive(temp) },
+        assign(temp.into(), shifted),
+        assign(place, combined),
+        Statement { source_info, kind: StatementKind::StorageDead(temp) },
+    ];
+    Some(Reduced { statements, without_overflow: None })
+}
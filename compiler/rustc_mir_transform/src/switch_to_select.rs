@@ -0,0 +1,268 @@
+//! Replaces switches which only choose between two integer constants by arithmetic.
+//!
+//! A `match` on a `bool` which produces one of two integers looks like this:
+//! ```ignore (MIR)
+//! bb0: {
+//!     switchInt(_1) -> [0: bb2, otherwise: bb1];
+//! }
+//!
+//! bb1: {
+//!     _0 = const 10_u32;
+//!     goto -> bb3;
+//! }
+//!
+//! bb2: {
+//!     _0 = const 3_u32;
+//!     goto -> bb3;
+//! }
+//! ```
+//! Converting the `bool` to `0` or `1` lets us compute the result without branching:
+//! ```ignore (MIR)
+//! bb0: {
+//!     StorageLive(_2);
+//!     _2 = _1 as u32 (IntToInt);
+//!     StorageLive(_3);
+//!     _3 = Mul(move _2, const 7_u32);
+//!     _0 = Add(move _3, const 3_u32);
+//!     StorageDead(_3);
+//!     StorageDead(_2);
+//!     goto -> bb3;
+//! }
+//! ```
+//! Switches on other integers, which have a single value besides `otherwise` or two values and
+//! an unreachable `otherwise`, first compare the discriminant with one of the values.
+//!
+//! Apart from `StorageLive` and `StorageDead` statements, which must be the same in both, the
+//! targets of the switch may only assign a constant to the same place and jump to the same block,
+//! so that the assignment is the only effect we need to preserve. `MatchBranchSimplification` runs
+//! before this pass and already turns switches choosing between two `bool`s into a comparison, so
+//! we only handle integers.
+
+use rustc_middle::mir::interpret::Scalar;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt};
+
+use crate::simplify::simplify_cfg;
+use crate::MirPass;
+
+pub struct SwitchToSelect;
+
+impl<'tcx> MirPass<'tcx> for SwitchToSelect {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let mut selects = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::SwitchInt { ref discr, ref targets } = data.terminator().kind
+            else {
+                continue;
+            };
+            let (value, chosen, other) = match targets.all_targets() {
+                &[chosen, other] => (targets.iter().next().unwrap().0, chosen, other),
+                &[chosen, other, otherwise]
+                    if body.basic_blocks[otherwise].is_empty_unreachable() =>
+                {
+                    (targets.iter().next().unwrap().0, chosen, other)
+                }
+                _ => continue,
+            };
+            if chosen == other || bb == chosen || bb == other {
+                continue;
+            }
+            let Some(arm) = Arm::new(tcx, param_env, &body.basic_blocks[chosen]) else {
+                continue;
+            };
+            let Some(other_arm) = Arm::new(tcx, param_env, &body.basic_blocks[other]) else {
+                continue;
+            };
+            let (statements, other_statements) =
+                (&body.basic_blocks[chosen].statements, &body.basic_blocks[other].statements);
+            if arm.place != other_arm.place
+                || arm.target != other_arm.target
+                || arm.index != other_arm.index
+                || statements.len() != other_statements.len()
+                || std::iter::zip(statements, other_statements)
+                    .enumerate()
+                    .any(|(index, (s, o))| index != arm.index && s.kind != o.kind)
+            {
+                continue;
+            }
+            let ty = arm.place.ty(&body.local_decls, tcx).ty;
+            if !ty.is_integral() {
+                continue;
+            }
+
+            debug!(?bb, ?arm, ?other_arm, "replacing switch by select");
+            selects.push(Select {
+                bb,
+                discr: discr.clone(),
+                value,
+                ty,
+                chosen_value: arm.value,
+                other_value: other_arm.value,
+                arm: chosen,
+                index: arm.index,
+                place: arm.place,
+                target: arm.target,
+            });
+        }
+
+        if selects.is_empty() {
+            return;
+        }
+        for select in selects {
+            select.apply(tcx, param_env, body);
+        }
+        simplify_cfg(tcx, body);
+    }
+}
+
+/// A target of a switch which only assigns an integer constant.
+#[derive(Debug)]
+struct Arm<'tcx> {
+    /// The index of the assignment among the statements of the block.
+    index: usize,
+    place: Place<'tcx>,
+    /// The bits of the constant.
+    value: u128,
+    /// The block the arm jumps to.
+    target: BasicBlock,
+}
+
+impl<'tcx> Arm<'tcx> {
+    fn new(
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        data: &BasicBlockData<'tcx>,
+    ) -> Option<Arm<'tcx>> {
+        let TerminatorKind::Goto { target } = data.terminator().kind else { return None };
+        let mut assignment = None;
+        for (index, statement) in data.statements.iter().enumerate() {
+            match statement.kind {
+                StatementKind::StorageLive(_)
+                | StatementKind::StorageDead(_)
+                | StatementKind::Nop => {}
+                StatementKind::Assign(box (place, Rvalue::Use(Operand::Constant(ref constant))))
+                    if assignment.is_none() =>
+                {
+                    let value = constant.const_.try_eval_bits(tcx, param_env)?;
+                    assignment = Some((index, place, value));
+                }
+                _ => return None,
+            }
+        }
+        let (index, place, value) = assignment?;
+        Some(Arm { index, place, value, target })
+    }
+}
+
+/// A switch to replace by `place = other_value + (discr == value) * (chosen_value - other_value)`.
+struct Select<'tcx> {
+    bb: BasicBlock,
+    discr: Operand<'tcx>,
+    value: u128,
+    ty: Ty<'tcx>,
+    chosen_value: u128,
+    other_value: u128,
+    /// The block assigning `chosen_value`, whose other statements we keep.
+    arm: BasicBlock,
+    /// The index of the assignment in `arm`.
+    index: usize,
+    place: Place<'tcx>,
+    target: BasicBlock,
+}
+
+impl<'tcx> Select<'tcx> {
+    fn apply(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>, body: &mut Body<'tcx>) {
+        let Select { bb, discr, value, ty, chosen_value, other_value, arm, index, place, target } =
+            self;
+        let source_info = body.basic_blocks[bb].terminator().source_info;
+        let span = source_info.span;
+        let size = tcx.layout_of(param_env.and(ty)).unwrap().size;
+        let constant =
+            |bits| Operand::const_from_scalar(tcx, ty, Scalar::from_uint(bits, size), span);
+        let discr_ty = discr.ty(&body.local_decls, tcx);
+        let arm_statements = body.basic_blocks[arm].statements.clone();
+
+        let mut statements = Vec::new();
+        let mut temps = Vec::new();
+        let mut temp = |statements: &mut Vec<_>, ty, rvalue| {
+            let temp = body.local_decls.push(LocalDecl::new(ty, span));
+            statements.push(Statement { source_info, kind: StatementKind::StorageLive(temp) });
+            statements.push(Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((temp.into(), rvalue))),
+            });
+            temps.push(temp);
+            Operand::Move(temp.into())
+        };
+
+        // A `bool` is already `1` if and only if it is not `0`, so we only compare other
+        // discriminants.
+        let (condition, chosen_value, other_value) = if discr_ty.is_bool() {
+            if value == 0 {
+                (discr, other_value, chosen_value)
+            } else {
+                (discr, chosen_value, other_value)
+            }
+        } else {
+            let discr_size = tcx.layout_of(param_env.and(discr_ty)).unwrap().size;
+            let value = Operand::const_from_scalar(
+                tcx,
+                discr_ty,
+                Scalar::from_uint(value, discr_size),
+                span,
+            );
+            let condition = temp(
+                &mut statements,
+                tcx.types.bool,
+                Rvalue::BinaryOp(BinOp::Eq, Box::new((discr, value))),
+            );
+            (condition, chosen_value, other_value)
+        };
+
+        // The switch evaluates the discriminant before any statement of the arm, so we need to
+        // convert the condition before them too.
+        let condition = temp(&mut statements, ty, Rvalue::Cast(CastKind::IntToInt, condition, ty));
+        for (i, statement) in arm_statements.into_iter().enumerate() {
+            if i != index {
+                statements.push(statement);
+                continue;
+            }
+            // Integer arithmetic in MIR wraps, so computing the difference modulo the size of
+            // the type gives the right result for signed integers too.
+            let difference = size.truncate(chosen_value.wrapping_sub(other_value));
+            let mut result = condition.clone();
+            if difference != 1 {
+                result = temp(
+                    &mut statements,
+                    ty,
+                    Rvalue::BinaryOp(BinOp::Mul, Box::new((result, constant(difference)))),
+                );
+            }
+            let rvalue = if other_value == 0 {
+                Rvalue::Use(result)
+            } else {
+                Rvalue::BinaryOp(BinOp::Add, Box::new((result, constant(other_value))))
+            };
+            statements.push(Statement {
+                source_info: statement.source_info,
+                kind: StatementKind::Assign(Box::new((place, rvalue))),
+            });
+        }
+        statements.extend(temps.into_iter().rev().map(|temp| Statement {
+            source_info,
+            kind: StatementKind::StorageDead(temp),
+        }));
+
+        let data = &mut body.basic_blocks.as_mut()[bb];
+        data.statements.extend(statements);
+        data.terminator_mut().kind = TerminatorKind::Goto { target };
+    }
+}
@@ -0,0 +1,189 @@
+//! Turns calls of a function to itself in tail position into a jump back to its start, so that
+//! accumulator-style recursion runs in a loop instead of growing the stack.
+//!
+//! ```ignore (MIR)
+//! bb0: {
+//!     switchInt(_1) -> [0: bb2, otherwise: bb1];
+//! }
+//!
+//! bb1: {
+//!     _3 = Sub(_1, const 1_u64);
+//!     _4 = Add(_2, _1);
+//!     _0 = sum(move _3, move _4) -> [return: bb3, unwind continue];
+//! }
+//! ```
+//! where `bb3` only returns, becomes
+//! ```ignore (MIR)
+//! bb0: {
+//!     goto -> bb4;
+//! }
+//!
+//! bb1: {
+//!     _3 = Sub(_1, const 1_u64);
+//!     _4 = Add(_2, _1);
+//!     _5 = move _3;
+//!     _6 = move _4;
+//!     _1 = move _5;
+//!     _2 = move _6;
+//!     goto -> bb4;
+//! }
+//!
+//! bb4: {
+//!     switchInt(_1) -> [0: bb2, otherwise: bb1];
+//! }
+//! ```
+//! The start block can't have predecessors, so its contents are moved into a new block first.
+//! The arguments are moved to temporaries before any parameter is assigned, as they may read the
+//! parameters of the current call.
+//!
+//! The call must write its result to the return place, and nothing but storage markers may happen
+//! until it is returned. In particular, no local is dropped after the call, so reusing the frame
+//! doesn't skip any destructor. No local may be borrowed either, as the arguments could otherwise
+//! point to locals of the current call, which are overwritten by the next iteration.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, GenericArgs, GenericArgsRef, TyCtxt};
+use rustc_mir_dataflow::impls::borrowed_locals;
+
+use crate::MirPass;
+
+pub struct TailRecursionToLoop;
+
+impl<'tcx> MirPass<'tcx> for TailRecursionToLoop {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let def_id = body.source.def_id();
+        debug!(?def_id);
+
+        // Closures can't name themselves, and the caller location of `#[track_caller]` functions
+        // and the arguments of coroutines are not passed like the other arguments.
+        if !matches!(body.source.instance, ty::InstanceDef::Item(_))
+            || body.source.promoted.is_some()
+            || body.coroutine.is_some()
+            || body.spread_arg.is_some()
+            || tcx.is_closure(def_id)
+            || tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::TRACK_CALLER)
+        {
+            return;
+        }
+
+        let identity = GenericArgs::identity_for_item(tcx, def_id);
+        let mut tail_calls: Vec<_> = body
+            .basic_blocks
+            .indices()
+            .filter(|&bb| is_tail_self_call(body, def_id, identity, bb))
+            .collect();
+        if tail_calls.is_empty() || !borrowed_locals(body).is_empty() {
+            return;
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        let source_info = basic_blocks[START_BLOCK].terminator().source_info;
+        let loop_start = basic_blocks.push(BasicBlockData::new(None));
+        basic_blocks.swap(START_BLOCK, loop_start);
+        basic_blocks[START_BLOCK].terminator =
+            Some(Terminator { source_info, kind: TerminatorKind::Goto { target: loop_start } });
+        for bb in &mut tail_calls {
+            if *bb == START_BLOCK {
+                *bb = loop_start;
+            }
+        }
+
+        for bb in tail_calls {
+            debug!(?bb, "turning tail call into a loop");
+            let terminator = body.basic_blocks[bb].terminator();
+            let source_info = terminator.source_info;
+            let TerminatorKind::Call { ref args, target: Some(target), .. } = terminator.kind else {
+                bug!("expected a tail call in {:?}", bb)
+            };
+            let args = args.clone();
+            let storage_dead = return_path(body, target)
+                .unwrap()
+                .into_iter()
+                .flat_map(|bb| body.basic_blocks[bb].statements.iter().cloned())
+                .collect::<Vec<_>>();
+
+            let mut statements = Vec::with_capacity(2 * args.len() + storage_dead.len());
+            let mut temps = Vec::with_capacity(args.len());
+            for arg in args {
+                let ty = arg.ty(&body.local_decls, tcx);
+                let temp = body.local_decls.push(LocalDecl::new(ty, source_info.span));
+                temps.push(temp);
+                statements.push(Statement {
+                    source_info,
+                    kind: StatementKind::Assign(Box::new((temp.into(), Rvalue::Use(arg)))),
+                });
+            }
+            for (param, temp) in body.args_iter().zip(temps) {
+                statements.push(Statement {
+                    source_info,
+                    kind: StatementKind::Assign(Box::new((
+                        param.into(),
+                        Rvalue::Use(Operand::Move(temp.into())),
+                    ))),
+                });
+            }
+            statements.extend(storage_dead);
+
+            let data = &mut body.basic_blocks.as_mut()[bb];
+            data.statements.extend(statements);
+            data.terminator_mut().kind = TerminatorKind::Goto { target: loop_start };
+        }
+    }
+}
+
+/// Whether `bb` ends with a call of the function to itself, in tail position.
+fn is_tail_self_call<'tcx>(
+    body: &Body<'tcx>,
+    def_id: DefId,
+    identity: GenericArgsRef<'tcx>,
+    bb: BasicBlock,
+) -> bool {
+    // Unwinding out of the call may run cleanup code of the current call, which doesn't happen
+    // anymore once both calls share the same frame.
+    let TerminatorKind::Call {
+        ref func,
+        ref args,
+        destination,
+        target: Some(target),
+        unwind: UnwindAction::Continue | UnwindAction::Unreachable,
+        ..
+    } = body.basic_blocks[bb].terminator().kind
+    else {
+        return false;
+    };
+    func.const_fn_def() == Some((def_id, identity))
+        && args.len() == body.arg_count
+        && destination == Place::return_place()
+        && return_path(body, target).is_some()
+}
+
+/// The blocks going from `bb` to a `return`, if they only contain `StorageDead` statements and
+/// `goto`s.
+fn return_path(body: &Body<'_>, mut bb: BasicBlock) -> Option<Vec<BasicBlock>> {
+    let mut path = Vec::new();
+    // An empty infinite loop never reaches a `return`.
+    while path.len() < body.basic_blocks.len() {
+        let data = &body.basic_blocks[bb];
+        if !data
+            .statements
+            .iter()
+            .all(|statement| matches!(statement.kind, StatementKind::StorageDead(_)))
+        {
+            return None;
+        }
+        path.push(bb);
+        match data.terminator().kind {
+            TerminatorKind::Return => return Some(path),
+            TerminatorKind::Goto { target } => bb = target,
+            _ => return None,
+        }
+    }
+    None
+}
@@ -0,0 +1,363 @@
+//! Removes the `Option`s that combinators like `map` or `and_then` build, when they are matched
+//! right away, e.g. by `unwrap_or`.
+//!
+//! Once everything is inlined, `x.map(|v| v + 1).unwrap_or(d)` builds an `Option` in each arm of
+//! the first match, only to match on it again in a join block:
+//! ```ignore (MIR)
+//! bb1: {
+//!     _3 = Option::<u32>::None;
+//!     goto -> bb3;
+//! }
+//!
+//! bb2: {
+//!     _4 = ((_1 as Some).0: u32);
+//!     _5 = Add(_4, const 1_u32);
+//!     _3 = Option::<u32>::Some(move _5);
+//!     goto -> bb3;
+//! }
+//!
+//! bb3: {
+//!     _6 = discriminant(_3);
+//!     switchInt(move _6) -> [0: bb4, 1: bb5, otherwise: bb6];
+//! }
+//!
+//! bb5: {
+//!     _0 = move ((_3 as Some).0: u32);
+//!     return;
+//! }
+//! ```
+//! Each arm knows which variant it built, so it can jump to the matching target directly. The
+//! payload is stored in a local of its own, so the `Option` is never built:
+//! ```ignore (MIR)
+//! bb1: {
+//!     goto -> bb4;
+//! }
+//!
+//! bb2: {
+//!     _4 = ((_1 as Some).0: u32);
+//!     _5 = Add(_4, const 1_u32);
+//!     _7 = move _5;
+//!     goto -> bb5;
+//! }
+//!
+//! bb5: {
+//!     _0 = move _7;
+//!     return;
+//! }
+//! ```
+//! The `Option` must be a temporary which doesn't escape: it may only be assigned a variant, have
+//! its discriminant read in the join block, and have its payload read. The join block must only
+//! be reached from the assignments, and its other statements are copied into each of them.
+//!
+//! The `Option` itself is gone, so the debuginfo of the inlined variables that were the `Option`,
+//! like the `self` of `unwrap_or`, is removed. The `Option`s of the function itself are kept.
+
+use rustc_hir::def_id::DefId;
+use rustc_index::bit_set::BitSet;
+use rustc_index::{IndexSlice, IndexVec};
+use rustc_middle::mir::visit::{MutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_target::abi::{FieldIdx, VariantIdx};
+
+use crate::simplify::{simplify_cfg, simplify_locals};
+use crate::MirPass;
+
+pub struct ThreadOptionMatches;
+
+impl<'tcx> MirPass<'tcx> for ThreadOptionMatches {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+        let Some(option_did) = tcx.lang_items().option_type() else { return };
+
+        let mut changed = false;
+        loop {
+            let (uses, occurrences) = option_uses(body, option_did);
+            // Threading the jumps of one `Option` changes its blocks, so the `Option`s using the
+            // same blocks are left to the next round.
+            let mut touched = BitSet::new_empty(body.basic_blocks.len());
+            let mut skipped = false;
+            for (local, uses) in uses.iter_enumerated() {
+                let Some(uses) = uses else { continue };
+                let Some(threading) = Threading::new(tcx, body, &occurrences, local, uses) else {
+                    continue;
+                };
+                if threading.blocks(uses).any(|bb| touched.contains(bb)) {
+                    skipped = true;
+                    continue;
+                }
+                debug!(?local, "threading matches of option");
+                for bb in threading.blocks(uses) {
+                    touched.insert(bb);
+                }
+                threading.apply(body, local, uses);
+                changed = true;
+            }
+            if !skipped {
+                break;
+            }
+        }
+
+        if changed {
+            simplify_cfg(tcx, body);
+            simplify_locals(body, tcx);
+        }
+    }
+}
+
+/// How a temporary of type `Option` is used.
+#[derive(Default, Debug)]
+struct OptionUses {
+    /// The statements assigning a variant to the `Option`.
+    assignments: Vec<Location>,
+    /// The statements reading the discriminant of the `Option`.
+    discriminants: Vec<Location>,
+    /// The statements reading the payload of the `Option`.
+    payloads: Vec<Location>,
+    /// Whether the `Option` is used in any other way.
+    escapes: bool,
+}
+
+/// The uses of the temporaries of type `Option`, and the number of occurrences of each local,
+/// storage statements excepted.
+fn option_uses(
+    body: &Body<'_>,
+    option_did: DefId,
+) -> (IndexVec<Local, Option<OptionUses>>, IndexVec<Local, u32>) {
+    let uses = body
+        .local_decls
+        .iter_enumerated()
+        .map(|(local, decl)| {
+            let is_option = matches!(decl.ty.kind(), ty::Adt(def, _) if def.did() == option_did);
+            (is_option && body.local_kind(local) == LocalKind::Temp).then(OptionUses::default)
+        })
+        .collect();
+    let occurrences = IndexVec::from_elem(0, &body.local_decls);
+    let mut collector = OptionUseCollector { body, option_did, uses, occurrences };
+    collector.visit_body(body);
+    (collector.uses, collector.occurrences)
+}
+
+struct OptionUseCollector<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    option_did: DefId,
+    uses: IndexVec<Local, Option<OptionUses>>,
+    occurrences: IndexVec<Local, u32>,
+}
+
+impl<'tcx> OptionUseCollector<'_, 'tcx> {
+    fn tracked(&mut self, place: Place<'tcx>) -> Option<&mut OptionUses> {
+        self.uses[place.local].as_mut()
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for OptionUseCollector<'_, 'tcx> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        let StatementKind::Assign(box (place, ref rvalue)) = statement.kind else {
+            return self.super_statement(statement, location);
+        };
+        match *rvalue {
+            Rvalue::Aggregate(box AggregateKind::Adt(did, ..), ref operands)
+                if did == self.option_did
+                    && place.as_local().is_some()
+                    && let Some(uses) = self.tracked(place) =>
+            {
+                uses.assignments.push(location);
+                for operand in operands {
+                    self.visit_operand(operand, location);
+                }
+            }
+            Rvalue::Discriminant(option)
+                if option.as_local().is_some()
+                    && let Some(uses) = self.tracked(option) =>
+            {
+                uses.discriminants.push(location);
+                self.visit_place(
+                    &place,
+                    PlaceContext::MutatingUse(MutatingUseContext::Store),
+                    location,
+                );
+            }
+            Rvalue::Use(Operand::Copy(option) | Operand::Move(option))
+                if let [ProjectionElem::Downcast(_, variant), ProjectionElem::Field(field, _)] =
+                    option.projection[..]
+                    && variant == VariantIdx::from_u32(1)
+                    && field == FieldIdx::from_u32(0)
+                    && let Some(uses) = self.tracked(option) =>
+            {
+                uses.payloads.push(location);
+                self.visit_place(
+                    &place,
+                    PlaceContext::MutatingUse(MutatingUseContext::Store),
+                    location,
+                );
+            }
+            _ => self.super_statement(statement, location),
+        }
+    }
+
+    fn visit_var_debug_info(&mut self, var_debug_info: &VarDebugInfo<'tcx>) {
+        // The inlined variables may be `Option`s, but the function's own variables escape.
+        if let VarDebugInfoContents::Place(place) = var_debug_info.value
+            && self.uses[place.local].is_some()
+            && var_debug_info.source_info.scope.inlined_instance(&self.body.source_scopes).is_some()
+        {
+            return;
+        }
+        self.super_var_debug_info(var_debug_info);
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_storage_marker() {
+            return;
+        }
+        self.occurrences[local] += 1;
+        if let Some(uses) = &mut self.uses[local] {
+            uses.escapes = true;
+        }
+    }
+}
+
+/// The jumps from the assignments of an `Option` to the targets of the match on it.
+struct Threading {
+    /// The block matching on the `Option`.
+    join: BasicBlock,
+    /// The target of each assignment.
+    targets: Vec<BasicBlock>,
+}
+
+impl Threading {
+    fn new<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        body: &Body<'tcx>,
+        occurrences: &IndexSlice<Local, u32>,
+        option: Local,
+        uses: &OptionUses,
+    ) -> Option<Threading> {
+        let [discriminant] = uses.discriminants[..] else { return None };
+        if uses.escapes || uses.assignments.is_empty() {
+            return None;
+        }
+
+        // The join block matches on the discriminant, which isn't used anywhere else.
+        let join = discriminant.block;
+        let join_data = &body.basic_blocks[join];
+        let StatementKind::Assign(box (discr_place, _)) =
+            join_data.statements[discriminant.statement_index].kind
+        else {
+            bug!()
+        };
+        let discr_local = discr_place.as_local()?;
+        let TerminatorKind::SwitchInt {
+            discr: Operand::Copy(switched) | Operand::Move(switched),
+            ref targets,
+        } = join_data.terminator().kind
+        else {
+            return None;
+        };
+        if switched.as_local() != Some(discr_local) || occurrences[discr_local] != 2 {
+            return None;
+        }
+
+        // The join block is only reached right after one of the assignments.
+        let mut assigning_blocks: Vec<_> = uses.assignments.iter().map(|loc| loc.block).collect();
+        assigning_blocks.sort_unstable();
+        assigning_blocks.dedup();
+        let mut predecessors = body.basic_blocks.predecessors()[join].to_vec();
+        predecessors.sort_unstable();
+        if assigning_blocks.len() != uses.assignments.len()
+            || assigning_blocks != predecessors
+            || assigning_blocks.contains(&join)
+        {
+            return None;
+        }
+
+        let option_ty = body.local_decls[option].ty;
+        let targets = uses
+            .assignments
+            .iter()
+            .map(|&location| {
+                let data = &body.basic_blocks[location.block];
+                let TerminatorKind::Goto { target } = data.terminator().kind else { return None };
+                debug_assert_eq!(target, join);
+                let StatementKind::Assign(box (_, Rvalue::Aggregate(box ref kind, _))) =
+                    data.statements[location.statement_index].kind
+                else {
+                    bug!()
+                };
+                let AggregateKind::Adt(_, variant, ..) = *kind else { bug!() };
+                let discr = option_ty.discriminant_for_variant(tcx, variant)?;
+                Some(targets.target_for_value(discr.val))
+            })
+            .collect::<Option<_>>()?;
+        Some(Threading { join, targets })
+    }
+
+    /// The blocks changed by threading the jumps.
+    fn blocks<'a>(&'a self, uses: &'a OptionUses) -> impl Iterator<Item = BasicBlock> + 'a {
+        std::iter::once(self.join)
+            .chain(uses.assignments.iter().map(|location| location.block))
+            .chain(uses.payloads.iter().map(|location| location.block))
+    }
+
+    fn apply(self, body: &mut Body<'_>, option: Local, uses: &OptionUses) {
+        let ty::Adt(_, args) = *body.local_decls[option].ty.kind() else { bug!() };
+        let span = body.local_decls[option].source_info.span;
+        let payload = body.local_decls.push(LocalDecl::new(args.type_at(0), span));
+
+        let basic_blocks = body.basic_blocks.as_mut_preserves_cfg();
+        for &location in &uses.payloads {
+            let statement = &mut basic_blocks[location.block].statements[location.statement_index];
+            let StatementKind::Assign(box (_, Rvalue::Use(ref mut operand))) = statement.kind else {
+                bug!()
+            };
+            *operand = match *operand {
+                Operand::Copy(_) => Operand::Copy(payload.into()),
+                _ => Operand::Move(payload.into()),
+            };
+        }
+        for &location in &uses.assignments {
+            let statement = &mut basic_blocks[location.block].statements[location.statement_index];
+            let StatementKind::Assign(box (_, Rvalue::Aggregate(_, ref mut operands))) =
+                statement.kind
+            else {
+                bug!()
+            };
+            statement.kind = match operands.pop() {
+                Some(operand) => {
+                    StatementKind::Assign(Box::new((payload.into(), Rvalue::Use(operand))))
+                }
+                None => StatementKind::Nop,
+            };
+        }
+
+        // The join block is skipped, so its other statements happen before each jump instead.
+        let join_statements: Vec<_> = basic_blocks[self.join]
+            .statements
+            .iter()
+            .filter(|statement| {
+                !matches!(
+                    statement.kind,
+                    StatementKind::Assign(box (_, Rvalue::Discriminant(place)))
+                        if place.local == option
+                )
+            })
+            .cloned()
+            .collect();
+        let basic_blocks = body.basic_blocks.as_mut();
+        for (location, target) in uses.assignments.iter().zip(self.targets) {
+            let data = &mut basic_blocks[location.block];
+            data.statements.extend(join_statements.iter().cloned());
+            data.terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+
+        body.var_debug_info.retain(|info| {
+            !matches!(info.value, VarDebugInfoContents::Place(place) if place.local == option)
+        });
+    }
+}
@@ -487,6 +487,10 @@ passes_must_not_suspend =
     `must_not_suspend` attribute should be applied to a struct, enum, or trait
     .label = is not a struct, enum, or trait
 
+passes_must_not_suspend_malformed =
+    malformed `must_not_suspend` attribute
+    .label = expected `reason = "..."` or `label = "..."`
+
 passes_must_use_async =
     `must_use` attribute on `async` functions applies to the anonymous `Future` returned by the function, not the value within
     .label = this attribute does nothing, the `Future`s returned by async functions are already `must_use`
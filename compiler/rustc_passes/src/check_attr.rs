@@ -147,7 +147,7 @@ fn check_attributes(
                     self.check_rustc_std_internal_symbol(attr, span, target)
                 }
                 sym::naked => self.check_naked(hir_id, attr, span, target),
-                sym::rustc_never_returns_null_ptr => {
+                sym::rustc_never_returns_null_ptr | sym::rustc_exact_chunks => {
                     self.check_applied_to_fn_or_method(hir_id, attr, span, target)
                 }
                 sym::rustc_legacy_const_generics => {
@@ -1352,7 +1352,19 @@ fn check_must_use(&self, hir_id: HirId, attr: &Attribute, target: Target) -> boo
     /// Checks if `#[must_not_suspend]` is applied to a function. Returns `true` if valid.
     fn check_must_not_suspend(&self, attr: &Attribute, span: Span, target: Target) -> bool {
         match target {
-            Target::Struct | Target::Enum | Target::Union | Target::Trait => true,
+            Target::Struct | Target::Enum | Target::Union | Target::Trait => {
+                // `#[must_not_suspend(reason = "...", label = "...")]` only accepts these keys.
+                for item in attr.meta_item_list().unwrap_or_default() {
+                    if !(item.has_name(sym::reason) || item.has_name(sym::label))
+                        || item.value_str().is_none()
+                    {
+                        self.tcx
+                            .sess
+                            .emit_err(errors::MustNotSuspendMalformed { span: item.span() });
+                    }
+                }
+                true
+            }
             _ => {
                 self.tcx.sess.emit_err(errors::MustNotSuspend { attr_span: attr.span, span });
                 false
@@ -420,6 +420,14 @@ pub struct MustNotSuspend {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_must_not_suspend_malformed)]
+pub struct MustNotSuspendMalformed {
+    #[primary_span]
+    #[label]
+    pub span: Span,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(passes_cold)]
 #[warning]
@@ -392,6 +392,7 @@ mod desc {
     pub const parse_instrument_xray: &str = "either a boolean (`yes`, `no`, `on`, `off`, etc), or a comma separated list of settings: `always` or `never` (mutually exclusive), `ignore-loops`, `instruction-threshold=N`, `skip-entry`, `skip-exit`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
     pub const parse_treat_err_as_bug: &str = "either no value or a non-negative number";
+    pub const parse_mir_opt_timings: &str = "either no value or a number of functions";
     pub const parse_trait_solver: &str =
         "one of the supported solver modes (`classic`, `next`, or `next-coherence`)";
     pub const parse_lto: &str =
@@ -1009,6 +1010,19 @@ pub(crate) fn parse_instrument_xray(
         true
     }
 
+    pub(crate) fn parse_mir_opt_timings(slot: &mut Option<usize>, v: Option<&str>) -> bool {
+        match v {
+            Some(s) => {
+                *slot = s.parse().ok();
+                slot.is_some()
+            }
+            None => {
+                *slot = Some(10);
+                true
+            }
+        }
+    }
+
     pub(crate) fn parse_treat_err_as_bug(slot: &mut Option<NonZeroUsize>, v: Option<&str>) -> bool {
         match v {
             Some(s) => match s.parse() {
@@ -1566,6 +1580,10 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         `foo` matches all passes for functions whose name contains 'foo',
         `foo & ConstProp` only the 'ConstProp' pass for function names containing 'foo',
         `foo | bar` all passes for function names containing 'foo' or 'bar'."),
+    dump_mir_cfg: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "dump the control-flow graph of bodies before and after MIR passes as graphviz `.dot` \
+        files named `<fn>.<pass>.before.dot` and `<fn>.<pass>.after.dot`, without dumping the \
+        MIR itself. `val` selects passes and functions like for `-Zdump-mir`."),
     dump_mir_dataflow: bool = (false, parse_bool, [UNTRACKED],
         "in addition to `.mir` files, create graphviz `.dot` files with dataflow results \
         (default: no)"),
@@ -1598,6 +1616,10 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "emit a section containing stack size metadata (default: no)"),
     emit_thin_lto: bool = (true, parse_bool, [TRACKED],
         "emit the bc module with thin LTO info (default: yes)"),
+    emit_unsafety_json: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [TRACKED],
+        "write the operations of the crate which require `unsafe` as JSON, to a file in the given \
+        directory (default: the current directory)"),
     export_executable_symbols: bool = (false, parse_bool, [TRACKED],
         "export symbols from executables, as if they were dynamic libraries"),
     extra_const_ub_checks: bool = (false, parse_bool, [TRACKED],
@@ -1624,6 +1646,9 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
     graphviz_font: String = ("Courier, monospace".to_string(), parse_string, [UNTRACKED],
         "use the given `fontname` in graphviz output; can be overridden by setting \
         environment variable `RUSTC_GRAPHVIZ_FONT` (default: `Courier, monospace`)"),
+    group_unsafety_violations: bool = (false, parse_bool, [UNTRACKED],
+        "report all operations of the same kind which require `unsafe` in a function as a single \
+        error (default: no)"),
     hir_stats: bool = (false, parse_bool, [UNTRACKED],
         "print some statistics about AST and HIR (default: no)"),
     human_readable_cgu_names: bool = (false, parse_bool, [TRACKED],
@@ -1704,16 +1729,25 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         Passes that are not specified are enabled or disabled by other flags as usual."),
     mir_include_spans: bool = (false, parse_bool, [UNTRACKED],
         "use line numbers relative to the function in mir pretty printing"),
+    mir_inline_report: bool = (false, parse_bool, [UNTRACKED],
+        "print the decision of the MIR inliner for each call site to stderr, with the cost of the \
+        callee, the threshold and the reason (default: no)"),
     mir_keep_place_mention: bool = (false, parse_bool, [TRACKED],
         "keep place mention MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
     #[rustc_lint_opt_deny_field_access("use `Session::mir_opt_level` instead of this field")]
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
+    mir_opt_timings: Option<usize> = (None, parse_mir_opt_timings, [UNTRACKED],
+        "print the given number of functions which spent the most time in MIR passes, with the \
+        time taken by each pass (default if specified without a value: 10)"),
     move_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
         "the size at which the `large_assignments` lint starts to be emitted"),
     mutable_noalias: bool = (true, parse_bool, [TRACKED],
         "emit noalias metadata for mutable references (default: yes)"),
+    next_solver_normalization_fuel: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "the number of aliases the new trait solver normalizes eagerly when relating aliases, \
+        before deferring the rest to a nested goal (default: up to the recursion limit)"),
     nll_facts: bool = (false, parse_bool, [UNTRACKED],
         "dump facts from NLL analysis into side files (default: no)"),
     nll_facts_dir: String = ("nll-facts".to_string(), parse_string, [UNTRACKED],
@@ -1814,6 +1848,13 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
 written to standard error output)"),
     report_delayed_bugs: bool = (false, parse_bool, [TRACKED],
         "immediately print bugs registered with `span_delayed_bug` (default: no)"),
+    report_solver_depth: bool = (false, parse_bool, [UNTRACKED],
+        "report the maximum recursion depth reached by the new trait solver while proving the \
+        obligations of each item (default: no)"),
+    require_unsafe_blocks: bool = (false, parse_bool, [TRACKED],
+        "require `unsafe` blocks for the unsafe operations in `unsafe fn`s too, reporting them as \
+        errors instead of `unsafe_op_in_unsafe_fn` lints, in the MIR unsafety checker \
+        (default: no)"),
     sanitizer: SanitizerSet = (SanitizerSet::empty(), parse_sanitizers, [TRACKED],
         "use a sanitizer"),
     sanitizer_cfi_canonical_jump_tables: Option<bool> = (Some(true), parse_opt_bool, [TRACKED],
@@ -11,7 +11,7 @@
 use crate::{filesearch, lint};
 
 use rustc_data_structures::flock;
-use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexSet};
 use rustc_data_structures::jobserver::{self, Client};
 use rustc_data_structures::profiling::{SelfProfiler, SelfProfilerRef};
 use rustc_data_structures::sync::{
@@ -193,6 +193,11 @@ pub struct Session {
     /// drown everything else in noise.
     miri_unleashed_features: Lock<Vec<(Span, Option<Symbol>)>>,
 
+    /// The spans that an arithmetic overflow or unconditional panic lint was already emitted
+    /// at, along with the lint. The same assertion can be linted in more than one body, e.g. in
+    /// both the runtime and the CTFE MIR of a `const fn`, and we only want to report it once.
+    reported_assert_lints: Lock<FxHashSet<(Span, lint::LintId)>>,
+
     /// Architecture to use for interpreting asm!.
     pub asm_arch: Option<InlineAsmArch>,
 
@@ -251,6 +256,16 @@ pub fn miri_unleashed_feature(&self, span: Span, feature_gate: Option<Symbol>) {
         self.miri_unleashed_features.lock().push((span, feature_gate));
     }
 
+    /// Whether the assertion lint `lint` was already emitted at `span`.
+    pub fn assert_lint_reported(&self, span: Span, lint: lint::LintId) -> bool {
+        self.reported_assert_lints.lock().contains(&(span, lint))
+    }
+
+    /// Records that the assertion lint `lint` was emitted at `span`.
+    pub fn mark_assert_lint_reported(&self, span: Span, lint: lint::LintId) {
+        self.reported_assert_lints.lock().insert((span, lint));
+    }
+
     pub fn local_crate_source_file(&self) -> Option<PathBuf> {
         let path = self.io.input.opt_path()?;
         if self.should_prefer_remapped_for_codegen() {
@@ -1505,6 +1520,7 @@ pub fn build_session(
         driver_lint_caps,
         ctfe_backtrace,
         miri_unleashed_features: Lock::new(Default::default()),
+        reported_assert_lints: Lock::new(Default::default()),
         asm_arch,
         target_features: Default::default(),
         unstable_target_features: Default::default(),
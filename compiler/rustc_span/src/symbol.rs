@@ -503,6 +503,9 @@
         cfi,
         cfi_encoding,
         char,
+        checked_add,
+        checked_mul,
+        checked_sub,
         client,
         clippy,
         clobber_abi,
@@ -908,6 +911,7 @@
         iter_repeat,
         iterator,
         iterator_collect_fn,
+        iterator_sum_fn,
         kcfi,
         keyword,
         kind,
@@ -1201,6 +1205,7 @@
         pointer_like,
         poll,
         post_dash_lto: "post-lto",
+        pow,
         powerpc_target_feature,
         powf32,
         powf64,
@@ -1387,11 +1392,13 @@
         rustc_dummy,
         rustc_dump_env_program_clauses,
         rustc_dump_program_clauses,
+        rustc_dump_required_target_features,
         rustc_dump_user_args,
         rustc_dump_vtable,
         rustc_effective_visibility,
         rustc_error,
         rustc_evaluate_where_clauses,
+        rustc_exact_chunks,
         rustc_expected_cgu_reuse,
         rustc_has_incoherent_inherent_impls,
         rustc_hidden_type_of_opaques,
@@ -1553,6 +1560,9 @@
         slice,
         slice_from_raw_parts,
         slice_from_raw_parts_mut,
+        slice_get_unchecked,
+        slice_get_unchecked_mut,
+        slice_iter,
         slice_len_fn,
         slice_patterns,
         slicing_syntax,
@@ -1723,6 +1733,7 @@
         unsafe_block_in_unsafe_fn,
         unsafe_cell,
         unsafe_cell_raw_get,
+        unsafe_extern_blocks,
         unsafe_no_drop_flag,
         unsafe_pin_internals,
         unsize,
@@ -1758,6 +1769,9 @@
         variant_count,
         vec,
         vec_macro,
+        vec_new,
+        vec_push,
+        vec_with_capacity,
         version,
         vfp2,
         vis,
@@ -11,29 +11,77 @@
 //! * bidirectional-normalizes-to: If `A` and `B` are both projections, and both
 //!   may apply, then we can compute the "intersection" of both normalizes-to by
 //!   performing them together. This is used specifically to resolve ambiguities.
-use super::EvalCtxt;
+use super::{EvalCtxt, SolverMode};
 use rustc_infer::infer::DefineOpaqueTypes;
 use rustc_infer::traits::query::NoSolution;
-use rustc_middle::traits::solve::{Certainty, Goal, QueryResult};
-use rustc_middle::ty;
+use rustc_middle::traits::solve::{
+    AmbiguityCause, Certainty, Goal, MaybeCause, OverflowOrigin, QueryResult,
+};
+use rustc_middle::traits::Reveal;
+use rustc_middle::ty::{self, TypeVisitableExt};
 
 impl<'tcx> EvalCtxt<'_, 'tcx> {
     #[instrument(level = "debug", skip(self), ret)]
     pub(super) fn compute_alias_relate_goal(
         &mut self,
         goal: Goal<'tcx, (ty::Term<'tcx>, ty::Term<'tcx>, ty::AliasRelationDirection)>,
+    ) -> QueryResult<'tcx> {
+        let tcx = self.tcx();
+        let (lhs, rhs, _) = goal.predicate;
+        // If one of the aliases still refers to inference variables, an ambiguity most likely
+        // means that we need more type information, not that multiple impls apply.
+        let unresolved_alias = [lhs, rhs].into_iter().any(|term| {
+            term.to_alias_ty(tcx).is_some_and(|alias| alias.args.has_non_region_infer())
+        });
+
+        let mut response = self.normalize_and_relate(goal)?;
+        if unresolved_alias && response.value.certainty == Certainty::AMBIGUOUS {
+            response.value.certainty =
+                Certainty::Maybe(MaybeCause::Ambiguity(AmbiguityCause::UnresolvedAlias));
+        }
+        Ok(response)
+    }
+
+    fn normalize_and_relate(
+        &mut self,
+        goal: Goal<'tcx, (ty::Term<'tcx>, ty::Term<'tcx>, ty::AliasRelationDirection)>,
     ) -> QueryResult<'tcx> {
         let tcx = self.tcx();
         let Goal { param_env, predicate: (lhs, rhs, direction) } = goal;
 
-        let Some(lhs) = self.try_normalize_term(param_env, lhs)? else {
-            return self.evaluate_added_goals_and_make_canonical_response(Certainty::OVERFLOW);
+        // Tag the overflow so that we can report the aliases we failed to relate.
+        let overflow = Certainty::Maybe(MaybeCause::Overflow(OverflowOrigin::AliasRelate));
+        // With `-Znext-solver-normalization-fuel`, only that many aliases are normalized by this
+        // goal. At least one is, so that the deferred goal below makes progress.
+        let mut fuel =
+            tcx.sess.opts.unstable_opts.next_solver_normalization_fuel.map(|fuel| fuel.max(1));
+        let Some(lhs) = self.try_normalize_term(param_env, &mut fuel, lhs)? else {
+            return self.evaluate_added_goals_and_make_canonical_response(overflow);
+        };
+
+        let Some(rhs) = self.try_normalize_term(param_env, &mut fuel, rhs)? else {
+            return self.evaluate_added_goals_and_make_canonical_response(overflow);
+        };
+
+        let Some(lhs) = self.try_normalize_self_ty(param_env, &mut fuel, lhs)? else {
+            return self.evaluate_added_goals_and_make_canonical_response(overflow);
         };
 
-        let Some(rhs) = self.try_normalize_term(param_env, rhs)? else {
-            return self.evaluate_added_goals_and_make_canonical_response(Certainty::OVERFLOW);
+        let Some(rhs) = self.try_normalize_self_ty(param_env, &mut fuel, rhs)? else {
+            return self.evaluate_added_goals_and_make_canonical_response(overflow);
         };
 
+        // Once the fuel runs out, the remaining aliases may still be normalizable, so we relate
+        // them in a nested goal instead, which gets its own fuel.
+        if fuel == Some(0) && (lhs.to_alias_ty(tcx).is_some() || rhs.to_alias_ty(tcx).is_some()) {
+            self.add_goal(Goal::new(
+                tcx,
+                param_env,
+                ty::Binder::dummy(ty::PredicateKind::AliasRelate(lhs, rhs, direction)),
+            ));
+            return self.evaluate_added_goals_and_make_canonical_response(Certainty::Yes);
+        }
+
         let variance = match direction {
             ty::AliasRelationDirection::Equate => ty::Variance::Invariant,
             ty::AliasRelationDirection::Subtype => ty::Variance::Covariant,
@@ -49,7 +97,7 @@ pub(super) fn compute_alias_relate_goal(
                 if rhs.is_infer() {
                     self.relate(param_env, lhs, variance, rhs)?;
                     self.evaluate_added_goals_and_make_canonical_response(Certainty::Yes)
-                } else if alias.is_opaque(tcx) {
+                } else if self.is_definable_opaque(param_env, alias) {
                     self.define_opaque(param_env, alias, rhs)
                 } else {
                     Err(NoSolution)
@@ -59,7 +107,7 @@ pub(super) fn compute_alias_relate_goal(
                 if lhs.is_infer() {
                     self.relate(param_env, lhs, variance, rhs)?;
                     self.evaluate_added_goals_and_make_canonical_response(Certainty::Yes)
-                } else if alias.is_opaque(tcx) {
+                } else if self.is_definable_opaque(param_env, alias) {
                     self.define_opaque(param_env, alias, lhs)
                 } else {
                     Err(NoSolution)
@@ -73,36 +121,116 @@ pub(super) fn compute_alias_relate_goal(
     }
 
     /// Normalize the `term` to equate it later. This does not define opaque types.
-    #[instrument(level = "debug", skip(self, param_env), ret)]
+    #[instrument(level = "debug", skip(self, param_env, fuel), ret)]
     fn try_normalize_term(
         &mut self,
         param_env: ty::ParamEnv<'tcx>,
+        fuel: &mut Option<usize>,
         term: ty::Term<'tcx>,
     ) -> Result<Option<ty::Term<'tcx>>, NoSolution> {
         match term.unpack() {
             ty::TermKind::Ty(ty) => {
                 // We do no define opaque types here but instead do so in `relate_rigid_alias_or_opaque`.
                 Ok(self
-                    .try_normalize_ty_recur(param_env, DefineOpaqueTypes::No, 0, ty)
+                    .try_normalize_ty_recur(param_env, DefineOpaqueTypes::No, 0, fuel, ty)
                     .map(Into::into))
             }
             ty::TermKind::Const(_) => {
-                if let Some(alias) = term.to_alias_ty(self.tcx()) {
-                    let term = self.next_term_infer_of_kind(term);
-                    self.add_goal(Goal::new(
-                        self.tcx(),
+                let Some(alias) = term.to_alias_ty(self.tcx()) else {
+                    return Ok(Some(term));
+                };
+                if *fuel == Some(0) {
+                    return Ok(Some(term));
+                }
+
+                // As with types, a const alias which depends on unresolved inference variables
+                // is replaced by an inference variable, and its `normalizes-to` goal stays
+                // ambiguous until they are resolved. A const alias which can't be normalized
+                // is rigid, and only related with other aliases by its arguments.
+                match self.commit_if_ok(|this| {
+                    let normalized = this.next_term_infer_of_kind(term);
+                    this.add_goal(Goal::new(
+                        this.tcx(),
                         param_env,
-                        ty::ProjectionPredicate { projection_ty: alias, term },
+                        ty::ProjectionPredicate { projection_ty: alias, term: normalized },
                     ));
-                    self.try_evaluate_added_goals()?;
-                    Ok(Some(self.resolve_vars_if_possible(term)))
-                } else {
-                    Ok(Some(term))
+                    this.try_evaluate_added_goals()?;
+                    Ok(this.resolve_vars_if_possible(normalized))
+                }) {
+                    Ok(normalized) => {
+                        if let Some(fuel) = fuel {
+                            *fuel -= 1;
+                        }
+                        Ok(Some(normalized))
+                    }
+                    Err(NoSolution) => Ok(Some(term)),
                 }
             }
         }
     }
 
+    /// Normalize the self type of a projection which could not be normalized itself, e.g.
+    /// `<<U as Id<T>>::Id as Unnormalizable>::Assoc` to `<U as Unnormalizable>::Assoc`.
+    ///
+    /// Such a projection is rigid because of a where-bound on its self type, like
+    /// `where <U as Id<T>>::Id: Unnormalizable`, and its value only depends on the normalized
+    /// self type. The inner alias may mention inference variables that the normalized one
+    /// doesn't, which would otherwise prevent us from relating it with these variables.
+    #[instrument(level = "debug", skip(self, param_env, fuel), ret)]
+    fn try_normalize_self_ty(
+        &mut self,
+        param_env: ty::ParamEnv<'tcx>,
+        fuel: &mut Option<usize>,
+        term: ty::Term<'tcx>,
+    ) -> Result<Option<ty::Term<'tcx>>, NoSolution> {
+        let tcx = self.tcx();
+        let Some(ty) = term.ty() else {
+            return Ok(Some(term));
+        };
+        let ty::Alias(ty::Projection, alias) = *ty.kind() else {
+            return Ok(Some(term));
+        };
+        let self_ty = alias.self_ty();
+        if !matches!(self_ty.kind(), ty::Alias(ty::Projection, _)) {
+            return Ok(Some(term));
+        }
+        let Some(normalized_self_ty) =
+            self.try_normalize_ty_recur(param_env, DefineOpaqueTypes::No, 0, fuel, self_ty)
+        else {
+            return Ok(None);
+        };
+        if normalized_self_ty == self_ty {
+            return Ok(Some(term));
+        }
+
+        // With its new self type, the projection may now be normalizable.
+        let alias = alias.with_self_ty(tcx, normalized_self_ty);
+        self.try_normalize_term(param_env, fuel, alias.to_ty(tcx).into())
+    }
+
+    /// Whether `alias` is an opaque type whose hidden type may be constrained by this goal.
+    ///
+    /// The opaques of a different inference scope, like those of another function, are rigid
+    /// here: they are only equal to themselves, which relating their arguments handles. This
+    /// matches the old solver, which only registers hidden types in the defining scope.
+    fn is_definable_opaque(
+        &self,
+        param_env: ty::ParamEnv<'tcx>,
+        alias: ty::AliasTy<'tcx>,
+    ) -> bool {
+        if !alias.is_opaque(self.tcx()) {
+            return false;
+        }
+        match (param_env.reveal(), self.solver_mode()) {
+            (Reveal::UserFacing, SolverMode::Normal) => {
+                alias.def_id.as_local().is_some_and(|def_id| self.can_define_opaque_ty(def_id))
+            }
+            // Coherence only checks the item bounds of the opaque, and revealing uses its
+            // actual hidden type, which don't depend on the scope.
+            (Reveal::UserFacing, SolverMode::Coherence) | (Reveal::All, _) => true,
+        }
+    }
+
     fn define_opaque(
         &mut self,
         param_env: ty::ParamEnv<'tcx>,
@@ -126,14 +254,14 @@ fn relate_rigid_alias_or_opaque(
     ) -> QueryResult<'tcx> {
         let tcx = self.tcx();
         let mut candidates = vec![];
-        if lhs.is_opaque(tcx) {
+        if self.is_definable_opaque(param_env, lhs) {
             candidates.extend(
                 self.probe_misc_candidate("define-lhs-opaque")
                     .enter(|ecx| ecx.define_opaque(param_env, lhs, rhs.to_ty(tcx).into())),
             );
         }
 
-        if rhs.is_opaque(tcx) {
+        if self.is_definable_opaque(param_env, rhs) {
             candidates.extend(
                 self.probe_misc_candidate("define-rhs-opaque")
                     .enter(|ecx| ecx.define_opaque(param_env, rhs, lhs.to_ty(tcx).into())),
@@ -2,6 +2,7 @@
 
 use super::{EvalCtxt, SolverMode};
 use crate::traits::coherence;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::def_id::DefId;
 use rustc_infer::traits::query::NoSolution;
 use rustc_infer::traits::Reveal;
@@ -11,11 +12,12 @@
 };
 use rustc_middle::traits::BuiltinImplSource;
 use rustc_middle::ty::fast_reject::{SimplifiedType, TreatParams};
-use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_middle::ty::{self, GenericArgKind, Ty, TyCtxt};
 use rustc_middle::ty::{fast_reject, TypeFoldable};
 use rustc_middle::ty::{ToPredicate, TypeVisitableExt};
 use rustc_span::ErrorGuaranteed;
 use std::fmt::Debug;
+use std::iter;
 
 pub(super) mod structural_traits;
 
@@ -254,6 +256,63 @@ fn consider_unsize_to_dyn_candidate(
     ) -> QueryResult<'tcx>;
 }
 
+/// Returns the impl of `trait_def_id` which applies to every self type and trait arguments, like
+/// `impl<T, U> Id<T> for U`, if there is one which no other impl can overlap with.
+///
+/// That's a positive impl whose trait arguments are distinct generic parameters, without any
+/// where-clauses besides the implicit `Sized` bounds, which no impl specializes, of a trait which
+/// isn't `#[marker]`. Coherence then makes sure that no other impl, positive or negative, applies
+/// where this one does.
+fn universal_impl(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Option<DefId> {
+    if tcx.trait_def(trait_def_id).is_marker {
+        return None;
+    }
+
+    let sized_trait = tcx.lang_items().sized_trait();
+    let blanket_impls = tcx.trait_impls_of(trait_def_id).blanket_impls();
+    let &impl_def_id = blanket_impls.iter().find(|&&impl_def_id| {
+        if tcx.impl_polarity(impl_def_id) != ty::ImplPolarity::Positive {
+            return false;
+        }
+        let mut params = FxHashSet::default();
+        let args = tcx.impl_trait_ref(impl_def_id).unwrap().instantiate_identity().args;
+        let distinct_params = args.iter().all(|arg| {
+            let index = match arg.unpack() {
+                GenericArgKind::Type(ty) => match *ty.kind() {
+                    ty::Param(param) => param.index,
+                    _ => return false,
+                },
+                GenericArgKind::Lifetime(region) => match *region {
+                    ty::ReEarlyParam(param) => param.index,
+                    _ => return false,
+                },
+                GenericArgKind::Const(ct) => match ct.kind() {
+                    ty::ConstKind::Param(param) => param.index,
+                    _ => return false,
+                },
+            };
+            params.insert(index)
+        });
+        distinct_params
+            && tcx.predicates_of(impl_def_id).predicates.iter().all(|(clause, _)| {
+                matches!(
+                    clause.kind().skip_binder(),
+                    ty::ClauseKind::Trait(pred)
+                        if Some(pred.def_id()) == sized_trait
+                            && pred.polarity == ty::ImplPolarity::Positive
+                            && matches!(pred.self_ty().kind(), ty::Param(_))
+                )
+            })
+    })?;
+
+    // Specializing impls overlap with the impl they specialize.
+    let graph = tcx.specialization_graph_of(trait_def_id).ok()?;
+    let specialized = graph.children.get(&impl_def_id).is_some_and(|children| {
+        !children.non_blanket_impls.is_empty() || !children.blanket_impls.is_empty()
+    });
+    (graph.has_errored.is_none() && !specialized).then_some(impl_def_id)
+}
+
 impl<'tcx> EvalCtxt<'_, 'tcx> {
     pub(super) fn assemble_and_evaluate_candidates<G: GoalKind<'tcx>>(
         &mut self,
@@ -391,6 +450,11 @@ fn assemble_non_blanket_impl_candidates<G: GoalKind<'tcx>>(
         goal: Goal<'tcx, G>,
         candidates: &mut Vec<Candidate<'tcx>>,
     ) {
+        // The impls for a specific self type can't apply where the universal impl does.
+        if self.applicable_universal_impl(goal).is_some() {
+            return;
+        }
+
         let tcx = self.tcx();
         let self_ty = goal.predicate.self_ty();
         let trait_impls = tcx.trait_impls_of(goal.predicate.trait_def_id(tcx));
@@ -516,7 +580,13 @@ fn assemble_blanket_impl_candidates<G: GoalKind<'tcx>>(
     ) {
         let tcx = self.tcx();
         let trait_impls = tcx.trait_impls_of(goal.predicate.trait_def_id(tcx));
-        for &impl_def_id in trait_impls.blanket_impls() {
+        // The other blanket impls can't apply where the universal impl does either.
+        let universal_impl = self.applicable_universal_impl(goal);
+        let blanket_impls = match &universal_impl {
+            Some(impl_def_id) => std::slice::from_ref(impl_def_id),
+            None => trait_impls.blanket_impls(),
+        };
+        for &impl_def_id in blanket_impls {
             match G::consider_impl_candidate(self, goal, impl_def_id) {
                 Ok(candidate) => candidates.push(candidate),
                 Err(NoSolution) => (),
@@ -524,6 +594,34 @@ fn assemble_blanket_impl_candidates<G: GoalKind<'tcx>>(
         }
     }
 
+    /// Returns the [universal impl][universal_impl] of the trait of `goal`, if it certainly
+    /// applies to `goal`. This is the case when the arguments of the goal which the impl requires
+    /// to be `Sized` are trivially sized.
+    ///
+    /// No other impl can apply to the goal then, so there is no need to consider them.
+    fn applicable_universal_impl<G: GoalKind<'tcx>>(&self, goal: Goal<'tcx, G>) -> Option<DefId> {
+        // Building the specialization graph needed by `universal_impl` checks the impls for
+        // overlap, which is done in coherence mode.
+        if self.solver_mode() == SolverMode::Coherence {
+            return None;
+        }
+
+        let tcx = self.tcx();
+        let impl_def_id = universal_impl(tcx, goal.predicate.trait_def_id(tcx))?;
+        let impl_args = tcx.impl_trait_ref(impl_def_id).unwrap().instantiate_identity().args;
+        let goal_args = goal.predicate.trait_ref(tcx).args;
+        let sized = tcx.predicates_of(impl_def_id).predicates.iter().all(|(clause, _)| {
+            let Some(ty::ClauseKind::Trait(pred)) = clause.kind().no_bound_vars() else {
+                return false;
+            };
+            let param = ty::GenericArg::from(pred.self_ty());
+            iter::zip(impl_args.iter(), goal_args.iter()).any(|(impl_arg, goal_arg)| {
+                impl_arg == param && goal_arg.expect_ty().is_trivially_sized(tcx)
+            })
+        });
+        sized.then_some(impl_def_id)
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn assemble_builtin_impl_candidates<G: GoalKind<'tcx>>(
         &mut self,
@@ -94,7 +94,7 @@ pub(in crate::solve) fn evaluate_added_goals_and_make_canonical_response(
         );
 
         let certainty = certainty.unify_with(goals_certainty);
-        if let Certainty::OVERFLOW = certainty {
+        if let Certainty::Maybe(maybe_cause @ MaybeCause::Overflow(_)) = certainty {
             // If we have overflow, it's probable that we're substituting a type
             // into itself infinitely and any partial substitutions in the query
             // response are probably not useful anyways, so just return an empty
@@ -106,7 +106,7 @@ pub(in crate::solve) fn evaluate_added_goals_and_make_canonical_response(
             //
             // Changing this to retain some constraints in the future
             // won't be a breaking change, so this is good enough for now.
-            return Ok(self.make_ambiguous_response_no_constraints(MaybeCause::Overflow));
+            return Ok(self.make_ambiguous_response_no_constraints(maybe_cause));
         }
 
         let var_values = self.var_values;
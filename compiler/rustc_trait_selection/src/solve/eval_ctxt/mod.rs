@@ -143,6 +143,19 @@ fn evaluate_root_goal(
         Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution>,
         Option<inspect::GoalEvaluation<'tcx>>,
     );
+
+    /// Like [`InferCtxtEvalExt::evaluate_root_goal`], but also returns the
+    /// maximum recursion depth reached while solving the goal, which is the
+    /// smallest recursion limit with which it does not overflow.
+    fn evaluate_root_goal_with_depth(
+        &self,
+        goal: Goal<'tcx, ty::Predicate<'tcx>>,
+        generate_proof_tree: GenerateProofTree,
+    ) -> (
+        Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution>,
+        Option<inspect::GoalEvaluation<'tcx>>,
+        usize,
+    );
 }
 
 impl<'tcx> InferCtxtEvalExt<'tcx> for InferCtxt<'tcx> {
@@ -154,6 +167,20 @@ fn evaluate_root_goal(
     ) -> (
         Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution>,
         Option<inspect::GoalEvaluation<'tcx>>,
+    ) {
+        let (result, tree, _) = self.evaluate_root_goal_with_depth(goal, generate_proof_tree);
+        (result, tree)
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    fn evaluate_root_goal_with_depth(
+        &self,
+        goal: Goal<'tcx, ty::Predicate<'tcx>>,
+        generate_proof_tree: GenerateProofTree,
+    ) -> (
+        Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution>,
+        Option<inspect::GoalEvaluation<'tcx>>,
+        usize,
     ) {
         EvalCtxt::enter_root(self, generate_proof_tree, |ecx| {
             ecx.evaluate_goal(GoalEvaluationKind::Root, goal)
@@ -173,11 +200,13 @@ pub(super) fn local_overflow_limit(&self) -> usize {
     /// Creates a root evaluation context and search graph. This should only be
     /// used from outside of any evaluation, and other methods should be preferred
     /// over using this manually (such as [`InferCtxtEvalExt::evaluate_root_goal`]).
+    ///
+    /// Also returns the maximum recursion depth reached by the search graph.
     fn enter_root<R>(
         infcx: &InferCtxt<'tcx>,
         generate_proof_tree: GenerateProofTree,
         f: impl FnOnce(&mut EvalCtxt<'_, 'tcx>) -> R,
-    ) -> (R, Option<inspect::GoalEvaluation<'tcx>>) {
+    ) -> (R, Option<inspect::GoalEvaluation<'tcx>>, usize) {
         let mode = if infcx.intercrate { SolverMode::Coherence } else { SolverMode::Normal };
         let mut search_graph = search_graph::SearchGraph::new(infcx.tcx, mode);
 
@@ -214,7 +243,7 @@ fn enter_root<R>(
         );
 
         assert!(search_graph.is_empty());
-        (result, tree)
+        (result, tree, search_graph.reached_depth())
     }
 
     /// Creates a nested evaluation context that shares the same search graph as the
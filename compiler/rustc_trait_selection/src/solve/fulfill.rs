@@ -1,7 +1,9 @@
 use std::mem;
 
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::LocalDefId;
 use rustc_infer::infer::InferCtxt;
-use rustc_infer::traits::solve::MaybeCause;
+use rustc_infer::traits::solve::{Goal, MaybeCause};
 use rustc_infer::traits::Obligation;
 use rustc_infer::traits::{
     query::NoSolution, FulfillmentError, FulfillmentErrorCode, MismatchedProjectionTypes,
@@ -27,6 +29,21 @@
 pub struct FulfillmentCtxt<'tcx> {
     obligations: Vec<PredicateObligation<'tcx>>,
 
+    /// The `AliasRelate` goals which were registered in this context, with their inference
+    /// variables resolved at the time of registration.
+    ///
+    /// Equating the same pair of aliases again does not tell us anything new, so we skip
+    /// such goals, similar to the `done_cache` of the old solver's obligation forest. This
+    /// set belongs to this context rather than to the `InferCtxt`: other contexts using the
+    /// same inference context still evaluate their own goals.
+    ///
+    /// An entry can't cause us to skip a goal which would tell us something new. A goal only
+    /// matches an entry if its resolved form is the same, so its inference variables are
+    /// still unconstrained. As this context is only used in a single snapshot, this means
+    /// that the earlier goal is either still pending, or held without constraining them. If
+    /// it failed, we remove its entry, so that later goals are evaluated and reported again.
+    registered_alias_relate: FxHashSet<Goal<'tcx, ty::Predicate<'tcx>>>,
+
     /// The snapshot in which this context was created. Using the context
     /// outside of this snapshot leads to subtle bugs if the snapshot
     /// gets rolled back. Because of this we explicitly check that we only
@@ -36,7 +53,53 @@ pub struct FulfillmentCtxt<'tcx> {
 
 impl<'tcx> FulfillmentCtxt<'tcx> {
     pub fn new(infcx: &InferCtxt<'tcx>) -> FulfillmentCtxt<'tcx> {
-        FulfillmentCtxt { obligations: Vec::new(), usable_in_snapshot: infcx.num_open_snapshots() }
+        FulfillmentCtxt {
+            obligations: Vec::new(),
+            registered_alias_relate: Default::default(),
+            usable_in_snapshot: infcx.num_open_snapshots(),
+        }
+    }
+
+    /// Adds a new obligation, unless it is an `AliasRelate` goal which was already registered.
+    fn push_obligation(&mut self, infcx: &InferCtxt<'tcx>, obligation: PredicateObligation<'tcx>) {
+        if let ty::PredicateKind::AliasRelate(..) = obligation.predicate.kind().skip_binder() {
+            let predicate = infcx.resolve_vars_if_possible(obligation.predicate);
+            if !self.registered_alias_relate.insert(Goal::new(
+                infcx.tcx,
+                obligation.param_env,
+                predicate,
+            )) {
+                debug!(?predicate, "skipping already registered alias-relate goal");
+                return;
+            }
+        }
+        self.obligations.push(obligation);
+    }
+
+    /// Evaluates `goal`, recording the depth reached by the solver for `body_id` with
+    /// `-Zreport-solver-depth`. A failed `AliasRelate` goal is forgotten, so that it is
+    /// evaluated and reported again if it is registered later.
+    fn evaluate_root_goal(
+        &mut self,
+        infcx: &InferCtxt<'tcx>,
+        goal: Goal<'tcx, ty::Predicate<'tcx>>,
+        body_id: LocalDefId,
+    ) -> Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution> {
+        let (result, _, depth) =
+            infcx.evaluate_root_goal_with_depth(goal, GenerateProofTree::IfEnabled);
+        if infcx.tcx.sess.opts.unstable_opts.report_solver_depth {
+            // Keep the maximum over all obligations of the item.
+            let mut solver_depths = infcx.tcx.solver_depths.lock();
+            let reached_depth = solver_depths.entry(body_id).or_default();
+            *reached_depth = (*reached_depth).max(depth);
+        }
+        if result.is_err()
+            && let ty::PredicateKind::AliasRelate(..) = goal.predicate.kind().skip_binder()
+        {
+            let predicate = infcx.resolve_vars_if_possible(goal.predicate);
+            self.registered_alias_relate.remove(&goal.with(infcx.tcx, predicate));
+        }
+        result
     }
 }
 
@@ -48,10 +111,11 @@ fn register_predicate_obligation(
         obligation: PredicateObligation<'tcx>,
     ) {
         assert_eq!(self.usable_in_snapshot, infcx.num_open_snapshots());
-        self.obligations.push(obligation);
+        self.push_obligation(infcx, obligation);
     }
 
     fn collect_remaining_errors(&mut self, infcx: &InferCtxt<'tcx>) -> Vec<FulfillmentError<'tcx>> {
+        self.registered_alias_relate.clear();
         self.obligations
             .drain(..)
             .map(|obligation| {
@@ -60,11 +124,17 @@ fn collect_remaining_errors(&mut self, infcx: &InferCtxt<'tcx>) -> Vec<Fulfillme
                         .evaluate_root_goal(obligation.clone().into(), GenerateProofTree::IfEnabled)
                         .0
                     {
-                        Ok((_, Certainty::Maybe(MaybeCause::Ambiguity), _)) => {
-                            FulfillmentErrorCode::CodeAmbiguity { overflow: false }
+                        Ok((_, Certainty::Maybe(MaybeCause::Ambiguity(cause)), _)) => {
+                            FulfillmentErrorCode::CodeAmbiguity {
+                                overflow: None,
+                                ambiguity: Some(cause),
+                            }
                         }
-                        Ok((_, Certainty::Maybe(MaybeCause::Overflow), _)) => {
-                            FulfillmentErrorCode::CodeAmbiguity { overflow: true }
+                        Ok((_, Certainty::Maybe(MaybeCause::Overflow(origin)), _)) => {
+                            FulfillmentErrorCode::CodeAmbiguity {
+                                overflow: Some(origin),
+                                ambiguity: None,
+                            }
                         }
                         Ok((_, Certainty::Yes, _)) => {
                             bug!("did not expect successful goal when collecting ambiguity errors")
@@ -96,7 +166,7 @@ fn select_where_possible(&mut self, infcx: &InferCtxt<'tcx>) -> Vec<FulfillmentE
             for obligation in mem::take(&mut self.obligations) {
                 let goal = obligation.clone().into();
                 let (changed, certainty, nested_goals) =
-                    match infcx.evaluate_root_goal(goal, GenerateProofTree::IfEnabled).0 {
+                    match self.evaluate_root_goal(infcx, goal, obligation.cause.body_id) {
                         Ok(result) => result,
                         Err(NoSolution) => {
                             errors.push(FulfillmentError {
@@ -151,14 +221,17 @@ fn select_where_possible(&mut self, infcx: &InferCtxt<'tcx>) -> Vec<FulfillmentE
                     };
                 // Push any nested goals that we get from unifying our canonical response
                 // with our obligation onto the fulfillment context.
-                self.obligations.extend(nested_goals.into_iter().map(|goal| {
-                    Obligation::new(
-                        infcx.tcx,
-                        obligation.cause.clone(),
-                        goal.param_env,
-                        goal.predicate,
-                    )
-                }));
+                for goal in nested_goals {
+                    self.push_obligation(
+                        infcx,
+                        Obligation::new(
+                            infcx.tcx,
+                            obligation.cause.clone(),
+                            goal.param_env,
+                            goal.predicate,
+                        ),
+                    );
+                }
                 has_changed |= changed;
                 match certainty {
                     Certainty::Yes => {}
@@ -182,6 +255,8 @@ fn drain_unstalled_obligations(
         &mut self,
         _: &InferCtxt<'tcx>,
     ) -> Vec<PredicateObligation<'tcx>> {
+        // The drained goals are no longer evaluated by this context.
+        self.registered_alias_relate.clear();
         std::mem::take(&mut self.obligations)
     }
 }
@@ -296,14 +296,17 @@ fn try_normalize_ty(
         param_env: ty::ParamEnv<'tcx>,
         ty: Ty<'tcx>,
     ) -> Option<Ty<'tcx>> {
-        self.try_normalize_ty_recur(param_env, DefineOpaqueTypes::Yes, 0, ty)
+        self.try_normalize_ty_recur(param_env, DefineOpaqueTypes::Yes, 0, &mut None, ty)
     }
 
+    /// If `fuel` is set, it is the number of aliases we may still normalize. Once it runs out,
+    /// the remaining alias is returned as is, even if it could be normalized further.
     fn try_normalize_ty_recur(
         &mut self,
         param_env: ty::ParamEnv<'tcx>,
         define_opaque_types: DefineOpaqueTypes,
         depth: usize,
+        fuel: &mut Option<usize>,
         ty: Ty<'tcx>,
     ) -> Option<Ty<'tcx>> {
         if !self.tcx().recursion_limit().value_within_limit(depth) {
@@ -313,6 +316,9 @@ fn try_normalize_ty_recur(
         let ty::Alias(kind, projection_ty) = *ty.kind() else {
             return Some(ty);
         };
+        if *fuel == Some(0) {
+            return Some(ty);
+        }
 
         // We do no always define opaque types eagerly to allow non-defining uses in the defining scope.
         if let (DefineOpaqueTypes::No, ty::AliasKind::Opaque) = (define_opaque_types, kind) {
@@ -339,8 +345,11 @@ fn try_normalize_ty_recur(
             );
             this.add_goal(normalizes_to_goal);
             this.try_evaluate_added_goals()?;
+            if let Some(fuel) = fuel {
+                *fuel -= 1;
+            }
             let ty = this.resolve_vars_if_possible(normalized_ty);
-            Ok(this.try_normalize_ty_recur(param_env, define_opaque_types, depth + 1, ty))
+            Ok(this.try_normalize_ty_recur(param_env, define_opaque_types, depth + 1, fuel, ty))
         }) {
             Ok(ty) => ty,
             Err(NoSolution) => Some(ty),
@@ -0,0 +1,145 @@
+//! A fast path for normalizing projections of blanket impls which return one of their generic
+//! parameters, like `<U as Id<T>>::Id` with
+//! ```ignore (illustrative)
+//! impl<T, U> Id<T> for U {
+//!     type Id = U;
+//! }
+//! ```
+//! These are often nested deeply, so we avoid assembling candidates for them when the impl is
+//! the only way to normalize them, and take the generic argument directly from the projection.
+//! Whenever anything but the impl could apply, or its where-clauses could fail to hold, we
+//! return `None` and normalize the projection as usual.
+use rustc_hir::def_id::DefId;
+use rustc_middle::traits::solve::{Certainty, Goal, QueryResult};
+use rustc_middle::ty::{self, TyCtxt, TypeVisitableExt};
+
+use super::EvalCtxt;
+use crate::solve::SolverMode;
+
+impl<'tcx> EvalCtxt<'_, 'tcx> {
+    pub(super) fn try_normalize_identity_projection(
+        &mut self,
+        goal: Goal<'tcx, ty::ProjectionPredicate<'tcx>>,
+    ) -> Option<QueryResult<'tcx>> {
+        let tcx = self.tcx();
+        let projection_ty = goal.predicate.projection_ty;
+        // During coherence, impls in other crates could apply as well.
+        if self.solver_mode() == SolverMode::Coherence
+            || projection_ty.references_error()
+            || !matches!(
+                projection_ty.self_ty().kind(),
+                ty::Bool
+                    | ty::Char
+                    | ty::Int(_)
+                    | ty::Uint(_)
+                    | ty::Float(_)
+                    | ty::Adt(..)
+                    | ty::Str
+                    | ty::Array(..)
+                    | ty::Slice(_)
+                    | ty::RawPtr(_)
+                    | ty::Ref(..)
+                    | ty::FnDef(..)
+                    | ty::FnPtr(_)
+                    | ty::Never
+                    | ty::Tuple(_)
+                    | ty::Param(_)
+            )
+        {
+            return None;
+        }
+
+        // Where-clauses in the environment are preferred over impls.
+        if goal.param_env.caller_bounds().iter().any(|clause| {
+            clause
+                .as_projection_clause()
+                .is_some_and(|projection| projection.projection_def_id() == projection_ty.def_id)
+        }) {
+            return None;
+        }
+
+        let item_def_id = projection_ty.def_id;
+        if !tcx.generics_of(item_def_id).params.is_empty()
+            || !tcx.predicates_of(item_def_id).predicates.is_empty()
+        {
+            return None;
+        }
+
+        let trait_def_id = projection_ty.trait_def_id(tcx);
+        let impls = tcx.trait_impls_of(trait_def_id);
+        let &[impl_def_id] = impls.blanket_impls() else { return None };
+        if !impls.non_blanket_impls().is_empty()
+            || tcx.lang_items().iter().any(|(_, def_id)| def_id == trait_def_id)
+            || tcx.impl_polarity(impl_def_id) != ty::ImplPolarity::Positive
+        {
+            return None;
+        }
+
+        let &impl_item_def_id = tcx.impl_item_implementor_ids(impl_def_id).get(&item_def_id)?;
+        if !tcx.defaultness(impl_item_def_id).is_final() {
+            return None;
+        }
+        let ty::Param(value) = *tcx.type_of(impl_item_def_id).instantiate_identity().kind() else {
+            return None;
+        };
+
+        let impl_args = impl_args_from_trait_args(tcx, impl_def_id, projection_ty.args)?;
+        for clause in tcx.predicates_of(impl_def_id).instantiate(tcx, impl_args).predicates {
+            let Some(trait_clause) = clause.as_trait_clause() else { return None };
+            let trait_clause = trait_clause.no_bound_vars()?;
+            // Generic parameters are usually known to be `Sized` from the environment.
+            if !tcx.lang_items().sized_trait().is_some_and(|sized| trait_clause.def_id() == sized)
+                || !(trait_clause.self_ty().is_trivially_sized(tcx)
+                    || goal.param_env.caller_bounds().contains(&clause))
+            {
+                return None;
+            }
+        }
+
+        let expected = goal.predicate.term.ty().expect("associated types can't be consts");
+        let actual = impl_args.type_at(value.index as usize);
+        Some(self.probe_misc_candidate("identity projection").enter(|ecx| {
+            ecx.eq(goal.param_env, expected, actual)?;
+            ecx.evaluate_added_goals_and_make_canonical_response(Certainty::Yes)
+        }))
+    }
+}
+
+/// If every generic parameter of the impl is used exactly once as an argument of the trait it
+/// implements, like in `impl<T, U> Trait<T> for U`, the impl applies to every instance of the
+/// trait, and this returns the arguments of the impl matching the arguments `trait_args` of an
+/// instance.
+fn impl_args_from_trait_args<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    impl_def_id: DefId,
+    trait_args: ty::GenericArgsRef<'tcx>,
+) -> Option<ty::GenericArgsRef<'tcx>> {
+    let generics = tcx.generics_of(impl_def_id);
+    let impl_trait_ref = tcx.impl_trait_ref(impl_def_id)?.instantiate_identity();
+    // Only take the arguments of the trait itself, the projection may have its own ones.
+    let trait_args = &trait_args[..impl_trait_ref.args.len()];
+    let mut impl_args = vec![None; generics.count()];
+    for (impl_trait_arg, &trait_arg) in std::iter::zip(impl_trait_ref.args, trait_args) {
+        let index = match impl_trait_arg.unpack() {
+            ty::GenericArgKind::Type(ty) => match *ty.kind() {
+                ty::Param(param) => param.index,
+                _ => return None,
+            },
+            ty::GenericArgKind::Lifetime(region) => match *region {
+                ty::ReEarlyParam(param) => param.index,
+                _ => return None,
+            },
+            ty::GenericArgKind::Const(ct) => match ct.kind() {
+                ty::ConstKind::Param(param) => param.index,
+                _ => return None,
+            },
+        };
+        let impl_arg = &mut impl_args[index as usize];
+        if impl_arg.is_some() {
+            return None;
+        }
+        *impl_arg = Some(trait_arg);
+    }
+    let impl_args: Option<Vec<_>> = impl_args.into_iter().collect();
+    Some(tcx.mk_args(&impl_args?))
+}
@@ -18,7 +18,9 @@
 use rustc_middle::ty::{ToPredicate, TypeVisitableExt};
 use rustc_span::{sym, ErrorGuaranteed, DUMMY_SP};
 
+mod identity_projection;
 mod inherent_projection;
+mod object_projection;
 mod opaques;
 mod weak_types;
 
@@ -45,10 +47,18 @@ pub(super) fn compute_projection_goal(
                 // `exists<U> <T as Trait>::Assoc == U` and then take the resulting type for
                 // `U` and equate it with `u32`. This means that we don't need a separate
                 // projection cache in the solver, since we're piggybacking off of regular
-                // goal caching.
+                // goal caching. This also caches the projections which can't be normalized
+                // because they have no candidate. As inference variables are canonicalized,
+                // such a failure can't be reused once inference constrains the projection.
                 if self.term_is_fully_unconstrained(goal) {
                     match self.tcx().associated_item(def_id).container {
                         ty::AssocItemContainer::TraitContainer => {
+                            if let Some(result) = self.try_normalize_identity_projection(goal) {
+                                return result;
+                            }
+                            if let Some(result) = self.try_normalize_object_projection(goal) {
+                                return result;
+                            }
                             let candidates = self.assemble_and_evaluate_candidates(goal);
                             self.merge_candidates(candidates)
                         }
@@ -246,28 +256,27 @@ fn consider_impl_candidate(
                 );
             }
 
-            // Finally we construct the actual value of the associated type.
+            // Finally we construct the actual value of the associated item.
             let term = match assoc_def.item.kind {
-                ty::AssocKind::Type => tcx.type_of(assoc_def.item.def_id).map_bound(|ty| ty.into()),
+                ty::AssocKind::Type => {
+                    tcx.type_of(assoc_def.item.def_id).instantiate(tcx, args).into()
+                }
                 ty::AssocKind::Const => {
-                    if tcx.features().associated_const_equality {
-                        bug!("associated const projection is not supported yet")
-                    } else {
-                        ty::EarlyBinder::bind(
-                            ty::Const::new_error_with_message(
-                                tcx,
-                                tcx.type_of(assoc_def.item.def_id).instantiate_identity(),
-                                DUMMY_SP,
-                                "associated const projection is not supported yet",
-                            )
-                            .into(),
-                        )
-                    }
+                    // The value of the const in the impl may depend on inference variables in
+                    // `args`, in which case we can only evaluate it once they are resolved.
+                    let ty = tcx.type_of(assoc_def.item.def_id).instantiate(tcx, args);
+                    let unevaluated = ty::UnevaluatedConst::new(assoc_def.item.def_id, args);
+                    let Some(ct) = ecx.try_const_eval_resolve(goal.param_env, unevaluated, ty)
+                    else {
+                        return ecx
+                            .evaluate_added_goals_and_make_canonical_response(Certainty::AMBIGUOUS);
+                    };
+                    ct.into()
                 }
                 ty::AssocKind::Fn => unreachable!("we should never project to a fn"),
             };
 
-            ecx.eq(goal.param_env, goal.predicate.term, term.instantiate(tcx, args))
+            ecx.eq(goal.param_env, goal.predicate.term, term)
                 .expect("expected goal term to be fully unconstrained");
             ecx.evaluate_added_goals_and_make_canonical_response(Certainty::Yes)
         })
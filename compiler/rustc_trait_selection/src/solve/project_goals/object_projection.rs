@@ -0,0 +1,45 @@
+//! Normalizing projections whose self type is a trait object which has a binding for the
+//! associated type, like `<dyn Trait<Assoc = u32> as Trait>::Assoc`, or
+//! `<dyn Sub<Assoc = u32> as Super>::Assoc` if `Super` is a supertrait of `Sub`.
+//!
+//! Like the old solver, we only use the binding of the object type for these, without considering
+//! impls. An impl which also applies to the object type, e.g. a blanket impl for `T: ?Sized`,
+//! would otherwise make the projection ambiguous, so relating it to a concrete type fails. Objects
+//! without a binding for the associated type, e.g. because it requires `Self: Sized`, are
+//! normalized as usual, as are projections whose arguments don't match the binding.
+use rustc_middle::traits::solve::{Goal, QueryResult};
+use rustc_middle::ty::{self, ToPredicate, TypeVisitableExt};
+
+use super::EvalCtxt;
+use crate::solve::assembly::GoalKind;
+use crate::solve::SolverMode;
+
+impl<'tcx> EvalCtxt<'_, 'tcx> {
+    pub(super) fn try_normalize_object_projection(
+        &mut self,
+        goal: Goal<'tcx, ty::ProjectionPredicate<'tcx>>,
+    ) -> Option<QueryResult<'tcx>> {
+        let tcx = self.tcx();
+        let projection_ty = goal.predicate.projection_ty;
+        // Preferring the binding over impls is incomplete, so we must not do so during coherence.
+        if self.solver_mode() == SolverMode::Coherence || projection_ty.references_error() {
+            return None;
+        }
+        let self_ty = projection_ty.self_ty();
+        let ty::Dynamic(bounds, ..) = *self_ty.kind() else { return None };
+        if !tcx.trait_def(projection_ty.trait_def_id(tcx)).implement_via_object
+            || bounds.principal_def_id().is_some_and(|def_id| !tcx.check_is_object_safe(def_id))
+        {
+            return None;
+        }
+
+        // An object type has at most one binding for each associated type.
+        let binding = bounds
+            .projection_bounds()
+            .find(|binding| binding.item_def_id() == projection_ty.def_id)?;
+        let assumption: ty::Clause<'tcx> = binding.with_self_ty(tcx, self_ty).to_predicate(tcx);
+        let result =
+            ty::ProjectionPredicate::consider_object_bound_candidate(self, goal, assumption);
+        result.is_ok().then_some(result)
+    }
+}
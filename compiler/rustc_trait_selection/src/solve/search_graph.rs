@@ -51,6 +51,10 @@ pub(super) struct SearchGraph<'tcx> {
     /// An element is *deeper* in the stack if its index is *lower*.
     stack: IndexVec<StackDepth, StackEntry<'tcx>>,
     stack_entries: FxHashMap<CanonicalInput<'tcx>, StackDepth>,
+    /// The maximum depth reached by any goal, including the depth used by
+    /// the nested goals of cache hits. This is the smallest recursion limit
+    /// with which the goals evaluated in this search graph don't overflow.
+    reached_depth: StackDepth,
 }
 
 impl<'tcx> SearchGraph<'tcx> {
@@ -60,6 +64,7 @@ pub(super) fn new(tcx: TyCtxt<'tcx>, mode: SolverMode) -> SearchGraph<'tcx> {
             local_overflow_limit: tcx.recursion_limit().0.checked_ilog2().unwrap_or(0) as usize,
             stack: Default::default(),
             stack_entries: Default::default(),
+            reached_depth: StackDepth::from_usize(0),
         }
     }
 
@@ -71,10 +76,15 @@ pub(super) fn local_overflow_limit(&self) -> usize {
         self.local_overflow_limit
     }
 
+    pub(super) fn reached_depth(&self) -> usize {
+        self.reached_depth.as_usize()
+    }
+
     /// Update the stack and reached depths on cache hits.
     #[instrument(level = "debug", skip(self))]
     fn on_cache_hit(&mut self, additional_depth: usize, encountered_overflow: bool) {
         let reached_depth = self.stack.next_index().plus(additional_depth);
+        self.reached_depth = self.reached_depth.max(reached_depth);
         if let Some(last) = self.stack.raw.last_mut() {
             last.reached_depth = last.reached_depth.max(reached_depth);
             last.encountered_overflow |= encountered_overflow;
@@ -89,6 +99,7 @@ fn on_cache_hit(&mut self, additional_depth: usize, encountered_overflow: bool)
     fn pop_stack(&mut self) -> StackEntry<'tcx> {
         let elem = self.stack.pop().unwrap();
         assert!(self.stack_entries.remove(&elem.input).is_some());
+        self.reached_depth = self.reached_depth.max(elem.reached_depth);
         if let Some(last) = self.stack.raw.last_mut() {
             last.reached_depth = last.reached_depth.max(elem.reached_depth);
             last.encountered_overflow |= elem.encountered_overflow;
@@ -5,6 +5,7 @@
 use crate::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use crate::infer::InferCtxtExt as _;
 use crate::infer::{self, InferCtxt};
+use crate::solve::{GenerateProofTree, InferCtxtEvalExt};
 use crate::traits::error_reporting::infer_ctxt_ext::InferCtxtExt;
 use crate::traits::error_reporting::{ambiguity, ambiguity::Ambiguity::*};
 use crate::traits::query::evaluate_obligation::InferCtxtExt as _;
@@ -28,6 +29,7 @@
 use rustc_infer::infer::error_reporting::TypeErrCtxt;
 use rustc_infer::infer::{InferOk, TypeTrace};
 use rustc_middle::traits::select::OverflowError;
+use rustc_middle::traits::solve::{AmbiguityCause, Certainty, Goal, OverflowOrigin};
 use rustc_middle::traits::{DefiningAnchor, SelectionOutputTypeParameterMismatch};
 use rustc_middle::ty::abstract_const::NotConstEvaluatable;
 use rustc_middle::ty::error::{ExpectedFound, TypeError};
@@ -1315,12 +1317,34 @@ pub(super) trait InferCtxtPrivExt<'tcx> {
 
     fn report_fulfillment_error(&self, error: &FulfillmentError<'tcx>);
 
+    fn report_alias_relate_overflow(&self, obligation: PredicateObligation<'tcx>)
+    -> ErrorGuaranteed;
+
+    /// Normalizes the alias `ty` one step at a time with the new solver, and returns the types
+    /// it goes through if it comes back to one of them, e.g. `[A, B, A]`.
+    fn alias_normalization_cycle(
+        &self,
+        param_env: ty::ParamEnv<'tcx>,
+        ty: Ty<'tcx>,
+        span: Span,
+    ) -> Option<Vec<Ty<'tcx>>>;
+
+    fn report_unresolved_alias_ambiguity(&self, obligation: &PredicateObligation<'tcx>);
+
     fn report_projection_error(
         &self,
         obligation: &PredicateObligation<'tcx>,
         error: &MismatchedProjectionTypes<'tcx>,
     );
 
+    /// Reports a failed `AliasRelate` goal of the new solver which relates a projection with an
+    /// inference variable occurring in it as an infinite type, returning whether it did so.
+    fn report_cyclic_alias_relate(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        predicate: ty::Predicate<'tcx>,
+    ) -> bool;
+
     fn maybe_detailed_projection_msg(
         &self,
         pred: ty::ProjectionPredicate<'tcx>,
@@ -1570,10 +1594,26 @@ fn report_fulfillment_error(&self, error: &FulfillmentError<'tcx>) {
             FulfillmentErrorCode::CodeProjectionError(ref e) => {
                 self.report_projection_error(&error.obligation, e);
             }
-            FulfillmentErrorCode::CodeAmbiguity { overflow: false } => {
+            FulfillmentErrorCode::CodeAmbiguity {
+                overflow: None,
+                ambiguity: Some(AmbiguityCause::UnresolvedAlias),
+            } if let ty::PredicateKind::AliasRelate(..) =
+                error.obligation.predicate.kind().skip_binder() =>
+            {
+                self.report_unresolved_alias_ambiguity(&error.obligation);
+            }
+            FulfillmentErrorCode::CodeAmbiguity { overflow: None, .. } => {
                 self.maybe_report_ambiguity(&error.obligation);
             }
-            FulfillmentErrorCode::CodeAmbiguity { overflow: true } => {
+            FulfillmentErrorCode::CodeAmbiguity {
+                overflow: Some(OverflowOrigin::AliasRelate),
+                ..
+            } if let ty::PredicateKind::AliasRelate(..) =
+                error.obligation.predicate.kind().skip_binder() =>
+            {
+                self.report_alias_relate_overflow(error.obligation.clone());
+            }
+            FulfillmentErrorCode::CodeAmbiguity { overflow: Some(_), .. } => {
                 self.report_overflow_no_abort(error.obligation.clone());
             }
             FulfillmentErrorCode::CodeSubtypeError(ref expected_found, ref err) => {
@@ -1616,6 +1656,102 @@ fn report_fulfillment_error(&self, error: &FulfillmentError<'tcx>) {
         }
     }
 
+    /// Reports an overflow while normalizing one of the sides of an `AliasRelate` goal, which
+    /// usually means that an alias normalizes to itself, or to an ever growing type.
+    fn report_alias_relate_overflow(&self, obligation: PredicateObligation<'tcx>)
+    -> ErrorGuaranteed {
+        let obligation = self.resolve_vars_if_possible(obligation);
+        let ty::PredicateKind::AliasRelate(lhs, rhs, _) =
+            obligation.predicate.kind().skip_binder()
+        else {
+            bug!("expected an `AliasRelate` goal, found {:?}", obligation.predicate);
+        };
+        let mut err = struct_span_err!(
+            self.tcx.sess,
+            obligation.cause.span,
+            E0275,
+            "overflow normalizing the aliases `{lhs}` and `{rhs}` to relate them",
+        );
+        err.note("normalizing one of them did not finish before reaching the recursion limit");
+        for term in [lhs, rhs] {
+            let Some(ty) = term.ty() else { continue };
+            let Some(cycle) =
+                self.alias_normalization_cycle(obligation.param_env, ty, obligation.cause.span)
+            else {
+                continue;
+            };
+            let chain = cycle.iter().map(|ty| format!("`{ty}`")).collect::<Vec<_>>().join(" -> ");
+            err.note(format!("normalizing `{ty}` goes around in a cycle: {chain}"));
+        }
+        self.suggest_new_overflow_limit(&mut err);
+        self.note_obligation_cause(&mut err, &obligation);
+        err.emit()
+    }
+
+    fn alias_normalization_cycle(
+        &self,
+        param_env: ty::ParamEnv<'tcx>,
+        ty: Ty<'tcx>,
+        span: Span,
+    ) -> Option<Vec<Ty<'tcx>>> {
+        let tcx = self.tcx;
+        self.probe(|_| {
+            let mut chain = vec![ty];
+            while tcx.recursion_limit().value_within_limit(chain.len()) {
+                let ty::Alias(_, alias) = *chain.last().unwrap().kind() else { return None };
+                let normalized = self.next_ty_var(TypeVariableOrigin {
+                    kind: TypeVariableOriginKind::NormalizeProjectionType,
+                    span,
+                });
+                let predicate =
+                    ty::ProjectionPredicate { projection_ty: alias, term: normalized.into() };
+                let goal = Goal::new(tcx, param_env, ty::Binder::dummy(predicate));
+                let Ok((_, Certainty::Yes, _)) =
+                    self.evaluate_root_goal(goal, GenerateProofTree::Never).0
+                else {
+                    return None;
+                };
+                let normalized = self.resolve_vars_if_possible(normalized);
+                let cycles = chain.contains(&normalized);
+                chain.push(normalized);
+                if cycles {
+                    return Some(chain);
+                }
+            }
+            None
+        })
+    }
+
+    /// Reports an ambiguous `AliasRelate` goal whose alias still refers to inference variables,
+    /// which means that more type annotations are needed, not that an impl is missing.
+    fn report_unresolved_alias_ambiguity(&self, obligation: &PredicateObligation<'tcx>) {
+        let obligation = self.resolve_vars_if_possible(obligation.clone());
+        let ty::PredicateKind::AliasRelate(lhs, rhs, _) =
+            obligation.predicate.kind().skip_binder()
+        else {
+            bug!("expected an `AliasRelate` goal, found {:?}", obligation.predicate);
+        };
+        if obligation.predicate.references_error() || self.tainted_by_errors().is_some() {
+            return;
+        }
+        let alias = [lhs, rhs]
+            .into_iter()
+            .find(|term| {
+                term.to_alias_ty(self.tcx).is_some_and(|alias| alias.args.has_non_region_infer())
+            })
+            .unwrap_or(lhs);
+        let mut err = struct_span_err!(
+            self.tcx.sess,
+            obligation.cause.span,
+            E0284,
+            "type annotations needed to resolve `{alias}`",
+        );
+        let predicate = obligation.predicate;
+        err.span_label(obligation.cause.span, format!("cannot satisfy `{predicate}`"));
+        self.note_obligation_cause(&mut err, &obligation);
+        err.emit();
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn report_projection_error(
         &self,
@@ -1624,7 +1760,7 @@ fn report_projection_error(
     ) {
         let predicate = self.resolve_vars_if_possible(obligation.predicate);
 
-        if predicate.references_error() {
+        if predicate.references_error() || self.report_cyclic_alias_relate(obligation, predicate) {
             return;
         }
 
@@ -1718,6 +1854,21 @@ fn report_projection_error(
                 });
             let mut diag = struct_span_err!(self.tcx.sess, obligation.cause.span, E0271, "{msg}");
 
+            // The new solver fails to relate two aliases which it cannot normalize any further
+            // if they are projections with different trait refs.
+            if let ty::PredicateKind::AliasRelate(lhs, rhs, _) = predicate.kind().skip_binder()
+                && let Some(lhs) = lhs.ty()
+                && let Some(rhs) = rhs.ty()
+                && let ty::Alias(ty::Projection, lhs_data) = *lhs.kind()
+                && let ty::Alias(ty::Projection, rhs_data) = *rhs.kind()
+                && lhs_data.trait_ref(self.tcx) != rhs_data.trait_ref(self.tcx)
+            {
+                diag.note(with_forced_trimmed_paths!(format!(
+                    "cannot relate `{lhs}` with `{rhs}`, as they are projections from different \
+                    trait references"
+                )));
+            }
+
             let secondary_span = (|| {
                 let ty::PredicateKind::Clause(ty::ClauseKind::Projection(proj)) =
                     predicate.kind().skip_binder()
@@ -1794,6 +1945,60 @@ fn report_projection_error(
         });
     }
 
+    fn report_cyclic_alias_relate(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        predicate: ty::Predicate<'tcx>,
+    ) -> bool {
+        // The generalizer relates an alias lazily if it mentions the inference variable the alias
+        // is assigned to, e.g. `?x == Vec<<?x as Id>::Id>` results in `?x == Vec<?y>` and
+        // `<Vec<?y> as Id>::Id == ?y`. If the trait is implemented, this can then only fail if
+        // the alias normalizes to a type containing the variable, which is an infinite type.
+        if !self.next_trait_solver() {
+            return false;
+        }
+        let ty::PredicateKind::AliasRelate(lhs, rhs, _) = predicate.kind().skip_binder() else {
+            return false;
+        };
+        let (Some(lhs), Some(rhs)) = (lhs.ty(), rhs.ty()) else {
+            return false;
+        };
+        let (alias, vid) = match (*lhs.kind(), *rhs.kind()) {
+            (ty::Alias(ty::Projection, alias), ty::Infer(ty::TyVar(vid)))
+            | (ty::Infer(ty::TyVar(vid)), ty::Alias(ty::Projection, alias)) => (alias, vid),
+            _ => return false,
+        };
+        let root = self.root_var(vid);
+        let occurs = alias
+            .args
+            .iter()
+            .flat_map(|arg| arg.walk())
+            .filter_map(|arg| arg.as_type())
+            .any(|ty| matches!(*ty.kind(), ty::Infer(ty::TyVar(v)) if self.root_var(v) == root));
+        if !occurs
+            || !predicate.kind().bound_vars().is_empty()
+            || !self.predicate_may_hold(&obligation.with(self.tcx, alias.trait_ref(self.tcx)))
+        {
+            return false;
+        }
+
+        let var = Ty::new_var(self.tcx, vid);
+        let alias = Ty::new_alias(self.tcx, ty::Projection, alias);
+        let mut diag = struct_span_err!(
+            self.tcx.sess,
+            obligation.cause.span,
+            E0271,
+            "cannot construct an infinite type"
+        );
+        diag.span_label(obligation.cause.span, "cyclic type of infinite size");
+        diag.note(with_forced_trimmed_paths!(format!(
+            "`{var}` would have to be equal to `{alias}`, which normalizes to a type containing it"
+        )));
+        self.note_obligation_cause(&mut diag, obligation);
+        diag.emit();
+        true
+    }
+
     fn maybe_detailed_projection_msg(
         &self,
         pred: ty::ProjectionPredicate<'tcx>,
@@ -134,7 +134,7 @@ fn collect_remaining_errors(
         _infcx: &InferCtxt<'tcx>,
     ) -> Vec<FulfillmentError<'tcx>> {
         self.predicates
-            .to_errors(CodeAmbiguity { overflow: false })
+            .to_errors(CodeAmbiguity { overflow: None, ambiguity: None })
             .into_iter()
             .map(to_fulfillment_error)
             .collect()
@@ -3627,6 +3627,7 @@ fn array_chunks<const N: usize>(self) -> ArrayChunks<Self, N>
     /// ```
     #[stable(feature = "iter_arith", since = "1.11.0")]
     #[rustc_do_not_const_check]
+    #[cfg_attr(not(test), rustc_diagnostic_item = "iterator_sum_fn")]
     fn sum<S>(self) -> S
     where
         Self: Sized,
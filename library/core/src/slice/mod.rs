@@ -661,6 +661,7 @@ pub fn get_mut<I>(&mut self, index: I) -> Option<&mut I::Output>
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
     #[must_use]
+    #[rustc_diagnostic_item = "slice_get_unchecked"]
     pub unsafe fn get_unchecked<I>(&self, index: I) -> &I::Output
     where
         I: SliceIndex<Self>,
@@ -703,6 +704,7 @@ pub unsafe fn get_unchecked<I>(&self, index: I) -> &I::Output
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
     #[must_use]
+    #[rustc_diagnostic_item = "slice_get_unchecked_mut"]
     pub unsafe fn get_unchecked_mut<I>(&mut self, index: I) -> &mut I::Output
     where
         I: SliceIndex<Self>,
@@ -1011,6 +1013,7 @@ fn revswap<T>(a: &mut [T], b: &mut [T], n: usize) {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
+    #[rustc_diagnostic_item = "slice_iter"]
     pub fn iter(&self) -> Iter<'_, T> {
         Iter::new(self)
     }
@@ -1191,6 +1194,7 @@ pub fn chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T> {
     #[stable(feature = "chunks_exact", since = "1.31.0")]
     #[inline]
     #[track_caller]
+    #[cfg_attr(not(bootstrap), rustc_exact_chunks)]
     pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T> {
         assert!(chunk_size != 0, "chunk size must be non-zero");
         ChunksExact::new(self, chunk_size)
@@ -1234,6 +1238,7 @@ pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T> {
     #[stable(feature = "chunks_exact", since = "1.31.0")]
     #[inline]
     #[track_caller]
+    #[cfg_attr(not(bootstrap), rustc_exact_chunks)]
     pub fn chunks_exact_mut(&mut self, chunk_size: usize) -> ChunksExactMut<'_, T> {
         assert!(chunk_size != 0, "chunk size must be non-zero");
         ChunksExactMut::new(self, chunk_size)
@@ -1690,6 +1695,7 @@ pub fn rchunks_mut(&mut self, chunk_size: usize) -> RChunksMut<'_, T> {
     #[stable(feature = "rchunks", since = "1.31.0")]
     #[inline]
     #[track_caller]
+    #[cfg_attr(not(bootstrap), rustc_exact_chunks)]
     pub fn rchunks_exact(&self, chunk_size: usize) -> RChunksExact<'_, T> {
         assert!(chunk_size != 0, "chunk size must be non-zero");
         RChunksExact::new(self, chunk_size)
@@ -1734,6 +1740,7 @@ pub fn rchunks_exact(&self, chunk_size: usize) -> RChunksExact<'_, T> {
     #[stable(feature = "rchunks", since = "1.31.0")]
     #[inline]
     #[track_caller]
+    #[cfg_attr(not(bootstrap), rustc_exact_chunks)]
     pub fn rchunks_exact_mut(&mut self, chunk_size: usize) -> RChunksExactMut<'_, T> {
         assert!(chunk_size != 0, "chunk size must be non-zero");
         RChunksExactMut::new(self, chunk_size)
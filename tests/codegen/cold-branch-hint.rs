@@ -0,0 +1,40 @@
+// compile-flags: -O -Cno-prepopulate-passes
+#![crate_type = "lib"]
+
+// This test checks that, when optimizing, branches to blocks which only lead to
+// a call to a `#[cold]` function are marked as unlikely.
+
+#[cold]
+#[inline(never)]
+#[no_mangle]
+pub fn cold_path() {}
+
+#[inline(never)]
+#[no_mangle]
+pub fn warm_path() {}
+
+// CHECK-LABEL: @check_cold
+// CHECK: [[COND:%.*]] = call i1 @llvm.expect.i1(i1 %x, i1 false)
+// CHECK: br i1 [[COND]]
+#[no_mangle]
+pub fn check_cold(x: bool) -> u32 {
+    if x {
+        cold_path();
+        1
+    } else {
+        2
+    }
+}
+
+// CHECK-LABEL: @check_warm
+// CHECK-NOT: @llvm.expect
+// CHECK: br i1 %x
+#[no_mangle]
+pub fn check_warm(x: bool) -> u32 {
+    if x {
+        warm_path();
+        1
+    } else {
+        2
+    }
+}
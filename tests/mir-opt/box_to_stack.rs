@@ -0,0 +1,135 @@
+// unit-test: BoxToStack
+
+#![feature(custom_mir, core_intrinsics, ptr_internals, raw_ref_op)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+use std::ptr::{NonNull, Unique};
+
+#[inline(never)]
+fn opaque(x: &u32) -> u32 {
+    *x
+}
+
+// EMIT_MIR box_to_stack.deref.BoxToStack.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn deref(x: u32) -> u32 {
+    // CHECK-LABEL: fn deref(
+    // CHECK-NOT: Box::<u32>::new
+    // CHECK: _4 = _1;
+    // CHECK: _0 = _4;
+    // CHECK: drop(_4)
+    mir!(
+        let b: Box<u32>;
+        let p: *const u32;
+        {
+            Call(b = Box::new(x), read, UnwindContinue())
+        }
+        read = {
+            p = Field::<*const u32>(Field::<NonNull<u32>>(Field::<Unique<u32>>(b, 0), 0), 0);
+            RET = *p;
+            Drop(b, ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR box_to_stack.borrowed.BoxToStack.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn borrowed(x: u32) -> u32 {
+    // CHECK-LABEL: fn borrowed(
+    // CHECK-NOT: Box::<u32>::new
+    // CHECK: _4 = &_5;
+    // CHECK: _0 = opaque(_4)
+    // CHECK: drop(_5)
+    mir!(
+        let b: Box<u32>;
+        let p: *const u32;
+        let r: &u32;
+        {
+            Call(b = Box::new(x), read, UnwindContinue())
+        }
+        read = {
+            p = Field::<*const u32>(Field::<NonNull<u32>>(Field::<Unique<u32>>(b, 0), 0), 0);
+            r = &*p;
+            Call(RET = opaque(r), done, UnwindContinue())
+        }
+        done = {
+            Drop(b, ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR box_to_stack.returned.BoxToStack.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn returned(x: u32) -> Box<u32> {
+    // CHECK-LABEL: fn returned(
+    // CHECK: _2 = Box::<u32>::new(_1)
+    // CHECK: _0 = move _2;
+    mir!(
+        let b: Box<u32>;
+        {
+            Call(b = Box::new(x), ret, UnwindContinue())
+        }
+        ret = {
+            RET = Move(b);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR box_to_stack.raw_pointer.BoxToStack.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn raw_pointer(x: u32) -> *const u32 {
+    // CHECK-LABEL: fn raw_pointer(
+    // CHECK: _2 = Box::<u32>::new(_1)
+    // CHECK: _0 = &raw const (*_3);
+    // CHECK: drop(_2)
+    mir!(
+        let b: Box<u32>;
+        let p: *const u32;
+        {
+            Call(b = Box::new(x), read, UnwindContinue())
+        }
+        read = {
+            p = Field::<*const u32>(Field::<NonNull<u32>>(Field::<Unique<u32>>(b, 0), 0), 0);
+            RET = &raw const *p;
+            Drop(b, ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR box_to_stack.large.BoxToStack.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn large(x: [u8; 1024]) -> [u8; 1024] {
+    // CHECK-LABEL: fn large(
+    // CHECK: _2 = Box::<[u8; 1024]>::new(_1)
+    // CHECK: _0 = (*_3);
+    // CHECK: drop(_2)
+    mir!(
+        let b: Box<[u8; 1024]>;
+        let p: *const [u8; 1024];
+        {
+            Call(b = Box::new(x), read, UnwindContinue())
+        }
+        read = {
+            p = Field::<*const [u8; 1024]>(
+                Field::<NonNull<[u8; 1024]>>(Field::<Unique<[u8; 1024]>>(b, 0), 0),
+                0,
+            );
+            RET = *p;
+            Drop(b, ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
@@ -0,0 +1,131 @@
+// skip-filecheck
+// unit-test: ConstDiscriminant
+
+#![feature(core_intrinsics, custom_mir)]
+use std::intrinsics::mir::*;
+
+// EMIT_MIR const_discriminant.option.ConstDiscriminant.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn option(x: u32) -> isize {
+    // The discriminant is still known after a `goto` to a block without other predecessors.
+    mir!(
+        let o: Option<u32>;
+        {
+            o = Some(x);
+            Goto(bb1)
+        }
+        bb1 = {
+            RET = Discriminant(o);
+            Return()
+        }
+    )
+}
+
+#[repr(u8)]
+enum E {
+    A = 3,
+    B(u8) = 7,
+    C { x: u32 } = 11,
+}
+
+// EMIT_MIR const_discriminant.multi_variant.ConstDiscriminant.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn multi_variant(x: u8) -> u8 {
+    mir!(
+        let e: E;
+        let d: u8;
+        {
+            e = E::B(x);
+            d = Discriminant(e);
+            SetDiscriminant(e, 2);
+            RET = Discriminant(e);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR const_discriminant.join.ConstDiscriminant.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn join(c: bool, x: u32) -> isize {
+    // Each predecessor writes a different variant, so the discriminant isn't known after the join.
+    mir!(
+        let o: Option<u32>;
+        {
+            match c { true => bb1, _ => bb2 }
+        }
+        bb1 = {
+            o = Some(x);
+            Goto(bb3)
+        }
+        bb2 = {
+            o = None;
+            Goto(bb3)
+        }
+        bb3 = {
+            RET = Discriminant(o);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR const_discriminant.field_write.ConstDiscriminant.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn field_write() -> isize {
+    // Writing the field of `Some` writes the niche holding the discriminant.
+    mir!(
+        let o: Option<NonZeroU32>;
+        {
+            SetDiscriminant(o, 1);
+            place!(Field(Field(Variant(o, 1), 0), 0)) = 0_u32;
+            RET = Discriminant(o);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR const_discriminant.partial_move.ConstDiscriminant.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn partial_move(s: String) -> isize {
+    // Moving out of the field of `Some` may leave the niche holding the discriminant uninitialized.
+    mir!(
+        let o: Option<String>;
+        let t: String;
+        {
+            o = Some(Move(s));
+            t = Move(Field::<String>(Variant(o, 1), 0));
+            RET = Discriminant(o);
+            Return()
+        }
+    )
+}
+
+union U {
+    o: Option<u32>,
+}
+
+// EMIT_MIR const_discriminant.union_field.ConstDiscriminant.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn union_field(x: u32) -> isize {
+    // Only the discriminant of `o` is known, not the one of the field of the union.
+    mir!(
+        let o: Option<u32>;
+        let u: U;
+        {
+            o = Some(x);
+            u = U { o: o };
+            RET = Discriminant(Field::<Option<u32>>(u, 0));
+            Return()
+        }
+    )
+}
+
+use std::num::NonZeroU32;
+
+fn main() {
+    option(5);
+    multi_variant(5);
+    join(true, 5);
+    field_write();
+    partial_move(String::new());
+    union_field(5);
+}
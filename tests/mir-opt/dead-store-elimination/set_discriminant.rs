@@ -0,0 +1,84 @@
+// skip-filecheck
+// unit-test: DeadStoreElimination
+
+#![feature(core_intrinsics, custom_mir)]
+use std::intrinsics::mir::*;
+
+// EMIT_MIR set_discriminant.overwritten.DeadStoreElimination.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn overwritten() -> Option<u32> {
+    // The first discriminant is never read before being set again.
+    mir!(
+        let x: Option<u32>;
+        {
+            SetDiscriminant(x, 0);
+            place!(Field(Variant(x, 1), 0)) = 5_u32;
+            SetDiscriminant(x, 1);
+            RET = Move(x);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR set_discriminant.read.DeadStoreElimination.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn read() -> (isize, Option<u32>) {
+    // The first discriminant is read before being set again, so it stays.
+    mir!(
+        let x: Option<u32>;
+        let d: isize;
+        {
+            SetDiscriminant(x, 0);
+            d = Discriminant(x);
+            place!(Field(Variant(x, 1), 0)) = 5_u32;
+            SetDiscriminant(x, 1);
+            RET = (d, Move(x));
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR set_discriminant.noop_drop.DeadStoreElimination.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn noop_drop() -> Option<u32> {
+    // Dropping an `Option<u32>` does nothing, so it doesn't read the discriminant.
+    mir!(
+        let x: Option<u32>;
+        {
+            SetDiscriminant(x, 0);
+            Drop(x, bb1, UnwindUnreachable())
+        }
+        bb1 = {
+            place!(Field(Variant(x, 1), 0)) = 5_u32;
+            SetDiscriminant(x, 1);
+            RET = Move(x);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR set_discriminant.drop.DeadStoreElimination.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+fn drop<T>(t: T) -> Option<T> {
+    // Dropping an `Option<T>` runs the drop glue of `T` for `Some`, so it reads the discriminant.
+    mir!(
+        let x: Option<T>;
+        {
+            place!(Field(Variant(x, 1), 0)) = Move(t);
+            SetDiscriminant(x, 1);
+            Drop(x, bb1, UnwindUnreachable())
+        }
+        bb1 = {
+            SetDiscriminant(x, 0);
+            RET = Move(x);
+            Return()
+        }
+    )
+}
+
+fn main() {
+    overwritten();
+    read();
+    noop_drop();
+    drop(String::new());
+}
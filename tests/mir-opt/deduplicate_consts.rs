@@ -0,0 +1,27 @@
+// unit-test: DeduplicateConsts
+
+#![crate_type = "lib"]
+
+// EMIT_MIR deduplicate_consts.byte_strings.DeduplicateConsts.diff
+pub fn byte_strings() -> (&'static [u8; 3], &'static [u8; 3]) {
+    // CHECK-LABEL: fn byte_strings(
+    // CHECK: _0 = (const b"abc", const b"abc");
+    // CHECK: ALLOC0 (size: 3, align: 1)
+    // CHECK-NOT: ALLOC1
+    (b"abc", b"abc")
+}
+
+// EMIT_MIR deduplicate_consts.different_bytes.DeduplicateConsts.diff
+pub fn different_bytes() -> (&'static [u8; 3], &'static [u8; 3]) {
+    // CHECK-LABEL: fn different_bytes(
+    // CHECK: ALLOC0 (size: 3, align: 1)
+    // CHECK: ALLOC1 (size: 3, align: 1)
+    (b"abc", b"abd")
+}
+
+// EMIT_MIR deduplicate_consts.distinct_types.DeduplicateConsts.diff
+pub fn distinct_types() -> (u32, i32, &'static [u8; 4], &'static str) {
+    // CHECK-LABEL: fn distinct_types(
+    // CHECK: _0 = (const 1_u32, const 1_i32, const b"\x01\x00\x00\x00", const "\u{1}\0\0\0");
+    (1, 1, b"\x01\0\0\0", "\x01\0\0\0")
+}
@@ -0,0 +1,36 @@
+// unit-test: Devirtualize
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+#![crate_type = "lib"]
+#![feature(trait_upcasting)]
+
+pub trait Trait {
+    fn method(&self) -> u32;
+}
+
+pub trait Sub: Trait {}
+
+pub struct S(u32);
+
+impl Trait for S {
+    fn method(&self) -> u32 {
+        self.0
+    }
+}
+
+// EMIT_MIR devirtualize.coerced.Devirtualize.diff
+pub fn coerced(s: &S) -> u32 {
+    // CHECK-LABEL: fn coerced(
+    // CHECK: let mut _2: &S;
+    // CHECK: _2 = move _3;
+    // CHECK: _0 = <S as Trait>::method(move _2)
+    <dyn Trait as Trait>::method(s)
+}
+
+// The vtable of an upcast trait object comes from the original one, which may be of any type.
+// EMIT_MIR devirtualize.upcast.Devirtualize.diff
+pub fn upcast(s: &dyn Sub) -> u32 {
+    // CHECK-LABEL: fn upcast(
+    // CHECK: _2 = move _3 as &dyn Trait (PointerCoercion(Unsize));
+    // CHECK: _0 = <dyn Trait as Trait>::method(move _2)
+    <dyn Trait as Trait>::method(s)
+}
@@ -0,0 +1,22 @@
+// skip-filecheck
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// Ensure that the drops of `e` only drop the fields of the variant it is known to have on each
+// path, without switching on its discriminant.
+
+enum Entry {
+    Vacant(String),
+    Occupied(String, String),
+}
+
+// EMIT_MIR elaborate_drops_known_variant.occupied_key.SimplifyCfg-elaborate-drops.after.mir
+fn occupied_key(e: Entry) -> String {
+    match e {
+        Entry::Occupied(key, _) => key,
+        Entry::Vacant(_) => panic!(),
+    }
+}
+
+fn main() {
+    let _ = occupied_key(Entry::Occupied(String::new(), String::new()));
+}
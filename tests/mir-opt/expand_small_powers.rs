@@ -0,0 +1,40 @@
+// unit-test: ExpandSmallPowers
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// EMIT_MIR expand_small_powers.zero.ExpandSmallPowers.diff
+pub fn zero(x: u32) -> u32 {
+    // CHECK-LABEL: fn zero(
+    // CHECK: _0 = const 1_u32;
+    x.pow(0)
+}
+
+// EMIT_MIR expand_small_powers.one.ExpandSmallPowers.diff
+pub fn one(x: u32) -> u32 {
+    // CHECK-LABEL: fn one(
+    // CHECK: _0 = move _2;
+    x.pow(1)
+}
+
+// EMIT_MIR expand_small_powers.square.ExpandSmallPowers.diff
+pub fn square(x: u32) -> u32 {
+    // CHECK-LABEL: fn square(
+    // CHECK: [[checked:_.*]] = CheckedMul(_2, _2);
+    // CHECK: _0 = move ([[checked]].0: u32);
+    x.pow(2)
+}
+
+// EMIT_MIR expand_small_powers.cube.ExpandSmallPowers.diff
+pub fn cube(x: i32) -> i32 {
+    // CHECK-LABEL: fn cube(
+    // CHECK: [[square:_.*]] = CheckedMul(_2, _2);
+    // CHECK: [[cube:_.*]] = CheckedMul(([[square]].0: i32), _2);
+    // CHECK: _0 = move ([[cube]].0: i32);
+    x.pow(3)
+}
+
+fn main() {
+    zero(5);
+    one(5);
+    square(5);
+    cube(-5);
+}
@@ -0,0 +1,24 @@
+// unit-test: FoldConstantArraySums
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// EMIT_MIR fold_constant_array_sums.constant.FoldConstantArraySums.diff
+pub fn constant() -> u32 {
+    // CHECK-LABEL: fn constant(
+    // CHECK-NOT: ::sum::<u32>(
+    // CHECK: _0 = const 10_u32;
+    let weights = [1_u32, 2, 3, 4];
+    weights.iter().sum()
+}
+
+// EMIT_MIR fold_constant_array_sums.runtime.FoldConstantArraySums.diff
+pub fn runtime(x: u32) -> u32 {
+    // CHECK-LABEL: fn runtime(
+    // CHECK: ::sum::<u32>(move _4)
+    let weights = [x, 2, 3, 4];
+    weights.iter().sum()
+}
+
+fn main() {
+    constant();
+    runtime(1);
+}
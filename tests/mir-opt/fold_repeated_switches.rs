@@ -0,0 +1,86 @@
+// unit-test: FoldRepeatedSwitches
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR fold_repeated_switches.fold.FoldRepeatedSwitches.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn fold(x: Option<u32>) -> u32 {
+    // CHECK-LABEL: fn fold(
+    // CHECK: bb0: {
+    // CHECK: switchInt(_2) -> [1: bb1, otherwise: bb4];
+    // CHECK: bb1: {
+    // CHECK: goto -> bb3;
+    mir!(
+        let d1: isize;
+        let d2: isize;
+        {
+            RET = 0;
+            d1 = Discriminant(x);
+            match d1 {
+                1 => some,
+                _ => join,
+            }
+        }
+        some = {
+            RET = 1;
+            Goto(join)
+        }
+        join = {
+            d2 = Discriminant(x);
+            match Move(d2) {
+                1 => again,
+                _ => exit,
+            }
+        }
+        again = {
+            RET = RET + 10;
+            Return()
+        }
+        exit = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR fold_repeated_switches.mutated.FoldRepeatedSwitches.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn mutated(x: Option<u32>) -> u32 {
+    // CHECK-LABEL: fn mutated(
+    // CHECK: bb1: {
+    // CHECK: discriminant(_1) = 0;
+    // CHECK-NEXT: goto -> bb2;
+    mir!(
+        let d1: isize;
+        let d2: isize;
+        {
+            RET = 0;
+            d1 = Discriminant(x);
+            match d1 {
+                1 => some,
+                _ => join,
+            }
+        }
+        some = {
+            RET = 1;
+            SetDiscriminant(x, 0);
+            Goto(join)
+        }
+        join = {
+            d2 = Discriminant(x);
+            match Move(d2) {
+                1 => again,
+                _ => exit,
+            }
+        }
+        again = {
+            RET = RET + 10;
+            Return()
+        }
+        exit = {
+            Return()
+        }
+    )
+}
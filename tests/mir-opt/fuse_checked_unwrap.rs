@@ -0,0 +1,29 @@
+// unit-test: FuseCheckedUnwrap
+// compile-flags: -Zunsound-mir-opts
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+#![crate_type = "lib"]
+
+// EMIT_MIR fuse_checked_unwrap.add.FuseCheckedUnwrap.diff
+pub fn add(x: u32, y: u32) -> u32 {
+    // CHECK-LABEL: fn add(
+    // CHECK: let mut [[checked:_.*]]: (u32, bool);
+    // CHECK-NOT: Option
+    // CHECK: [[checked]] = CheckedAdd(
+    // CHECK: assert(!move ([[checked]].1: bool), "attempt to compute `{} + {}`, which would overflow"
+    // CHECK-NOT: unwrap
+    // CHECK: _0 = move ([[checked]].0: u32);
+    x.checked_add(y).unwrap()
+}
+
+// EMIT_MIR fuse_checked_unwrap.mul.FuseCheckedUnwrap.diff
+pub fn mul(x: i64, y: i64) -> i64 {
+    // CHECK-LABEL: fn mul(
+    // CHECK: let mut [[checked:_.*]]: (i64, bool);
+    // CHECK-NOT: Option
+    // CHECK: [[checked]] = CheckedMul(
+    // CHECK: assert(!move ([[checked]].1: bool), "attempt to compute `{} * {}`, which would overflow"
+    // CHECK-NOT: unwrap
+    // CHECK: _0 = move ([[checked]].0: i64);
+    x.checked_mul(y).unwrap()
+}
@@ -0,0 +1,75 @@
+// unit-test: FuseFieldCopies
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+pub struct Pair {
+    a: u32,
+    b: i64,
+}
+
+// EMIT_MIR fuse_field_copies.copied.FuseFieldCopies.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn copied(p: Pair) -> Pair {
+    // CHECK-LABEL: fn copied(
+    // CHECK: _0 = _1;
+    // CHECK-NEXT: nop;
+    mir!(
+        {
+            place!(Field::<u32>(RET, 0)) = Field::<u32>(p, 0);
+            place!(Field::<i64>(RET, 1)) = Field::<i64>(p, 1);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR fuse_field_copies.moved.FuseFieldCopies.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn moved(t: (String, String)) -> (String, String) {
+    // CHECK-LABEL: fn moved(
+    // CHECK: _0 = move _1;
+    // CHECK-NEXT: nop;
+    mir!(
+        {
+            RET.0 = Move(t.0);
+            RET.1 = Move(t.1);
+            Return()
+        }
+    )
+}
+
+// Only the first field is copied, so the other one must be left alone.
+// EMIT_MIR fuse_field_copies.partial.FuseFieldCopies.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn partial(t: (u32, i64)) -> (u32, i64) {
+    // CHECK-LABEL: fn partial(
+    // CHECK: (_0.0: u32) = (_1.0: u32);
+    // CHECK-NEXT: (_0.1: i64) = const 5_i64;
+    mir!(
+        {
+            RET.0 = t.0;
+            RET.1 = 5;
+            Return()
+        }
+    )
+}
+
+// Writing to the first field of the source in between changes the value the copies see.
+// EMIT_MIR fuse_field_copies.interleaved.FuseFieldCopies.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn interleaved(t: (u32, i64)) -> (u32, i64) {
+    // CHECK-LABEL: fn interleaved(
+    // CHECK-NOT: _0 = _1;
+    mir!(
+        let u: (u32, i64);
+        {
+            u = t;
+            RET.0 = u.0;
+            u.0 = 7;
+            RET.1 = u.1;
+            Return()
+        }
+    )
+}
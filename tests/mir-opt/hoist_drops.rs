@@ -0,0 +1,63 @@
+// unit-test: HoistDrops
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR hoist_drops.hoisted.HoistDrops.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn hoisted(v: Vec<u32>) -> usize {
+    // CHECK-LABEL: fn hoisted(
+    // CHECK: bb1: {
+    // CHECK-NEXT: drop(_1) -> [return: bb4, unwind continue];
+    // CHECK: bb2: {
+    // CHECK-NEXT: _0 = _4;
+    // CHECK-NEXT: goto -> bb3;
+    // CHECK: bb4: {
+    // CHECK-NEXT: _4 = _3;
+    // CHECK-NEXT: goto -> bb2;
+    mir!(
+        let r: &Vec<u32>;
+        let len: usize;
+        let copy: usize;
+        {
+            r = &v;
+            Call(len = Vec::len(r), compute, UnwindContinue())
+        }
+        compute = {
+            copy = len;
+            Goto(done)
+        }
+        done = {
+            RET = copy;
+            Drop(v, ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR hoist_drops.deref.HoistDrops.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn deref(v: Vec<u32>, p: *const u32) -> u32 {
+    // CHECK-LABEL: fn deref(
+    // CHECK: bb0: {
+    // CHECK-NEXT: _0 = (*_2);
+    // CHECK-NEXT: goto -> bb1;
+    // CHECK: bb1: {
+    // CHECK-NEXT: drop(_1) -> [return: bb2, unwind continue];
+    mir!(
+        {
+            RET = *p;
+            Goto(done)
+        }
+        done = {
+            Drop(v, ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
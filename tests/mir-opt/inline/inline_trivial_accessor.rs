@@ -0,0 +1,42 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// Checks that trivial accessors, and only them, are inlined at the optimization levels where the
+// inliner doesn't otherwise run.
+//
+// compile-flags: -Zmir-opt-level=1
+
+pub struct Point {
+    x: u32,
+    y: u32,
+}
+
+impl Point {
+    fn x(&self) -> u32 {
+        self.x
+    }
+
+    fn max(&self) -> u32 {
+        Ord::max(self.x, self.y)
+    }
+}
+
+// EMIT_MIR inline_trivial_accessor.get_x.InlineTrivialAccessors.diff
+pub fn get_x(p: &Point) -> u32 {
+    // CHECK-LABEL: fn get_x(
+    // CHECK: scope 1 (inlined Point::x)
+    // CHECK-NOT: Point::x(
+    // CHECK: _0 = ((*{{_.*}}).0: u32);
+    p.x()
+}
+
+// EMIT_MIR inline_trivial_accessor.get_max.InlineTrivialAccessors.diff
+pub fn get_max(p: &Point) -> u32 {
+    // CHECK-LABEL: fn get_max(
+    // CHECK-NOT: inlined
+    // CHECK: Point::max(
+    p.max()
+}
+
+fn main() {
+    get_x(&Point { x: 1, y: 2 });
+    get_max(&Point { x: 1, y: 2 });
+}
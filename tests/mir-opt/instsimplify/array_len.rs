@@ -0,0 +1,62 @@
+// unit-test: InstSimplify
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR array_len.nested.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn nested(x: [[u8; 4]; 3], i: usize) -> usize {
+    // CHECK-LABEL: fn nested(
+    // CHECK-NOT: Len(
+    // CHECK: _3 = const 3_usize;
+    // CHECK: _4 = const 4_usize;
+    mir!(
+        let outer: usize;
+        let inner: usize;
+        {
+            outer = Len(x);
+            inner = Len(x[i]);
+            RET = outer + inner;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR array_len.deref.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn deref(x: &[[u8; 4]; 3], i: usize) -> usize {
+    // CHECK-LABEL: fn deref(
+    // CHECK-NOT: Len(
+    // CHECK: _3 = const 3_usize;
+    // CHECK: _4 = const 4_usize;
+    mir!(
+        let outer: usize;
+        let inner: usize;
+        {
+            outer = Len(*x);
+            inner = Len((*x)[i]);
+            RET = outer + inner;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR array_len.slice.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn slice(x: &[[u8; 4]], i: usize) -> usize {
+    // CHECK-LABEL: fn slice(
+    // CHECK: _3 = Len((*_1));
+    // CHECK: _4 = const 4_usize;
+    mir!(
+        let outer: usize;
+        let inner: usize;
+        {
+            outer = Len(*x);
+            inner = Len((*x)[i]);
+            RET = outer + inner;
+            Return()
+        }
+    )
+}
@@ -0,0 +1,26 @@
+// unit-test: InstSimplify
+
+// EMIT_MIR bit_chains.or_two.InstSimplify.diff
+pub fn or_two(x: u32) -> u32 {
+    // CHECK-LABEL: fn or_two(
+    // CHECK: [[a:_.*]] = BitOr(move {{_.*}}, const 3_u32);
+    // CHECK: _0 = move [[a]];
+    x | 1 | 2
+}
+
+// EMIT_MIR bit_chains.or_three.InstSimplify.diff
+pub fn or_three(x: u8) -> u8 {
+    // CHECK-LABEL: fn or_three(
+    // CHECK: [[a:_.*]] = BitOr(move {{_.*}}, const 7_u8);
+    // CHECK: [[b:_.*]] = move [[a]];
+    // CHECK: _0 = move [[b]];
+    x | 1 | 2 | 4
+}
+
+// EMIT_MIR bit_chains.and_two.InstSimplify.diff
+pub fn and_two(x: u64) -> u64 {
+    // CHECK-LABEL: fn and_two(
+    // CHECK: [[a:_.*]] = BitAnd(move {{_.*}}, const 15_u64);
+    // CHECK: _0 = move [[a]];
+    x & 0xff & 0x0f
+}
@@ -0,0 +1,45 @@
+// unit-test: InstSimplify
+
+// EMIT_MIR int_casts.unsigned_truncate.InstSimplify.diff
+pub fn unsigned_truncate(x: u32) -> u16 {
+    // CHECK-LABEL: fn unsigned_truncate(
+    // CHECK: _0 = _1 as u16 (IntToInt);
+    x as u64 as u16
+}
+
+// EMIT_MIR int_casts.unsigned_extend.InstSimplify.diff
+pub fn unsigned_extend(x: u8) -> u64 {
+    // CHECK-LABEL: fn unsigned_extend(
+    // CHECK: _0 = _1 as u64 (IntToInt);
+    x as u16 as u64
+}
+
+// EMIT_MIR int_casts.signed_truncate.InstSimplify.diff
+pub fn signed_truncate(x: i8) -> u32 {
+    // CHECK-LABEL: fn signed_truncate(
+    // CHECK: _0 = _1 as u32 (IntToInt);
+    x as i64 as u32
+}
+
+// EMIT_MIR int_casts.unsigned_through_signed.InstSimplify.diff
+pub fn unsigned_through_signed(x: u8) -> u64 {
+    // CHECK-LABEL: fn unsigned_through_signed(
+    // CHECK: _0 = _1 as u64 (IntToInt);
+    x as i16 as u64
+}
+
+// EMIT_MIR int_casts.sign_then_zero_extend.InstSimplify.diff
+pub fn sign_then_zero_extend(x: i8) -> u32 {
+    // CHECK-LABEL: fn sign_then_zero_extend(
+    // `-1_i8 as u16 as u32` is `0xffff`, not `0xffff_ffff`.
+    // CHECK: _0 = move _2 as u32 (IntToInt);
+    x as u16 as u32
+}
+
+// EMIT_MIR int_casts.narrowing.InstSimplify.diff
+pub fn narrowing(x: u64) -> u64 {
+    // CHECK-LABEL: fn narrowing(
+    // Going through `u32` clears the high bits.
+    // CHECK: _0 = move _2 as u64 (IntToInt);
+    x as u32 as u64
+}
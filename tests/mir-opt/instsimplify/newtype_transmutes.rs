@@ -0,0 +1,48 @@
+// unit-test: InstSimplify
+// compile-flags: -C panic=abort
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+#![feature(custom_mir)]
+
+use std::intrinsics::mir::*;
+use std::num::NonZeroU32;
+
+#[repr(transparent)]
+pub struct Transparent(u32);
+
+pub struct Wrapper(u32);
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+// EMIT_MIR newtype_transmutes.into_newtypes.InstSimplify.diff
+pub unsafe fn into_newtypes(x: u32) {
+    // CHECK-LABEL: fn into_newtypes(
+    // CHECK: _2 = Transparent(_1);
+    // CHECK: _3 = Wrapper(_1);
+    // CHECK: _4 = _1 as std::num::NonZeroU32 (Transmute);
+
+    mir! {
+        {
+            let A = CastTransmute::<u32, Transparent>(x);
+            let B = CastTransmute::<u32, Wrapper>(x);
+            let C = CastTransmute::<u32, NonZeroU32>(x); // Must check that `x` is not zero
+            Return()
+        }
+    }
+}
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+// EMIT_MIR newtype_transmutes.from_newtypes.InstSimplify.diff
+pub unsafe fn from_newtypes(a: Transparent, b: Wrapper) {
+    // CHECK-LABEL: fn from_newtypes(
+    // CHECK: _3 = (_1.0: u32);
+    // CHECK: _4 = (_2.0: u32);
+
+    mir! {
+        {
+            let A = CastTransmute::<Transparent, u32>(a);
+            let B = CastTransmute::<Wrapper, u32>(b);
+            Return()
+        }
+    }
+}
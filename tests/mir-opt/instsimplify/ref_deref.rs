@@ -0,0 +1,51 @@
+// unit-test: InstSimplify
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR ref_deref.shared.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn shared(x: &u32) -> &u32 {
+    // CHECK-LABEL: fn shared(
+    // CHECK: _0 = _1;
+    mir!({
+        RET = &*x;
+        Return()
+    })
+}
+
+// EMIT_MIR ref_deref.mutable.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn mutable(x: &mut u32) -> &mut u32 {
+    // CHECK-LABEL: fn mutable(
+    // CHECK: _0 = _1;
+    mir!({
+        RET = &mut *x;
+        Return()
+    })
+}
+
+// EMIT_MIR ref_deref.nested.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn nested(x: &&u32) -> &u32 {
+    // CHECK-LABEL: fn nested(
+    // CHECK: _0 = (*_1);
+    mir!({
+        RET = &**x;
+        Return()
+    })
+}
+
+// A shared reborrow of a mutable reference must stay a shared borrow.
+// EMIT_MIR ref_deref.mismatched.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn mismatched(x: &mut u32) -> &u32 {
+    // CHECK-LABEL: fn mismatched(
+    // CHECK: _0 = &(*_1);
+    mir!({
+        RET = &*x;
+        Return()
+    })
+}
@@ -0,0 +1,26 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// unit-test: InstSimplify
+
+// EMIT_MIR rem_pow2.rem_u32.InstSimplify.diff
+pub fn rem_u32(x: u32) -> u32 {
+    // CHECK-LABEL: fn rem_u32(
+    // CHECK-NOT: assert(
+    // CHECK: _0 = BitAnd(move _2, const 7_u32);
+    x % 8
+}
+
+// EMIT_MIR rem_pow2.rem_usize.InstSimplify.diff
+pub fn rem_usize(x: usize) -> usize {
+    // CHECK-LABEL: fn rem_usize(
+    // CHECK-NOT: assert(
+    // CHECK: _0 = BitAnd(move _2, const 15_usize);
+    x % 16
+}
+
+// EMIT_MIR rem_pow2.rem_i32.InstSimplify.diff
+pub fn rem_i32(x: i32) -> i32 {
+    // CHECK-LABEL: fn rem_i32(
+    // CHECK: assert(!move {{_.*}}, "attempt to compute the remainder of `{} % {}`, which would overflow"
+    // CHECK: _0 = Rem(move _2, const 8_i32);
+    x % 8
+}
@@ -0,0 +1,161 @@
+// unit-test: LoopInvariantLen
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR loop_invariant_len.hoist.LoopInvariantLen.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn hoist(s: &[u32], n: usize) -> usize {
+    // CHECK-LABEL: fn hoist(
+    // CHECK: bb0: {
+    // CHECK: [[len:_.*]] = Len((*_1));
+    // CHECK-NEXT: goto -> bb1;
+    // CHECK: bb2: {
+    // CHECK-NOT: Len(
+    // CHECK: _0 = Add(_0, [[len]]);
+    mir!(
+        let i: usize;
+        let len: usize;
+        {
+            RET = 0;
+            i = n;
+            Goto(header)
+        }
+        header = {
+            match i {
+                0 => exit,
+                _ => body,
+            }
+        }
+        body = {
+            len = Len(*s);
+            RET = RET + Move(len);
+            i = i - 1;
+            Goto(header)
+        }
+        exit = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR loop_invariant_len.modified.LoopInvariantLen.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn modified(s: &[u32], t: &[u32], n: usize) -> usize {
+    // CHECK-LABEL: fn modified(
+    // CHECK: bb2: {
+    // CHECK: Len((*_6));
+    mir!(
+        let i: usize;
+        let len: usize;
+        let x: &[u32];
+        {
+            RET = 0;
+            i = n;
+            x = s;
+            Goto(header)
+        }
+        header = {
+            match i {
+                0 => exit,
+                _ => body,
+            }
+        }
+        body = {
+            len = Len(*x);
+            RET = RET + Move(len);
+            x = t;
+            i = i - 1;
+            Goto(header)
+        }
+        exit = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR loop_invariant_len.borrowed.LoopInvariantLen.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn borrowed(s: &[u32], n: usize) -> usize {
+    // CHECK-LABEL: fn borrowed(
+    // CHECK: bb2: {
+    // CHECK: Len((*_1));
+    mir!(
+        let i: usize;
+        let len: usize;
+        let r: &mut &[u32];
+        {
+            RET = 0;
+            i = n;
+            r = &mut s;
+            Goto(header)
+        }
+        header = {
+            match i {
+                0 => exit,
+                _ => body,
+            }
+        }
+        body = {
+            len = Len(*s);
+            RET = RET + Move(len);
+            i = i - 1;
+            Goto(header)
+        }
+        exit = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR loop_invariant_len.nested.LoopInvariantLen.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn nested(s: &[u32], n: usize) -> usize {
+    // CHECK-LABEL: fn nested(
+    // CHECK: bb0: {
+    // CHECK: [[len:_.*]] = Len((*_1));
+    // CHECK-NEXT: goto -> bb1;
+    // CHECK-NOT: Len(
+    // CHECK: _0 = Add(_0, [[len]]);
+    mir!(
+        let i: usize;
+        let j: usize;
+        let len: usize;
+        {
+            RET = 0;
+            i = n;
+            Goto(outer)
+        }
+        outer = {
+            match i {
+                0 => exit,
+                _ => outer_body,
+            }
+        }
+        outer_body = {
+            j = n;
+            Goto(inner)
+        }
+        inner = {
+            match j {
+                0 => outer_latch,
+                _ => inner_body,
+            }
+        }
+        inner_body = {
+            len = Len(*s);
+            RET = RET + Move(len);
+            j = j - 1;
+            Goto(inner)
+        }
+        outer_latch = {
+            i = i - 1;
+            Goto(outer)
+        }
+        exit = {
+            Return()
+        }
+    )
+}
@@ -0,0 +1,71 @@
+// unit-test: MergeSwitchTargets
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR merge_switch_targets.storage_dead.MergeSwitchTargets.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn storage_dead(x: u8) -> u8 {
+    // CHECK-LABEL: fn storage_dead(
+    // CHECK: switchInt(_1) -> [0: [[bb:bb.*]], 1: [[bb]], otherwise: {{bb.*}}];
+    // CHECK-NOT: StorageDead(_2);
+    // CHECK: _0 = const 1_u8;
+    // CHECK-NEXT: return;
+    mir!(
+        let y: u8;
+        {
+            StorageLive(y);
+            y = x;
+            match x {
+                0 => a,
+                1 => b,
+                _ => c,
+            }
+        }
+        a = {
+            RET = 1;
+            StorageDead(y);
+            Return()
+        }
+        b = {
+            RET = 1;
+            Return()
+        }
+        c = {
+            RET = y;
+            StorageDead(y);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR merge_switch_targets.storage_live.MergeSwitchTargets.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn storage_live(x: u8) -> u8 {
+    // CHECK-LABEL: fn storage_live(
+    // CHECK: switchInt(_1) -> [0: bb1, otherwise: bb2];
+    mir!(
+        let y: u8;
+        {
+            StorageLive(y);
+            match x {
+                0 => a,
+                _ => b,
+            }
+        }
+        a = {
+            StorageDead(y);
+            StorageLive(y);
+            y = x;
+            RET = y;
+            Return()
+        }
+        b = {
+            y = x;
+            RET = y;
+            Return()
+        }
+    )
+}
@@ -0,0 +1,52 @@
+// unit-test: PruneRangeBranches
+
+// EMIT_MIR prune_range_branches.known_bound.PruneRangeBranches.diff
+pub fn known_bound(x: u32) -> u32 {
+    // CHECK-LABEL: fn known_bound(
+    // CHECK: bb1: {
+    // CHECK-NOT: switchInt(
+    // CHECK: _0 = const 1_u32;
+    // CHECK-NOT: const 2_u32;
+    // CHECK-NOT: const 3_u32;
+    if x < 5 {
+        match x {
+            0..=9 => 1,
+            10..=19 => 2,
+            _ => 3,
+        }
+    } else {
+        0
+    }
+}
+
+// EMIT_MIR prune_range_branches.constant.PruneRangeBranches.diff
+pub fn constant() -> u32 {
+    // CHECK-LABEL: fn constant(
+    // CHECK-NOT: switchInt(
+    // CHECK: _0 = const 2_u32;
+    let x = 12_u32;
+    match x {
+        0..=9 => 1,
+        10..=19 => 2,
+        _ => 3,
+    }
+}
+
+// EMIT_MIR prune_range_branches.unknown.PruneRangeBranches.diff
+pub fn unknown(x: i32) -> u32 {
+    // CHECK-LABEL: fn unknown(
+    // CHECK: _0 = const 1_u32;
+    // CHECK: _0 = const 2_u32;
+    // CHECK: _0 = const 3_u32;
+    match x {
+        0..=9 => 1,
+        10..=19 => 2,
+        _ => 3,
+    }
+}
+
+fn main() {
+    known_bound(3);
+    constant();
+    unknown(15);
+}
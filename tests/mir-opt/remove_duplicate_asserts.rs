@@ -0,0 +1,28 @@
+// unit-test: RemoveDuplicateAsserts
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// `pair` is what the body of a loop like `for i in 0..n { sum += get(v, i) * get(v, i) }` looks
+// like once `get` has been inlined.
+
+// EMIT_MIR remove_duplicate_asserts.pair.RemoveDuplicateAsserts.diff
+pub fn pair(v: &[u32], i: usize) -> (u32, u32) {
+    // CHECK-LABEL: fn pair(
+    // CHECK: assert(
+    // CHECK: bb1: {
+    // CHECK-NOT: assert(
+    // CHECK: goto -> bb2;
+    (v[i], v[i])
+}
+
+// EMIT_MIR remove_duplicate_asserts.different_slices.RemoveDuplicateAsserts.diff
+pub fn different_slices(v: &[u32], w: &[u32], i: usize) -> (u32, u32) {
+    // CHECK-LABEL: fn different_slices(
+    // CHECK: bb1: {
+    // CHECK: assert(
+    (v[i], w[i])
+}
+
+fn main() {
+    let _ = pair(&[1, 2, 3], 1);
+    let _ = different_slices(&[1, 2, 3], &[4, 5, 6], 1);
+}
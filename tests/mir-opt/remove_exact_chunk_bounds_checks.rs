@@ -0,0 +1,31 @@
+// unit-test: RemoveExactChunkBoundsChecks
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// EMIT_MIR remove_exact_chunk_bounds_checks.constant.RemoveExactChunkBoundsChecks.diff
+pub fn constant(slice: &[u32]) -> u32 {
+    // CHECK-LABEL: fn constant(
+    // CHECK: bb5: {
+    // CHECK-NOT: assert(
+    // CHECK: goto -> bb8;
+    let mut sum = 0;
+    for chunk in slice.chunks_exact(2) {
+        sum += chunk[1];
+    }
+    sum
+}
+
+// EMIT_MIR remove_exact_chunk_bounds_checks.runtime.RemoveExactChunkBoundsChecks.diff
+pub fn runtime(slice: &[u32], chunk_size: usize) -> u32 {
+    // CHECK-LABEL: fn runtime(
+    // CHECK: assert(
+    let mut sum = 0;
+    for chunk in slice.chunks_exact(chunk_size) {
+        sum += chunk[1];
+    }
+    sum
+}
+
+fn main() {
+    let _ = constant(&[1, 2, 3, 4]);
+    let _ = runtime(&[1, 2, 3, 4], 2);
+}
@@ -0,0 +1,72 @@
+// unit-test: RemoveNullChecks
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+use std::ptr::{addr_of, NonNull};
+
+// EMIT_MIR remove_null_checks.from_reference.RemoveNullChecks.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn from_reference(r: &u8) -> u8 {
+    // CHECK-LABEL: fn from_reference(
+    // CHECK: _4 = const false;
+    // CHECK-NOT: switchInt
+    // CHECK: _0 = (*_1);
+    mir!(
+        let p: *const u8;
+        let null: *const u8;
+        let is_null: bool;
+        {
+            p = addr_of!(*r);
+            null = CastTransmute::<usize, *const u8>(0);
+            is_null = p == null;
+            match is_null { false => bb2, _ => bb1 }
+        }
+        bb1 = {
+            RET = 0;
+            Return()
+        }
+        bb2 = {
+            RET = *r;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR remove_null_checks.from_non_null.RemoveNullChecks.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn from_non_null(p: NonNull<u8>) -> bool {
+    // CHECK-LABEL: fn from_non_null(
+    // CHECK: _3 = _2 as *mut u8 (PtrToPtr);
+    // CHECK: _0 = const true;
+    mir!(
+        let q: *const u8;
+        let m: *mut u8;
+        let null: *mut u8;
+        {
+            q = Field::<*const u8>(p, 0);
+            m = q as *mut u8;
+            null = CastTransmute::<usize, *mut u8>(0);
+            RET = m != null;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR remove_null_checks.from_address.RemoveNullChecks.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn from_address(addr: usize) -> bool {
+    // CHECK-LABEL: fn from_address(
+    // CHECK: _0 = Eq(_2, _3);
+    mir!(
+        let p: *const u8;
+        let null: *const u8;
+        {
+            p = CastTransmute::<usize, *const u8>(addr);
+            null = CastTransmute::<usize, *const u8>(0);
+            RET = p == null;
+            Return()
+        }
+    )
+}
@@ -0,0 +1,32 @@
+// unit-test: RemoveRangeLoopBoundsChecks
+// compile-flags: -Zmir-enable-passes=+LowerSliceLenCalls
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// EMIT_MIR remove_range_loop_bounds_checks.sum.RemoveRangeLoopBoundsChecks.diff
+pub fn sum(slice: &[u32]) -> u32 {
+    // CHECK-LABEL: fn sum(
+    // CHECK: bb5: {
+    // CHECK-NOT: assert(
+    // CHECK: goto -> bb8;
+    let mut sum = 0;
+    for i in 0..slice.len() {
+        sum += slice[i];
+    }
+    sum
+}
+
+// EMIT_MIR remove_range_loop_bounds_checks.other_slice.RemoveRangeLoopBoundsChecks.diff
+pub fn other_slice(slice: &[u32], other: &[u32]) -> u32 {
+    // CHECK-LABEL: fn other_slice(
+    // CHECK: assert(
+    let mut sum = 0;
+    for i in 0..other.len() {
+        sum += slice[i];
+    }
+    sum
+}
+
+fn main() {
+    let _ = sum(&[1, 2, 3]);
+    let _ = other_slice(&[1, 2, 3], &[1, 2]);
+}
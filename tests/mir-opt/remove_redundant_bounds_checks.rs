@@ -0,0 +1,32 @@
+// unit-test: RemoveRedundantBoundsChecks
+// compile-flags: -Zmir-enable-passes=+LowerSliceLenCalls
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// EMIT_MIR remove_redundant_bounds_checks.bound.RemoveRedundantBoundsChecks.diff
+pub fn bound(index: usize, slice: &[u8]) -> u8 {
+    // CHECK-LABEL: fn bound(
+    // CHECK: bb2: {
+    // CHECK-NOT: assert(
+    // CHECK: goto -> bb3;
+    if index < slice.len() {
+        slice[index]
+    } else {
+        42
+    }
+}
+
+// EMIT_MIR remove_redundant_bounds_checks.other_index.RemoveRedundantBoundsChecks.diff
+pub fn other_index(index: usize, other: usize, slice: &[u8]) -> u8 {
+    // CHECK-LABEL: fn other_index(
+    // CHECK: assert(
+    if index < slice.len() {
+        slice[other]
+    } else {
+        42
+    }
+}
+
+fn main() {
+    let _ = bound(1, &[1, 2, 3]);
+    let _ = other_index(1, 2, &[1, 2, 3]);
+}
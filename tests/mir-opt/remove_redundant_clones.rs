@@ -0,0 +1,85 @@
+// unit-test: RemoveRedundantClones
+// compile-flags: -Zunsound-mir-opts
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR remove_redundant_clones.dropped.RemoveRedundantClones.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn dropped(x: String) -> String {
+    // CHECK-LABEL: fn dropped(
+    // CHECK-NOT: clone
+    // CHECK: _2 = move _1;
+    // CHECK-NOT: drop(_1)
+    mir!(
+        let y: String;
+        let r: &String;
+        {
+            r = &x;
+            Call(y = Clone::clone(Move(r)), dropped, UnwindContinue())
+        }
+        dropped = {
+            Drop(x, ret, UnwindContinue())
+        }
+        ret = {
+            RET = Move(y);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR remove_redundant_clones.used_later.RemoveRedundantClones.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn used_later(x: String) -> String {
+    // `x` is still needed after the clone, so it can't be moved.
+    // CHECK-LABEL: fn used_later(
+    // CHECK: _2 = <String as Clone>::clone(move _3)
+    // CHECK: drop(_2)
+    mir!(
+        let y: String;
+        let r: &String;
+        {
+            r = &x;
+            Call(y = Clone::clone(Move(r)), dropped, UnwindContinue())
+        }
+        dropped = {
+            Drop(y, ret, UnwindContinue())
+        }
+        ret = {
+            RET = Move(x);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR remove_redundant_clones.dropped_before.RemoveRedundantClones.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn dropped_before(x: String, c: bool) -> String {
+    // The drop of `x` is shared with a path which doesn't clone it.
+    // CHECK-LABEL: fn dropped_before(
+    // CHECK: _3 = <String as Clone>::clone(move _4)
+    // CHECK: drop(_1)
+    mir!(
+        let y: String;
+        let r: &String;
+        {
+            match c { true => clone, _ => empty }
+        }
+        clone = {
+            r = &x;
+            Call(y = Clone::clone(Move(r)), dropped, UnwindContinue())
+        }
+        empty = {
+            Call(y = String::new(), dropped, UnwindContinue())
+        }
+        dropped = {
+            Drop(x, ret, UnwindContinue())
+        }
+        ret = {
+            RET = Move(y);
+            Return()
+        }
+    )
+}
@@ -15,6 +15,14 @@ fn opt_generic_copy<T: Copy>(x: T) {
     drop(x);
 }
 
+#[derive(Clone, Copy)]
+struct Pair(u32, u32);
+
+// EMIT_MIR remove_unneeded_drops.opt_copy_struct.RemoveUnneededDrops.diff
+fn opt_copy_struct(x: Pair) {
+    drop(x);
+}
+
 // EMIT_MIR remove_unneeded_drops.cannot_opt_generic.RemoveUnneededDrops.diff
 // since the pass is not running on monomorphisized code,
 // we can't (but probably should) optimize this
@@ -25,6 +33,7 @@ fn cannot_opt_generic<T>(x: T) {
 fn main() {
     opt(true);
     opt_generic_copy(42);
+    opt_copy_struct(Pair(1, 2));
     cannot_opt_generic(42);
     dont_opt(vec![true]);
 }
@@ -0,0 +1,67 @@
+// unit-test: ReserveVecCapacity
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR reserve_vec_capacity.pushes.ReserveVecCapacity.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn pushes() -> Vec<u32> {
+    // CHECK-LABEL: fn pushes(
+    // CHECK: _0 = Vec::<u32>::with_capacity(const 3_usize)
+    mir!(
+        let unit: ();
+        let r: &mut Vec<u32>;
+        {
+            Call(RET = Vec::new(), first, UnwindContinue())
+        }
+        first = {
+            r = &mut RET;
+            Call(unit = Vec::push(Move(r), 1), second, UnwindContinue())
+        }
+        second = {
+            r = &mut RET;
+            Call(unit = Vec::push(Move(r), 2), third, UnwindContinue())
+        }
+        third = {
+            r = &mut RET;
+            Call(unit = Vec::push(Move(r), 3), ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// The length is read between the pushes, so only one push is known to happen first.
+// EMIT_MIR reserve_vec_capacity.read.ReserveVecCapacity.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn read() -> Vec<u32> {
+    // CHECK-LABEL: fn read(
+    // CHECK: _0 = Vec::<u32>::new()
+    mir!(
+        let unit: ();
+        let r: &mut Vec<u32>;
+        let s: &Vec<u32>;
+        let len: usize;
+        {
+            Call(RET = Vec::new(), first, UnwindContinue())
+        }
+        first = {
+            r = &mut RET;
+            Call(unit = Vec::push(Move(r), 1), read, UnwindContinue())
+        }
+        read = {
+            s = &RET;
+            Call(len = Vec::len(Move(s)), second, UnwindContinue())
+        }
+        second = {
+            r = &mut RET;
+            Call(unit = Vec::push(Move(r), 2), ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
@@ -0,0 +1,85 @@
+// unit-test: ReuseFieldReads
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+pub struct Config {
+    flag: bool,
+    level: u32,
+}
+
+pub struct State {
+    config: Config,
+    count: u32,
+}
+
+fn bump(state: &mut State) {
+    state.count += 1;
+}
+
+// EMIT_MIR reuse_field_reads.reused.ReuseFieldReads.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn reused(state: &State) -> bool {
+    // CHECK-LABEL: fn reused(
+    // CHECK: _2 = (((*_1).0: Config).0: bool);
+    // CHECK: _4 = ((*_1).1: u32);
+    // CHECK: _3 = _2;
+    mir!(
+        let a: bool;
+        let b: bool;
+        let count: u32;
+        {
+            a = Field::<bool>(Field::<Config>(*state, 0), 0);
+            count = Field::<u32>(*state, 1);
+            b = Field::<bool>(Field::<Config>(*state, 0), 0);
+            RET = a & b;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR reuse_field_reads.written.ReuseFieldReads.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn written(state: &mut State) -> bool {
+    // CHECK-LABEL: fn written(
+    // CHECK: _2 = (((*_1).0: Config).0: bool);
+    // CHECK: _3 = (((*_1).0: Config).0: bool);
+    mir!(
+        let a: bool;
+        let b: bool;
+        {
+            a = Field::<bool>(Field::<Config>(*state, 0), 0);
+            place!(Field(Field(*state, 0), 0)) = false;
+            b = Field::<bool>(Field::<Config>(*state, 0), 0);
+            RET = a & b;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR reuse_field_reads.called.ReuseFieldReads.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn called(state: &mut State) -> u32 {
+    // CHECK-LABEL: fn called(
+    // CHECK: _2 = (((*_1).0: Config).1: u32);
+    // CHECK: bump(
+    // CHECK: _3 = (((*_1).0: Config).1: u32);
+    mir!(
+        let a: u32;
+        let b: u32;
+        let r: &mut State;
+        let unit: ();
+        {
+            a = Field::<u32>(Field::<Config>(*state, 0), 1);
+            r = &mut *state;
+            Call(unit = bump(Move(r)), next, UnwindContinue())
+        }
+        next = {
+            b = Field::<u32>(Field::<Config>(*state, 0), 1);
+            RET = a ^ b;
+            Return()
+        }
+    )
+}
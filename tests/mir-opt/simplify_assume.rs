@@ -0,0 +1,82 @@
+// unit-test: SimplifyConstCondition-after-const-prop
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::assume;
+use std::intrinsics::mir::*;
+
+// EMIT_MIR simplify_assume.constant_true.SimplifyConstCondition-after-const-prop.diff
+#[custom_mir(dialect = "analysis", phase = "post-cleanup")]
+pub unsafe fn constant_true() {
+    // CHECK-LABEL: fn constant_true(
+    // CHECK-NOT: assume(
+    mir!(
+        {
+            Call(RET = assume(true), ret, UnwindUnreachable())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR simplify_assume.constant_false.SimplifyConstCondition-after-const-prop.diff
+#[custom_mir(dialect = "analysis", phase = "post-cleanup")]
+pub unsafe fn constant_false() {
+    // CHECK-LABEL: fn constant_false(
+    // CHECK: bb0: {
+    // CHECK-NEXT: unreachable;
+    mir!(
+        {
+            Call(RET = assume(false), ret, UnwindUnreachable())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR simplify_assume.dominated.SimplifyConstCondition-after-const-prop.diff
+#[custom_mir(dialect = "analysis", phase = "post-cleanup")]
+pub unsafe fn dominated(x: bool) {
+    // CHECK-LABEL: fn dominated(
+    // CHECK: assume(_1);
+    // CHECK-NEXT: nop;
+    mir!(
+        {
+            Call(RET = assume(x), bb1, UnwindUnreachable())
+        }
+        bb1 = {
+            Call(RET = assume(x), ret, UnwindUnreachable())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// The first `assume` is only on one of the paths leading to the second one.
+// EMIT_MIR simplify_assume.not_dominated.SimplifyConstCondition-after-const-prop.diff
+#[custom_mir(dialect = "analysis", phase = "post-cleanup")]
+pub unsafe fn not_dominated(c: bool, x: bool) {
+    // CHECK-LABEL: fn not_dominated(
+    // CHECK: bb1: {
+    // CHECK-NEXT: assume(_2);
+    // CHECK: bb2: {
+    // CHECK-NEXT: assume(_2);
+    mir!(
+        {
+            match c { true => bb1, _ => bb2 }
+        }
+        bb1 = {
+            Call(RET = assume(x), bb2, UnwindUnreachable())
+        }
+        bb2 = {
+            Call(RET = assume(x), ret, UnwindUnreachable())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
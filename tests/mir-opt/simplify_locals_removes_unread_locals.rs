@@ -0,0 +1,66 @@
+// unit-test: SimplifyLocals-final
+
+// Locals which are written but never read lose their writes and their storage markers, unless
+// the write is the destination of a call.
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+#[inline(never)]
+fn opaque(x: u32) -> u32 {
+    x
+}
+
+// EMIT_MIR simplify_locals_removes_unread_locals.unread.SimplifyLocals-final.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn unread(x: u32) -> u32 {
+    // CHECK-LABEL: fn unread(
+    // CHECK-NOT: let
+    // CHECK: bb0: {
+    // CHECK-NEXT: _0 = _1;
+    // CHECK-NEXT: return;
+    mir!(
+        let y: u32;
+        let z: u32;
+        {
+            StorageLive(y);
+            y = x + 1;
+            StorageLive(z);
+            z = y;
+            RET = x;
+            StorageDead(z);
+            StorageDead(y);
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR simplify_locals_removes_unread_locals.call.SimplifyLocals-final.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn call(x: u32) -> u32 {
+    // CHECK-LABEL: fn call(
+    // CHECK: StorageLive(_2);
+    // CHECK-NEXT: _2 = opaque(_1)
+    // CHECK: bb1: {
+    // CHECK-NEXT: _0 = _1;
+    // CHECK-NEXT: StorageDead(_2);
+    // CHECK-NEXT: return;
+    mir!(
+        let y: u32;
+        let z: u32;
+        {
+            StorageLive(y);
+            Call(y = opaque(x), ret, UnwindContinue())
+        }
+        ret = {
+            StorageLive(z);
+            z = x;
+            RET = x;
+            StorageDead(z);
+            StorageDead(y);
+            Return()
+        }
+    )
+}
@@ -0,0 +1,82 @@
+// unit-test: SinkColdBlocks
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+#[cold]
+#[inline(never)]
+fn cold() -> u32 {
+    0
+}
+
+// EMIT_MIR sink_cold_blocks.checked.SinkColdBlocks.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn checked(x: u32) -> u32 {
+    // CHECK-LABEL: fn checked(
+    // CHECK: switchInt(_1) -> [0: bb3, 1: bb4, otherwise: bb1];
+    // CHECK: bb1: {
+    // CHECK-NEXT: _0 = _1;
+    // CHECK: bb2: {
+    // CHECK-NEXT: return;
+    // CHECK: bb3: {
+    // CHECK-NEXT: _0 = cold()
+    // CHECK: bb4: {
+    // CHECK-NEXT: unreachable;
+    mir!(
+        {
+            match x {
+                0 => zero,
+                1 => impossible,
+                _ => ok,
+            }
+        }
+        zero = {
+            Call(RET = cold(), ret, UnwindContinue())
+        }
+        impossible = {
+            Unreachable()
+        }
+        ok = {
+            RET = x;
+            Goto(ret)
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR sink_cold_blocks.propagated.SinkColdBlocks.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn propagated(x: bool) -> u32 {
+    // CHECK-LABEL: fn propagated(
+    // CHECK: switchInt(_1) -> [1: bb2, otherwise: bb1];
+    // CHECK: bb1: {
+    // CHECK-NEXT: _0 = const 2_u32;
+    // CHECK: bb2: {
+    // CHECK-NEXT: _0 = const 1_u32;
+    // CHECK-NEXT: goto -> bb3;
+    // CHECK: bb3: {
+    // CHECK-NEXT: unreachable;
+    mir!(
+        {
+            match x {
+                true => leads_to_unreachable,
+                _ => ok,
+            }
+        }
+        leads_to_unreachable = {
+            RET = 1;
+            Goto(impossible)
+        }
+        impossible = {
+            Unreachable()
+        }
+        ok = {
+            RET = 2;
+            Return()
+        }
+    )
+}
@@ -0,0 +1,106 @@
+// unit-test: StrengthReduction
+// compile-flags: -Coverflow-checks=on
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// Multiplications without overflow checks wrap, like shifts and additions.
+
+// EMIT_MIR strength_reduction.times_two.StrengthReduction.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn times_two(x: u32) -> u32 {
+    // CHECK-LABEL: fn times_two(
+    // CHECK: _0 = Shl(_1, const 1_u32);
+    mir!({
+        RET = x * 2;
+        Return()
+    })
+}
+
+// EMIT_MIR strength_reduction.times_three.StrengthReduction.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn times_three(x: u32) -> u32 {
+    // CHECK-LABEL: fn times_three(
+    // CHECK: [[shl:_.*]] = Shl(_1, const 1_u32);
+    // CHECK: _0 = Add(move [[shl]], _1);
+    mir!({
+        RET = x * 3;
+        Return()
+    })
+}
+
+// EMIT_MIR strength_reduction.times_four.StrengthReduction.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn times_four(x: u32) -> u32 {
+    // CHECK-LABEL: fn times_four(
+    // CHECK: _0 = Shl(_1, const 2_u32);
+    mir!({
+        RET = 4 * x;
+        Return()
+    })
+}
+
+// EMIT_MIR strength_reduction.times_eight.StrengthReduction.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn times_eight(x: u32) -> u32 {
+    // CHECK-LABEL: fn times_eight(
+    // CHECK: _0 = Shl(_1, const 3_u32);
+    mir!({
+        RET = x * 8;
+        Return()
+    })
+}
+
+// EMIT_MIR strength_reduction.times_nine.StrengthReduction.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn times_nine(x: u32) -> u32 {
+    // CHECK-LABEL: fn times_nine(
+    // CHECK: [[shl:_.*]] = Shl(_1, const 3_u32);
+    // CHECK: _0 = Add(move [[shl]], _1);
+    mir!({
+        RET = x * 9;
+        Return()
+    })
+}
+
+// EMIT_MIR strength_reduction.times_seven.StrengthReduction.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn times_seven(x: i8) -> i8 {
+    // CHECK-LABEL: fn times_seven(
+    // CHECK: [[shl:_.*]] = Shl(_1, const 3_u32);
+    // CHECK: _0 = Sub(move [[shl]], _1);
+    mir!({
+        RET = x * 7;
+        Return()
+    })
+}
+
+// With overflow checks, `x * 2` overflows exactly when `x + x` does.
+
+// EMIT_MIR strength_reduction.checked_times_two.StrengthReduction.diff
+pub fn checked_times_two(x: u32) -> u32 {
+    // CHECK-LABEL: fn checked_times_two(
+    // CHECK: [[add:_.*]] = CheckedAdd(_2, _2);
+    // CHECK: assert(!move ([[add]].1: bool), "attempt to compute `{} * {}`, which would overflow"
+    x * 2
+}
+
+// EMIT_MIR strength_reduction.checked_times_one.StrengthReduction.diff
+pub fn checked_times_one(x: u32) -> u32 {
+    // CHECK-LABEL: fn checked_times_one(
+    // CHECK: [[result:_.*]] = (_2, const false);
+    // CHECK-NOT: assert(
+    // CHECK: _0 = move ([[result]].0: u32);
+    x * 1
+}
+
+// EMIT_MIR strength_reduction.checked_times_nine.StrengthReduction.diff
+pub fn checked_times_nine(x: u32) -> u32 {
+    // CHECK-LABEL: fn checked_times_nine(
+    // CHECK: CheckedMul(_2, const 9_u32);
+    // CHECK: assert(
+    x * 9
+}
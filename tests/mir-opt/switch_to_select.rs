@@ -0,0 +1,107 @@
+// unit-test: SwitchToSelect
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+pub enum E {
+    A,
+    B,
+}
+
+// EMIT_MIR switch_to_select.on_bool.SwitchToSelect.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn on_bool(x: bool) -> u32 {
+    // CHECK-LABEL: fn on_bool(
+    // CHECK-NOT: switchInt
+    // CHECK: [[int:_.*]] = _1 as u32 (IntToInt);
+    // CHECK: [[mul:_.*]] = Mul(move [[int]], const 7_u32);
+    // CHECK: _0 = Add(move [[mul]], const 3_u32);
+    mir!(
+        {
+            match x {
+                false => zero,
+                _ => one,
+            }
+        }
+        one = {
+            RET = 10;
+            Goto(ret)
+        }
+        zero = {
+            RET = 3;
+            Goto(ret)
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR switch_to_select.on_discriminant.SwitchToSelect.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn on_discriminant(e: E) -> i8 {
+    // CHECK-LABEL: fn on_discriminant(
+    // CHECK-NOT: switchInt
+    // CHECK: [[eq:_.*]] = Eq(_2, const 0_isize);
+    // CHECK: [[int:_.*]] = move [[eq]] as i8 (IntToInt);
+    // CHECK: StorageDead(_2);
+    // CHECK: [[mul:_.*]] = Mul(move [[int]], const -5_i8);
+    // CHECK: _0 = Add(move [[mul]], const 6_i8);
+    mir!(
+        let d: isize;
+        {
+            StorageLive(d);
+            d = Discriminant(e);
+            match d {
+                0 => a,
+                1 => b,
+                _ => unreachable,
+            }
+        }
+        a = {
+            StorageDead(d);
+            RET = 1;
+            Goto(ret)
+        }
+        b = {
+            StorageDead(d);
+            RET = 6;
+            Goto(ret)
+        }
+        unreachable = {
+            Unreachable()
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR switch_to_select.side_effect.SwitchToSelect.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn side_effect(x: bool, y: &mut u32) -> u32 {
+    // CHECK-LABEL: fn side_effect(
+    // CHECK: switchInt(_1)
+    mir!(
+        {
+            match x {
+                false => zero,
+                _ => one,
+            }
+        }
+        one = {
+            RET = 10;
+            (*y) = 1;
+            Goto(ret)
+        }
+        zero = {
+            RET = 3;
+            Goto(ret)
+        }
+        ret = {
+            Return()
+        }
+    )
+}
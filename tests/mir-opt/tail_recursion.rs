@@ -0,0 +1,145 @@
+// unit-test: TailRecursionToLoop
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+#[inline(never)]
+fn make() -> String {
+    String::new()
+}
+
+// EMIT_MIR tail_recursion.sum.TailRecursionToLoop.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn sum(n: u64, acc: u64) -> u64 {
+    // CHECK-LABEL: fn sum(
+    // CHECK: bb0: {
+    // CHECK-NEXT: goto -> bb4;
+    // CHECK: _1 = move _5;
+    // CHECK-NEXT: _2 = move _6;
+    // CHECK-NEXT: goto -> bb4;
+    // CHECK-NOT: sum(
+    mir!(
+        let m: u64;
+        let a: u64;
+        {
+            match n {
+                0 => done,
+                _ => rec,
+            }
+        }
+        done = {
+            RET = acc;
+            Return()
+        }
+        rec = {
+            m = n - 1;
+            a = acc + n;
+            Call(RET = sum(Move(m), Move(a)), ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR tail_recursion.swapped.TailRecursionToLoop.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn swapped(a: u32, b: u32, n: u32) -> u32 {
+    // The new arguments read the current parameters, so they are all copied before any of them
+    // is assigned.
+    // CHECK-LABEL: fn swapped(
+    // CHECK: _5 = _2;
+    // CHECK-NEXT: _6 = _1;
+    // CHECK-NEXT: _7 = move _4;
+    // CHECK-NEXT: _1 = move _5;
+    // CHECK-NEXT: _2 = move _6;
+    // CHECK-NEXT: _3 = move _7;
+    // CHECK-NEXT: goto -> bb4;
+    mir!(
+        let m: u32;
+        {
+            match n {
+                0 => done,
+                _ => rec,
+            }
+        }
+        done = {
+            RET = a;
+            Return()
+        }
+        rec = {
+            m = n - 1;
+            Call(RET = swapped(b, a, Move(m)), ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR tail_recursion.dropped.TailRecursionToLoop.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn dropped(s: String, n: u32) -> u32 {
+    // `t` is dropped after the call returns, so the call is not in tail position.
+    // CHECK-LABEL: fn dropped(
+    // CHECK: _0 = dropped(move _1, move _3)
+    mir!(
+        let m: u32;
+        let t: String;
+        {
+            match n {
+                0 => done,
+                _ => rec,
+            }
+        }
+        done = {
+            RET = n;
+            Drop(s, ret, UnwindContinue())
+        }
+        rec = {
+            Call(t = make(), call, UnwindContinue())
+        }
+        call = {
+            m = n - 1;
+            Call(RET = dropped(Move(s), Move(m)), drop, UnwindContinue())
+        }
+        drop = {
+            Drop(t, ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR tail_recursion.borrowed.TailRecursionToLoop.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn borrowed(r: &u32, n: u32) -> u32 {
+    // The next call reads `m` through `r`, and `m` would be overwritten by a loop.
+    // CHECK-LABEL: fn borrowed(
+    // CHECK: _0 = borrowed(move _4, _3)
+    mir!(
+        let m: u32;
+        let p: &u32;
+        {
+            match n {
+                0 => done,
+                _ => rec,
+            }
+        }
+        done = {
+            RET = *r;
+            Return()
+        }
+        rec = {
+            m = n - 1;
+            p = &m;
+            Call(RET = borrowed(Move(p), m), ret, UnwindContinue())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
@@ -0,0 +1,135 @@
+// unit-test: ThreadOptionMatches
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR thread_option_matches.map_unwrap_or.ThreadOptionMatches.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn map_unwrap_or(x: Option<u32>, d: u32) -> u32 {
+    // `x.map(|v| v + 1).unwrap_or(d)`, once inlined.
+    // CHECK-LABEL: fn map_unwrap_or(
+    // CHECK-NOT: let {{.*}}: std::option::Option<u32>;
+    // CHECK: switchInt(_3) -> [0: bb2, 1: bb1, otherwise: bb3];
+    // CHECK: bb1: {
+    // CHECK: [[payload:_.*]] = move _5;
+    // CHECK-NEXT: _0 = move [[payload]];
+    // CHECK-NEXT: return;
+    // CHECK: bb2: {
+    // CHECK-NEXT: _0 = _2;
+    // CHECK-NEXT: return;
+    mir!(
+        let discr: isize;
+        let v: u32;
+        let w: u32;
+        let o: Option<u32>;
+        let od: isize;
+        {
+            discr = Discriminant(x);
+            match discr {
+                0 => none,
+                1 => some,
+                _ => unreachable_bb,
+            }
+        }
+        none = {
+            o = None;
+            Goto(join)
+        }
+        some = {
+            v = Field::<u32>(Variant(x, 1), 0);
+            w = v + 1;
+            o = Some(Move(w));
+            Goto(join)
+        }
+        join = {
+            od = Discriminant(o);
+            match od {
+                0 => default,
+                1 => value,
+                _ => unreachable_bb,
+            }
+        }
+        default = {
+            RET = d;
+            Return()
+        }
+        value = {
+            RET = Move(Field::<u32>(Variant(o, 1), 0));
+            Return()
+        }
+        unreachable_bb = {
+            Unreachable()
+        }
+    )
+}
+
+// EMIT_MIR thread_option_matches.and_then_unwrap_or.ThreadOptionMatches.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn and_then_unwrap_or(x: Option<u32>, d: u32) -> u32 {
+    // `x.and_then(|v| if v > 0 { Some(v) } else { None }).unwrap_or(d)`, once inlined.
+    // CHECK-LABEL: fn and_then_unwrap_or(
+    // CHECK-NOT: let {{.*}}: std::option::Option<u32>;
+    // CHECK: switchInt(_3) -> [0: bb3, 1: bb1, otherwise: bb4];
+    // CHECK: switchInt(_5) -> [1: bb2, otherwise: bb3];
+    // CHECK: bb2: {
+    // CHECK-NEXT: [[payload:_.*]] = _4;
+    // CHECK-NEXT: _0 = move [[payload]];
+    // CHECK: bb3: {
+    // CHECK-NEXT: _0 = _2;
+    mir!(
+        let discr: isize;
+        let v: u32;
+        let c: bool;
+        let o: Option<u32>;
+        let od: isize;
+        {
+            discr = Discriminant(x);
+            match discr {
+                0 => none,
+                1 => some,
+                _ => unreachable_bb,
+            }
+        }
+        none = {
+            o = None;
+            Goto(join)
+        }
+        some = {
+            v = Field::<u32>(Variant(x, 1), 0);
+            c = v > 0;
+            match c {
+                true => positive,
+                _ => zero,
+            }
+        }
+        positive = {
+            o = Some(v);
+            Goto(join)
+        }
+        zero = {
+            o = None;
+            Goto(join)
+        }
+        join = {
+            od = Discriminant(o);
+            match od {
+                0 => default,
+                1 => value,
+                _ => unreachable_bb,
+            }
+        }
+        default = {
+            RET = d;
+            Return()
+        }
+        value = {
+            RET = Move(Field::<u32>(Variant(o, 1), 0));
+            Return()
+        }
+        unreachable_bb = {
+            Unreachable()
+        }
+    )
+}
@@ -0,0 +1,52 @@
+#![feature(rustc_private)]
+
+//! This program implements a rustc driver that prints, for each function, the
+//! unsafe operations which are not in an `unsafe` block, as returned by
+//! `TyCtxt::unsafety_violations`. Inside of an `unsafe fn`, these are only
+//! linted by `unsafe_op_in_unsafe_fn`, so the crate still compiles.
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+
+use rustc_driver::Compilation;
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+
+fn main() {
+    let exit_code = rustc_driver::catch_with_exit_code(move || {
+        let rustc_args: Vec<_> = std::env::args().collect();
+        let mut callbacks = CompilerCalls;
+        rustc_driver::RunCompiler::new(&rustc_args, &mut callbacks).run()
+    });
+    std::process::exit(exit_code);
+}
+
+pub struct CompilerCalls;
+
+impl rustc_driver::Callbacks for CompilerCalls {
+    fn after_analysis<'tcx>(
+        &mut self,
+        compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        compiler.sess.abort_if_errors();
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            for def_id in tcx.hir().body_owners() {
+                if !matches!(tcx.def_kind(def_id), DefKind::Fn) {
+                    continue;
+                }
+                let violations: Vec<_> = tcx
+                    .unsafety_violations(def_id)
+                    .iter()
+                    .map(|violation| (violation.kind, &violation.details))
+                    .collect();
+                println!("{}: {:?}", tcx.def_path_str(def_id), violations);
+            }
+        });
+
+        Compilation::Continue
+    }
+}
@@ -0,0 +1,15 @@
+unsafe fn dangerous() {}
+
+unsafe fn in_unsafe_fn(ptr: *const u8) -> u8 {
+    dangerous();
+    *ptr
+}
+
+fn in_unsafe_block() {
+    unsafe { dangerous() }
+}
+
+fn main() {
+    unsafe { in_unsafe_fn(&0) };
+    in_unsafe_block();
+}
@@ -0,0 +1,9 @@
+#![crate_type = "lib"]
+
+pub fn foo(x: u32) -> u32 {
+    if x > 10 { x - 10 } else { x + 1 }
+}
+
+pub fn bar() -> Vec<u32> {
+    vec![foo(1)]
+}
@@ -0,0 +1,16 @@
+static mut COUNTER: u32 = 0;
+
+pub unsafe fn bump() {
+    COUNTER += 1;
+}
+
+pub fn read(p: *const u32) -> u32 {
+    unsafe {
+        bump();
+        *p
+    }
+}
+
+pub fn safe() -> u32 {
+    1
+}
@@ -0,0 +1,26 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+operations = json.load(sys.stdin)
+assert isinstance(operations, list)
+
+found = set()
+for operation in operations:
+    assert set(operation) == {"span", "kind", "in_unsafe_fn"}, operation
+    span = operation["span"]
+    assert set(span) == {"file_name", "line_start", "column_start", "line_end", "column_end"}, span
+    assert span["file_name"] == "foo.rs", span
+    for key in ("line_start", "column_start", "line_end", "column_end"):
+        assert isinstance(span[key], int) and span[key] >= 1, span
+    assert isinstance(operation["kind"], str), operation
+    assert isinstance(operation["in_unsafe_fn"], bool), operation
+    found.add((operation["kind"], span["line_start"], operation["in_unsafe_fn"]))
+
+expected = {
+    ("use_of_mutable_static", 4, True),
+    ("call_to_unsafe_function", 9, False),
+    ("deref_of_raw_pointer", 10, False),
+}
+assert found == expected, found
@@ -0,0 +1,13 @@
+#[inline]
+pub fn small(x: u32) -> u32 {
+    x + 1
+}
+
+#[inline(never)]
+pub fn never(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn caller(x: u32) -> u32 {
+    never(small(x))
+}
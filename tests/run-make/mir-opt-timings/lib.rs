@@ -0,0 +1,16 @@
+#[inline]
+pub fn small(x: u32) -> u32 {
+    x + 1
+}
+
+pub fn sum(slice: &[u32]) -> u32 {
+    let mut sum = 0;
+    for &x in slice {
+        sum += small(x);
+    }
+    sum
+}
+
+pub fn caller(x: u32) -> u32 {
+    small(x) * 2
+}
@@ -0,0 +1,24 @@
+// Defaults of associated consts in traits are only evaluated if an impl does not override them,
+// so overflows in them are only warned about by default.
+
+#![crate_type = "lib"]
+
+pub trait Foo {
+    const ADD: u8 = 200 + 100;
+    //~^ WARN this arithmetic operation will overflow
+    const NEG: i32 = -i32::MIN;
+    //~^ WARN this arithmetic operation will overflow
+}
+
+// An explicitly requested level is still respected.
+pub trait Bar {
+    #[deny(arithmetic_overflow)]
+    const ADD: u8 = 200 + 100;
+    //~^ ERROR this arithmetic operation will overflow
+}
+
+// Overrides in impls are linted like normal.
+impl Foo for () {
+    const ADD: u8 = 255 + 1;
+    //~^ ERROR this arithmetic operation will overflow
+}
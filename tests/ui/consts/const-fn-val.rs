@@ -1,6 +1,7 @@
 // run-pass
 #![allow(non_upper_case_globals)]
 #![allow(overflowing_literals)]
+#![allow(static_mut_never_written)]
 
 fn foo() -> isize {
     return 0xca7f000d;
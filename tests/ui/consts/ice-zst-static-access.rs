@@ -5,6 +5,8 @@
 // and
 // https://github.com/rust-lang/rust/issues/71709
 
+#![allow(static_mut_never_written)]
+
 #[derive(Copy, Clone)]
 pub struct Glfw;
 
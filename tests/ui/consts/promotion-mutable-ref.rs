@@ -1,5 +1,6 @@
 // run-pass
 #![feature(const_mut_refs)]
+#![allow(static_mut_never_written)]
 
 static mut TEST: i32 = {
     // We must not promote this, as CTFE needs to be able to mutate it later.
@@ -0,0 +1,6 @@
+unsafe extern "C" {
+    //~^ ERROR extern block cannot be declared unsafe
+    static VALUE: u32;
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+// Checks that an overflow in a generic function is only reported once, however many times the
+// function is instantiated.
+// build-pass
+
+#![warn(arithmetic_overflow)]
+
+fn overflow<T>(_: T) -> u8 {
+    255u8 + 1
+    //~^ WARN this arithmetic operation will overflow
+}
+
+fn main() {
+    overflow(0u8);
+    overflow(0i32);
+    overflow("");
+    overflow(());
+}
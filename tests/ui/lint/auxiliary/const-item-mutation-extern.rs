@@ -0,0 +1 @@
+pub const EXTERN: Vec<i32> = Vec::new();
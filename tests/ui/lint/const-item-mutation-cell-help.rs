@@ -0,0 +1,29 @@
+// check-pass
+// Mutably borrowing a `const` item points at interior mutability, and at `Cell` for the types
+// which are `Copy`.
+
+#[derive(Clone, Copy)]
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+struct Slot<T>(T);
+
+impl<T: Copy> Slot<T> {
+    const EMPTY: Option<T> = None;
+}
+
+const COUNTER: Counter = Counter { count: 0 };
+const NAMES: Vec<String> = Vec::new();
+
+fn main() {
+    COUNTER.increment(); //~ WARN taking a mutable reference to a `const` item
+    Slot::<u8>::EMPTY.take(); //~ WARN taking a mutable reference to a `const` item
+    NAMES.push(String::new()); //~ WARN taking a mutable reference to a `const` item
+}
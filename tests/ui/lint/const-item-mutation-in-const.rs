@@ -0,0 +1,21 @@
+// check-pass
+// The initializers of `const` items are checked for mutations of other `const` items too.
+
+struct MyStruct {
+    field: bool,
+}
+
+const MY_STRUCT: MyStruct = MyStruct { field: true };
+
+const MODIFIES: bool = {
+    MY_STRUCT.field = false; //~ WARN attempting to modify a `const` item
+    MY_STRUCT.field
+};
+
+const MODIFIES_COPY: bool = {
+    let mut copy = MY_STRUCT;
+    copy.field = false;
+    MY_STRUCT.field && !copy.field
+};
+
+fn main() {}
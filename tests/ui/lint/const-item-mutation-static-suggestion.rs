@@ -0,0 +1,20 @@
+// check-pass
+// aux-build:const-item-mutation-extern.rs
+
+extern crate const_item_mutation_extern;
+
+struct MyStruct;
+
+impl MyStruct {
+    const ASSOC: Vec<i32> = Vec::new();
+}
+
+pub const LOCAL: Vec<i32> = Vec::new();
+pub const EMPTY: &mut [i32] = &mut [];
+
+fn main() {
+    LOCAL.push(0); //~ WARN taking a mutable reference to a `const` item
+    MyStruct::ASSOC.push(0); //~ WARN taking a mutable reference to a `const` item
+    const_item_mutation_extern::EXTERN.push(0); //~ WARN taking a mutable reference to a `const` item
+    &mut EMPTY; //~ WARN taking a mutable reference to a `const` item
+}
@@ -0,0 +1,11 @@
+// check-pass
+
+// The write to the temporary created from a `const` item is labeled, and the definition of the
+// `const` item is pointed at in a note.
+
+const ARRAY: [u32; 2] = [0, 0];
+
+fn main() {
+    ARRAY[1] = 1;
+    //~^ WARN attempting to modify a `const` item
+}
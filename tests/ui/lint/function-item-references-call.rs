@@ -0,0 +1,24 @@
+// check-pass
+// Suggest calling functions without arguments whose return value can be formatted as a pointer.
+#![warn(function_item_references)]
+
+fn get_ref() -> &'static u32 {
+    &42
+}
+
+fn get_ptr(x: &u32) -> *const u32 {
+    x
+}
+
+unsafe fn unsafe_get_ref() -> &'static u32 {
+    &42
+}
+
+fn main() {
+    println!("{:p}", &get_ref);
+    //~^ WARN taking a reference to a function item does not give a function pointer
+    println!("{:p}", &get_ptr);
+    //~^ WARN taking a reference to a function item does not give a function pointer
+    println!("{:p}", &unsafe_get_ref);
+    //~^ WARN taking a reference to a function item does not give a function pointer
+}
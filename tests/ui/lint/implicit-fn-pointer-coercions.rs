@@ -0,0 +1,33 @@
+// check-pass
+// Function items implicitly coerced to function pointers are linted, unlike explicit casts.
+#![warn(implicit_fn_pointer_coercions)]
+
+fn foo(x: u32) -> u32 {
+    x
+}
+
+fn generic<T>(x: T) -> T {
+    x
+}
+
+unsafe fn unsafe_foo() {}
+
+fn apply(f: fn(u32) -> u32) -> u32 {
+    f(1)
+}
+
+fn main() {
+    // References to function items formatted as pointers are linted by `function_item_references`.
+    println!("{:p}", &foo);
+    //~^ WARN taking a reference to a function item does not give a function pointer
+
+    apply(foo);
+    //~^ WARN function item `foo` is implicitly coerced to a function pointer
+    let _generic: fn(u32) -> u32 = generic::<u32>;
+    //~^ WARN function item `generic` is implicitly coerced to a function pointer
+    let _unsafe: unsafe fn() = unsafe_foo;
+    //~^ WARN function item `unsafe_foo` is implicitly coerced to a function pointer
+
+    apply(foo as fn(u32) -> u32);
+    let _address = foo as usize;
+}
@@ -0,0 +1,36 @@
+// edition:2018
+#![feature(must_not_suspend)]
+#![deny(must_not_suspend)]
+
+#[must_not_suspend]
+struct No {}
+
+async fn other() {}
+
+async fn allowed_binding() {
+    #[allow(must_not_suspend)]
+    let no = No {};
+    other().await;
+    drop(no);
+}
+
+async fn allowed_binding_then_held() {
+    #[allow(must_not_suspend)]
+    let allowed = No {};
+    other().await;
+    drop(allowed);
+
+    let no = No {};
+    //~^ ERROR `No` held across
+    other().await;
+    drop(no);
+}
+
+#[allow(must_not_suspend)]
+async fn allowed_fn() {
+    let no = No {};
+    other().await;
+    drop(no);
+}
+
+fn main() {}
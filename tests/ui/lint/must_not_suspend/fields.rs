@@ -0,0 +1,48 @@
+// edition:2018
+#![feature(must_not_suspend)]
+#![deny(must_not_suspend)]
+
+// The fields of ADTs are looked through, and at most five steps of the path to the
+// `#[must_not_suspend]` type are noted.
+
+#[must_not_suspend = "You gotta use Umm's, ya know?"]
+struct Umm {
+    i: i64,
+}
+
+struct Level1 {
+    umm: Umm,
+}
+
+struct Level2(Level1);
+
+enum Level3 {
+    Held(Level2),
+    Empty,
+}
+
+struct Level4 {
+    level3: Level3,
+}
+
+struct Level5 {
+    level4: Level4,
+}
+
+fn bar() -> Level5 {
+    Level5 { level4: Level4 { level3: Level3::Held(Level2(Level1 { umm: Umm { i: 1 } })) } }
+}
+
+async fn other() {}
+
+pub async fn five_steps() {
+    let _guard = bar(); //~ ERROR `Umm` held across
+    other().await;
+}
+
+pub async fn six_steps() {
+    let _guard = Box::new(bar()); //~ ERROR `Umm` held across
+    other().await;
+}
+
+fn main() {}
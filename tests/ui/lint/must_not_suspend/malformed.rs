@@ -0,0 +1,13 @@
+// edition:2018
+#![feature(must_not_suspend)]
+
+#[must_not_suspend(reason = "You gotta use Umm's, ya know?", note = "Umm is held here")]
+//~^ ERROR malformed `must_not_suspend` attribute
+struct Umm {
+    i: i64
+}
+
+#[must_not_suspend(label)] //~ ERROR malformed `must_not_suspend` attribute
+struct Ahh;
+
+fn main() {}
@@ -0,0 +1,21 @@
+// edition:2018
+#![feature(must_not_suspend)]
+#![deny(must_not_suspend)]
+
+#[must_not_suspend = "You gotta use Umm's, ya know?"]
+struct Umm {
+    i: i64,
+}
+
+fn bar() -> (u8, Box<[Umm; 1]>) {
+    (0, Box::new([Umm { i: 1 }]))
+}
+
+async fn other() {}
+
+pub async fn uhoh() {
+    let _guard = bar(); //~ ERROR boxed array of `Umm` in tuple element 1 held across
+    other().await;
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// edition:2018
+// compile-flags: -Zverbose
+// With `-Zverbose`, the lint notes the state of the coroutine in which the value is held.
+#![feature(must_not_suspend)]
+#![deny(must_not_suspend)]
+
+#[must_not_suspend]
+struct No {}
+
+#[must_not_suspend]
+struct Nope {}
+
+async fn other() {}
+
+async fn states() {
+    let no = No {};
+    //~^ ERROR `No` held across
+    other().await;
+    drop(no);
+    other().await;
+    let nope = Nope {};
+    //~^ ERROR `Nope` held across
+    other().await;
+    drop(nope);
+}
+
+fn main() {}
@@ -0,0 +1,58 @@
+// Checks that `redundant_unchecked_indexing` only lints unchecked indexing with an index which
+// was already checked against the length of the same slice on every path to it.
+
+#![deny(redundant_unchecked_indexing)]
+
+pub fn checked(v: &[u32], i: usize) -> u32 {
+    if i < v.len() {
+        unsafe { *v.get_unchecked(i) }
+        //~^ ERROR unchecked indexing with an index which is already checked
+    } else {
+        0
+    }
+}
+
+pub fn asserted(v: &mut [u32], i: usize) {
+    assert!(i < v.len());
+    unsafe { *v.get_unchecked_mut(i) = 0 };
+    //~^ ERROR unchecked indexing with an index which is already checked
+}
+
+pub fn early_return(v: &[u32], i: usize) -> u32 {
+    if v.len() <= i {
+        return 0;
+    }
+    unsafe { *v.get_unchecked(i) }
+    //~^ ERROR unchecked indexing with an index which is already checked
+}
+
+pub fn unchecked(v: &[u32], i: usize) -> u32 {
+    unsafe { *v.get_unchecked(i) }
+}
+
+pub fn other_branch(v: &[u32], i: usize) -> u32 {
+    if i < v.len() { 0 } else { unsafe { *v.get_unchecked(i) } }
+}
+
+pub fn other_slice(v: &[u32], w: &[u32], i: usize) -> u32 {
+    if i < w.len() { unsafe { *v.get_unchecked(i) } } else { 0 }
+}
+
+pub fn other_index(v: &[u32], i: usize, j: usize) -> u32 {
+    if i < v.len() { unsafe { *v.get_unchecked(j) } } else { 0 }
+}
+
+pub fn reassigned_index(v: &[u32], mut i: usize) -> u32 {
+    if i < v.len() {
+        i += 1;
+        unsafe { *v.get_unchecked(i) }
+    } else {
+        0
+    }
+}
+
+pub fn range(v: &[u32], i: usize) -> &[u32] {
+    if i < v.len() { unsafe { v.get_unchecked(..i) } } else { &[] }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+// check-pass
+// Mutable statics which are only ever read could be immutable.
+
+use std::cell::Cell;
+use std::ptr::addr_of_mut;
+
+static mut READ: u32 = 0;
+//~^ WARN mutable static `READ` is never written
+
+static mut WRITTEN: u32 = 0;
+
+static mut BORROWED_MUT: u32 = 0;
+
+static mut ADDRESS_TAKEN: u32 = 0;
+
+static mut INTERIOR_MUTABLE: Cell<u32> = Cell::new(0);
+
+#[no_mangle]
+static mut EXTERNAL: u32 = 0;
+
+// These can't be immutable statics, as they aren't `Sync`, or could be written through a shared
+// reference.
+static mut NOT_SYNC: *const u32 = std::ptr::null();
+
+static mut ONLY_READ_CELL: Cell<u32> = Cell::new(0);
+
+fn main() {
+    unsafe {
+        WRITTEN = READ;
+        let borrowed = &mut BORROWED_MUT;
+        *borrowed += 1;
+        *addr_of_mut!(ADDRESS_TAKEN) = 1;
+        INTERIOR_MUTABLE.set(1);
+        let _external = EXTERNAL;
+        let _not_sync = NOT_SYNC;
+        let _only_read_cell = ONLY_READ_CELL.get();
+    }
+}
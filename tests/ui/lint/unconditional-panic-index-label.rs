@@ -0,0 +1,16 @@
+// build-fail
+// Check that the index of an indexing operation which will always panic is labelled.
+
+fn main() {
+    let array = [1, 2, 3];
+    let _ = array[5];
+    //~^ ERROR this operation will panic at runtime
+
+    let slice: &[i32] = &array;
+    let _ = slice[3];
+    //~^ ERROR this operation will panic at runtime
+
+    let index = 4;
+    let _ = array[index];
+    //~^ ERROR this operation will panic at runtime
+}
@@ -0,0 +1,43 @@
+// run-pass
+// needs-unwind
+// revisions: checks nochecks
+// compile-flags: -Zmir-opt-level=2
+// [checks]compile-flags: -Coverflow-checks=on
+// [nochecks]compile-flags: -Coverflow-checks=off
+
+// Check that the multiplications replacing `pow` with a small constant exponent overflow exactly
+// when `pow` does, panicking with overflow checks and wrapping without them.
+
+use std::hint::black_box;
+use std::panic::{self, catch_unwind};
+
+macro_rules! check_pow {
+    ($x:expr, $($exp:literal),*) => {{
+        let x = black_box($x);
+        $(
+            let expected = if cfg!(checks) {
+                x.checked_pow($exp)
+            } else {
+                Some(x.wrapping_pow($exp))
+            };
+            assert_eq!(catch_unwind(|| x.pow($exp)).ok(), expected, "{}.pow({})", x, $exp);
+        )*
+    }};
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    for x in [0u8, 1, 2, 3, 6, 7, 15, 16, 255] {
+        check_pow!(x, 0, 1, 2, 3, 7, 8);
+    }
+    for x in [i8::MIN, -16, -6, -5, -2, -1, 0, 1, 2, 5, 6, 11, 12, i8::MAX] {
+        check_pow!(x, 0, 1, 2, 3, 7, 8);
+    }
+    for x in [u32::MAX, 1 << 16, (1 << 16) - 1, 1625, 1626] {
+        check_pow!(x, 0, 1, 2, 3);
+    }
+    for x in [i64::MIN, -2_097_152, -2_097_151, 2_097_151, 2_097_152, i64::MAX] {
+        check_pow!(x, 0, 1, 2, 3);
+    }
+}
@@ -0,0 +1,24 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=3 -Copt-level=0
+// Self-recursive calls in tail position are turned into loops, so recursing this deep doesn't
+// overflow the stack even without LLVM optimizations.
+
+fn sum(n: u64, acc: u64) -> u64 {
+    if n == 0 { acc } else { sum(n - 1, acc + n) }
+}
+
+fn swapped(a: u32, b: u32, n: u32) -> (u32, u32) {
+    if n == 0 { (a, b) } else { swapped(b, a, n - 1) }
+}
+
+// Borrowing `s` keeps this one recursive.
+fn count(s: String, n: usize) -> usize {
+    if n == 0 { s.len() } else { count(s + "a", n - 1) }
+}
+
+fn main() {
+    const DEPTH: u64 = 10_000_000;
+    assert_eq!(sum(DEPTH, 0), DEPTH * (DEPTH + 1) / 2);
+    assert_eq!(swapped(1, 2, 10_000_001), (2, 1));
+    assert_eq!(count(String::new(), 1000), 1000);
+}
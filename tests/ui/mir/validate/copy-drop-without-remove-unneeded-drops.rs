@@ -0,0 +1,14 @@
+// Drops of `Copy` values are only rejected in optimized MIR when `RemoveUnneededDrops` has
+// removed them, so disabling the pass must not make the validator fail.
+
+// build-pass
+// compile-flags: -Zinline-mir=yes -Zmir-enable-passes=-RemoveUnneededDrops -Zvalidate-mir
+
+#![crate_type = "lib"]
+
+#[derive(Clone, Copy)]
+pub struct Pair(u32, u32);
+
+pub fn drop_pair(x: Pair) {
+    drop(x);
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 // ignore-emscripten weird assertion?
+// normalize-stderr-test "requires alignment \d+" -> "requires alignment $$ALIGN"
 
 #[repr(packed)]
 struct Foo1 {
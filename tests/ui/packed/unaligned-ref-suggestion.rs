@@ -0,0 +1,21 @@
+// Check the notes and suggestions offered when taking a reference to a packed field.
+
+#[allow(dead_code)]
+struct NotCopy(u32);
+
+#[repr(packed)]
+struct Packed {
+    x: u8,
+    y: u32,
+    s: NotCopy,
+}
+
+fn main() {
+    let mut p = Packed { x: 0, y: 1, s: NotCopy(2) };
+    let _ = &p.y; //~ ERROR reference to packed field is unaligned
+    let _ = &mut p.y; //~ ERROR reference to packed field is unaligned
+    let _ = &p.s; //~ ERROR reference to packed field is unaligned
+    let _ = &p.x; // ok, has align 1
+    let _ = &p.y as *const u32; //~ ERROR reference to packed field is unaligned
+    let _: *const u32 = &p.y; //~ ERROR reference to packed field is unaligned
+}
@@ -0,0 +1,21 @@
+// only-x86_64
+
+#![feature(target_feature_11)]
+
+#[target_feature(enable = "avx,bmi1,bmi2")]
+fn avx_bmi1_bmi2() {}
+
+#[target_feature(enable = "bmi1")]
+fn subset() {
+    avx_bmi1_bmi2();
+    //~^ ERROR call to function with `#[target_feature]` is unsafe
+}
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "bmi1")]
+fn nested_subset() {
+    avx_bmi1_bmi2();
+    //~^ ERROR call to function with `#[target_feature]` is unsafe
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+// Tests the target features collected by the `required_target_features` query, which are the
+// features of all the `#[target_feature]` functions called, sorted by name.
+
+// only-x86_64
+
+#![feature(rustc_attrs)]
+#![feature(target_feature_11)]
+
+#[target_feature(enable = "bmi2")]
+#[target_feature(enable = "avx")]
+fn avx_bmi2() {}
+
+#[target_feature(enable = "fma")]
+fn fma() {}
+
+#[rustc_dump_required_target_features]
+fn safe_caller() {
+    //~^ ERROR required target features: avx, bmi2
+    avx_bmi2();
+    //~^ ERROR call to function with `#[target_feature]` is unsafe
+}
+
+#[rustc_dump_required_target_features]
+fn unsafe_block_caller() {
+    //~^ ERROR required target features: avx, bmi2, fma
+    unsafe {
+        fma();
+        avx_bmi2();
+    }
+}
+
+#[rustc_dump_required_target_features]
+#[target_feature(enable = "avx")]
+fn target_feature_caller() {
+    avx_bmi2();
+    //~^ ERROR call to function with `#[target_feature]` is unsafe
+    //~^^^ ERROR required target features: avx, bmi2
+}
+
+// Calls in closures are not taken into account.
+#[rustc_dump_required_target_features]
+fn closure_caller() {
+    //~^ ERROR required target features: fma
+    unsafe { fma() };
+    let _ = || unsafe { avx_bmi2() };
+}
+
+fn main() {}
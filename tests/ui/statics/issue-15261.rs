@@ -1,6 +1,7 @@
 // build-pass
 #![allow(dead_code)]
 #![allow(non_upper_case_globals)]
+#![allow(static_mut_never_written)]
 
 // pretty-expanded FIXME #23616
 
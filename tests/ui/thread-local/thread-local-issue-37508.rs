@@ -7,6 +7,7 @@
 #![no_main]
 #![no_std]
 #![feature(thread_local, lang_items)]
+#![allow(static_mut_never_written)]
 
 #[lang = "eh_personality"]
 extern "C" fn eh_personality() {}
@@ -0,0 +1,31 @@
+// compile-flags: -Ztrait-solver=next
+
+// Derived from `generalize/occurs-check-nested-alias.rs`. Nothing constrains the inference
+// variables in the alias, so relating it is ambiguous because we need more type information,
+// not because an impl is missing.
+
+trait Id<T> {
+    type Id;
+}
+impl<T, U> Id<T> for U {
+    type Id = U;
+}
+
+struct Inv<T>(*mut T);
+
+fn unconstrained<T>() -> T {
+    todo!()
+}
+
+fn create<T, U>(_: &U) -> (Inv<T>, Inv<<U as Id<T>>::Id>) {
+    todo!()
+}
+
+fn foo() {
+    let q = unconstrained();
+    let (_, y) = create::<_, _>(&q);
+    let _: Inv<u32> = y;
+    //~^ ERROR type annotations needed to resolve `<_ as Id<_>>::Id`
+}
+
+fn main() {}
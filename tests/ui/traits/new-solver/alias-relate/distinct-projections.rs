@@ -0,0 +1,17 @@
+// compile-flags: -Ztrait-solver=next
+
+// Check that we explain why two rigid projections from different
+// trait references cannot be related.
+
+trait Trait {
+    type Assoc;
+}
+
+struct Inv<T>(*mut T);
+
+fn foo<T: Trait, U: Trait>(x: Inv<T::Assoc>) -> Inv<U::Assoc> {
+    x
+    //~^ ERROR type mismatch resolving `<T as Trait>::Assoc == <U as Trait>::Assoc`
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// compile-flags: -Ztrait-solver=next
+
+// The fulfillment context only keeps one of several identical `AliasRelate` goals, so equating
+// the same two rigid projections twice is only reported once. This matches the old solver,
+// whose obligation forest deduplicates obligations as well.
+
+trait Trait {
+    type Assoc;
+}
+
+struct Inv<T>(*mut T);
+
+fn twice<T: Trait, U: Trait>(
+    x: Inv<T::Assoc>,
+    y: Inv<T::Assoc>,
+) -> (Inv<U::Assoc>, Inv<U::Assoc>) {
+    (x, y)
+    //~^ ERROR type mismatch resolving `<T as Trait>::Assoc == <U as Trait>::Assoc`
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+// compile-flags: -Ztrait-solver=next
+// check-pass
+
+// Makes sure that relating an associated const with a value is deferred
+// while the const depends on an unresolved const inference variable.
+
+#![feature(associated_const_equality)]
+
+trait Size {
+    const SIZE: usize;
+}
+
+impl<const N: usize> Size for [u8; N] {
+    const SIZE: usize = N;
+}
+
+fn zeroed<const N: usize>() -> [u8; N] {
+    [0; N]
+}
+
+fn requires_size<T: Size<SIZE = 4>>(_: &T) {}
+
+fn main() {
+    let bytes = zeroed();
+    // `<[u8; ?n] as Size>::SIZE == 4` is only decided once `?n` is inferred below.
+    requires_size(&bytes);
+    let _: [u8; 4] = bytes;
+}
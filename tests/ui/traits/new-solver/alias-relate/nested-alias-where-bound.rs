@@ -0,0 +1,39 @@
+// compile-flags: -Ztrait-solver=next
+// check-pass
+
+// Like `generalize/occurs-check-nested-alias.rs`, but the outer projection is only rigid
+// thanks to a where-bound on the inner one. Equating `?T` with
+// `<<?U as Id<?T>>::Id as Unnormalizable>::Assoc` has to normalize the inner alias to `T`,
+// after which the projection no longer mentions `?T`.
+#![crate_type = "lib"]
+#![allow(unused)]
+trait Unnormalizable {
+    type Assoc;
+}
+
+trait Id<T> {
+    type Id;
+}
+impl<T, U> Id<T> for U {
+    type Id = U;
+}
+
+struct Inv<T>(*mut T);
+
+fn unconstrained<T>() -> T {
+    todo!()
+}
+
+fn create<T, U>(x: &U) -> (Inv<T>, Inv<<<U as Id<T>>::Id as Unnormalizable>::Assoc>)
+where
+    <U as Id<T>>::Id: Unnormalizable,
+{
+    todo!()
+}
+
+fn foo<T: Unnormalizable>() {
+    let q = unconstrained();
+    let (mut x, y) = create::<_, _>(&q);
+    x = y;
+    drop::<T>(q);
+}
@@ -0,0 +1,56 @@
+// revisions: adequate deferred
+// compile-flags: -Ztrait-solver=next
+//[adequate] compile-flags: -Znext-solver-normalization-fuel=64
+//[deferred] compile-flags: -Znext-solver-normalization-fuel=1
+// check-pass
+
+// With enough fuel, relating the deeply nested alias with `u8` normalizes it in a single
+// `AliasRelate` goal. Otherwise each goal only normalizes one alias and defers the rest to a
+// nested goal, which has to succeed as well.
+
+trait Id<T> {
+    type Id;
+}
+
+impl<T, U> Id<T> for U {
+    type Id = U;
+}
+
+type A0 = u8;
+type A1 = <A0 as Id<A0>>::Id;
+type A2 = <A1 as Id<A1>>::Id;
+type A3 = <A2 as Id<A2>>::Id;
+type A4 = <A3 as Id<A3>>::Id;
+type A5 = <A4 as Id<A4>>::Id;
+type A6 = <A5 as Id<A5>>::Id;
+type A7 = <A6 as Id<A6>>::Id;
+type A8 = <A7 as Id<A7>>::Id;
+type A9 = <A8 as Id<A8>>::Id;
+type A10 = <A9 as Id<A9>>::Id;
+type A11 = <A10 as Id<A10>>::Id;
+type A12 = <A11 as Id<A11>>::Id;
+type A13 = <A12 as Id<A12>>::Id;
+type A14 = <A13 as Id<A13>>::Id;
+type A15 = <A14 as Id<A14>>::Id;
+type A16 = <A15 as Id<A15>>::Id;
+type A17 = <A16 as Id<A16>>::Id;
+type A18 = <A17 as Id<A17>>::Id;
+type A19 = <A18 as Id<A18>>::Id;
+type A20 = <A19 as Id<A19>>::Id;
+type A21 = <A20 as Id<A20>>::Id;
+type A22 = <A21 as Id<A21>>::Id;
+type A23 = <A22 as Id<A22>>::Id;
+type A24 = <A23 as Id<A23>>::Id;
+type A25 = <A24 as Id<A24>>::Id;
+type A26 = <A25 as Id<A25>>::Id;
+type A27 = <A26 as Id<A26>>::Id;
+type A28 = <A27 as Id<A27>>::Id;
+type A29 = <A28 as Id<A28>>::Id;
+type A30 = <A29 as Id<A29>>::Id;
+type A31 = <A30 as Id<A30>>::Id;
+type A32 = <A31 as Id<A31>>::Id;
+
+fn main() {
+    let x: A32 = 1u8;
+    let _: u8 = x;
+}
@@ -0,0 +1,45 @@
+// revisions: current next
+//[next] compile-flags: -Ztrait-solver=next
+// check-pass
+
+// Returning an `impl Trait` defines it as a rigid projection, even from a closure. An opaque
+// which is defined elsewhere can't be defined by relating it with a projection, but relating
+// it with a projection normalizing to it works.
+#![feature(type_alias_impl_trait)]
+
+trait Trait {
+    type Assoc;
+    fn assoc(&self) -> Self::Assoc;
+}
+
+fn define<T: Trait>(x: T) -> impl Sized {
+    x.assoc()
+}
+
+fn define_in_closure<T: Trait>(x: T) -> impl Sized {
+    let f = || x.assoc();
+    f()
+}
+
+mod tait {
+    pub type Opaque = impl Sized;
+
+    pub fn define() -> Opaque {}
+}
+
+struct Unit;
+
+impl Trait for Unit {
+    type Assoc = tait::Opaque;
+    fn assoc(&self) -> tait::Opaque {
+        tait::define()
+    }
+}
+
+fn other_scope<T: Trait<Assoc = tait::Opaque>>(x: T) -> tait::Opaque {
+    x.assoc()
+}
+
+fn main() {
+    other_scope(Unit);
+}
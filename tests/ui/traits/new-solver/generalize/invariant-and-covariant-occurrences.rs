@@ -0,0 +1,27 @@
+// revisions: old next
+//[next] compile-flags: -Ztrait-solver=next
+// check-pass
+
+// The generalizer keeps types it can name as is in invariant positions, and replaces their
+// regions and inference variables with fresh ones otherwise. Make sure it doesn't reuse the
+// result for one occurrence of a type for another occurrence with a different variance.
+
+struct Inv<T>(*mut T);
+
+fn same<T>(_: T, _: T) {}
+
+fn invariant_first<'a, 'b: 'a>(inv: Inv<&'b u32>, short: &'a u32, long: &'b u32) {
+    same((inv, long), (Inv(std::ptr::null_mut()), short));
+}
+
+fn covariant_first<'a, 'b: 'a>(inv: Inv<&'b u32>, short: &'a u32, long: &'b u32) {
+    same((long, inv), (short, Inv(std::ptr::null_mut())));
+}
+
+fn infer<T: Default>(x: T) -> (Inv<T>, T) {
+    let pair = (Inv(std::ptr::null_mut()), Default::default());
+    same(&pair, &(Inv(std::ptr::null_mut::<T>()), x));
+    pair
+}
+
+fn main() {}
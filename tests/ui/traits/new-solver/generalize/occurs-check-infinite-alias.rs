@@ -0,0 +1,21 @@
+// compile-flags: -Ztrait-solver=next
+
+// Relating `?x` with `<Option<?x> as Id>::Id` fails as soon as the alias is normalized, as this
+// would require `?x` to be equal to `Option<?x>`.
+
+trait Id {
+    type Id;
+}
+impl<T> Id for T {
+    type Id = T;
+}
+
+fn id<T>(x: T) -> <T as Id>::Id {
+    x
+}
+
+fn main() {
+    let mut x = None;
+    x = Some(id(x));
+    //~^ ERROR cannot construct an infinite type
+}
@@ -0,0 +1,38 @@
+// revisions: old next
+// check-pass
+
+// Like `occurs-check-nested-alias.rs`, but with the inference variable
+// nested more deeply inside of aliases. This should not recurse forever
+// when generalizing.
+//[next] compile-flags: -Ztrait-solver=next
+#![crate_type = "lib"]
+#![allow(unused)]
+trait Unnormalizable {
+    type Assoc;
+}
+
+trait Id<T> {
+    type Id;
+}
+impl<T, U> Id<T> for U {
+    type Id = U;
+}
+
+struct Inv<T>(*mut T);
+
+fn unconstrained<T>() -> T {
+    todo!()
+}
+
+fn create<T, U: Unnormalizable>(
+    x: &U,
+) -> (Inv<T>, Inv<<<<<U as Id<T>>::Id as Id<T>>::Id as Id<T>>::Id as Unnormalizable>::Assoc>) {
+    todo!()
+}
+
+fn foo<T: Unnormalizable>() {
+    let q = unconstrained();
+    let (mut x, y) = create::<_, _>(&q);
+    x = y;
+    drop::<T>(q);
+}
@@ -1,9 +1,9 @@
 // revisions: old next
-//[old] check-pass
+// check-pass
 
-// Need to emit an alias-relate instead of a `Projection` goal here.
+// The new solver emits an alias-relate goal here, which normalizes the inner alias
+// so that the outer one no longer mentions the inference variable.
 //[next] compile-flags: -Ztrait-solver=next
-//[next] known-bug: trait-system-refactor-initiative#8
 #![crate_type = "lib"]
 #![allow(unused)]
 trait Unnormalizable {
@@ -0,0 +1,32 @@
+// check-pass
+// compile-flags: -Ztrait-solver=next
+
+// Identity projections which don't only depend on the impl are normalized as usual.
+
+#![crate_type = "lib"]
+
+pub trait Id<T> {
+    type Id;
+}
+
+// The impl only applies if `T: Copy`.
+impl<T: Copy, U> Id<T> for U {
+    type Id = U;
+}
+
+pub fn conditional<T: Copy>(x: <String as Id<T>>::Id) -> String {
+    x
+}
+
+pub trait Same<T> {
+    type Same;
+}
+
+impl<T, U> Same<T> for U {
+    type Same = U;
+}
+
+// The where-clause is preferred over the impl.
+pub fn from_env<T: Same<(), Same = u32>>(x: <T as Same<()>>::Same) -> u32 {
+    x
+}
@@ -0,0 +1,28 @@
+// check-pass
+// compile-flags: -Ztrait-solver=next
+
+// Many identical projections which can't be normalized, as there is no impl for them, so they
+// stay rigid. The failing normalization only has to be computed once, as its result is cached
+// like the one of any other goal.
+
+trait Trait {
+    type Assoc;
+}
+
+fn id<T: Trait>(x: T::Assoc) -> T::Assoc {
+    x
+}
+
+macro_rules! chain {
+    ($x:expr;) => { $x };
+    ($x:expr; $_head:tt $($tail:tt)*) => { chain!(id::<T>($x); $($tail)*) };
+}
+
+fn many<T: Trait>(x: T::Assoc) -> T::Assoc {
+    chain!(x;
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9
+    )
+}
+
+fn main() {}
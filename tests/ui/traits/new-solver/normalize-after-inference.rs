@@ -0,0 +1,24 @@
+// check-pass
+// compile-flags: -Ztrait-solver=next
+
+// The projection can't be normalized while its self type is still unknown. This must not be
+// cached in a way which prevents normalizing it once inference constrains the self type.
+
+trait Trait {
+    type Assoc;
+}
+
+impl Trait for u32 {
+    type Assoc = u8;
+}
+
+fn assoc<T: Trait>(_: &T) -> Option<T::Assoc> {
+    None
+}
+
+fn main() {
+    let mut x = Default::default();
+    let y = assoc(&x);
+    x = 1u32;
+    let _: Option<u8> = y;
+}
@@ -0,0 +1,57 @@
+// revisions: old next
+//[next] compile-flags: -Ztrait-solver=next
+// check-pass
+
+// Relating projections whose self type is a trait object with concrete types, which uses the
+// bindings of the object type, or impls if it has no binding for the associated type.
+
+trait Trait {
+    type Assoc;
+}
+
+trait Super {
+    type Assoc;
+}
+
+trait Sub: Super {}
+
+trait Fixed: Super<Assoc = u8> {}
+
+trait Other {
+    type Assoc;
+}
+
+impl<T: ?Sized + Trait> Other for T {
+    type Assoc = <T as Trait>::Assoc;
+}
+
+fn explicit(x: <dyn Trait<Assoc = u32> as Trait>::Assoc) -> u32 {
+    x
+}
+
+fn generic<T>(x: <dyn Trait<Assoc = T> as Trait>::Assoc) -> T {
+    x
+}
+
+fn supertrait(x: <dyn Sub<Assoc = u32> as Super>::Assoc) -> u32 {
+    x
+}
+
+// The binding comes from the supertrait bound of `Fixed`.
+fn elaborated(x: <dyn Fixed as Super>::Assoc) -> u8 {
+    x
+}
+
+// `dyn Trait` has no binding for `<dyn Trait as Other>::Assoc`, so this uses the impl.
+fn through_impl(x: <dyn Trait<Assoc = u32> as Other>::Assoc) -> u32 {
+    let y: u32 = x;
+    y
+}
+
+fn main() {
+    explicit(1);
+    generic::<u16>(1);
+    supertrait(1);
+    elaborated(1);
+    through_impl(1);
+}
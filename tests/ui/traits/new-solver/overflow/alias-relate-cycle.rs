@@ -0,0 +1,24 @@
+//~ ERROR overflow evaluating the requirement `<T as Foo2>::Assoc2` [E0275]
+// compile-flags: -Ztrait-solver=next
+
+// When relating two aliases overflows because one of them normalizes back to itself through
+// other aliases, we show the aliases it goes through.
+
+trait Foo1 {
+    type Assoc1;
+}
+
+trait Foo2 {
+    type Assoc2;
+}
+
+fn relate<T, U>(x: T::Assoc1) -> U::Assoc1
+where
+    T: Foo1<Assoc1 = <T as Foo2>::Assoc2> + Foo2<Assoc2 = <T as Foo1>::Assoc1>,
+    U: Foo1,
+{
+    x
+    //~^ ERROR overflow normalizing the aliases `<T as Foo1>::Assoc1` and `<U as Foo1>::Assoc1`
+}
+
+fn main() {}
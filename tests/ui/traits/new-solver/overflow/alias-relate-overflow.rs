@@ -0,0 +1,16 @@
+//~ ERROR overflow evaluating the requirement `<T as Foo>::Assoc` [E0275]
+// compile-flags: -Ztrait-solver=next
+
+// Relating an alias which normalizes to itself to another alias overflows
+// while normalizing it, which we report by naming both aliases.
+
+trait Foo {
+    type Assoc;
+}
+
+fn relate<T: Foo<Assoc = <T as Foo>::Assoc>, U: Foo>(x: T::Assoc) -> U::Assoc {
+    x
+    //~^ ERROR overflow normalizing the aliases `<T as Foo>::Assoc` and `<U as Foo>::Assoc`
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// compile-flags: -Ztrait-solver=next -Zreport-solver-depth
+// check-pass
+
+// Proving `Nested<T>: Depth` requires proving `T: Depth`, so the depth
+// reached by the solver grows with each level of nesting.
+
+#![crate_type = "lib"]
+
+pub trait Depth {}
+
+impl Depth for () {}
+
+impl<T: Depth> Depth for Nested<T> {}
+
+pub struct Nested<T>(T);
+
+fn requires_depth<T: Depth>() {}
+
+pub fn shallow() {
+    requires_depth::<()>();
+}
+
+pub fn deep() {
+    requires_depth::<Nested<Nested<Nested<()>>>>();
+}
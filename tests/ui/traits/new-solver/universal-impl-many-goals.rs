@@ -0,0 +1,40 @@
+// check-pass
+// compile-flags: -Ztrait-solver=next
+
+// A blanket impl which applies to every self type and trait arguments is used for many goals. No
+// other impl can apply where it does, so it is the only impl candidate considered for them.
+
+trait Id<T> {
+    type Id;
+
+    fn id(self) -> Self::Id;
+}
+
+impl<T, U> Id<T> for U {
+    type Id = U;
+
+    fn id(self) -> U {
+        self
+    }
+}
+
+macro_rules! ids {
+    ($($ty:ty),*) => {
+        fn ids() {
+            $(
+                let x: $ty = Default::default();
+                let x: <$ty as Id<$ty>>::Id = Id::<u8>::id(Id::<u16>::id(Id::<u32>::id(x)));
+                let _: $ty = x;
+            )*
+        }
+    };
+}
+
+ids!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char, (), String,
+    Vec<u8>, Option<u8>, Box<u8>, (u8, u16), (u32, (u64, bool)), [u8; 4], [String; 2], &'static str
+);
+
+fn main() {
+    ids();
+}
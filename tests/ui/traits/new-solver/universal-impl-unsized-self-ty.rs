@@ -0,0 +1,27 @@
+// check-pass
+// compile-flags: -Ztrait-solver=next
+
+// The blanket impl only applies to sized self types, so a more specific impl for an unsized type
+// doesn't overlap with it. That impl has to be used for the goals with an unsized self type.
+
+trait Id<T> {
+    type Id;
+}
+
+impl<T, U> Id<T> for U {
+    type Id = U;
+}
+
+impl<T> Id<T> for str {
+    type Id = T;
+}
+
+impl<T> Id<T> for [u8] {
+    type Id = (T, T);
+}
+
+fn main() {
+    let _: <u32 as Id<u8>>::Id = 0u32;
+    let _: <str as Id<u8>>::Id = 0u8;
+    let _: <[u8] as Id<u8>>::Id = (0u8, 0u8);
+}
@@ -0,0 +1,32 @@
+// Dereferencing a pointer which was cast from an integer notes that its address may not be valid
+// for the type it points to.
+
+struct Aligned(u64);
+
+fn literal() -> u32 {
+    *(0x1000 as *const u32)
+    //~^ ERROR dereference of raw pointer is unsafe
+}
+
+fn copied() -> u64 {
+    let p = 0x1008 as *const Aligned;
+    let q = p;
+    (*q).0
+    //~^ ERROR dereference of raw pointer is unsafe
+}
+
+fn reassigned(other: *const u32, b: bool) -> u32 {
+    let mut p = 0x1000 as *const u32;
+    if b {
+        p = other;
+    }
+    *p
+    //~^ ERROR dereference of raw pointer is unsafe
+}
+
+fn argument(p: *const u32) -> u32 {
+    *p
+    //~^ ERROR dereference of raw pointer is unsafe
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+// compile-flags: -Z group-unsafety-violations
+
+// Operations of the same kind which require `unsafe` are reported as a single error per function,
+// with a label for each of them. Operations of different kinds are still reported separately.
+
+static mut COUNTER: u32 = 0;
+
+fn derefs(a: *const u32, b: *const u32, c: *const u32) -> u32 {
+    let x = *a; //~ ERROR dereference of raw pointer is unsafe
+    let y = *b;
+    let z = *c;
+    x + y + z
+}
+
+fn mixed(p: *const u32) -> u32 {
+    let x = *p; //~ ERROR dereference of raw pointer is unsafe
+    let y = COUNTER; //~ ERROR use of mutable static is unsafe
+    let z = *p;
+    let w = COUNTER;
+    x + y + z + w
+}
+
+fn single(p: *const u32) -> u32 {
+    let x = *p; //~ ERROR dereference of raw pointer is unsafe
+    x
+}
+
+fn main() {}
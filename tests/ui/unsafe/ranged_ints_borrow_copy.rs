@@ -0,0 +1,23 @@
+// run-rustfix
+// Borrows of layout constrained fields which are `Copy` can borrow a copy of the field instead.
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+#[rustc_layout_scalar_valid_range_start(1)]
+#[repr(transparent)]
+pub(crate) struct NonZero<T>(pub(crate) T);
+
+fn borrow<T: Copy>(x: &NonZero<T>) {
+    let _y = &x.0;
+    //~^ ERROR borrow of layout constrained field with interior mutability
+}
+
+fn take<T>(_: &T) {}
+
+fn borrow_in_call<T: Copy>(x: NonZero<T>) {
+    take(&x.0);
+    //~^ ERROR borrow of layout constrained field with interior mutability
+}
+
+fn main() {}
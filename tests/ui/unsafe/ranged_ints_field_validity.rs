@@ -0,0 +1,18 @@
+// The note about initializing a layout restricted type mentions the invariant of its field.
+
+#![feature(rustc_attrs)]
+
+#[rustc_layout_scalar_valid_range_start(1)]
+struct NonNullRef(&'static u8);
+
+#[rustc_layout_scalar_valid_range_start(1)]
+struct True(bool);
+
+#[rustc_layout_scalar_valid_range_start(1)]
+struct NonZeroU8(u8);
+
+fn main() {
+    let _x = NonNullRef(&0); //~ ERROR initializing type with `rustc_layout_scalar_valid_range` attr
+    let _y = True(true); //~ ERROR initializing type with `rustc_layout_scalar_valid_range` attr
+    let _z = NonZeroU8(1); //~ ERROR initializing type with `rustc_layout_scalar_valid_range` attr
+}
@@ -0,0 +1,27 @@
+// compile-flags: -Zrequire-unsafe-blocks
+// With `-Zrequire-unsafe-blocks`, unsafe operations require an `unsafe` block even in an
+// `unsafe fn`, whether `unsafe_op_in_unsafe_fn` is allowed or not.
+
+unsafe fn unsafe_fn() {}
+
+unsafe fn deref(p: *const u8) -> u8 {
+    *p //~ ERROR dereference of raw pointer is unsafe and requires unsafe block
+}
+
+#[deny(unsafe_op_in_unsafe_fn)]
+unsafe fn call() {
+    unsafe_fn(); //~ ERROR call to unsafe function is unsafe and requires unsafe block
+}
+
+fn safe(p: *const u8) -> u8 {
+    *p //~ ERROR dereference of raw pointer is unsafe and requires unsafe block
+}
+
+unsafe fn in_block(p: *const u8) -> u8 {
+    unsafe {
+        unsafe_fn();
+        *p
+    }
+}
+
+fn main() {}
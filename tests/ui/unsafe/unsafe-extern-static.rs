@@ -0,0 +1,20 @@
+// Declaring an `extern` block `unsafe` does not make reading its statics safe, and the error
+// explains why.
+
+#![feature(unsafe_extern_blocks)]
+
+extern "C" {
+    static PLAIN: u32;
+}
+
+unsafe extern "C" {
+    static DECLARED_UNSAFE: u32;
+}
+
+fn main() {
+    let _plain = PLAIN;
+    //~^ ERROR use of extern static is unsafe and requires unsafe function or block
+    let _declared_unsafe = DECLARED_UNSAFE;
+    //~^ ERROR use of extern static is unsafe and requires unsafe function or block
+    let _sum = unsafe { PLAIN + DECLARED_UNSAFE };
+}
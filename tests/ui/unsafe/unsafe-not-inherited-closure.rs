@@ -0,0 +1,39 @@
+// Check that the span of the enclosing unsafe context is the one around the item whose body
+// contains the unsafe operation, even when it is nested in several closures. Looking up the
+// context from the item rather than from the lint root of the operation gives the same result
+// here, as closures never start a new safety context; this only guards against regressions.
+
+#![allow(unused, dead_code)]
+
+unsafe fn unsafe_call() {}
+
+unsafe fn outer() {
+//~^ NOTE: items do not inherit unsafety
+    fn inner() {
+        let f = || {
+            let g = || unsafe_call();
+            //~^ ERROR: call to unsafe function
+            //~| NOTE: call to unsafe function
+            //~| NOTE: consult the function's documentation
+        };
+    }
+
+    // Closures are part of the body of `outer`, so this is fine.
+    let h = || {
+        let i = || unsafe_call();
+    };
+}
+
+unsafe fn outer_with_block() {
+    unsafe {
+    //~^ NOTE: items do not inherit unsafety
+        fn inner() {
+            let f = || unsafe_call();
+            //~^ ERROR: call to unsafe function
+            //~| NOTE: call to unsafe function
+            //~| NOTE: consult the function's documentation
+        }
+    }
+}
+
+fn main() {}
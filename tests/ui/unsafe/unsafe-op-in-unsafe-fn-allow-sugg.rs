@@ -0,0 +1,22 @@
+// run-rustfix
+// Check that besides wrapping the body of an `unsafe fn` in an `unsafe` block, the suggestions
+// for `unsafe_op_in_unsafe_fn` offer to allow the lint on the whole function.
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+unsafe fn unsf() {}
+
+pub unsafe fn function() {
+    unsf(); //~ ERROR call to unsafe function is unsafe
+}
+
+pub struct S;
+
+impl S {
+    #[inline]
+    pub unsafe fn method(&self) {
+        unsf(); //~ ERROR call to unsafe function is unsafe
+    }
+}
+
+fn main() {}
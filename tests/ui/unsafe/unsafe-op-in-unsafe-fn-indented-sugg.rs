@@ -0,0 +1,23 @@
+// run-rustfix
+// Check that the suggestion to wrap the body of an `unsafe fn` in an `unsafe` block indents the
+// block and its contents.
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+unsafe fn unsf() {}
+
+pub struct S;
+
+impl S {
+    pub unsafe fn method(&self) {
+        let x = 1;
+        unsf(); //~ ERROR call to unsafe function is unsafe
+
+        let _ = x;
+    }
+}
+
+// The braces of the body aren't on lines of their own, so the block isn't indented.
+pub unsafe fn one_line() { unsf() } //~ ERROR call to unsafe function is unsafe
+
+fn main() {}
@@ -0,0 +1,23 @@
+// Bodies without any construct which may be an unsafe operation skip the MIR unsafety checker.
+// Check that binding a field of a type with a restricted range of valid values by reference,
+// explicitly or through default binding modes, is still found.
+
+#![feature(rustc_attrs)]
+
+#[rustc_layout_scalar_valid_range_start(1)]
+pub struct NonZero(u32);
+
+fn explicit_ref_mut(mut x: NonZero) {
+    let NonZero(ref mut _y) = x;
+    //~^ ERROR mutation of layout constrained field is unsafe
+}
+
+fn default_binding_mode(x: &mut NonZero) {
+    let NonZero(_y) = x;
+    //~^ ERROR mutation of layout constrained field is unsafe
+}
+
+fn main() {
+    explicit_ref_mut(unsafe { NonZero(1) });
+    default_binding_mode(&mut unsafe { NonZero(1) });
+}
@@ -0,0 +1,37 @@
+// Bodies without any construct which may be an unsafe operation skip the MIR unsafety checker.
+// Check that this doesn't change which `unsafe` blocks are reported as unused, and that unsafe
+// operations in closures, patterns and paths are still found.
+
+static mut COUNTER: u32 = 0;
+
+union U {
+    a: u32,
+}
+
+fn arithmetic(x: u32) -> u32 {
+    unsafe { x + 1 }
+    //~^ WARNING unnecessary `unsafe` block
+}
+
+fn in_closure(p: *const u32) -> impl Fn() -> u32 {
+    move || *p
+    //~^ ERROR dereference of raw pointer is unsafe
+}
+
+fn in_pattern(u: U) -> u32 {
+    let U { a } = u;
+    //~^ ERROR access to union field is unsafe
+    a
+}
+
+fn mutable_static() -> u32 {
+    COUNTER
+    //~^ ERROR use of mutable static is unsafe
+}
+
+fn main() {
+    arithmetic(1);
+    in_closure(&1);
+    in_pattern(U { a: 1 });
+    mutable_static();
+}
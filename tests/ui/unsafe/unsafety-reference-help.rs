@@ -0,0 +1,14 @@
+// compile-flags: -Zverbose
+// With `-Zverbose`, operations which require `unsafe` point to the section of the reference
+// explaining why.
+
+unsafe fn f() {}
+
+fn main() {
+    f();
+    //~^ ERROR call to unsafe function is unsafe
+    let x = 0;
+    let p = &x as *const i32;
+    let _y = *p;
+    //~^ ERROR dereference of raw pointer is unsafe
+}
@@ -0,0 +1,21 @@
+// build-pass
+// compile-flags: -Zmir-opt-level=3 -Zinline-mir
+
+#![warn(unused_unsafe_after_inlining)]
+
+#[inline]
+unsafe fn nothing_unsafe() -> u32 {
+    1
+}
+
+#[inline]
+unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+fn main() {
+    let x = unsafe { nothing_unsafe() }; //~ WARN `unsafe` block is unnecessary after inlining
+    let y = unsafe { read(&x) };
+    let z = unsafe { *(&y as *const u32) };
+    assert_eq!(x + y + z, 3);
+}
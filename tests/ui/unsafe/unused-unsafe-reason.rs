@@ -0,0 +1,18 @@
+// Check that unnecessary `unsafe` blocks say why they are unnecessary: either they are nested
+// inside another `unsafe` block, or they contain no unsafe operations at all.
+
+#![deny(unused_unsafe)]
+
+unsafe fn f() {}
+
+fn no_unsafe_ops() -> u32 {
+    unsafe { 1 + 1 } //~ ERROR unnecessary `unsafe` block
+}
+
+fn nested() {
+    unsafe {
+        unsafe { f() } //~ ERROR unnecessary `unsafe` block
+    }
+}
+
+fn main() {}
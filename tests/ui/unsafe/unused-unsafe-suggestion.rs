@@ -0,0 +1,56 @@
+// run-rustfix
+
+#![deny(unused_unsafe)]
+#![allow(unused_braces, dead_code)]
+
+unsafe fn unsafe_call() {}
+
+fn f() {}
+
+fn statements() {
+    unsafe {
+    //~^ ERROR unnecessary `unsafe` block
+        f();
+        f();
+    }
+    f();
+}
+
+fn expression_without_semicolon() {
+    unsafe { f() } //~ ERROR unnecessary `unsafe` block
+    f();
+}
+
+fn tail() -> u32 {
+    unsafe {
+    //~^ ERROR unnecessary `unsafe` block
+        1
+    }
+}
+
+fn value() -> u32 {
+    let x = unsafe { f(); 1 }; //~ ERROR unnecessary `unsafe` block
+    x
+}
+
+fn comments() {
+    unsafe /* nothing unsafe here */ { //~ ERROR unnecessary `unsafe` block
+        f();
+    }
+    unsafe { // neither here
+    //~^ ERROR unnecessary `unsafe` block
+        f();
+    }
+}
+
+fn nested() {
+    unsafe {
+        unsafe_call();
+        unsafe {
+        //~^ ERROR unnecessary `unsafe` block
+            unsafe_call();
+        }
+    }
+}
+
+fn main() {}
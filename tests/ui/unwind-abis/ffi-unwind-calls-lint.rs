@@ -22,4 +22,17 @@ fn main() {
     // Call to function pointer should also warn.
     ptr();
     //~^ WARNING call to function pointer with FFI-unwind ABI
+    // Calls which cannot unwind are fine.
+    unsafe { bar(); }
+    let ptr: extern "C" fn() = bar::bar;
+    ptr();
+}
+
+mod bar {
+    #[no_mangle]
+    pub extern "C" fn bar() {}
+}
+
+extern "C" {
+    fn bar();
 }